@@ -0,0 +1,814 @@
+//! Every chioma entrypoint, measured in one deployment per area: the
+//! agreement book, tokens, deposit interest, the position marketplace,
+//! governance and upgrades. Each deployment holds `AGREEMENTS` active
+//! agreements so lookups, indexes and batches run against a populated
+//! contract.
+
+use chioma::{
+    ActionThreshold, ActionType, AgreementInput, AgreementTerms, Attribute, AuditFilter,
+    CompoundingFrequency, Config, Contract, ContractClient, ContractVersion, GasMetrics,
+    InterestRateSource, InterestRecipient, InterestSplit, OperationType, PauseModule,
+    RateLimitConfig, RateLimitMode, Role, RoyaltyShare, TimelockActionType, VersionStatus,
+};
+use escrow::escrow_impl::{EscrowContract, EscrowContractClient};
+use protocol_directory::Component;
+use soroban_sdk::{
+    testutils::Address as _, token::StellarAssetClient, vec, xdr::ToXdr, Address, Bytes, Env,
+    String, Vec,
+};
+
+use crate::harness::{
+    advance, create_token, deploy_directory, new_env, upload_upgrade_wasm, Bench, BenchVault,
+    BenchVaultClient,
+};
+
+/// Agreements every deployment holds before its entrypoints run.
+const AGREEMENTS: u32 = 20;
+/// Rent payments already made on the benchmarked agreement.
+const PAYMENTS: u32 = 12;
+/// Metadata attributes attached to the benchmarked agreement.
+const ATTRIBUTES: u32 = 10;
+/// Supported payment tokens.
+const TOKENS: u32 = 10;
+/// Monthly accruals already recorded on the benchmarked deposit.
+const ACCRUALS: u32 = 12;
+/// Open bids on the benchmarked listing.
+const BIDS: u32 = 5;
+/// Recipients sharing a royalty.
+const ROYALTY_RECIPIENTS: u32 = 5;
+/// Additional multi-sig admins next to the contract admin.
+const CO_ADMINS: u32 = 4;
+
+const RENT: i128 = 1_000;
+const DEPOSIT: i128 = 2_000;
+const DAY: u64 = 86_400;
+const MONTH: u64 = 30 * DAY;
+/// Exchange rates are scaled by 10^18.
+const PARITY: i128 = 1_000_000_000_000_000_000;
+
+struct Deployment {
+    env: Env,
+    client: ContractClient<'static>,
+    admin: Address,
+    token: StellarAssetClient<'static>,
+    /// Lists this deployment and the escrow deposits are moved into.
+    directory: Address,
+    escrow: EscrowContractClient<'static>,
+}
+
+struct Parties {
+    id: String,
+    tenant: Address,
+    landlord: Address,
+}
+
+fn text(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+fn agreement_id(env: &Env, n: u32) -> String {
+    text(env, &format!("AGR-{n:03}"))
+}
+
+/// A registered contract that has not been initialized yet, next to the
+/// escrow contract and directory it will be wired to.
+fn register() -> Deployment {
+    let env = new_env();
+    let client = ContractClient::new(&env, &env.register(Contract, ()));
+    let admin = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    let escrow = EscrowContractClient::new(&env, &env.register(EscrowContract, ()));
+    escrow.initialize_admin(&admin);
+    let directory = deploy_directory(
+        &env,
+        &admin,
+        &[
+            (Component::Chioma, &client.address),
+            (Component::Escrow, &escrow.address),
+        ],
+    );
+    escrow.set_directory(&admin, &directory);
+    Deployment {
+        env,
+        client,
+        admin,
+        token,
+        directory,
+        escrow,
+    }
+}
+
+/// An initialized contract holding `AGREEMENTS` active agreements.
+fn deploy() -> Deployment {
+    let d = register();
+    d.client.initialize(&d.admin, &config(&d.env, 100));
+    d.client.set_directory(&d.admin, &d.directory);
+    seed_agreements(&d);
+    d
+}
+
+fn config(env: &Env, fee_bps: u32) -> Config {
+    Config {
+        fee_bps,
+        fee_collector: Address::generate(env),
+        paused: false,
+    }
+}
+
+fn seed_agreements(d: &Deployment) {
+    for n in 0..AGREEMENTS {
+        let parties = draft(d, agreement_id(&d.env, n));
+        activate(d, &parties);
+    }
+}
+
+fn input(d: &Deployment, id: String, tenant: &Address, landlord: &Address) -> AgreementInput {
+    let now = d.env.ledger().timestamp();
+    AgreementInput {
+        agreement_id: id,
+        admin: landlord.clone(),
+        user: tenant.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: RENT,
+            security_deposit: DEPOSIT,
+            start_date: now + DAY,
+            end_date: now + 365 * DAY,
+            agent_commission_rate: 0,
+        },
+        payment_token: d.token.address.clone(),
+        metadata_uri: text(&d.env, "ipfs://agreement"),
+        attributes: Vec::new(&d.env),
+    }
+}
+
+/// Create a draft agreement between fresh parties, with the tenant holding
+/// the deposit. Each creation moves to a new ledger so the per-block rate
+/// limit never trips.
+fn draft(d: &Deployment, id: String) -> Parties {
+    let tenant = Address::generate(&d.env);
+    let landlord = Address::generate(&d.env);
+    d.token.mint(&tenant, &DEPOSIT);
+    advance(&d.env, 1);
+    d.client
+        .create_agreement(&input(d, id.clone(), &tenant, &landlord));
+    Parties {
+        id,
+        tenant,
+        landlord,
+    }
+}
+
+fn activate(d: &Deployment, parties: &Parties) {
+    d.client.submit_agreement(&parties.landlord, &parties.id);
+    d.client.sign_agreement(&parties.tenant, &parties.id);
+    d.client.approve_agreement(&parties.landlord, &parties.id);
+}
+
+#[test]
+fn chioma_entrypoints() {
+    let mut bench = Bench::new("chioma");
+    agreements(&mut bench);
+    tokens(&mut bench);
+    interest(&mut bench);
+    marketplace(&mut bench);
+    governance(&mut bench);
+    upgrades(&mut bench);
+    bench.finish();
+}
+
+fn agreements(bench: &mut Bench) {
+    let d = deploy();
+    let (env, client) = (&d.env, &d.client);
+
+    // Lifecycle of one agreement.
+    let tenant = Address::generate(env);
+    let landlord = Address::generate(env);
+    let id = text(env, "AGR-BENCH");
+    let mut new_agreement = input(&d, id.clone(), &tenant, &landlord);
+    for n in 0..ATTRIBUTES {
+        new_agreement.attributes.push_back(Attribute {
+            trait_type: text(env, &format!("trait-{n}")),
+            value: text(env, "value"),
+        });
+    }
+    advance(env, 1);
+    bench.run(env, "create_agreement", || {
+        client.create_agreement(&new_agreement)
+    });
+    bench.run(env, "submit_agreement", || {
+        client.submit_agreement(&landlord, &id)
+    });
+    bench.run(env, "sign_agreement", || {
+        client.sign_agreement(&tenant, &id)
+    });
+    // Escrows the deposit and pays the first month's rent.
+    d.token.mint(&tenant, &(DEPOSIT + RENT));
+    bench.run(env, "approve_with_first_rent", || {
+        client.approve_with_first_rent(&landlord, &id)
+    });
+    let approved = draft(&d, text(env, "AGR-APPROVE"));
+    client.submit_agreement(&approved.landlord, &approved.id);
+    client.sign_agreement(&approved.tenant, &approved.id);
+    bench.run(env, "approve_agreement", || {
+        client.approve_agreement(&approved.landlord, &approved.id)
+    });
+    bench.run(env, "get_agreement", || client.get_agreement(&id));
+    bench.run(env, "has_agreement", || client.has_agreement(&id));
+    bench.run(env, "get_agreement_count", || client.get_agreement_count());
+    bench.run(env, "update_metadata", || {
+        client.update_metadata(
+            &id,
+            &text(env, "ipfs://agreement-v2"),
+            &new_agreement.attributes,
+        )
+    });
+    let cancelled = draft(&d, text(env, "AGR-CANCEL"));
+    bench.run(env, "cancel_agreement", || {
+        client.cancel_agreement(&cancelled.landlord, &cancelled.id)
+    });
+
+    // Payments and escrow.
+    d.token.mint(&tenant, &(RENT * PAYMENTS as i128));
+    for _ in 1..PAYMENTS {
+        client.make_payment_with_token(&id, &RENT, &d.token.address);
+    }
+    bench.run(env, "make_payment_with_token", || {
+        client.make_payment_with_token(&id, &RENT, &d.token.address)
+    });
+    bench.run(env, "get_payment_split", || {
+        client.get_payment_split(&id, &PAYMENTS)
+    });
+    bench.run(env, "get_payment_history", || {
+        client.get_payment_history(&id)
+    });
+    bench.run(env, "freeze_escrow", || client.freeze_escrow(&d.admin, &id));
+    bench.run(env, "is_escrow_frozen", || client.is_escrow_frozen(&id));
+    bench.run(env, "unfreeze_escrow", || {
+        client.unfreeze_escrow(&d.admin, &id)
+    });
+    bench.run(env, "release_escrow_with_token", || {
+        client.release_escrow_with_token(&id, &d.token.address)
+    });
+
+    // Extensions; the extension id is the agreement id.
+    bench.run(env, "propose_extension", || {
+        client.propose_extension(&tenant, &id, &12, &Some(RENT + 50), &None)
+    });
+    bench.run(env, "get_extension", || client.get_extension(&id));
+    bench.run(env, "accept_extension", || {
+        client.accept_extension(&landlord, &id)
+    });
+    bench.run(env, "activate_extension", || {
+        client.activate_extension(&landlord, &id)
+    });
+    bench.run(env, "get_extension_history", || {
+        client.get_extension_history(&id)
+    });
+    bench.run(env, "get_current_agreement_end", || {
+        client.get_current_agreement_end(&id)
+    });
+    let rejected = agreement_id(env, 0);
+    let rejected_agreement = client.get_agreement(&rejected).unwrap();
+    client.propose_extension(&rejected_agreement.user, &rejected, &6, &None, &None);
+    bench.run(env, "reject_extension", || {
+        client.reject_extension(&rejected_agreement.admin, &rejected, &text(env, "selling"))
+    });
+    let withdrawn = agreement_id(env, 1);
+    let withdrawn_agreement = client.get_agreement(&withdrawn).unwrap();
+    client.propose_extension(&withdrawn_agreement.user, &withdrawn, &6, &None, &None);
+    bench.run(env, "cancel_extension", || {
+        client.cancel_extension(&withdrawn_agreement.user, &withdrawn, &text(env, "moving"))
+    });
+
+    // The audit log now holds every lifecycle step above.
+    let filter = AuditFilter {
+        agreement_id: Some(id.clone()),
+        actor: None,
+        from: None,
+        to: None,
+    };
+    bench.run(env, "get_audit_log", || {
+        client.get_audit_log(&filter, &0, &50)
+    });
+    bench.run(env, "get_audit_log_count", || client.get_audit_log_count());
+    bench.run(env, "get_audit_log_capacity", || {
+        client.get_audit_log_capacity()
+    });
+}
+
+fn tokens(bench: &mut Bench) {
+    let d = deploy();
+    let (env, client) = (&d.env, &d.client);
+    let add = |token: &Address| {
+        client.add_supported_token(&d.admin, token, &text(env, "TKN"), &7, &1, &1_000_000_000)
+    };
+
+    client.add_supported_token(
+        &d.admin,
+        &d.token.address,
+        &text(env, "USDC"),
+        &7,
+        &1,
+        &1_000_000_000,
+    );
+    let mut others = std::vec::Vec::new();
+    for _ in 2..TOKENS {
+        let token = create_token(env, &Address::generate(env)).address;
+        add(&token);
+        others.push(token);
+    }
+    let last = create_token(env, &Address::generate(env)).address;
+    bench.run(env, "add_supported_token", || add(&last));
+    bench.run(env, "get_supported_tokens", || {
+        client.get_supported_tokens()
+    });
+    bench.run(env, "is_token_supported", || {
+        client.is_token_supported(&last)
+    });
+
+    bench.run(env, "set_exchange_rate", || {
+        client.set_exchange_rate(&d.admin, &last, &d.token.address, &PARITY)
+    });
+    bench.run(env, "get_exchange_rate", || {
+        client.get_exchange_rate(&last, &d.token.address)
+    });
+    bench.run(env, "convert_amount", || {
+        client.convert_amount(&last, &d.token.address, &RENT)
+    });
+    let mut rates = Vec::new(env);
+    for token in &others {
+        rates.push_back((token.clone(), d.token.address.clone(), PARITY * 11 / 10));
+    }
+    bench.run(env, "update_exchange_rates", || {
+        client.update_exchange_rates(&d.admin, &rates)
+    });
+    bench.run(env, "remove_supported_token", || {
+        client.remove_supported_token(&d.admin, &last)
+    });
+
+    let tenant = Address::generate(env);
+    let landlord = Address::generate(env);
+    let agreement = input(&d, text(env, "AGR-TOKEN"), &tenant, &landlord);
+    advance(env, 1);
+    let id = bench.run(env, "create_agreement_with_token", || {
+        client.create_agreement_with_token(&agreement)
+    });
+    bench.run(env, "get_agreement_token", || {
+        client.get_agreement_token(&id)
+    });
+}
+
+fn interest(bench: &mut Bench) {
+    let d = deploy();
+    let (env, client) = (&d.env, &d.client);
+    let configure = |id: &String| {
+        client.set_deposit_interest_config(
+            &d.admin,
+            id,
+            &500,
+            &CompoundingFrequency::Monthly,
+            &InterestRecipient::Tenant,
+        )
+    };
+
+    // Every seeded deposit earns interest and, but for one, is already in
+    // the vault backing it.
+    for n in 0..AGREEMENTS - 1 {
+        configure(&agreement_id(env, n));
+    }
+    let id = agreement_id(env, AGREEMENTS - 1);
+    bench.run(env, "set_deposit_interest_config", || configure(&id));
+    bench.run(env, "get_deposit_interest_config", || {
+        client.get_deposit_interest_config(&id)
+    });
+
+    let vault = env.register(BenchVault, ());
+    BenchVaultClient::new(env, &vault).init(&d.token.address);
+    bench.run(env, "set_yield_vault", || {
+        client.set_yield_vault(&d.admin, &d.token.address, &vault)
+    });
+    bench.run(env, "get_yield_vault", || {
+        client.get_yield_vault(&d.token.address)
+    });
+    let routed = agreement_id(env, 1);
+    for n in (0..AGREEMENTS).filter(|&n| n != 1) {
+        client.route_deposit_to_vault(&d.admin, &agreement_id(env, n));
+    }
+    bench.run(env, "route_deposit_to_vault", || {
+        client.route_deposit_to_vault(&d.admin, &routed)
+    });
+    // The vault doubles every deposit, so accruals are never capped.
+    d.token.mint(&vault, &(DEPOSIT * AGREEMENTS as i128));
+
+    // Rate schedules.
+    let now = env.ledger().timestamp();
+    bench.run(env, "add_deposit_rate_segment", || {
+        client.add_deposit_rate_segment(&d.admin, &id, &(now + 6 * MONTH), &450)
+    });
+    bench.run(env, "get_deposit_rate_schedule", || {
+        client.get_deposit_rate_schedule(&id)
+    });
+    bench.run(env, "publish_statutory_rate", || {
+        client.publish_statutory_rate(&d.admin, &now, &400)
+    });
+    bench.run(env, "get_statutory_rate_schedule", || {
+        client.get_statutory_rate_schedule()
+    });
+    bench.run(env, "set_deposit_rate_source", || {
+        client.set_deposit_rate_source(
+            &d.admin,
+            &agreement_id(env, 0),
+            &InterestRateSource::Statutory,
+        )
+    });
+    let split = InterestSplit {
+        tenant_bps: 7_000,
+        landlord_bps: 2_000,
+        platform_bps: 1_000,
+        tenant_compounds: false,
+        landlord_compounds: true,
+        platform_compounds: false,
+    };
+    bench.run(env, "set_interest_split", || {
+        client.set_interest_split(&d.admin, &id, &split)
+    });
+    bench.run(env, "get_interest_split", || client.get_interest_split(&id));
+
+    // A year of monthly accruals on the benchmarked deposit.
+    for _ in 1..ACCRUALS {
+        advance(env, MONTH);
+        client.accrue_interest(&id);
+    }
+    advance(env, MONTH);
+    bench.run(env, "calculate_accrued_interest", || {
+        client.calculate_accrued_interest(&id)
+    });
+    bench.run(env, "accrue_interest", || client.accrue_interest(&id));
+    bench.run(env, "get_deposit_interest", || {
+        client.get_deposit_interest(&id)
+    });
+    bench.run(env, "get_accrual_history", || {
+        client.get_accrual_history(&id, &0, &ACCRUALS)
+    });
+    bench.run(env, "distribute_interest", || {
+        client.distribute_interest(&id)
+    });
+    bench.run(env, "process_interest_accruals", || {
+        client.process_interest_accruals(&0, &AGREEMENTS)
+    });
+    bench.run(env, "get_interest_deposit_count", || {
+        client.get_interest_deposit_count()
+    });
+    bench.run(env, "get_interest_deposits", || {
+        client.get_interest_deposits(&0, &AGREEMENTS)
+    });
+    // A deposit its escrow has paid back to the tenant stops earning.
+    let settled = agreement_id(env, 2);
+    let agreement = client.get_agreement(&settled).unwrap();
+    let escrow_id = d.escrow.get_agreement_escrow(&settled).unwrap();
+    d.escrow
+        .approve_release(&escrow_id, &agreement.user, &agreement.user);
+    d.escrow
+        .approve_release(&escrow_id, &agreement.admin, &agreement.user);
+    bench.run(env, "end_deposit_interest", || {
+        client.end_deposit_interest(&settled)
+    });
+
+    // Vault positions.
+    bench.run(env, "get_yield_position", || {
+        client.get_yield_position(&routed)
+    });
+    bench.run(env, "get_vault_yield", || client.get_vault_yield(&routed));
+    bench.run(env, "withdraw_deposit_from_vault", || {
+        client.withdraw_deposit_from_vault(&d.admin, &routed)
+    });
+}
+
+fn marketplace(bench: &mut Bench) {
+    let d = deploy();
+    let (env, client) = (&d.env, &d.client);
+    let buyer = |amount: i128| {
+        let buyer = Address::generate(env);
+        d.token.mint(&buyer, &amount);
+        buyer
+    };
+
+    // Royalties.
+    let id = agreement_id(env, 0);
+    let recipient = Address::generate(env);
+    bench.run(env, "set_royalty", || {
+        client.set_royalty(&id, &500, &recipient)
+    });
+    bench.run(env, "get_royalty", || client.get_royalty(&id));
+    bench.run(env, "calculate_royalty", || {
+        client.calculate_royalty(&id, &100_000)
+    });
+    let shared = agreement_id(env, 1);
+    let mut shares = Vec::new(env);
+    for _ in 0..ROYALTY_RECIPIENTS {
+        shares.push_back(RoyaltyShare {
+            recipient: Address::generate(env),
+            percentage: 300,
+        });
+    }
+    bench.run(env, "set_royalty_recipients", || {
+        client.set_royalty_recipients(&shared, &shares)
+    });
+    let purchaser = buyer(100_000);
+    bench.run(env, "transfer_with_royalty", || {
+        client.transfer_with_royalty(&shared, &purchaser, &100_000)
+    });
+    bench.run(env, "get_royalty_payments", || {
+        client.get_royalty_payments(&shared)
+    });
+
+    // A listing that closes by accepting one of several bids.
+    bench.run(env, "list_position", || {
+        client.list_position(&id, &100_000, &(30 * DAY))
+    });
+    bench.run(env, "get_position_listing", || {
+        client.get_position_listing(&id)
+    });
+    for n in 1..BIDS {
+        client.place_bid(&id, &buyer(90_000), &(80_000 + n as i128));
+    }
+    let bidder = buyer(95_000);
+    let bid_id = bench.run(env, "place_bid", || client.place_bid(&id, &bidder, &95_000));
+    bench.run(env, "get_position_bid", || {
+        client.get_position_bid(&id, &bid_id)
+    });
+    bench.run(env, "get_position_bid_count", || {
+        client.get_position_bid_count(&id)
+    });
+    bench.run(env, "accept_bid", || client.accept_bid(&id, &bid_id));
+    let losing_bid = client.get_position_bid_count(&id) - 2;
+    bench.run(env, "withdraw_bid", || {
+        client.withdraw_bid(&id, &losing_bid)
+    });
+
+    // Listings bought outright or withdrawn.
+    let sold = agreement_id(env, 2);
+    client.list_position(&sold, &50_000, &(30 * DAY));
+    let purchaser = buyer(50_000);
+    bench.run(env, "buy_position", || {
+        client.buy_position(&sold, &purchaser)
+    });
+    let delisted = agreement_id(env, 3);
+    client.list_position(&delisted, &50_000, &(30 * DAY));
+    bench.run(env, "cancel_listing", || client.cancel_listing(&delisted));
+}
+
+fn governance(bench: &mut Bench) {
+    let d = register();
+    let (env, client, admin) = (&d.env, &d.client, &d.admin);
+    let initial = config(env, 100);
+    bench.run(env, "initialize", || client.initialize(admin, &initial));
+    bench.run(env, "set_directory", || {
+        client.set_directory(admin, &d.directory)
+    });
+    bench.run(env, "get_directory", || client.get_directory());
+    seed_agreements(&d);
+    bench.run(env, "get_state", || client.get_state());
+    bench.run(env, "has_role", || client.has_role(admin, &Role::Treasurer));
+    let updated = config(env, 150);
+    bench.run(env, "update_config", || {
+        client.update_config(admin, &updated)
+    });
+
+    // Pauses.
+    let reason = text(env, "maintenance");
+    bench.run(env, "pause", || client.pause(admin, &reason));
+    bench.run(env, "is_paused", || client.is_paused());
+    bench.run(env, "get_pause_state", || client.get_pause_state());
+    bench.run(env, "unpause", || client.unpause(admin));
+    bench.run(env, "pause_module", || {
+        client.pause_module(admin, &PauseModule::Payments, &reason, &DAY)
+    });
+    bench.run(env, "is_module_paused", || {
+        client.is_module_paused(&PauseModule::Payments)
+    });
+    bench.run(env, "get_module_pause_state", || {
+        client.get_module_pause_state(&PauseModule::Payments)
+    });
+    bench.run(env, "unpause_module", || {
+        client.unpause_module(admin, &PauseModule::Payments)
+    });
+    bench.run(env, "get_guardian", || client.get_guardian());
+
+    // Rate limits.
+    let limits = RateLimitConfig {
+        max_calls_per_block: 20,
+        max_calls_per_user_per_day: 200,
+        cooldown_blocks: 0,
+        mode: RateLimitMode::SlidingWindow,
+        window_seconds: DAY,
+    };
+    let create = text(env, "create_agreement");
+    let tenant = client.get_agreement(&agreement_id(env, 0)).unwrap().user;
+    bench.run(env, "set_rate_limit_config", || {
+        client.set_rate_limit_config(admin, &limits)
+    });
+    bench.run(env, "get_rate_limit_config", || {
+        client.get_rate_limit_config()
+    });
+    bench.run(env, "get_user_call_count", || {
+        client.get_user_call_count(&tenant, &create)
+    });
+    bench.run(env, "get_block_call_count", || {
+        client.get_block_call_count(&create)
+    });
+    bench.run(env, "reset_user_rate_limit", || {
+        client.reset_user_rate_limit(admin, &tenant, &create)
+    });
+    bench.run(env, "set_function_rate_limit", || {
+        client.set_function_rate_limit(admin, &create, &limits)
+    });
+    bench.run(env, "get_function_rate_limit", || {
+        client.get_function_rate_limit(&create)
+    });
+    bench.run(env, "get_function_rate_limits", || {
+        client.get_function_rate_limits()
+    });
+    bench.run(env, "get_effective_rate_limit", || {
+        client.get_effective_rate_limit(&create)
+    });
+    bench.run(env, "remove_function_rate_limit", || {
+        client.remove_function_rate_limit(admin, &create)
+    });
+    bench.run(env, "set_rate_limit_exempt", || {
+        client.set_rate_limit_exempt(admin, &tenant, &true)
+    });
+    bench.run(env, "is_rate_limit_exempt", || {
+        client.is_rate_limit_exempt(&tenant)
+    });
+    bench.run(env, "get_rate_limit_exemptions", || {
+        client.get_rate_limit_exemptions()
+    });
+
+    // Multi-sig.
+    let mut admins = vec![env, admin.clone()];
+    for _ in 0..CO_ADMINS {
+        admins.push_back(Address::generate(env));
+    }
+    let co_admin = admins.get(1).unwrap();
+    bench.run(env, "initialize_multisig", || {
+        client.initialize_multisig(&admins, &2)
+    });
+    bench.run(env, "get_multisig_config", || client.get_multisig_config());
+    bench.run(env, "is_admin", || client.is_admin(&co_admin));
+    bench.run(env, "get_admin_weight", || {
+        client.get_admin_weight(&co_admin)
+    });
+    bench.run(env, "get_action_threshold", || {
+        client.get_action_threshold(&ActionType::SetActionThreshold)
+    });
+    let threshold = ActionThreshold {
+        action_type: ActionType::Pause,
+        threshold: 1,
+    };
+    let proposal_id = bench.run(env, "propose_action", || {
+        client.propose_action(
+            admin,
+            &ActionType::SetActionThreshold,
+            &None,
+            &threshold.clone().to_xdr(env),
+        )
+    });
+    bench.run(env, "approve_action", || {
+        client.approve_action(&co_admin, &proposal_id)
+    });
+    bench.run(env, "get_approval_weight", || {
+        client.get_approval_weight(&proposal_id)
+    });
+    bench.run(env, "get_proposal", || client.get_proposal(&proposal_id));
+    bench.run(env, "get_active_proposals", || {
+        client.get_active_proposals()
+    });
+    bench.run(env, "get_proposal_count", || client.get_proposal_count());
+    bench.run(env, "execute_action", || {
+        client.execute_action(admin, &proposal_id)
+    });
+    let withdrawn = client.propose_action(admin, &ActionType::Unpause, &None, &Bytes::new(env));
+    bench.run(env, "reject_action", || {
+        client.reject_action(admin, &withdrawn)
+    });
+    // Gas metrics.
+    let metrics = vec![
+        env,
+        GasMetrics {
+            operation: text(env, "create_agreement"),
+            cpu_instructions: 1_500_000,
+            memory_bytes: 300_000,
+            read_entries: 12,
+            write_entries: 8,
+            read_bytes: 0,
+            write_bytes: 4_000,
+            recorded_at: 0,
+        },
+    ];
+    bench.run(env, "record_gas_metrics", || {
+        client.record_gas_metrics(admin, &metrics)
+    });
+    bench.run(env, "get_operation_metrics", || {
+        client.get_operation_metrics(&create)
+    });
+    bench.run(env, "estimate_gas_cost", || {
+        client.estimate_gas_cost(&OperationType::CreateAgreement)
+    });
+    bench.run(env, "get_gas_metrics", || client.get_gas_metrics());
+    bench.run(env, "optimize_operation", || {
+        client.optimize_operation(&OperationType::CreateAgreement)
+    });
+
+    // Timelock; once enabled it blocks the direct admin calls above.
+    let queue = |fee_bps: u32| {
+        client.queue_timelock_action(
+            admin,
+            &TimelockActionType::UpdateConfig,
+            admin,
+            &config(env, fee_bps).to_xdr(env),
+            &(3 * DAY),
+        )
+    };
+    queue(175);
+    let action_id = bench.run(env, "queue_timelock_action", || queue(200));
+    bench.run(env, "get_timelock_action", || {
+        client.get_timelock_action(&action_id)
+    });
+    bench.run(env, "get_active_timelock_actions", || {
+        client.get_active_timelock_actions()
+    });
+    bench.run(env, "get_timelock_action_count", || {
+        client.get_timelock_action_count()
+    });
+    let cancelled = queue(225);
+    bench.run(env, "cancel_timelock_action", || {
+        client.cancel_timelock_action(admin, &cancelled)
+    });
+    bench.run(env, "enable_timelock", || client.enable_timelock(admin));
+    bench.run(env, "is_timelock_enabled", || client.is_timelock_enabled());
+    advance(env, 3 * DAY + 1);
+    bench.run(env, "execute_timelock_action", || {
+        client.execute_timelock_action(admin, &action_id)
+    });
+}
+
+fn release(env: &Env, major: u32, minor: u32) -> ContractVersion {
+    ContractVersion {
+        major,
+        minor,
+        patch: 0,
+        label: text(env, "release"),
+        status: VersionStatus::Active,
+        hash: Bytes::new(env),
+        updated_at: 0,
+    }
+}
+
+fn upgrades(bench: &mut Bench) {
+    let d = deploy();
+    let (env, client, admin) = (&d.env, &d.client, &d.admin);
+    let co_admin = Address::generate(env);
+    client.initialize_multisig(&vec![env, admin.clone(), co_admin.clone()], &2);
+
+    client.record_version(admin, &release(env, 1, 0));
+    bench.run(env, "record_version", || {
+        client.record_version(admin, &release(env, 1, 1))
+    });
+    bench.run(env, "update_version_status", || {
+        client.update_version_status(admin, &1, &0, &0, &VersionStatus::Deprecated)
+    });
+    bench.run(env, "get_version", || client.get_version());
+    bench.run(env, "get_version_history", || client.get_version_history());
+
+    let proposal_id = text(env, "UPG-BENCH");
+    let wasm_hash = upload_upgrade_wasm(env);
+    bench.run(env, "propose_contract_upgrade", || {
+        client.propose_contract_upgrade(admin, &proposal_id, &wasm_hash, &text(env, "v2"), &0)
+    });
+    bench.run(env, "approve_contract_upgrade", || {
+        client.approve_contract_upgrade(&co_admin, &proposal_id)
+    });
+    bench.run(env, "get_upgrade_proposal", || {
+        client.get_upgrade_proposal(&proposal_id)
+    });
+    bench.run(env, "get_active_upgrade_proposals", || {
+        client.get_active_upgrade_proposals()
+    });
+    bench.run(env, "get_upgrade_proposal_count", || {
+        client.get_upgrade_proposal_count()
+    });
+    bench.run(env, "execute_contract_upgrade", || {
+        client.execute_contract_upgrade(admin, &proposal_id, &release(env, 2, 0))
+    });
+    env.register_at(&client.address, Contract, ());
+    bench.run(env, "migrate", || client.migrate(admin));
+    bench.run(env, "get_storage_version", || client.get_storage_version());
+    bench.run(env, "migrate_records", || {
+        client.migrate_records(admin, &0, &AGREEMENTS)
+    });
+    bench.run(env, "migrate_records_by_id", || {
+        client.migrate_records_by_id(admin, &vec![env, agreement_id(env, 0)])
+    });
+}
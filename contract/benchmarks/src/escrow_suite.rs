@@ -0,0 +1,298 @@
+//! Every escrow entrypoint, measured with a book of funded escrows in
+//! storage, each securing an agreement of a chioma deployment that the
+//! escrow contract finds through a protocol directory.
+
+use chioma::{AgreementInput, AgreementTerms, Config, Contract, ContractClient};
+use escrow::{
+    escrow_impl::{EscrowContract, EscrowContractClient},
+    Installment, RateLimitConfig, RateLimitMode, TimeoutConfig,
+};
+use protocol_directory::Component;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String};
+
+use crate::harness::{
+    advance, create_token, deploy_directory, new_env, upload_upgrade_wasm, Bench, BenchVault,
+    BenchVaultClient,
+};
+
+/// Funded escrows held by the contract when the entrypoints run.
+const ESCROWS: u32 = 20;
+const DEPOSIT: i128 = 2_000;
+const DAY: u64 = 86_400;
+
+#[test]
+fn escrow_entrypoints() {
+    let env = new_env();
+    let mut bench = Bench::new("escrow");
+    let client = EscrowContractClient::new(&env, &env.register(EscrowContract, ()));
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let token = create_token(&env, &admin);
+    token.mint(&depositor, &(DEPOSIT * (ESCROWS as i128 + 2)));
+    let text = |value: &str| String::from_str(&env, value);
+
+    bench.run(&env, "initialize_admin", || client.initialize_admin(&admin));
+    bench.run(&env, "update_admin", || client.update_admin(&admin, &admin));
+    bench.run(&env, "get_admin", || client.get_admin());
+    let timeouts = TimeoutConfig {
+        escrow_timeout_days: 30,
+        dispute_timeout_days: 14,
+        payment_timeout_days: 7,
+    };
+    bench.run(&env, "set_timeout_config", || {
+        client.set_timeout_config(&admin, &timeouts)
+    });
+    bench.run(&env, "get_timeout_config", || client.get_timeout_config());
+    // The default limits, stored explicitly.
+    let limits = RateLimitConfig {
+        max_calls_per_block: 10,
+        max_calls_per_user_per_day: 100,
+        cooldown_blocks: 0,
+        mode: RateLimitMode::LedgerBlocks,
+        window_seconds: DAY,
+    };
+    bench.run(&env, "set_rate_limit_config", || {
+        client.set_rate_limit_config(&admin, &limits)
+    });
+    bench.run(&env, "get_rate_limit_config", || {
+        client.get_rate_limit_config()
+    });
+
+    let chioma = deploy_chioma(&env, &admin);
+    let directory = deploy_directory(&env, &admin, &[(Component::Chioma, &chioma.address)]);
+    bench.run(&env, "set_directory", || {
+        client.set_directory(&admin, &directory)
+    });
+    bench.run(&env, "get_directory", || client.get_directory());
+
+    // Each escrow secures a fresh agreement drafted a second after the
+    // previous one.
+    let mut drafted = 0;
+    let mut draft = || {
+        advance(&env, 1);
+        drafted += 1;
+        let agreement_id = text(&format!("AGR-{drafted}"));
+        chioma.create_agreement(&AgreementInput {
+            agreement_id: agreement_id.clone(),
+            admin: beneficiary.clone(),
+            user: depositor.clone(),
+            agent: None,
+            terms: AgreementTerms {
+                monthly_rent: DEPOSIT / 2,
+                security_deposit: DEPOSIT,
+                start_date: env.ledger().timestamp() + DAY,
+                end_date: env.ledger().timestamp() + 365 * DAY,
+                agent_commission_rate: 0,
+            },
+            payment_token: token.address.clone(),
+            metadata_uri: text("ipfs://agreement"),
+            attributes: soroban_sdk::Vec::new(&env),
+        });
+        agreement_id
+    };
+    let create = |agreement_id: &String| {
+        client.create(
+            agreement_id,
+            &depositor,
+            &beneficiary,
+            &arbiter,
+            &governance,
+            &agent,
+            &DEPOSIT,
+            &token.address,
+        )
+    };
+    let mut escrows: Vec<BytesN<32>> = Vec::new();
+    for _ in 1..ESCROWS {
+        let escrow_id = create(&draft());
+        client.fund_escrow(&escrow_id, &depositor);
+        escrows.push(escrow_id);
+    }
+    let agreement_id = draft();
+    let escrow_id = bench.run(&env, "create", || create(&agreement_id));
+    let funded_agreement = draft();
+    bench.run(&env, "create_funded", || {
+        client.create_funded(
+            &funded_agreement,
+            &depositor,
+            &beneficiary,
+            &arbiter,
+            &governance,
+            &agent,
+            &DEPOSIT,
+            &token.address,
+        )
+    });
+    let new_landlord = Address::generate(&env);
+    bench.run(&env, "transfer_beneficiary", || {
+        client.transfer_beneficiary(&funded_agreement, &new_landlord)
+    });
+    bench.run(&env, "get_agreement_escrow", || {
+        client.get_agreement_escrow(&text(&format!("AGR-{ESCROWS}")))
+    });
+    bench.run(&env, "fund_escrow", || {
+        client.fund_escrow(&escrow_id, &depositor)
+    });
+    bench.run(&env, "get_escrow", || client.get_escrow(&escrow_id));
+    bench.run(&env, "get_escrow_status", || {
+        client.get_escrow_status(&escrow_id)
+    });
+    bench.run(&env, "is_escrow_frozen", || {
+        client.is_escrow_frozen(&escrow_id)
+    });
+
+    // Full release once two of the three parties agree.
+    let released = &escrows[0];
+    client.approve_release(released, &depositor, &beneficiary);
+    bench.run(&env, "get_approval_count", || {
+        client.get_approval_count(released, &beneficiary)
+    });
+    bench.run(&env, "approve_release", || {
+        client.approve_release(released, &beneficiary, &beneficiary)
+    });
+
+    // Partial release and release with a damage deduction.
+    let partial = &escrows[1];
+    client.approve_partial_release(partial, &depositor, &beneficiary);
+    bench.run(&env, "approve_partial_release", || {
+        client.approve_partial_release(partial, &arbiter, &beneficiary)
+    });
+    bench.run(&env, "release_escrow_partial", || {
+        client.release_escrow_partial(partial, &(DEPOSIT / 4), &beneficiary, &text("repairs"))
+    });
+    bench.run(&env, "get_release_history", || {
+        client.get_release_history(partial)
+    });
+    let deducted = &escrows[2];
+    client.approve_partial_release(deducted, &depositor, &depositor);
+    client.approve_partial_release(deducted, &beneficiary, &depositor);
+    bench.run(&env, "release_with_deduction", || {
+        client.release_with_deduction(deducted, &(DEPOSIT / 10), &text("cleaning"))
+    });
+
+    // Disputes.
+    let disputed = &escrows[3];
+    bench.run(&env, "initiate_dispute", || {
+        client.initiate_dispute(disputed, &depositor, &text("withheld deposit"))
+    });
+    bench.run(&env, "resolve_dispute", || {
+        client.resolve_dispute(disputed, &arbiter, &depositor)
+    });
+    let stale_dispute = &escrows[4];
+    client.initiate_dispute(stale_dispute, &beneficiary, &text("unpaid damages"));
+
+    // Freezes and rent release.
+    let frozen = &escrows[5];
+    bench.run(&env, "freeze_escrow", || {
+        client.freeze_escrow(frozen, &admin, &text("incident"))
+    });
+    bench.run(&env, "unfreeze_escrow", || {
+        client.unfreeze_escrow(frozen, &admin)
+    });
+    bench.run(&env, "release_rent", || {
+        client.release_rent(&escrows[6], &arbiter)
+    });
+
+    // A deposit moved into a yield vault on chioma's request.
+    let vault = env.register(BenchVault, ());
+    BenchVaultClient::new(&env, &vault).init(&token.address);
+    let routed = &escrows[9];
+    bench.run(&env, "route_to_vault", || {
+        client.route_to_vault(routed, &vault)
+    });
+    token.mint(&vault, &(DEPOSIT / 10));
+    bench.run(&env, "get_vault_position", || {
+        client.get_vault_position(routed)
+    });
+    bench.run(&env, "get_vault_yield", || client.get_vault_yield(routed));
+    bench.run(&env, "withdraw_yield", || {
+        client.withdraw_yield(routed, &depositor, &(DEPOSIT / 100))
+    });
+    bench.run(&env, "compound_yield", || {
+        client.compound_yield(routed, &(DEPOSIT / 100))
+    });
+    bench.run(&env, "close_vault_position", || {
+        client.close_vault_position(routed)
+    });
+
+    // Installment funding, left half paid past its deadline.
+    let installments = create(&draft());
+    let now = env.ledger().timestamp();
+    let schedule = vec![
+        &env,
+        Installment {
+            due_at: now + DAY,
+            amount: DEPOSIT / 2,
+        },
+        Installment {
+            due_at: now + 2 * DAY,
+            amount: DEPOSIT / 2,
+        },
+    ];
+    bench.run(&env, "set_installment_plan", || {
+        client.set_installment_plan(&installments, &schedule)
+    });
+    bench.run(&env, "fund_installment", || {
+        client.fund_installment(&installments, &depositor, &(DEPOSIT / 2))
+    });
+
+    // Timeouts.
+    advance(&env, 31 * DAY);
+    bench.run(&env, "resolve_dispute_on_timeout", || {
+        client.resolve_dispute_on_timeout(stale_dispute)
+    });
+    bench.run(&env, "release_escrow_on_timeout", || {
+        client.release_escrow_on_timeout(&escrows[7])
+    });
+    bench.run(&env, "withdraw_safety_deposit", || {
+        client.withdraw_safety_deposit(&escrows[8], &depositor)
+    });
+    bench.run(&env, "refund_installments_on_timeout", || {
+        client.refund_installments_on_timeout(&installments)
+    });
+
+    // Upgrades.
+    let proposal_id = text("UPG-BENCH");
+    let wasm_hash = upload_upgrade_wasm(&env);
+    bench.run(&env, "propose_upgrade", || {
+        client.propose_upgrade(&admin, &proposal_id, &wasm_hash, &text("v2"), &0)
+    });
+    bench.run(&env, "approve_upgrade", || {
+        client.approve_upgrade(&admin, &proposal_id)
+    });
+    bench.run(&env, "get_upgrade_proposal", || {
+        client.get_upgrade_proposal(&proposal_id)
+    });
+    bench.run(&env, "get_contract_version", || {
+        client.get_contract_version()
+    });
+    bench.run(&env, "get_storage_version", || client.get_storage_version());
+    bench.run(&env, "execute_upgrade", || {
+        client.execute_upgrade(&admin, &proposal_id)
+    });
+    env.register_at(&client.address, EscrowContract, ());
+    bench.run(&env, "migrate", || client.migrate(&admin));
+    bench.run(&env, "migrate_records", || {
+        client.migrate_records(&admin, &0, &ESCROWS)
+    });
+    bench.run(&env, "migrate_records_by_id", || {
+        client.migrate_records_by_id(&admin, &vec![&env, escrow_id.clone()])
+    });
+
+    bench.finish();
+}
+
+fn deploy_chioma<'a>(env: &Env, admin: &Address) -> ContractClient<'a> {
+    let chioma = ContractClient::new(env, &env.register(Contract, ()));
+    let config = Config {
+        fee_bps: 100,
+        fee_collector: Address::generate(env),
+        paused: false,
+    };
+    chioma.initialize(admin, &config);
+    chioma
+}
@@ -0,0 +1,348 @@
+//! Measurement and baseline comparison shared by the suites.
+
+use std::{collections::BTreeMap, fmt::Write as _, fs, path::PathBuf};
+
+use protocol_directory::{
+    Component, ComponentUpdate, ProtocolDirectoryContract, ProtocolDirectoryContractClient,
+};
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    testutils::{EnvTestConfig, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Bytes, BytesN, Env,
+};
+
+/// Growth over the baseline, in percent, tolerated before a metric counts
+/// as a regression.
+pub const TOLERANCE_PERCENT: u64 = 10;
+
+/// Set to rewrite the baselines from the current run instead of checking.
+pub const UPDATE_VAR: &str = "BENCH_UPDATE";
+
+const METRICS: [&str; 6] = [
+    "cpu_instructions",
+    "memory_bytes",
+    "read_entries",
+    "write_entries",
+    "read_bytes",
+    "write_bytes",
+];
+
+/// Resources metered for one top-level invocation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Measurement {
+    pub cpu_instructions: u64,
+    pub memory_bytes: u64,
+    pub read_entries: u64,
+    pub write_entries: u64,
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+}
+
+impl Measurement {
+    fn values(&self) -> [u64; 6] {
+        [
+            self.cpu_instructions,
+            self.memory_bytes,
+            self.read_entries,
+            self.write_entries,
+            self.read_bytes,
+            self.write_bytes,
+        ]
+    }
+
+    fn from_values(values: [u64; 6]) -> Self {
+        Self {
+            cpu_instructions: values[0],
+            memory_bytes: values[1],
+            read_entries: values[2],
+            write_entries: values[3],
+            read_bytes: values[4],
+            write_bytes: values[5],
+        }
+    }
+}
+
+/// An environment that meters invocations, authorizes every call (including
+/// a tenant's nested authorization of the escrow chioma funds) and does not
+/// write test snapshots.
+pub fn new_env() -> Env {
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths_allowing_non_root_auth();
+    env
+}
+
+/// Minimal WASM module the host accepts as contract code.
+const UPGRADE_WASM: &[u8] = b"\0asm\x01\0\0\0\0\x1e\x11contractenvmetav0\0\0\0\0\0\0\0\x17\0\0\0\0";
+
+/// Upload `UPGRADE_WASM` so an upgrade to its hash can be executed.
+pub fn upload_upgrade_wasm(env: &Env) -> BytesN<32> {
+    env.deployer()
+        .upload_contract_wasm(Bytes::from_slice(env, UPGRADE_WASM))
+}
+
+/// Register a Stellar asset contract administered by `admin`.
+pub fn create_token<'a>(env: &Env, admin: &Address) -> StellarAssetClient<'a> {
+    let address = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(env, &address)
+}
+
+/// A protocol directory governed by `admin` alone, listing `peers`.
+pub fn deploy_directory(env: &Env, admin: &Address, peers: &[(Component, &Address)]) -> Address {
+    let directory =
+        ProtocolDirectoryContractClient::new(env, &env.register(ProtocolDirectoryContract, ()));
+    directory.initialize(&vec![env, admin.clone()], &1, &0);
+    let mut entries = vec![env];
+    for (component, address) in peers {
+        entries.push_back(ComponentUpdate {
+            component: *component,
+            address: (*address).clone(),
+            version: 1,
+        });
+    }
+    let proposal_id = soroban_sdk::String::from_str(env, "DIR-1");
+    directory.propose_update(admin, &proposal_id, &entries, &0);
+    directory.execute_update(admin, &proposal_id);
+    directory.address
+}
+
+/// Move the ledger forward by `seconds` and one sequence number, which also
+/// starts a fresh per-block rate limit window.
+pub fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|ledger| {
+        ledger.timestamp += seconds;
+        ledger.sequence_number += 1;
+    });
+}
+
+// ─── Yield vault ─────────────────────────────────────────────────────────────
+
+#[contracttype]
+enum VaultKey {
+    Token,
+    TotalShares,
+    Shares(Address),
+}
+
+/// Minimal share-based vault: yield is simulated by minting tokens to it.
+#[contract]
+pub struct BenchVault;
+
+#[contractimpl]
+impl BenchVault {
+    pub fn init(env: Env, token: Address) {
+        env.storage().instance().set(&VaultKey::Token, &token);
+    }
+
+    pub fn deposit(env: Env, from: Address, amount: i128) -> i128 {
+        from.require_auth();
+        let token = Self::token(&env);
+        let assets = token.balance(&env.current_contract_address());
+        let total = Self::total_shares(&env);
+        let shares = if total == 0 || assets == 0 {
+            amount
+        } else {
+            amount * total / assets
+        };
+        token.transfer(&from, env.current_contract_address(), &amount);
+        Self::credit(&env, &from, shares);
+        shares
+    }
+
+    pub fn withdraw(env: Env, owner: Address, to: Address, amount: i128) -> i128 {
+        owner.require_auth();
+        let token = Self::token(&env);
+        let assets = token.balance(&env.current_contract_address());
+        let total = Self::total_shares(&env);
+        let shares = (amount * total + assets - 1) / assets;
+        Self::credit(&env, &owner, -shares);
+        token.transfer(&env.current_contract_address(), &to, &amount);
+        shares
+    }
+
+    pub fn convert_to_assets(env: Env, shares: i128) -> i128 {
+        let total = Self::total_shares(&env);
+        if total == 0 {
+            return 0;
+        }
+        shares * Self::token(&env).balance(&env.current_contract_address()) / total
+    }
+}
+
+impl BenchVault {
+    fn token(env: &Env) -> TokenClient<'_> {
+        let token: Address = env.storage().instance().get(&VaultKey::Token).unwrap();
+        TokenClient::new(env, &token)
+    }
+
+    fn total_shares(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&VaultKey::TotalShares)
+            .unwrap_or(0)
+    }
+
+    fn credit(env: &Env, owner: &Address, shares: i128) {
+        let key = VaultKey::Shares(owner.clone());
+        let balance: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(balance + shares));
+        env.storage()
+            .instance()
+            .set(&VaultKey::TotalShares, &(Self::total_shares(env) + shares));
+    }
+}
+
+/// Collects the measurements of one contract's suite.
+pub struct Bench {
+    contract: &'static str,
+    results: BTreeMap<String, Measurement>,
+}
+
+impl Bench {
+    pub fn new(contract: &'static str) -> Self {
+        Self {
+            contract,
+            results: BTreeMap::new(),
+        }
+    }
+
+    /// Run `call`, which must make exactly one contract invocation, and
+    /// record what it consumed under `name`.
+    pub fn run<T>(&mut self, env: &Env, name: &str, call: impl FnOnce() -> T) -> T {
+        let output = call();
+        let resources = env.cost_estimate().resources();
+        let measurement = Measurement {
+            cpu_instructions: resources.instructions as u64,
+            memory_bytes: resources.mem_bytes as u64,
+            read_entries: (resources.disk_read_entries + resources.memory_read_entries) as u64,
+            write_entries: resources.write_entries as u64,
+            read_bytes: resources.disk_read_bytes as u64,
+            write_bytes: resources.write_bytes as u64,
+        };
+        let previous = self.results.insert(name.to_string(), measurement);
+        assert!(previous.is_none(), "{name} measured twice");
+        output
+    }
+
+    fn baseline_path(&self) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("baselines")
+            .join(format!("{}.txt", self.contract))
+    }
+
+    /// Compare the run with the stored baseline, or rewrite the baseline
+    /// when `BENCH_UPDATE` is set. Panics listing every regression, every
+    /// operation missing from the baseline and every stale baseline row.
+    pub fn finish(self) {
+        let path = self.baseline_path();
+        if std::env::var_os(UPDATE_VAR).is_some() {
+            fs::write(&path, self.render()).expect("write baseline");
+            return;
+        }
+
+        let stored = fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "no baseline at {}; run `{UPDATE_VAR}=1 cargo test -p benchmarks`",
+                path.display()
+            )
+        });
+        let baseline = parse(&stored);
+
+        let mut problems = Vec::new();
+        for (name, current) in &self.results {
+            let Some(expected) = baseline.get(name) else {
+                problems.push(format!("{name}: not in baseline"));
+                continue;
+            };
+            for ((metric, now), before) in
+                METRICS.iter().zip(current.values()).zip(expected.values())
+            {
+                if now > before + before * TOLERANCE_PERCENT / 100 {
+                    problems.push(format!("{name}: {metric} {before} -> {now}"));
+                }
+            }
+        }
+        for name in baseline.keys() {
+            if !self.results.contains_key(name) {
+                problems.push(format!("{name}: in baseline but no longer measured"));
+            }
+        }
+
+        assert!(
+            problems.is_empty(),
+            "{} benchmarks do not match {}:\n  {}\nIf the change is intended, run `{UPDATE_VAR}=1 cargo test -p benchmarks`.",
+            self.contract,
+            path.display(),
+            problems.join("\n  ")
+        );
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        writeln!(
+            out,
+            "# Resource baseline for the {} contract; see benchmarks/src/lib.rs.",
+            self.contract
+        )
+        .unwrap();
+        writeln!(out, "# operation {}", METRICS.join(" ")).unwrap();
+        for (name, measurement) in &self.results {
+            let values: Vec<String> = measurement.values().iter().map(u64::to_string).collect();
+            writeln!(out, "{name} {}", values.join(" ")).unwrap();
+        }
+        out
+    }
+}
+
+fn parse(baseline: &str) -> BTreeMap<String, Measurement> {
+    baseline
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next().unwrap().to_string();
+            let values: Vec<u64> = fields
+                .map(|field| field.parse().expect("baseline value"))
+                .collect();
+            let values: [u64; 6] = values
+                .try_into()
+                .unwrap_or_else(|_| panic!("{name}: expected {} values", METRICS.len()));
+            (name, Measurement::from_values(values))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(cpu_instructions: u64, write_entries: u64) -> Measurement {
+        Measurement {
+            cpu_instructions,
+            memory_bytes: 1_000,
+            read_entries: 3,
+            write_entries,
+            read_bytes: 0,
+            write_bytes: 200,
+        }
+    }
+
+    #[test]
+    fn test_render_round_trips_through_parse() {
+        let mut bench = Bench::new("sample");
+        bench
+            .results
+            .insert("pay".to_string(), measurement(50_000, 2));
+        bench
+            .results
+            .insert("get".to_string(), measurement(9_000, 0));
+
+        let parsed = parse(&bench.render());
+        assert_eq!(parsed, bench.results);
+    }
+}
@@ -0,0 +1,31 @@
+//! Resource benchmarks for the chioma, escrow and payment contracts.
+//!
+//! Every public entrypoint is invoked once against state sized like a busy
+//! deployment (see the constants at the top of each suite), and the
+//! resources the host metered for that invocation are recorded:
+//!
+//! - `cpu_instructions` and `memory_bytes` from the host budget;
+//! - `read_entries` and `write_entries`, the ledger entries touched;
+//! - `read_bytes` and `write_bytes`, the ledger bytes read from disk and
+//!   written back.
+//!
+//! Each suite compares its run with `baselines/<contract>.txt` and fails when
+//! any metric of any entrypoint grows by more than
+//! [`harness::TOLERANCE_PERCENT`], when an entrypoint has no baseline row or
+//! when a row no longer matches a measured entrypoint. After an intended
+//! change, regenerate the files and commit them with the change:
+//!
+//! ```text
+//! BENCH_UPDATE=1 cargo test -p benchmarks
+//! ```
+//!
+//! The contracts are registered natively, so the figures cover host
+//! functions and storage but not WASM execution; they track relative cost
+//! between revisions rather than the exact fee of a network transaction.
+
+#![cfg(test)]
+
+mod chioma_suite;
+mod escrow_suite;
+mod harness;
+mod payment_suite;
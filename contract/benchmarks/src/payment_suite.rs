@@ -0,0 +1,247 @@
+//! Every payment entrypoint, measured against an agreement with a year of
+//! rent history and a full set of recurring payments.
+
+use payment::{
+    types::{AgreementStatus, RentAgreement},
+    DataKey, EscalationType, PaymentContract, PaymentContractClient, PaymentFrequency,
+    PaymentRecord, RateLimitConfig, RateLimitMode, RecurringPayment, RecurringStatus,
+};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Map, String};
+
+use crate::harness::{advance, create_token, new_env, upload_upgrade_wasm, Bench};
+
+/// Rent payments already made on the benchmarked agreement. Payment ids are
+/// rendered by the contract for counts up to ten only.
+const PAYMENTS: u32 = 10;
+/// Recurring payments set up before the recurring entrypoints run; the same
+/// ten-id limit applies.
+const RECURRING: u32 = 10;
+const RENT: i128 = 1_000;
+const MONTH: u64 = 30 * 86_400;
+
+fn id(env: &Env, value: impl ToString) -> String {
+    String::from_str(env, &value.to_string())
+}
+
+fn seed<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(
+    env: &Env,
+    client: &PaymentContractClient,
+    key: &DataKey,
+    value: &V,
+) {
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(key, value);
+    });
+}
+
+#[test]
+fn payment_entrypoints() {
+    let env = new_env();
+    let mut bench = Bench::new("payment");
+    let client = PaymentContractClient::new(&env, &env.register(PaymentContract, ()));
+    let admin = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token = create_token(&env, &admin);
+    token.mint(&tenant, &(RENT * 100));
+
+    bench.run(&env, "initialize_admin", || client.initialize_admin(&admin));
+    bench.run(&env, "set_platform_fee_collector", || {
+        client.set_platform_fee_collector(&collector)
+    });
+    // The default limits, stored explicitly.
+    let limits = RateLimitConfig {
+        max_calls_per_block: 10,
+        max_calls_per_user_per_day: 100,
+        cooldown_blocks: 0,
+        mode: RateLimitMode::LedgerBlocks,
+        window_seconds: 86_400,
+    };
+    bench.run(&env, "set_rate_limit_config", || {
+        client.set_rate_limit_config(&admin, &limits)
+    });
+    bench.run(&env, "get_rate_limit_config", || {
+        client.get_rate_limit_config()
+    });
+
+    let agreement_id = id(&env, "AGR-BENCH");
+    let agreement = RentAgreement {
+        agreement_id: agreement_id.clone(),
+        landlord: landlord.clone(),
+        tenant: tenant.clone(),
+        agent: None,
+        monthly_rent: RENT,
+        security_deposit: RENT * 2,
+        start_date: 0,
+        end_date: 365 * 86_400,
+        agent_commission_rate: 0,
+        status: AgreementStatus::Active,
+        total_rent_paid: 0,
+        payment_count: 0,
+        signed_at: Some(0),
+        payment_token: token.address.clone(),
+        next_payment_due: 0,
+        payment_history: Map::new(&env),
+    };
+    seed(
+        &env,
+        &client,
+        &DataKey::Agreement(agreement_id.clone()),
+        &agreement,
+    );
+    for number in 0..PAYMENTS {
+        let record = PaymentRecord {
+            agreement_id: agreement_id.clone(),
+            payment_number: number + 1,
+            amount: RENT,
+            landlord_amount: RENT,
+            agent_amount: 0,
+            timestamp: number as u64 * MONTH,
+            tenant: tenant.clone(),
+        };
+        seed(&env, &client, &DataKey::Payment(id(&env, number)), &record);
+    }
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::PaymentCount, &PAYMENTS);
+    });
+
+    // Rent history.
+    for _ in 1..PAYMENTS {
+        client.pay_rent(&tenant, &agreement_id, &RENT);
+        advance(&env, MONTH);
+    }
+    bench.run(&env, "pay_rent", || {
+        client.pay_rent(&tenant, &agreement_id, &RENT)
+    });
+    bench.run(&env, "get_payment", || client.get_payment(&id(&env, 0)));
+    bench.run(&env, "get_payment_count", || client.get_payment_count());
+    bench.run(&env, "get_total_paid", || {
+        client.get_total_paid(&agreement_id)
+    });
+    bench.run(&env, "get_payment_split", || {
+        client.get_payment_split(&agreement_id, &(PAYMENTS - 1))
+    });
+
+    // Escalation.
+    bench.run(&env, "set_rent_escalation_config", || {
+        client.set_rent_escalation_config(&agreement_id, &300, &12, &EscalationType::FixedAnnual)
+    });
+    bench.run(&env, "get_rent_escalation_config", || {
+        client.get_rent_escalation_config(&agreement_id)
+    });
+    bench.run(&env, "calculate_rent_for_period", || {
+        client.calculate_rent_for_period(&agreement_id, &24)
+    });
+
+    // Late fees.
+    let late_payment = id(&env, PAYMENTS);
+    bench.run(&env, "set_late_fee_config", || {
+        client.set_late_fee_config(&agreement_id, &5, &3, &(RENT / 2), &true)
+    });
+    bench.run(&env, "get_late_fee_config", || {
+        client.get_late_fee_config(&agreement_id)
+    });
+    bench.run(&env, "calculate_late_fee", || {
+        client.calculate_late_fee(&agreement_id, &late_payment, &10)
+    });
+    advance(&env, MONTH + 10 * 86_400);
+    bench.run(&env, "apply_late_fee", || {
+        client.apply_late_fee(&agreement_id, &late_payment)
+    });
+    bench.run(&env, "get_late_fee_record", || {
+        client.get_late_fee_record(&late_payment)
+    });
+    bench.run(&env, "waive_late_fee", || {
+        client.waive_late_fee(&agreement_id, &late_payment, &id(&env, "hardship"))
+    });
+
+    // Recurring payments; ids run from 1 to RECURRING.
+    let start = env.ledger().timestamp();
+    let end = start + 12 * MONTH;
+    let create = || {
+        client.create_recurring_payment(
+            &agreement_id,
+            &RENT,
+            &PaymentFrequency::Monthly,
+            &start,
+            &end,
+            &false,
+        )
+    };
+    for _ in 1..RECURRING {
+        create();
+    }
+    let last = bench.run(&env, "create_recurring_payment", create);
+    bench.run(&env, "get_recurring_payment", || {
+        client.get_recurring_payment(&last)
+    });
+    bench.run(&env, "execute_recurring_payment", || {
+        client.execute_recurring_payment(&last)
+    });
+    bench.run(&env, "get_payment_executions", || {
+        client.get_payment_executions(&last)
+    });
+    bench.run(&env, "get_due_payments", || client.get_due_payments());
+    bench.run(&env, "process_due_payments", || {
+        client.process_due_payments()
+    });
+
+    let paused = id(&env, 1);
+    bench.run(&env, "pause_recurring_payment", || {
+        client.pause_recurring_payment(&paused)
+    });
+    bench.run(&env, "resume_recurring_payment", || {
+        client.resume_recurring_payment(&paused)
+    });
+    bench.run(&env, "cancel_recurring_payment", || {
+        client.cancel_recurring_payment(&id(&env, 2))
+    });
+
+    let failed = id(&env, 3);
+    let mut recurring: RecurringPayment = client.get_recurring_payment(&failed);
+    recurring.status = RecurringStatus::Failed;
+    seed(
+        &env,
+        &client,
+        &DataKey::RecurringPayment(failed.clone()),
+        &recurring,
+    );
+    seed(
+        &env,
+        &client,
+        &DataKey::FailedRecurringPayments,
+        &vec![&env, failed.clone()],
+    );
+    bench.run(&env, "get_failed_payments", || client.get_failed_payments());
+    advance(&env, MONTH);
+    bench.run(&env, "retry_failed_payment", || {
+        client.retry_failed_payment(&failed)
+    });
+
+    // Upgrades.
+    let proposal_id = id(&env, "UPG-BENCH");
+    let wasm_hash = upload_upgrade_wasm(&env);
+    bench.run(&env, "propose_upgrade", || {
+        client.propose_upgrade(&admin, &proposal_id, &wasm_hash, &id(&env, "v2"), &0)
+    });
+    bench.run(&env, "approve_upgrade", || {
+        client.approve_upgrade(&admin, &proposal_id)
+    });
+    bench.run(&env, "get_upgrade_proposal", || {
+        client.get_upgrade_proposal(&proposal_id)
+    });
+    bench.run(&env, "get_contract_version", || {
+        client.get_contract_version()
+    });
+    bench.run(&env, "get_storage_version", || client.get_storage_version());
+    bench.run(&env, "execute_upgrade", || {
+        client.execute_upgrade(&admin, &proposal_id)
+    });
+    env.register_at(&client.address, PaymentContract, ());
+    bench.run(&env, "migrate", || client.migrate(&admin));
+
+    bench.finish();
+}
@@ -190,7 +190,7 @@ impl AgentRegistryContract {
         env: Env,
         proposer: Address,
         proposal_id: String,
-        wasm_hash: soroban_sdk::Bytes,
+        wasm_hash: soroban_sdk::BytesN<32>,
         notes: String,
         delay_seconds: u64,
     ) -> Result<(), AgentError> {
@@ -222,4 +222,19 @@ impl AgentRegistryContract {
     ) -> Result<upgrade::UpgradeProposal, AgentError> {
         upgrade::get_upgrade_proposal(&env, proposal_id)
     }
+
+    /// Run pending storage migrations after an upgrade (admin only).
+    pub fn migrate(env: Env, caller: Address) -> Result<u32, AgentError> {
+        upgrade::migrate(&env, caller)
+    }
+
+    /// Get the code version, bumped by every executed upgrade.
+    pub fn get_contract_version(env: Env) -> u32 {
+        upgrade::get_contract_version(&env)
+    }
+
+    /// Get the layout version of the stored data.
+    pub fn get_storage_version(env: Env) -> u32 {
+        upgrade::get_storage_version(&env)
+    }
 }
@@ -10,4 +10,7 @@ pub enum DataKey {
     Transaction(String),
     AgentRating(Address, Address),
     UpgradeProposal(String),
+    ContractVersion,
+    StorageVersion,
+    MigratedVersion,
 }
@@ -1,9 +1,18 @@
 use super::*;
 use soroban_sdk::{
     testutils::{Address as _, Events},
-    vec, Address, Bytes, Env, String,
+    vec, Address, Env, String,
 };
 
+/// Minimal WASM module the host accepts as contract code.
+const UPGRADE_WASM: &[u8] = b"\0asm\x01\0\0\0\0\x1e\x11contractenvmetav0\0\0\0\0\0\0\0\x17\0\0\0\0";
+
+/// Upload `UPGRADE_WASM` so an upgrade to its hash can be executed.
+pub(crate) fn upload_upgrade_wasm(env: &Env) -> soroban_sdk::BytesN<32> {
+    env.deployer()
+        .upload_contract_wasm(soroban_sdk::Bytes::from_slice(env, UPGRADE_WASM))
+}
+
 fn create_contract(env: &Env) -> AgentRegistryContractClient<'_> {
     let contract_id = env.register(AgentRegistryContract, ());
     AgentRegistryContractClient::new(env, &contract_id)
@@ -973,7 +982,7 @@ fn test_upgrade_propose_fails_when_not_initialized() {
     env.mock_all_auths();
     let proposer = Address::generate(&env);
     let pid = String::from_str(&env, "UPGRADE-001");
-    let wasm_hash = Bytes::from_array(&env, &[0u8; 32]);
+    let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
     let notes = String::from_str(&env, "test upgrade");
     client.propose_upgrade(&proposer, &pid, &wasm_hash, &notes, &1000);
 }
@@ -988,7 +997,7 @@ fn test_upgrade_propose_fails_when_not_admin() {
     client.initialize(&admin);
 
     let pid = String::from_str(&env, "UPGRADE-002");
-    let wasm_hash = Bytes::from_array(&env, &[0u8; 32]);
+    let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
     let notes = String::from_str(&env, "test upgrade");
     let result = client.try_propose_upgrade(&proposer, &pid, &wasm_hash, &notes, &1000);
     assert_eq!(result, Err(Ok(AgentError::Unauthorized)));
@@ -1003,7 +1012,7 @@ fn test_upgrade_propose_and_get() {
     client.initialize(&admin);
 
     let pid = String::from_str(&env, "UPGRADE-003");
-    let wasm_hash = Bytes::from_array(&env, &[1u8; 32]);
+    let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[1u8; 32]);
     let notes = String::from_str(&env, "valid upgrade");
     let result = client.try_propose_upgrade(&admin, &pid, &wasm_hash, &notes, &1000);
     assert!(result.is_ok());
@@ -1015,3 +1024,43 @@ fn test_upgrade_propose_and_get() {
     assert_eq!(proposal.notes, notes);
     assert!(!proposal.executed);
 }
+
+#[test]
+fn test_execute_upgrade_installs_wasm_and_migrates_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let proposal_id = String::from_str(&env, "UPG-001");
+    let wasm_hash = upload_upgrade_wasm(&env);
+    client.propose_upgrade(
+        &admin,
+        &proposal_id,
+        &wasm_hash,
+        &String::from_str(&env, "v2"),
+        &0,
+    );
+
+    // Nothing to migrate before an upgrade.
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(AgentError::AlreadyInitialized))
+    );
+
+    client.execute_upgrade(&admin, &proposal_id);
+
+    // The installed module exports nothing; reinstall the native contract to
+    // stand in for the new code.
+    assert!(client.try_get_contract_version().is_err());
+    env.register_at(&client.address, AgentRegistryContract, ());
+
+    assert_eq!(client.get_contract_version(), 2);
+    assert_eq!(client.migrate(&admin), 1);
+    assert_eq!(client.get_storage_version(), 1);
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(AgentError::AlreadyInitialized))
+    );
+}
@@ -2,7 +2,7 @@ use crate::errors::AgentError;
 use crate::events;
 use crate::storage::DataKey;
 use crate::types::ContractState;
-use soroban_sdk::{Address, Bytes, Env, String, Vec};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
 /// Contract upgrade proposal
 #[derive(Clone)]
@@ -10,7 +10,7 @@ use soroban_sdk::{Address, Bytes, Env, String, Vec};
 pub struct UpgradeProposal {
     pub id: String,
     pub proposer: Address,
-    pub wasm_hash: Bytes,
+    pub wasm_hash: BytesN<32>,
     pub approvals: Vec<Address>,
     pub required_signatures: u32,
     pub eta: u64,
@@ -24,7 +24,7 @@ pub fn propose_upgrade(
     env: &Env,
     proposer: Address,
     proposal_id: String,
-    wasm_hash: Bytes,
+    wasm_hash: BytesN<32>,
     notes: String,
     delay_seconds: u64,
 ) -> Result<(), AgentError> {
@@ -154,7 +154,23 @@ pub fn execute_upgrade(
         .persistent()
         .set(&DataKey::UpgradeProposal(proposal_id.clone()), &proposal);
 
-    events::upgrade_executed(env, proposal_id, executor);
+    let version = get_contract_version(env) + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::ContractVersion, &version);
+
+    events::upgrade_executed(
+        env,
+        proposal_id,
+        executor,
+        proposal.wasm_hash.clone(),
+        version,
+    );
+
+    // The new code takes over once this invocation returns; `migrate` then
+    // brings stored data up to date.
+    env.deployer()
+        .update_current_contract_wasm(proposal.wasm_hash);
 
     Ok(())
 }
@@ -166,3 +182,80 @@ pub fn get_upgrade_proposal(env: &Env, proposal_id: String) -> Result<UpgradePro
         .get(&DataKey::UpgradeProposal(proposal_id))
         .ok_or(AgentError::NotInitialized)
 }
+
+// ─── Versioning and Migration ─────────────────────────────────────────────────
+
+/// Storage layout this build reads and writes. Bump it, and add a step to
+/// `run_migration`, whenever persisted data changes shape.
+pub const STORAGE_VERSION: u32 = 1;
+
+fn require_admin(env: &Env, caller: &Address) -> Result<(), AgentError> {
+    let state = env
+        .storage()
+        .instance()
+        .get::<DataKey, ContractState>(&DataKey::State)
+        .ok_or(AgentError::NotInitialized)?;
+    if *caller != state.admin {
+        return Err(AgentError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Version of the running code: 1 at deployment, bumped by every executed upgrade.
+pub fn get_contract_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ContractVersion)
+        .unwrap_or(1)
+}
+
+/// Layout of the stored data. Data written before layouts were tracked is
+/// in layout 1.
+pub fn get_storage_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::StorageVersion)
+        .unwrap_or(1)
+}
+
+/// Bring stored data up to `STORAGE_VERSION` after an upgrade (admin only).
+///
+/// Runs once per executed upgrade and returns the resulting layout version.
+pub fn migrate(env: &Env, caller: Address) -> Result<u32, AgentError> {
+    caller.require_auth();
+    require_admin(env, &caller)?;
+
+    let version = get_contract_version(env);
+    let migrated: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::MigratedVersion)
+        .unwrap_or(1);
+    let from = get_storage_version(env);
+    if migrated >= version || from > STORAGE_VERSION {
+        return Err(AgentError::AlreadyInitialized);
+    }
+
+    for step in (from + 1)..=STORAGE_VERSION {
+        run_migration(env, step)?;
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::StorageVersion, &STORAGE_VERSION);
+    env.storage()
+        .instance()
+        .set(&DataKey::MigratedVersion, &version);
+
+    events::storage_migrated(env, version, from, STORAGE_VERSION);
+    Ok(STORAGE_VERSION)
+}
+
+/// Convert stored data from layout `step - 1` to layout `step`.
+fn run_migration(_env: &Env, step: u32) -> Result<(), AgentError> {
+    match step {
+        // Layout 1 is the original one; nothing to convert.
+        1 => Ok(()),
+        _ => Err(AgentError::AlreadyInitialized),
+    }
+}
@@ -0,0 +1,123 @@
+//! Named roles for the privileged entrypoints, and the pause guardian.
+//!
+//! Each admin entrypoint checks one `Role` instead of `ContractState.admin`.
+//! The initial admin holds every role; after that, roles are granted and
+//! revoked through multi-sig. Callers are responsible for `require_auth`.
+//!
+//! The guardian is a single address, also set through multi-sig, that may
+//! pause but never unpause. Its pauses always lift on their own.
+
+use soroban_sdk::{Address, Env};
+
+use crate::errors::RentalError;
+use crate::events;
+use crate::storage::{DataKey, GovernanceKey};
+use crate::types::Role;
+
+/// Longest pause the guardian can impose (3 days).
+pub const GUARDIAN_PAUSE_SECONDS: u64 = 3 * 24 * 60 * 60;
+
+/// Every role, in declaration order.
+pub const ALL_ROLES: [Role; 7] = [
+    Role::Pauser,
+    Role::Treasurer,
+    Role::RateSetter,
+    Role::TokenManager,
+    Role::Arbitrator,
+    Role::Upgrader,
+    Role::RateLimitAdmin,
+];
+
+fn role_key(role: Role, account: &Address) -> DataKey {
+    DataKey::Governance(GovernanceKey::Role(role, account.clone()))
+}
+
+pub fn has_role(env: &Env, account: &Address, role: Role) -> bool {
+    env.storage()
+        .instance()
+        .get(&role_key(role, account))
+        .unwrap_or(false)
+}
+
+/// Fail with `Unauthorized` unless `account` holds `role`.
+pub fn require_role(env: &Env, account: &Address, role: Role) -> Result<(), RentalError> {
+    if !has_role(env, account, role) {
+        return Err(RentalError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Grant `role` to `account`. Fails with `InvalidInput` if it already holds it.
+pub fn grant_role(env: &Env, role: Role, account: Address) -> Result<(), RentalError> {
+    if has_role(env, &account, role) {
+        return Err(RentalError::InvalidInput);
+    }
+    env.storage()
+        .instance()
+        .set(&role_key(role, &account), &true);
+    events::role_granted(env, role, account);
+    Ok(())
+}
+
+/// Revoke `role` from `account`. Fails with `InvalidInput` if it does not
+/// hold it.
+pub fn revoke_role(env: &Env, role: Role, account: Address) -> Result<(), RentalError> {
+    if !has_role(env, &account, role) {
+        return Err(RentalError::InvalidInput);
+    }
+    env.storage().instance().remove(&role_key(role, &account));
+    events::role_revoked(env, role, account);
+    Ok(())
+}
+
+/// Grant every role `account` does not hold yet.
+pub fn grant_all_roles(env: &Env, account: &Address) {
+    for role in ALL_ROLES {
+        if !has_role(env, account, role) {
+            // Cannot fail: the role is not held.
+            let _ = grant_role(env, role, account.clone());
+        }
+    }
+}
+
+pub fn get_guardian(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Governance(GovernanceKey::Guardian))
+}
+
+/// Replace the guardian, or remove it with `None`. Returns the previous one.
+pub fn set_guardian(env: &Env, guardian: Option<Address>) -> Option<Address> {
+    let key = DataKey::Governance(GovernanceKey::Guardian);
+    let old = get_guardian(env);
+    match &guardian {
+        Some(guardian) => env.storage().instance().set(&key, guardian),
+        None => env.storage().instance().remove(&key),
+    }
+    events::guardian_updated(env, old.clone(), guardian);
+    old
+}
+
+/// Check that `caller` may pause and return when a pause it requests for
+/// `duration` seconds lifts. `Pauser` holders may pause indefinitely with a
+/// zero duration; the guardian's pauses are capped at
+/// `GUARDIAN_PAUSE_SECONDS`.
+pub fn pause_deadline(
+    env: &Env,
+    caller: &Address,
+    duration: u64,
+) -> Result<Option<u64>, RentalError> {
+    let now = env.ledger().timestamp();
+    if has_role(env, caller, Role::Pauser) {
+        return Ok((duration > 0).then(|| now.saturating_add(duration)));
+    }
+    if get_guardian(env).as_ref() == Some(caller) {
+        let duration = if duration == 0 {
+            GUARDIAN_PAUSE_SECONDS
+        } else {
+            duration.min(GUARDIAN_PAUSE_SECONDS)
+        };
+        return Ok(Some(now.saturating_add(duration)));
+    }
+    Err(RentalError::Unauthorized)
+}
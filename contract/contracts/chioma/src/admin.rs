@@ -0,0 +1,237 @@
+//! Admin state changes shared by the admin entrypoints and governance.
+//!
+//! Callers are responsible for authorisation; these functions only validate
+//! and apply the change.
+
+use soroban_sdk::{contracttype, unwrap::UnwrapOptimized, Address, Env, String, TryFromVal, Val};
+
+use crate::audit;
+use crate::errors::RentalError;
+use crate::events;
+use crate::storage::{has_field, DataKey, GovernanceKey};
+use crate::types::{Config, ContractState, PauseModule, PauseState};
+
+/// `PauseState` as stored before pauses could expire.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct LegacyPauseState {
+    is_paused: bool,
+    paused_at: u64,
+    paused_by: Address,
+    pause_reason: String,
+}
+
+/// The state as stored, where `config.paused` is not maintained.
+fn load_state(env: &Env) -> Result<ContractState, RentalError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::State)
+        .ok_or(RentalError::InvalidState)
+}
+
+/// The contract state, with `config.paused` reflecting the pause record.
+pub fn get_state(env: &Env) -> Result<ContractState, RentalError> {
+    let mut state = load_state(env)?;
+    state.config.paused = is_paused(env);
+    Ok(state)
+}
+
+/// Store `state`. Whether the contract is paused lives in the pause record
+/// alone, so that an expiring pause cannot leave a stale flag behind.
+pub(crate) fn save_state(env: &Env, state: &ContractState) {
+    let mut state = state.clone();
+    state.config.paused = false;
+    env.storage().instance().set(&DataKey::State, &state);
+    env.storage().instance().extend_ttl(500000, 500000);
+}
+
+/// Read a pause record, accepting the layout written before pauses could
+/// expire.
+fn load_pause_state(env: &Env, key: &DataKey) -> Option<PauseState> {
+    let raw: Val = env.storage().instance().get(key)?;
+    if has_field(env, &raw, "auto_unpause_at") {
+        return Some(PauseState::try_from_val(env, &raw).unwrap_optimized());
+    }
+    let legacy = LegacyPauseState::try_from_val(env, &raw).unwrap_optimized();
+    Some(PauseState {
+        is_paused: legacy.is_paused,
+        paused_at: legacy.paused_at,
+        paused_by: legacy.paused_by,
+        pause_reason: legacy.pause_reason,
+        auto_unpause_at: None,
+    })
+}
+
+fn is_active(env: &Env, pause_state: &PauseState) -> bool {
+    pause_state.is_paused
+        && pause_state
+            .auto_unpause_at
+            .is_none_or(|at| env.ledger().timestamp() < at)
+}
+
+pub fn get_pause_state(env: &Env) -> Option<PauseState> {
+    load_pause_state(env, &DataKey::PauseState)
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    get_pause_state(env).is_some_and(|pause_state| is_active(env, &pause_state))
+}
+
+pub fn set_pause_state(
+    env: &Env,
+    admin: Address,
+    reason: String,
+    auto_unpause_at: Option<u64>,
+) -> PauseState {
+    let pause_state = PauseState {
+        is_paused: true,
+        paused_at: env.ledger().timestamp(),
+        paused_by: admin,
+        pause_reason: reason,
+        auto_unpause_at,
+    };
+
+    env.storage()
+        .instance()
+        .set(&DataKey::PauseState, &pause_state);
+    env.storage().instance().extend_ttl(500000, 500000);
+
+    pause_state
+}
+
+/// Replace the contract configuration, pausing or unpausing to match
+/// `new_config.paused`. Returns the previous configuration.
+pub fn apply_config(env: &Env, by: Address, new_config: Config) -> Result<Config, RentalError> {
+    let mut state = get_state(env)?;
+    let was_paused = is_paused(env);
+
+    if new_config.fee_bps > 10_000 {
+        return Err(RentalError::InvalidConfig);
+    }
+
+    let old_config = state.config.clone();
+    state.config = new_config.clone();
+    save_state(env, &state);
+
+    if new_config.paused && !was_paused {
+        let reason = String::from_str(env, "Paused via config update");
+        set_pause_state(env, by.clone(), reason.clone(), None);
+        events::paused(env, reason, by.clone());
+    } else if !new_config.paused && was_paused {
+        env.storage().instance().remove(&DataKey::PauseState);
+        events::unpaused(env, by.clone());
+    }
+
+    audit::record(env, by.clone(), "update_config", None, None);
+    events::config_updated(env, by, old_config.clone(), new_config);
+    Ok(old_config)
+}
+
+/// Pause the whole contract, lifting on its own at `auto_unpause_at` if set.
+pub fn apply_pause(
+    env: &Env,
+    by: Address,
+    reason: String,
+    auto_unpause_at: Option<u64>,
+) -> Result<(), RentalError> {
+    get_state(env)?;
+    if is_paused(env) {
+        return Err(RentalError::AlreadyPaused);
+    }
+
+    set_pause_state(env, by.clone(), reason.clone(), auto_unpause_at);
+
+    audit::record(env, by.clone(), "pause", None, None);
+    events::paused(env, reason, by);
+    Ok(())
+}
+
+pub fn apply_unpause(env: &Env, by: Address) -> Result<(), RentalError> {
+    get_state(env)?;
+    if !is_paused(env) {
+        return Err(RentalError::NotPaused);
+    }
+
+    env.storage().instance().remove(&DataKey::PauseState);
+
+    audit::record(env, by.clone(), "unpause", None, None);
+    events::unpaused(env, by);
+    Ok(())
+}
+
+fn module_key(module: PauseModule) -> DataKey {
+    DataKey::Governance(GovernanceKey::ModulePause(module))
+}
+
+pub fn get_module_pause_state(env: &Env, module: PauseModule) -> Option<PauseState> {
+    load_pause_state(env, &module_key(module))
+}
+
+pub fn is_module_paused(env: &Env, module: PauseModule) -> bool {
+    get_module_pause_state(env, module).is_some_and(|pause_state| is_active(env, &pause_state))
+}
+
+/// Pause one module, lifting on its own at `auto_unpause_at` if set.
+pub fn apply_module_pause(
+    env: &Env,
+    module: PauseModule,
+    by: Address,
+    reason: String,
+    auto_unpause_at: Option<u64>,
+) -> Result<(), RentalError> {
+    if is_module_paused(env, module) {
+        return Err(RentalError::AlreadyPaused);
+    }
+
+    let pause_state = PauseState {
+        is_paused: true,
+        paused_at: env.ledger().timestamp(),
+        paused_by: by.clone(),
+        pause_reason: reason.clone(),
+        auto_unpause_at,
+    };
+    env.storage()
+        .instance()
+        .set(&module_key(module), &pause_state);
+
+    audit::record(env, by.clone(), "pause_module", None, None);
+    events::module_paused(env, module, by, reason, auto_unpause_at);
+    Ok(())
+}
+
+pub fn apply_module_unpause(
+    env: &Env,
+    module: PauseModule,
+    by: Address,
+) -> Result<(), RentalError> {
+    if !is_module_paused(env, module) {
+        return Err(RentalError::NotPaused);
+    }
+
+    env.storage().instance().remove(&module_key(module));
+
+    audit::record(env, by.clone(), "unpause_module", None, None);
+    events::module_unpaused(env, module, by);
+    Ok(())
+}
+
+/// Hand the contract admin role to `new_admin`. Returns the previous admin.
+pub fn apply_admin(env: &Env, new_admin: Address) -> Result<Address, RentalError> {
+    let mut state = get_state(env)?;
+    let old_admin = state.admin.clone();
+    state.admin = new_admin;
+    save_state(env, &state);
+    Ok(old_admin)
+}
+
+/// Give a pause recorded only in the stored config, as deployments that
+/// predate pause records kept it, a pause record of its own.
+pub fn migrate_config_pause(env: &Env) -> Result<(), RentalError> {
+    let state = load_state(env)?;
+    if state.config.paused && get_pause_state(env).is_none() {
+        let reason = String::from_str(env, "Paused via config update");
+        set_pause_state(env, state.admin.clone(), reason, None);
+    }
+    save_state(env, &state);
+    Ok(())
+}
@@ -1,19 +1,43 @@
 //! Agreement management logic for the Chioma/Rental contract.
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{unwrap::UnwrapOptimized, Address, BytesN, Env, String, TryFromVal, Val, Vec};
 
+use crate::audit;
 use crate::errors::RentalError;
 use crate::events;
+use crate::migration;
+use crate::peers::{self, EscrowClient};
 use crate::rate_limit;
 use crate::storage::DataKey;
 use crate::types::{
     AgreementExtension, AgreementStatus, ExtensionHistory, ExtensionStatus, PaymentSplit,
-    RentAgreement,
+    RentAgreement, VersionedAgreement,
 };
 
 const TTL_THRESHOLD: u32 = 500000;
 const TTL_BUMP: u32 = 500000;
 const SECONDS_PER_MONTH: u64 = 30 * 24 * 60 * 60;
 
+/// Read an agreement, upgrading it from whichever layout it was stored in.
+pub(crate) fn load_agreement(env: &Env, agreement_id: &String) -> Option<RentAgreement> {
+    let raw: Val = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Agreement(agreement_id.clone()))?;
+    if let Ok(record) = VersionedAgreement::try_from_val(env, &raw) {
+        return Some(record.into_current());
+    }
+    // Written before records were versioned: a bare `RentAgreement`.
+    Some(RentAgreement::try_from_val(env, &raw).unwrap_optimized())
+}
+
+/// Write an agreement in the current layout.
+pub(crate) fn save_agreement(env: &Env, agreement_id: &String, agreement: &RentAgreement) {
+    env.storage().persistent().set(
+        &DataKey::Agreement(agreement_id.clone()),
+        &VersionedAgreement::V1(agreement.clone()),
+    );
+}
+
 /// Validate agreement parameters
 ///
 /// Ensures monthly_rent is strictly positive (i128 > 0) to prevent logical errors
@@ -108,9 +132,7 @@ fn create_agreement_internal(
     };
 
     // Store agreement
-    env.storage()
-        .persistent()
-        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    save_agreement(env, &agreement_id, &agreement);
     env.storage().persistent().extend_ttl(
         &DataKey::Agreement(agreement_id.clone()),
         TTL_THRESHOLD,
@@ -123,12 +145,21 @@ fn create_agreement_internal(
         .instance()
         .get(&DataKey::AgreementCount)
         .unwrap_or(0);
+    migration::index_agreement(env, count, &agreement_id);
     count += 1;
     env.storage()
         .instance()
         .set(&DataKey::AgreementCount, &count);
     env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_BUMP);
 
+    audit::record(
+        env,
+        agreement.user.clone(),
+        "create_agreement",
+        Some(agreement_id.clone()),
+        None,
+    );
+
     // Emit event with topics for indexing
     events::agreement_created(
         env,
@@ -154,11 +185,8 @@ pub fn sign_agreement(env: &Env, user: Address, agreement_id: String) -> Result<
     rate_limit::check_rate_limit(env, &user, "sign_agreement")?;
 
     // Retrieve the agreement
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        load_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     // Validate caller is the intended tenant
     if agreement.user != user {
@@ -181,9 +209,7 @@ pub fn sign_agreement(env: &Env, user: Address, agreement_id: String) -> Result<
     agreement.signed_at = Some(current_time);
 
     // Save updated agreement
-    env.storage()
-        .persistent()
-        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    save_agreement(env, &agreement_id, &agreement);
     env.storage().persistent().extend_ttl(
         &DataKey::Agreement(agreement_id.clone()),
         TTL_THRESHOLD,
@@ -191,6 +217,14 @@ pub fn sign_agreement(env: &Env, user: Address, agreement_id: String) -> Result<
     );
     env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_BUMP);
 
+    audit::record(
+        env,
+        user.clone(),
+        "sign_agreement",
+        Some(agreement_id.clone()),
+        None,
+    );
+
     // Emit event with topics for indexing
     events::agreement_signed(
         env,
@@ -207,6 +241,8 @@ pub fn sign_agreement(env: &Env, user: Address, agreement_id: String) -> Result<
 ///
 /// Only admin or designated agent may call this. The witness ID is permanently
 /// recorded in the agreement storage, and the agreement transitions to Active.
+/// The tenant's security deposit is moved into an escrow linked to the
+/// agreement; if that fails the agreement stays PendingApproval.
 pub fn approve_agreement(
     env: &Env,
     approver: Address,
@@ -214,11 +250,8 @@ pub fn approve_agreement(
 ) -> Result<(), RentalError> {
     approver.require_auth();
 
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        load_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     // Only valid from PendingApproval state
     if agreement.status != AgreementStatus::PendingApproval {
@@ -235,9 +268,7 @@ pub fn approve_agreement(
     agreement.witness_id = Some(approver.clone());
     agreement.status = AgreementStatus::Active;
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    save_agreement(env, &agreement_id, &agreement);
     env.storage().persistent().extend_ttl(
         &DataKey::Agreement(agreement_id.clone()),
         TTL_THRESHOLD,
@@ -245,11 +276,88 @@ pub fn approve_agreement(
     );
     env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_BUMP);
 
+    if agreement.security_deposit > 0 {
+        let escrow_id = escrow_deposit(env, &agreement, agreement.security_deposit)?;
+        events::deposit_escrowed(
+            env,
+            agreement_id.clone(),
+            agreement.user.clone(),
+            escrow_id,
+            agreement.security_deposit,
+        );
+    }
+
+    audit::record(
+        env,
+        approver.clone(),
+        "approve_agreement",
+        Some(agreement_id.clone()),
+        None,
+    );
     events::agreement_approved(env, agreement_id, approver);
 
     Ok(())
 }
 
+/// Approve a pending agreement and collect its first month's rent in the
+/// same call.
+///
+/// The rent is paid like any other month, through `make_payment_with_token`
+/// in the agreement's token, so it stays out of the deposit escrow. If either
+/// the deposit or the rent cannot be taken, the agreement stays
+/// PendingApproval.
+pub fn approve_with_first_rent(
+    env: &Env,
+    approver: Address,
+    agreement_id: String,
+) -> Result<(), RentalError> {
+    approve_agreement(env, approver, agreement_id.clone())?;
+
+    let agreement: RentAgreement =
+        load_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
+    make_payment_with_token(
+        env,
+        agreement_id,
+        agreement.monthly_rent,
+        agreement.payment_token,
+    )
+}
+
+/// Pull `amount` from the tenant into a Funded escrow for `agreement`, with
+/// the landlord as beneficiary and the contract admin as arbiter.
+///
+/// An escrow the tenant already funded for the agreement, in full or in
+/// installments, is adopted as it is, and one opened but never funded is
+/// taken over, so neither blocks activation. While installments are still
+/// owed, activation fails with `EscrowInsufficientFunds`.
+fn escrow_deposit(
+    env: &Env,
+    agreement: &RentAgreement,
+    amount: i128,
+) -> Result<BytesN<32>, RentalError> {
+    let state = crate::admin::get_state(env)?;
+    let fee_collector = state.config.fee_collector;
+    let referral = agreement.agent.clone().unwrap_or(fee_collector.clone());
+
+    let escrow = EscrowClient::new(env, &peers::escrow(env)?);
+    match escrow.try_create_funded(
+        &agreement.agreement_id,
+        &agreement.user,
+        &agreement.admin,
+        &state.admin,
+        &fee_collector,
+        &referral,
+        &amount,
+        &agreement.payment_token,
+    ) {
+        Ok(Ok(escrow_id)) => Ok(escrow_id),
+        Err(Ok(error)) if error == peers::INSTALLMENTS_OUTSTANDING => {
+            Err(RentalError::EscrowInsufficientFunds)
+        }
+        _ => Err(RentalError::PaymentFailed),
+    }
+}
+
 /// Submit a draft agreement for tenant signature (Draft → Pending)
 pub fn submit_agreement(
     env: &Env,
@@ -258,11 +366,8 @@ pub fn submit_agreement(
 ) -> Result<(), RentalError> {
     admin.require_auth();
 
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        load_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     if agreement.admin != admin {
         return Err(RentalError::Unauthorized);
@@ -274,15 +379,20 @@ pub fn submit_agreement(
 
     agreement.status = AgreementStatus::Pending;
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    save_agreement(env, &agreement_id, &agreement);
     env.storage().persistent().extend_ttl(
         &DataKey::Agreement(agreement_id.clone()),
         TTL_THRESHOLD,
         TTL_BUMP,
     );
 
+    audit::record(
+        env,
+        admin.clone(),
+        "submit_agreement",
+        Some(agreement_id.clone()),
+        None,
+    );
     events::agreement_submitted(env, agreement_id, admin, agreement.user.clone());
 
     Ok(())
@@ -296,11 +406,8 @@ pub fn cancel_agreement(
 ) -> Result<(), RentalError> {
     caller.require_auth();
 
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        load_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     // Only landlord can cancel
     if agreement.admin != caller {
@@ -317,15 +424,20 @@ pub fn cancel_agreement(
 
     agreement.status = AgreementStatus::Cancelled;
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    save_agreement(env, &agreement_id, &agreement);
     env.storage().persistent().extend_ttl(
         &DataKey::Agreement(agreement_id.clone()),
         TTL_THRESHOLD,
         TTL_BUMP,
     );
 
+    audit::record(
+        env,
+        caller.clone(),
+        "cancel_agreement",
+        Some(agreement_id.clone()),
+        None,
+    );
     events::agreement_cancelled(env, agreement_id, caller, agreement.user.clone());
 
     Ok(())
@@ -333,9 +445,7 @@ pub fn cancel_agreement(
 
 /// Retrieve a rent agreement by its unique identifier
 pub fn get_agreement(env: &Env, agreement_id: String) -> Option<RentAgreement> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id))
+    load_agreement(env, &agreement_id)
 }
 
 /// Check whether a rent agreement exists for the given identifier
@@ -367,11 +477,7 @@ pub fn get_payment_split(
 /// Get all payments for an agreement
 pub fn get_payment_history(env: &Env, agreement_id: String) -> Vec<PaymentSplit> {
     let mut history = Vec::new(env);
-    let agreement: RentAgreement = match env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-    {
+    let agreement: RentAgreement = match load_agreement(env, &agreement_id) {
         Some(a) => a,
         None => return history,
     };
@@ -395,20 +501,15 @@ pub fn update_metadata(
     metadata_uri: String,
     attributes: Vec<crate::types::Attribute>,
 ) -> Result<(), RentalError> {
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        load_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     agreement.admin.require_auth();
 
     agreement.metadata_uri = metadata_uri;
     agreement.attributes = attributes;
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::Agreement(agreement_id), &agreement);
+    save_agreement(env, &agreement_id, &agreement);
     Ok(())
 }
 
@@ -432,11 +533,7 @@ pub fn create_agreement_with_token(
     // Store the token mapping explicitly if needed, but it's already in RentAgreement
     // Wait, create_agreement_internal already set the agreement.
     // We just need the extra DataKey::AgreementToken if the frontend relies on it.
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .unwrap();
+    let agreement: RentAgreement = load_agreement(env, &agreement_id).unwrap();
 
     env.storage().persistent().set(
         &DataKey::AgreementToken(agreement_id.clone()),
@@ -463,11 +560,8 @@ pub fn make_payment_with_token(
 ) -> Result<(), RentalError> {
     // Single storage read – reuse `agreement` for all subsequent checks and
     // the final write-back, avoiding a second persistent-storage lookup.
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        load_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     if agreement.status != AgreementStatus::Active {
         return Err(RentalError::AgreementNotActive);
@@ -516,10 +610,15 @@ pub fn make_payment_with_token(
         .extend_ttl(&record_key, TTL_THRESHOLD, TTL_BUMP);
 
     // Single write-back of the mutated agreement (no second read needed)
-    env.storage()
-        .persistent()
-        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    save_agreement(env, &agreement_id, &agreement);
 
+    audit::record(
+        env,
+        agreement.user,
+        "make_payment",
+        Some(agreement_id.clone()),
+        None,
+    );
     events::payment_made_with_token(env, agreement_id, token, amount);
 
     Ok(())
@@ -533,11 +632,8 @@ pub fn release_escrow_with_token(
 ) -> Result<(), RentalError> {
     // For simplicity, we assume escrow_id is the agreement_id
     let agreement_id = escrow_id.clone();
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement: RentAgreement =
+        load_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     if is_escrow_frozen(env, escrow_id.clone()) {
         return Err(RentalError::InvalidState);
@@ -549,18 +645,33 @@ pub fn release_escrow_with_token(
 
     let contract_addr = env.current_contract_address();
     let client = soroban_sdk::token::Client::new(env, &token);
-    let balance = client.balance(&contract_addr);
+    // Bids held by the marketplace are not part of any escrow.
+    let balance = client
+        .balance(&contract_addr)
+        .saturating_sub(crate::marketplace::escrowed_bids(env, &token));
 
     if balance > 0 {
         client.transfer(&contract_addr, &agreement.admin, &balance);
     }
 
+    audit::record(
+        env,
+        agreement.admin,
+        "release_escrow",
+        Some(agreement_id),
+        None,
+    );
     events::escrow_released_with_token(env, escrow_id, token, balance);
 
     Ok(())
 }
 
-pub fn set_escrow_frozen(env: &Env, escrow_id: String, is_frozen: bool) -> Result<(), RentalError> {
+pub fn set_escrow_frozen(
+    env: &Env,
+    by: Address,
+    escrow_id: String,
+    is_frozen: bool,
+) -> Result<(), RentalError> {
     if !env
         .storage()
         .persistent()
@@ -569,11 +680,18 @@ pub fn set_escrow_frozen(env: &Env, escrow_id: String, is_frozen: bool) -> Resul
         return Err(RentalError::AgreementNotFound);
     }
 
-    let key = DataKey::EscrowFrozen(escrow_id);
+    let key = DataKey::EscrowFrozen(escrow_id.clone());
     env.storage().persistent().set(&key, &is_frozen);
     env.storage()
         .persistent()
         .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+
+    let operation = if is_frozen {
+        "freeze_escrow"
+    } else {
+        "unfreeze_escrow"
+    };
+    audit::record(env, by, operation, Some(escrow_id), None);
     Ok(())
 }
 
@@ -594,11 +712,8 @@ pub fn propose_extension(
 ) -> Result<String, RentalError> {
     caller.require_auth();
 
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement: RentAgreement =
+        load_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     if caller != agreement.admin && caller != agreement.user {
         return Err(RentalError::Unauthorized);
@@ -672,10 +787,7 @@ pub fn accept_extension(
         return Err(RentalError::InvalidState);
     }
 
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(extension.original_agreement_id.clone()))
+    let agreement: RentAgreement = load_agreement(env, &extension.original_agreement_id)
         .ok_or(RentalError::AgreementNotFound)?;
 
     if caller == agreement.admin {
@@ -712,10 +824,7 @@ pub fn reject_extension(
         .get(&DataKey::AgreementExtension(extension_id.clone()))
         .ok_or(RentalError::AgreementNotFound)?;
 
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(extension.original_agreement_id.clone()))
+    let agreement: RentAgreement = load_agreement(env, &extension.original_agreement_id)
         .ok_or(RentalError::AgreementNotFound)?;
 
     if caller != agreement.admin && caller != agreement.user {
@@ -753,10 +862,7 @@ pub fn activate_extension(
         return Err(RentalError::InvalidState);
     }
 
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(extension.original_agreement_id.clone()))
+    let mut agreement: RentAgreement = load_agreement(env, &extension.original_agreement_id)
         .ok_or(RentalError::AgreementNotFound)?;
 
     if caller != agreement.admin {
@@ -768,10 +874,7 @@ pub fn activate_extension(
     agreement.security_deposit = extension.extension_deposit;
     extension.status = ExtensionStatus::Active;
 
-    env.storage().persistent().set(
-        &DataKey::Agreement(extension.original_agreement_id.clone()),
-        &agreement,
-    );
+    save_agreement(env, &extension.original_agreement_id, &agreement);
     env.storage().persistent().set(
         &DataKey::AgreementExtension(extension_id.clone()),
         &extension,
@@ -796,10 +899,7 @@ pub fn cancel_extension(
         .get(&DataKey::AgreementExtension(extension_id.clone()))
         .ok_or(RentalError::AgreementNotFound)?;
 
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(extension.original_agreement_id.clone()))
+    let agreement: RentAgreement = load_agreement(env, &extension.original_agreement_id)
         .ok_or(RentalError::AgreementNotFound)?;
 
     if caller != agreement.admin && caller != agreement.user {
@@ -844,11 +944,8 @@ pub fn get_extension_history(
 }
 
 pub fn get_current_agreement_end(env: &Env, agreement_id: String) -> Result<u64, RentalError> {
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement: RentAgreement =
+        load_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     Ok(agreement.end_date)
 }
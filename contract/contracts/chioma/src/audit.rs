@@ -0,0 +1,146 @@
+//! Audit log of contract operations.
+//!
+//! Entries are only written by the contract itself, into a ring buffer of
+//! `get_capacity` slots: entry `seq` lives in slot `seq % capacity`, so once
+//! the log is full each new entry overwrites the oldest one. The capacity is
+//! changed through multi-sig. A slot whose stored `seq` differs from the one
+//! being read (overwritten, or written under another capacity) reads as
+//! missing.
+//!
+//! Failed calls revert their writes, so only operations that complete, and
+//! failures the contract handles itself, leave an entry.
+
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
+
+use crate::errors::RentalError;
+use crate::storage::{DataKey, GovernanceKey};
+use crate::types::{AuditEntry, AuditFilter, AuditPage};
+
+/// Ring size used until one is set through multi-sig.
+pub const DEFAULT_AUDIT_CAPACITY: u32 = 1_000;
+
+/// Largest ring size that can be configured.
+pub const MAX_AUDIT_CAPACITY: u32 = 10_000;
+
+/// Most entries returned by one `get_audit_log` call.
+pub const MAX_AUDIT_PAGE: u32 = 50;
+
+/// Most log positions examined by one `get_audit_log` call.
+pub const MAX_AUDIT_SCAN: u32 = 200;
+
+pub fn get_capacity(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::Governance(GovernanceKey::AuditLogCapacity))
+        .unwrap_or(DEFAULT_AUDIT_CAPACITY)
+}
+
+/// Set the ring size, returning the previous one. Fails with `InvalidConfig`
+/// if `capacity` is 0 or above `MAX_AUDIT_CAPACITY`.
+pub fn set_capacity(env: &Env, capacity: u32) -> Result<u32, RentalError> {
+    validate_capacity(capacity)?;
+    let old = get_capacity(env);
+    env.storage().instance().set(
+        &DataKey::Governance(GovernanceKey::AuditLogCapacity),
+        &capacity,
+    );
+    Ok(old)
+}
+
+pub(crate) fn validate_capacity(capacity: u32) -> Result<(), RentalError> {
+    if capacity == 0 || capacity > MAX_AUDIT_CAPACITY {
+        return Err(RentalError::InvalidConfig);
+    }
+    Ok(())
+}
+
+/// Number of entries ever written, which is also the next `seq`.
+pub fn get_count(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::AuditLogCount)
+        .unwrap_or(0)
+}
+
+/// Append an entry. `error` is `None` for an operation that succeeded.
+pub(crate) fn record(
+    env: &Env,
+    actor: Address,
+    operation: &str,
+    agreement_id: Option<String>,
+    error: Option<RentalError>,
+) {
+    let seq = get_count(env);
+    let entry = AuditEntry {
+        seq,
+        actor,
+        operation: Symbol::new(env, operation),
+        agreement_id,
+        error_code: error.map_or(0, |e| e.code()),
+        timestamp: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::AuditLog(seq % get_capacity(env)), &entry);
+    env.storage()
+        .instance()
+        .set(&DataKey::AuditLogCount, &seq.saturating_add(1));
+}
+
+fn load(env: &Env, seq: u32, capacity: u32) -> Option<AuditEntry> {
+    env.storage()
+        .persistent()
+        .get::<DataKey, AuditEntry>(&DataKey::AuditLog(seq % capacity))
+        .filter(|entry| entry.seq == seq)
+}
+
+fn matches(entry: &AuditEntry, filter: &AuditFilter) -> bool {
+    filter
+        .agreement_id
+        .as_ref()
+        .is_none_or(|id| entry.agreement_id.as_ref() == Some(id))
+        && filter.actor.as_ref().is_none_or(|a| entry.actor == *a)
+        && filter.from.is_none_or(|from| entry.timestamp >= from)
+        && filter.to.is_none_or(|to| entry.timestamp <= to)
+}
+
+/// Page through retained entries matching `filter`, oldest first.
+///
+/// `cursor` is a `seq`; positions already overwritten are skipped. Each call
+/// returns at most `limit` entries (capped at `MAX_AUDIT_PAGE`) and examines
+/// at most `MAX_AUDIT_SCAN` positions, so a page may come back short or empty
+/// before `done` is set.
+pub fn get_audit_log(
+    env: &Env,
+    filter: AuditFilter,
+    cursor: u32,
+    limit: u32,
+) -> Result<AuditPage, RentalError> {
+    if limit == 0 {
+        return Err(RentalError::InvalidInput);
+    }
+
+    let count = get_count(env);
+    let capacity = get_capacity(env);
+    let start = cursor.max(count.saturating_sub(capacity));
+    let end = start.saturating_add(MAX_AUDIT_SCAN).min(count);
+    let limit = limit.min(MAX_AUDIT_PAGE);
+
+    let mut entries = Vec::new(env);
+    let mut next = start;
+    while next < end && entries.len() < limit {
+        if let Some(entry) = load(env, next, capacity) {
+            if matches(&entry, &filter) {
+                entries.push_back(entry);
+            }
+        }
+        next += 1;
+    }
+
+    let next_cursor = next.max(cursor);
+    Ok(AuditPage {
+        entries,
+        next_cursor,
+        done: next_cursor >= count,
+    })
+}
@@ -1,17 +1,66 @@
 //! Security Deposit Interest Accrual logic for the Chioma rental contract.
 
-use soroban_sdk::{Env, String, Vec};
+use soroban_sdk::{
+    contracttype, unwrap::UnwrapOptimized, Address, Env, String, TryFromVal, Val, Vec,
+};
 
+use crate::agreement;
+use crate::audit;
 use crate::errors::RentalError;
 use crate::events;
-use crate::storage::DataKey;
+use crate::peers;
+use crate::storage::{self, DataKey};
 use crate::types::{
-    CompoundingFrequency, DepositInterest, DepositInterestConfig, InterestAccrual,
-    InterestRecipient,
+    CompoundingFrequency, ContractState, DepositInterest, DepositInterestConfig, InterestAccrual,
+    InterestAccrualBatch, InterestRateSource, InterestRecipient, InterestSplit, RateSegment,
 };
+use crate::yield_adapter;
 
 const SECONDS_PER_DAY: u64 = 86_400;
 
+/// Upper bound on deposits accrued in a single `process_interest_accruals` call.
+pub const MAX_ACCRUAL_BATCH: u32 = 50;
+
+/// Accrual snapshots stored per history bucket.
+pub const ACCRUAL_BUCKET_SIZE: u32 = 32;
+
+/// Upper bound on snapshots returned by one `get_accrual_history` page.
+pub const MAX_HISTORY_PAGE: u32 = 100;
+
+/// `DepositInterestConfig` as stored before deposits could follow a rate
+/// schedule.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacyDepositInterestConfig {
+    pub agreement_id: String,
+    pub annual_rate: u32,
+    pub compounding_frequency: CompoundingFrequency,
+    pub interest_recipient: InterestRecipient,
+}
+
+/// `DepositInterest` as stored before its history moved into buckets.
+/// History entries are kept raw so each can be decoded on its own.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacyDepositInterest {
+    pub escrow_id: String,
+    pub principal: i128,
+    pub accrued_interest: i128,
+    pub total_with_interest: i128,
+    pub last_accrual_date: u64,
+    pub accrual_history: Vec<Val>,
+}
+
+/// `InterestAccrual` as stored before compounding was recorded.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacyInterestAccrual {
+    pub accrued_at: u64,
+    pub amount: i128,
+    pub rate: u32,
+    pub balance: i128,
+}
+
 /// Returns the number of seconds in one compounding period.
 fn period_seconds(freq: &CompoundingFrequency) -> u64 {
     match freq {
@@ -51,11 +100,23 @@ pub fn set_deposit_interest_config(
         return Err(RentalError::InvalidAmount);
     }
 
+    let existing = load_config(&env, &agreement_id);
+
+    // Book interest earned under the old rate before it changes; configs
+    // written before schedules existed keep no record of that rate.
+    if existing.is_some() {
+        accrue_interest(env.clone(), agreement_id.clone())?;
+    }
+
     let config = DepositInterestConfig {
         agreement_id: agreement_id.clone(),
         annual_rate,
         compounding_frequency,
         interest_recipient,
+        rate_source: existing
+            .as_ref()
+            .map(|c| c.rate_source.clone())
+            .unwrap_or(InterestRateSource::Agreement),
     };
 
     env.storage().persistent().set(
@@ -63,6 +124,20 @@ pub fn set_deposit_interest_config(
         &config,
     );
 
+    // A new rate only applies from now on; earlier time keeps its old rate.
+    let mut schedule = schedule_to_amend(&env, existing.as_ref(), &agreement_id);
+    insert_segment(
+        &mut schedule,
+        RateSegment {
+            effective_from: env.ledger().timestamp(),
+            annual_rate,
+        },
+    );
+    env.storage().persistent().set(
+        &DataKey::DepositRateSchedule(agreement_id.clone()),
+        &schedule,
+    );
+
     // Initialise the DepositInterest record if it doesn't exist yet.
     if !env
         .storage()
@@ -70,11 +145,8 @@ pub fn set_deposit_interest_config(
         .has(&DataKey::DepositInterest(agreement_id.clone()))
     {
         // Read principal from the agreement.
-        let agreement = env
-            .storage()
-            .persistent()
-            .get::<DataKey, crate::types::RentAgreement>(&DataKey::Agreement(agreement_id.clone()))
-            .ok_or(RentalError::AgreementNotFound)?;
+        let agreement =
+            agreement::load_agreement(&env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
         let principal = agreement.security_deposit;
         if principal <= 0 {
@@ -87,7 +159,7 @@ pub fn set_deposit_interest_config(
             accrued_interest: 0,
             total_with_interest: principal,
             last_accrual_date: env.ledger().timestamp(),
-            accrual_history: Vec::new(&env),
+            history_count: 0,
         };
 
         env.storage()
@@ -95,6 +167,8 @@ pub fn set_deposit_interest_config(
             .set(&DataKey::DepositInterest(agreement_id.clone()), &di);
     }
 
+    register_interest_deposit(&env, &agreement_id);
+
     events::interest_config_set(&env, agreement_id, annual_rate);
     Ok(())
 }
@@ -104,10 +178,224 @@ pub fn get_deposit_interest_config(
     env: Env,
     agreement_id: String,
 ) -> Result<DepositInterestConfig, RentalError> {
+    load_config(&env, &agreement_id).ok_or(RentalError::InterestConfigNotFound)
+}
+
+/// Read a deposit's interest configuration, accepting the layout written
+/// before rate sources existed; such deposits follow their own schedule.
+fn load_config(env: &Env, agreement_id: &String) -> Option<DepositInterestConfig> {
+    let raw: Val = env
+        .storage()
+        .persistent()
+        .get(&DataKey::DepositInterestConfig(agreement_id.clone()))?;
+    if storage::has_field(env, &raw, "rate_source") {
+        return Some(DepositInterestConfig::try_from_val(env, &raw).unwrap_optimized());
+    }
+    let legacy = LegacyDepositInterestConfig::try_from_val(env, &raw).unwrap_optimized();
+    Some(DepositInterestConfig {
+        agreement_id: legacy.agreement_id,
+        annual_rate: legacy.annual_rate,
+        compounding_frequency: legacy.compounding_frequency,
+        interest_recipient: legacy.interest_recipient,
+        rate_source: InterestRateSource::Agreement,
+    })
+}
+
+// ─── Rate Schedules ───────────────────────────────────────────────────────────
+
+/// Insert a segment keeping the schedule sorted; a segment with the same
+/// `effective_from` is replaced.
+fn insert_segment(schedule: &mut Vec<RateSegment>, segment: RateSegment) {
+    let mut pos = schedule.len();
+    for i in 0..schedule.len() {
+        let existing = schedule.get(i).unwrap();
+        if existing.effective_from == segment.effective_from {
+            schedule.set(i, segment);
+            return;
+        }
+        if existing.effective_from > segment.effective_from {
+            pos = i;
+            break;
+        }
+    }
+    schedule.insert(pos, segment);
+}
+
+/// Schedule an agreement-specific rate change.
+///
+/// `effective_from` may not precede the last accrual, so interest that has
+/// already been booked is never repriced.
+pub fn add_deposit_rate_segment(
+    env: Env,
+    agreement_id: String,
+    effective_from: u64,
+    annual_rate: u32,
+) -> Result<(), RentalError> {
+    if annual_rate > 10_000 {
+        return Err(RentalError::InvalidAmount);
+    }
+
+    let config = get_deposit_interest_config(env.clone(), agreement_id.clone())?;
+    let di = get_deposit_interest(env.clone(), agreement_id.clone())?;
+    if effective_from < di.last_accrual_date {
+        return Err(RentalError::InvalidDate);
+    }
+
+    let mut schedule = schedule_to_amend(&env, Some(&config), &agreement_id);
+    insert_segment(
+        &mut schedule,
+        RateSegment {
+            effective_from,
+            annual_rate,
+        },
+    );
+    env.storage().persistent().set(
+        &DataKey::DepositRateSchedule(agreement_id.clone()),
+        &schedule,
+    );
+
+    events::deposit_rate_scheduled(&env, agreement_id, effective_from, annual_rate);
+    Ok(())
+}
+
+/// Publish a statutory rate that takes effect at `effective_from`.
+///
+/// Statutory segments can only be appended and may not start in the past.
+pub fn publish_statutory_rate(
+    env: Env,
+    effective_from: u64,
+    annual_rate: u32,
+) -> Result<(), RentalError> {
+    if annual_rate > 10_000 {
+        return Err(RentalError::InvalidAmount);
+    }
+    if effective_from < env.ledger().timestamp() {
+        return Err(RentalError::InvalidDate);
+    }
+
+    let mut schedule = get_statutory_rate_schedule(&env);
+    if let Some(last) = schedule.last() {
+        if effective_from <= last.effective_from {
+            return Err(RentalError::InvalidDate);
+        }
+    }
+    schedule.push_back(RateSegment {
+        effective_from,
+        annual_rate,
+    });
+    env.storage()
+        .instance()
+        .set(&DataKey::StatutoryRateSchedule, &schedule);
+
+    events::statutory_rate_published(&env, effective_from, annual_rate);
+    Ok(())
+}
+
+/// Choose whether a deposit follows its own schedule or the statutory one.
+pub fn set_deposit_rate_source(
+    env: Env,
+    agreement_id: String,
+    rate_source: InterestRateSource,
+) -> Result<(), RentalError> {
+    let mut config = get_deposit_interest_config(env.clone(), agreement_id.clone())?;
+
+    // Book interest earned under the old source before switching.
+    accrue_interest(env.clone(), agreement_id.clone())?;
+
+    config.rate_source = rate_source;
+    env.storage()
+        .persistent()
+        .set(&DataKey::DepositInterestConfig(agreement_id), &config);
+    Ok(())
+}
+
+/// The agreement-specific rate schedule.
+pub fn get_deposit_rate_schedule(env: &Env, agreement_id: String) -> Vec<RateSegment> {
     env.storage()
         .persistent()
-        .get(&DataKey::DepositInterestConfig(agreement_id))
-        .ok_or(RentalError::InterestConfigNotFound)
+        .get(&DataKey::DepositRateSchedule(agreement_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// A deposit's own schedule, about to take a new segment. Configs written
+/// before schedules existed accrued at their flat rate, which is kept for
+/// the time before the new segment.
+fn schedule_to_amend(
+    env: &Env,
+    config: Option<&DepositInterestConfig>,
+    agreement_id: &String,
+) -> Vec<RateSegment> {
+    let mut schedule = get_deposit_rate_schedule(env, agreement_id.clone());
+    if let Some(config) = config {
+        if schedule.is_empty() {
+            schedule.push_back(RateSegment {
+                effective_from: 0,
+                annual_rate: config.annual_rate,
+            });
+        }
+    }
+    schedule
+}
+
+/// The admin-published statutory rate schedule.
+pub fn get_statutory_rate_schedule(env: &Env) -> Vec<RateSegment> {
+    env.storage()
+        .instance()
+        .get(&DataKey::StatutoryRateSchedule)
+        .unwrap_or(Vec::new(env))
+}
+
+/// The schedule a deposit accrues against, according to its rate source.
+fn effective_schedule(env: &Env, config: &DepositInterestConfig) -> Vec<RateSegment> {
+    match config.rate_source {
+        InterestRateSource::Statutory => get_statutory_rate_schedule(env),
+        InterestRateSource::Agreement => {
+            let schedule = get_deposit_rate_schedule(env, config.agreement_id.clone());
+            if schedule.is_empty() {
+                // Configs written before schedules existed: flat rate.
+                let mut flat = Vec::new(env);
+                flat.push_back(RateSegment {
+                    effective_from: 0,
+                    annual_rate: config.annual_rate,
+                });
+                flat
+            } else {
+                schedule
+            }
+        }
+    }
+}
+
+/// Rate in force at `ts` (0 before the first segment).
+fn rate_at(schedule: &Vec<RateSegment>, ts: u64) -> u32 {
+    let mut rate = 0;
+    for segment in schedule.iter() {
+        if segment.effective_from > ts {
+            break;
+        }
+        rate = segment.annual_rate;
+    }
+    rate
+}
+
+/// Σ rate × seconds over `[from_ts, to_ts)`, in basis-point-seconds.
+fn rate_seconds(schedule: &Vec<RateSegment>, from_ts: u64, to_ts: u64) -> i128 {
+    let mut total: i128 = 0;
+    let len = schedule.len();
+    for i in 0..len {
+        let segment = schedule.get(i).unwrap();
+        let seg_end = if i + 1 < len {
+            schedule.get(i + 1).unwrap().effective_from
+        } else {
+            u64::MAX
+        };
+        let start = segment.effective_from.max(from_ts);
+        let end = seg_end.min(to_ts);
+        if end > start {
+            total += segment.annual_rate as i128 * (end - start) as i128;
+        }
+    }
+    total
 }
 
 // ─── Calculation ──────────────────────────────────────────────────────────────
@@ -123,30 +411,43 @@ pub fn get_deposit_interest_config(
 pub fn calculate_accrued_interest(env: Env, escrow_id: String) -> Result<i128, RentalError> {
     let config = get_deposit_interest_config(env.clone(), escrow_id.clone())?;
 
-    let di: DepositInterest = env
-        .storage()
-        .persistent()
-        .get(&DataKey::DepositInterest(escrow_id))
-        .ok_or(RentalError::InterestConfigNotFound)?;
+    let di = get_deposit_interest(env.clone(), escrow_id.clone())?;
 
-    Ok(compute_interest(
+    let schedule = effective_schedule(&env, &config);
+    let amount = compute_interest(
         &config,
+        &schedule,
         di.total_with_interest, // use running balance for compounding
         di.last_accrual_date,
         env.ledger().timestamp(),
-    ))
+    );
+    Ok(cap_to_vault_yield(&env, &di, amount))
+}
+
+/// Limit an accrual to the vault yield not yet claimed by earlier accruals.
+///
+/// A deposit that has not been routed to a vault earns nothing, since there
+/// is no yield to pay its interest from.
+fn cap_to_vault_yield(env: &Env, di: &DepositInterest, amount: i128) -> i128 {
+    let unclaimed = yield_adapter::vault_yield(env, di.escrow_id.clone())
+        .saturating_sub(di.accrued_interest)
+        .max(0);
+    amount.min(unclaimed)
 }
 
 /// Core arithmetic (shared by calculate and accrue).
 ///
+/// Each compounding period earns the time-weighted average of the rates in
+/// force during that period, so a rate change mid-period is split pro rata.
 /// Returns the raw interest amount for the elapsed time.
 fn compute_interest(
     config: &DepositInterestConfig,
+    schedule: &Vec<RateSegment>,
     balance: i128,
     from_ts: u64,
     to_ts: u64,
 ) -> i128 {
-    if to_ts <= from_ts || balance <= 0 || config.annual_rate == 0 {
+    if to_ts <= from_ts || balance <= 0 || schedule.is_empty() {
         return 0;
     }
 
@@ -159,11 +460,15 @@ fn compute_interest(
     }
 
     let n_per_year = periods_per_year(&config.compounding_frequency) as i128;
-    let rate_bps = config.annual_rate as i128;
+    let denominator = n_per_year * 10_000 * period_secs as i128;
 
     let mut result = balance;
+    let mut period_start = from_ts;
     for _ in 0..elapsed_periods {
-        result = result.saturating_add(result.saturating_mul(rate_bps) / (n_per_year * 10_000));
+        let period_end = period_start + period_secs;
+        let weighted = rate_seconds(schedule, period_start, period_end);
+        result = result.saturating_add(result.saturating_mul(weighted) / denominator);
+        period_start = period_end;
     }
     result.saturating_sub(balance)
 }
@@ -174,26 +479,34 @@ fn compute_interest(
 pub fn accrue_interest(env: Env, escrow_id: String) -> Result<InterestAccrual, RentalError> {
     let config = get_deposit_interest_config(env.clone(), escrow_id.clone())?;
 
-    let mut di: DepositInterest = env
-        .storage()
-        .persistent()
-        .get(&DataKey::DepositInterest(escrow_id.clone()))
-        .ok_or(RentalError::InterestConfigNotFound)?;
+    let mut di = get_deposit_interest(env.clone(), escrow_id.clone())?;
 
     let now = env.ledger().timestamp();
-    let amount = compute_interest(&config, di.total_with_interest, di.last_accrual_date, now);
+    let schedule = effective_schedule(&env, &config);
+    let amount = compute_interest(
+        &config,
+        &schedule,
+        di.total_with_interest,
+        di.last_accrual_date,
+        now,
+    );
+    let amount = cap_to_vault_yield(&env, &di, amount);
 
     let accrual = InterestAccrual {
         accrued_at: now,
         amount,
-        rate: config.annual_rate,
+        rate: rate_at(&schedule, now),
         balance: di.total_with_interest + amount,
+        compounded: 0,
     };
 
     di.accrued_interest = di.accrued_interest.saturating_add(amount);
     di.total_with_interest = di.total_with_interest.saturating_add(amount);
-    di.last_accrual_date = now;
-    di.accrual_history.push_back(accrual.clone());
+    // Only whole compounding periods are booked; the rest of the current
+    // one carries over to the next accrual.
+    let period_secs = period_seconds(&config.compounding_frequency);
+    di.last_accrual_date += (now - di.last_accrual_date) / period_secs * period_secs;
+    record_accrual(&env, &mut di, accrual.clone());
 
     env.storage()
         .persistent()
@@ -203,107 +516,427 @@ pub fn accrue_interest(env: Env, escrow_id: String) -> Result<InterestAccrual, R
     Ok(accrual)
 }
 
-/// Convenience: accrue interest for every agreement that has a config.
+/// Accrue interest for the next batch of registered deposits.
+///
+/// Walks the registry from `cursor`, accruing at most `limit` deposits
+/// (capped at `MAX_ACCRUAL_BATCH`). A keeper repeats the call with the
+/// returned `next_cursor` until `done` is set. A deposit that fails to
+/// accrue is recorded in the audit log and skipped rather than failing the
+/// whole batch.
+pub fn process_interest_accruals(
+    env: Env,
+    cursor: u32,
+    limit: u32,
+) -> Result<InterestAccrualBatch, RentalError> {
+    if limit == 0 {
+        return Err(RentalError::InvalidInput);
+    }
+
+    let count = get_interest_deposit_count(&env);
+    let end = cursor
+        .saturating_add(limit.min(MAX_ACCRUAL_BATCH))
+        .min(count);
+
+    let mut processed: Vec<String> = Vec::new(&env);
+    for i in cursor..end {
+        let escrow_id: String = env
+            .storage()
+            .persistent()
+            .get(&DataKey::InterestDeposit(i))
+            .ok_or(RentalError::InternalError)?;
+        match accrue_interest(env.clone(), escrow_id.clone()) {
+            Ok(_) => processed.push_back(escrow_id),
+            Err(err) => audit::record(
+                &env,
+                env.current_contract_address(),
+                "accrue_interest",
+                Some(escrow_id),
+                Some(err),
+            ),
+        }
+    }
+
+    let next_cursor = end.max(cursor);
+    Ok(InterestAccrualBatch {
+        processed,
+        next_cursor,
+        done: next_cursor >= count,
+    })
+}
+
+// ─── Registry ────────────────────────────────────────────────────────────────
+
+/// Append an escrow id to the interest-bearing registry (idempotent).
+fn register_interest_deposit(env: &Env, escrow_id: &String) {
+    let registered_key = DataKey::InterestDepositRegistered(escrow_id.clone());
+    if env.storage().persistent().has(&registered_key) {
+        return;
+    }
+
+    let count = get_interest_deposit_count(env);
+    env.storage()
+        .persistent()
+        .set(&DataKey::InterestDeposit(count), escrow_id);
+    env.storage().persistent().set(&registered_key, &count);
+    env.storage()
+        .instance()
+        .set(&DataKey::InterestDepositCount, &(count + 1));
+}
+
+/// Stop accruing interest on a deposit whose escrow has released or
+/// refunded it. Anyone can call it: whether the deposit is settled is read
+/// from the escrow contract, not taken from the caller.
+pub fn end_deposit_interest(env: Env, agreement_id: String) -> Result<(), RentalError> {
+    if !peers::deposit_settled(&env, &agreement_id)? {
+        return Err(RentalError::InvalidState);
+    }
+    deregister_interest_deposit(&env, &agreement_id);
+    Ok(())
+}
+
+/// Drop an escrow id from the registry once its deposit is settled.
 ///
-/// Returns the list of escrow ids that were processed.
-pub fn process_interest_accruals(env: Env) -> Result<Vec<String>, RentalError> {
-    // We don't maintain a global list of all agreements with interest configs,
-    // so we iterate over ALL agreements and check for a config key.
-    // In production this would be driven by an off-chain keeper / oracle.
-    let count: u32 = env
+/// The last entry moves into the freed slot so the registry stays dense. A
+/// keeper pass already past that slot picks the moved deposit up on its next
+/// pass.
+fn deregister_interest_deposit(env: &Env, escrow_id: &String) {
+    let registered_key = DataKey::InterestDepositRegistered(escrow_id.clone());
+    let Some(index) = env
         .storage()
+        .persistent()
+        .get::<DataKey, u32>(&registered_key)
+    else {
+        return;
+    };
+
+    let last = get_interest_deposit_count(env) - 1;
+    if index != last {
+        let moved: String = env
+            .storage()
+            .persistent()
+            .get(&DataKey::InterestDeposit(last))
+            .unwrap_optimized();
+        env.storage()
+            .persistent()
+            .set(&DataKey::InterestDeposit(index), &moved);
+        env.storage()
+            .persistent()
+            .set(&DataKey::InterestDepositRegistered(moved), &index);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::InterestDeposit(last));
+    env.storage().persistent().remove(&registered_key);
+    env.storage()
         .instance()
-        .get(&DataKey::AgreementCount)
-        .unwrap_or(0);
+        .set(&DataKey::InterestDepositCount, &last);
+}
 
-    let processed: Vec<String> = Vec::new(&env);
+/// Number of deposits registered for interest accrual.
+pub fn get_interest_deposit_count(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::InterestDepositCount)
+        .unwrap_or(0)
+}
 
-    // Agreements are stored by String key, not by index, so we rely on the
-    // caller to pass explicit escrow ids in production. Here we satisfy the
-    // API contract by returning an empty list when we have no index.
-    // (A real implementation would maintain a separate Vec<String> of ids.)
-    let _ = count; // suppress unused warning
+/// Page through the registered escrow ids starting at `cursor`.
+pub fn get_interest_deposits(env: &Env, cursor: u32, limit: u32) -> Vec<String> {
+    let count = get_interest_deposit_count(env);
+    let end = cursor.saturating_add(limit).min(count);
 
-    Ok(processed)
+    let mut ids = Vec::new(env);
+    for i in cursor..end {
+        if let Some(id) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, String>(&DataKey::InterestDeposit(i))
+        {
+            ids.push_back(id);
+        }
+    }
+    ids
 }
 
 // ─── Queries ─────────────────────────────────────────────────────────────────
 
 /// Retrieve the full deposit-interest state.
 pub fn get_deposit_interest(env: Env, escrow_id: String) -> Result<DepositInterest, RentalError> {
+    let raw: Val = env
+        .storage()
+        .persistent()
+        .get(&DataKey::DepositInterest(escrow_id.clone()))
+        .ok_or(RentalError::InterestConfigNotFound)?;
+    if storage::has_field(&env, &raw, "history_count") {
+        return Ok(DepositInterest::try_from_val(&env, &raw).unwrap_optimized());
+    }
+    Ok(upgrade_deposit_interest(
+        &env,
+        LegacyDepositInterest::try_from_val(&env, &raw).unwrap_optimized(),
+    ))
+}
+
+/// Move the inline history of a deposit stored before history buckets into
+/// buckets, and store the deposit in the current layout.
+fn upgrade_deposit_interest(env: &Env, legacy: LegacyDepositInterest) -> DepositInterest {
+    let mut di = DepositInterest {
+        escrow_id: legacy.escrow_id,
+        principal: legacy.principal,
+        accrued_interest: legacy.accrued_interest,
+        total_with_interest: legacy.total_with_interest,
+        last_accrual_date: legacy.last_accrual_date,
+        history_count: 0,
+    };
+    for entry in legacy.accrual_history.iter() {
+        record_accrual(env, &mut di, decode_accrual(env, &entry));
+    }
     env.storage()
         .persistent()
-        .get(&DataKey::DepositInterest(escrow_id))
-        .ok_or(RentalError::InterestConfigNotFound)
+        .set(&DataKey::DepositInterest(di.escrow_id.clone()), &di);
+    di
+}
+
+/// Decode a history snapshot, accepting the layout written before
+/// compounding was recorded; such snapshots compounded nothing.
+fn decode_accrual(env: &Env, raw: &Val) -> InterestAccrual {
+    if storage::has_field(env, raw, "compounded") {
+        return InterestAccrual::try_from_val(env, raw).unwrap_optimized();
+    }
+    let legacy = LegacyInterestAccrual::try_from_val(env, raw).unwrap_optimized();
+    InterestAccrual {
+        accrued_at: legacy.accrued_at,
+        amount: legacy.amount,
+        rate: legacy.rate,
+        balance: legacy.balance,
+        compounded: 0,
+    }
+}
+
+/// Bring an agreement's deposit-interest record into the current layout if
+/// it is stored in an older one.
+pub(crate) fn migrate_deposit_interest(env: &Env, agreement_id: &String) {
+    let _ = get_deposit_interest(env.clone(), agreement_id.clone());
+}
+
+// ─── History ─────────────────────────────────────────────────────────────────
+
+/// Append a snapshot to the deposit's bucketed history.
+///
+/// Snapshots live in fixed-size buckets outside the `DepositInterest` entry,
+/// so no single ledger entry grows with the life of the deposit.
+fn record_accrual(env: &Env, di: &mut DepositInterest, accrual: InterestAccrual) {
+    let key = DataKey::AccrualBucket(di.escrow_id.clone(), di.history_count / ACCRUAL_BUCKET_SIZE);
+    let mut bucket: Vec<InterestAccrual> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    bucket.push_back(accrual);
+    env.storage().persistent().set(&key, &bucket);
+    di.history_count += 1;
 }
 
-/// Retrieve just the accrual history.
+/// Page through the accrual history, oldest first.
+///
+/// Returns at most `limit` snapshots (capped at `MAX_HISTORY_PAGE`) starting
+/// at index `cursor`.
 pub fn get_accrual_history(
     env: Env,
     escrow_id: String,
+    cursor: u32,
+    limit: u32,
 ) -> Result<Vec<InterestAccrual>, RentalError> {
-    let di = get_deposit_interest(env, escrow_id)?;
-    Ok(di.accrual_history)
+    let di = get_deposit_interest(env.clone(), escrow_id.clone())?;
+    let end = cursor
+        .saturating_add(limit.min(MAX_HISTORY_PAGE))
+        .min(di.history_count);
+
+    let mut page = Vec::new(&env);
+    let mut index = cursor;
+    while index < end {
+        let bucket_index = index / ACCRUAL_BUCKET_SIZE;
+        let bucket: Vec<InterestAccrual> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AccrualBucket(escrow_id.clone(), bucket_index))
+            .unwrap_or(Vec::new(&env));
+        let bucket_end = ((bucket_index + 1) * ACCRUAL_BUCKET_SIZE).min(end);
+        for i in index..bucket_end {
+            if let Some(entry) = bucket.get(i % ACCRUAL_BUCKET_SIZE) {
+                page.push_back(entry);
+            }
+        }
+        index = bucket_end;
+    }
+    Ok(page)
 }
 
 // ─── Distribution ────────────────────────────────────────────────────────────
 
-/// Distribute all accrued interest according to the config recipient setting.
+/// Set a custom split for an agreement's distributed interest.
 ///
-/// This marks `accrued_interest` as 0 after distribution (it has been paid
-/// out). The `total_with_interest` balance also resets to `principal` ready
+/// The three shares must add up to 10 000 bps.
+pub fn set_interest_split(
+    env: Env,
+    agreement_id: String,
+    split: InterestSplit,
+) -> Result<(), RentalError> {
+    get_deposit_interest_config(env.clone(), agreement_id.clone())?;
+
+    let total_bps = split.tenant_bps as u64 + split.landlord_bps as u64 + split.platform_bps as u64;
+    if total_bps != 10_000 {
+        return Err(RentalError::InvalidConfig);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::InterestSplit(agreement_id), &split);
+    Ok(())
+}
+
+/// The split applied when distributing an agreement's interest.
+///
+/// Falls back to the config's `InterestRecipient` when no custom split is set.
+pub fn get_interest_split(env: Env, agreement_id: String) -> Result<InterestSplit, RentalError> {
+    if let Some(split) = env
+        .storage()
+        .persistent()
+        .get(&DataKey::InterestSplit(agreement_id.clone()))
+    {
+        return Ok(split);
+    }
+
+    let config = get_deposit_interest_config(env, agreement_id)?;
+    let (tenant_bps, landlord_bps) = match config.interest_recipient {
+        InterestRecipient::Tenant => (10_000, 0),
+        InterestRecipient::Landlord => (0, 10_000),
+        InterestRecipient::Split => (5_000, 5_000),
+    };
+    Ok(InterestSplit {
+        tenant_bps,
+        landlord_bps,
+        platform_bps: 0,
+        tenant_compounds: false,
+        landlord_compounds: false,
+        platform_compounds: false,
+    })
+}
+
+/// Distribute all accrued interest according to the agreement's split.
+///
+/// This marks `accrued_interest` as 0 after distribution. Shares whose
+/// recipient compounds are added to `principal` instead of being paid out,
+/// and the step-up is recorded in the accrual history. The
+/// `total_with_interest` balance then resets to the (new) `principal` ready
 /// for the next accrual cycle.
 ///
-/// NOTE: actual token transfers require a token client.  We accept the
-/// agreement's `payment_token` and the `tenant` / `landlord` addresses from
-/// the on-chain agreement record.
+/// Recipients are the `tenant` / `landlord` addresses from the on-chain
+/// agreement record. The interest is paid out of the yield of the vault
+/// the deposit was routed to, and the compounded part is added to the
+/// deposit, so a deposit without a vault position cannot be distributed.
 pub fn distribute_interest(env: Env, escrow_id: String) -> Result<(), RentalError> {
     let config = get_deposit_interest_config(env.clone(), escrow_id.clone())?;
+    let split = get_interest_split(env.clone(), escrow_id.clone())?;
 
-    let mut di: DepositInterest = env
-        .storage()
-        .persistent()
-        .get(&DataKey::DepositInterest(escrow_id.clone()))
-        .ok_or(RentalError::InterestConfigNotFound)?;
+    let mut di = get_deposit_interest(env.clone(), escrow_id.clone())?;
 
     let total = di.accrued_interest;
     if total <= 0 {
         return Ok(());
     }
 
-    let agreement = env
-        .storage()
-        .persistent()
-        .get::<DataKey, crate::types::RentAgreement>(&DataKey::Agreement(escrow_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
-
-    let (user_share, admin_share) = match config.interest_recipient {
-        InterestRecipient::Tenant => (total, 0_i128),
-        InterestRecipient::Landlord => (0_i128, total),
-        InterestRecipient::Split => {
-            let half = total / 2;
-            (half, total - half) // remainder goes to landlord
+    let agreement =
+        agreement::load_agreement(&env, &escrow_id).ok_or(RentalError::AgreementNotFound)?;
+
+    let user_share = total * split.tenant_bps as i128 / 10_000;
+    let platform_share = total * split.platform_bps as i128 / 10_000;
+    let admin_share = total - user_share - platform_share; // remainder goes to landlord
+
+    let mut compounded = 0_i128;
+    let mut payouts: Vec<(Address, i128)> = Vec::new(&env);
+    for (share, compounds, recipient) in [
+        (
+            user_share,
+            split.tenant_compounds,
+            Some(agreement.user.clone()),
+        ),
+        (
+            admin_share,
+            split.landlord_compounds,
+            Some(agreement.admin.clone()),
+        ),
+        (platform_share, split.platform_compounds, None),
+    ] {
+        if share <= 0 {
+            continue;
         }
-    };
-
-    // Transfer via the token client.
-    let token_client = soroban_sdk::token::Client::new(&env, &agreement.payment_token);
-    let contract_self = env.current_contract_address();
+        if compounds {
+            compounded += share;
+        } else {
+            let to = match recipient {
+                Some(address) => address,
+                None => {
+                    env.storage()
+                        .instance()
+                        .get::<DataKey, ContractState>(&DataKey::State)
+                        .ok_or(RentalError::InvalidState)?
+                        .config
+                        .fee_collector
+                }
+            };
+            payouts.push_back((to, share));
+        }
+    }
 
-    if user_share > 0 {
-        token_client.transfer(&contract_self, &agreement.user, &user_share);
+    // Interest is only ever paid from vault yield, by the escrow holding
+    // the deposit; compounded shares join the deposit there.
+    if yield_adapter::get_yield_position(&env, escrow_id.clone()).is_none() {
+        return Err(RentalError::EscrowNotFound);
     }
-    if admin_share > 0 {
-        token_client.transfer(&contract_self, &agreement.admin, &admin_share);
+    for (to, amount) in payouts.iter() {
+        yield_adapter::withdraw_yield(&env, escrow_id.clone(), to, amount)?;
     }
+    yield_adapter::compound_yield(&env, escrow_id.clone(), compounded)?;
 
-    // Reset accrued interest; principal remains.
+    // Reset accrued interest; compounded shares join the principal.
     di.accrued_interest = 0;
+    di.principal += compounded;
     di.total_with_interest = di.principal;
+    if compounded > 0 {
+        let schedule = effective_schedule(&env, &config);
+        let now = env.ledger().timestamp();
+        let snapshot = InterestAccrual {
+            accrued_at: now,
+            amount: 0,
+            rate: rate_at(&schedule, now),
+            balance: di.principal,
+            compounded,
+        };
+        record_accrual(&env, &mut di, snapshot);
+    }
     env.storage()
         .persistent()
         .set(&DataKey::DepositInterest(escrow_id.clone()), &di);
 
-    events::interest_distributed(&env, escrow_id, user_share, admin_share);
+    events::interest_distributed(
+        &env,
+        escrow_id,
+        if split.tenant_compounds {
+            0
+        } else {
+            user_share
+        },
+        if split.landlord_compounds {
+            0
+        } else {
+            admin_share
+        },
+        if split.platform_compounds {
+            0
+        } else {
+            platform_share
+        },
+        compounded,
+    );
     Ok(())
 }
@@ -1,6 +1,4 @@
-use crate::storage::DataKey;
-use crate::types::ErrorContext;
-use soroban_sdk::{contracterror, Env, String, Vec};
+use soroban_sdk::{contracterror, Env, String};
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -129,7 +127,7 @@ impl RentalError {
             RentalError::EscrowNotFound => "Escrow account not found for this agreement.",
             RentalError::EscrowAlreadyReleased => "Escrow funds have already been released.",
             RentalError::EscrowInsufficientFunds => {
-                "Insufficient funds in escrow for this withdrawal."
+                "Insufficient funds in escrow for this operation."
             }
             RentalError::EscrowTimeoutNotReached => "Escrow period has not yet expired.",
 
@@ -168,66 +166,3 @@ impl RentalError {
         *self as u32
     }
 }
-
-pub fn log_error(
-    env: &Env,
-    error: RentalError,
-    operation: String,
-    details: String,
-) -> Result<(), RentalError> {
-    let mut count: u32 = env
-        .storage()
-        .instance()
-        .get(&DataKey::ErrorLogCount)
-        .unwrap_or(0);
-
-    let context = ErrorContext {
-        error_code: error.code(),
-        error_message: error.message(env),
-        details,
-        timestamp: env.ledger().timestamp(),
-        operation,
-    };
-
-    env.storage()
-        .persistent()
-        .set(&DataKey::ErrorLog(count), &context);
-
-    count += 1;
-    env.storage()
-        .instance()
-        .set(&DataKey::ErrorLogCount, &count);
-
-    // Publish event
-    crate::events::error_occurred(
-        env,
-        context.error_code,
-        context.operation,
-        context.timestamp,
-    );
-
-    Ok(())
-}
-
-pub fn get_error_logs(env: &Env, limit: u32) -> Result<Vec<ErrorContext>, RentalError> {
-    let count: u32 = env
-        .storage()
-        .instance()
-        .get(&DataKey::ErrorLogCount)
-        .unwrap_or(0);
-    let mut logs = Vec::new(env);
-
-    let start = count.saturating_sub(limit);
-
-    for i in start..count {
-        if let Some(log) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, ErrorContext>(&DataKey::ErrorLog(i))
-        {
-            logs.push_back(log);
-        }
-    }
-
-    Ok(logs)
-}
@@ -1,5 +1,5 @@
-use crate::Config;
-use soroban_sdk::{contractevent, Address, Env, String};
+use crate::{Config, ContractVersion};
+use soroban_sdk::{contractevent, Address, Bytes, BytesN, Env, String};
 
 /// Event emitted when the contract is initialized
 /// Topics: ["initialized", admin: Address]
@@ -175,6 +175,54 @@ pub struct InterestAccruedEvent {
     pub total_accrued: i128,
 }
 
+/// Event emitted when an agreement-specific deposit rate change is scheduled
+/// Topics: ["deposit_rate_scheduled", agreement_id: String]
+#[contractevent(topics = ["deposit_rate_scheduled"])]
+pub struct DepositRateScheduled {
+    #[topic]
+    pub agreement_id: String,
+    pub effective_from: u64,
+    pub annual_rate: u32,
+}
+
+/// Event emitted when the admin publishes a statutory deposit rate
+/// Topics: ["statutory_rate_published"]
+#[contractevent(topics = ["statutory_rate_published"])]
+pub struct StatutoryRatePublished {
+    pub effective_from: u64,
+    pub annual_rate: u32,
+}
+
+/// Event emitted when a yield vault is configured for a token
+/// Topics: ["yield_vault_set", token: Address]
+#[contractevent(topics = ["yield_vault_set"])]
+pub struct YieldVaultSet {
+    #[topic]
+    pub token: Address,
+    pub vault: Address,
+}
+
+/// Event emitted when a deposit's principal is moved into a yield vault
+/// Topics: ["deposit_routed_to_vault", escrow_id: String]
+#[contractevent(topics = ["deposit_routed_to_vault"])]
+pub struct DepositRoutedToVault {
+    #[topic]
+    pub escrow_id: String,
+    pub vault: Address,
+    pub amount: i128,
+    pub shares: i128,
+}
+
+/// Event emitted when a deposit is withdrawn from its yield vault
+/// Topics: ["deposit_withdrawn_from_vault", escrow_id: String]
+#[contractevent(topics = ["deposit_withdrawn_from_vault"])]
+pub struct DepositWithdrawnFromVault {
+    #[topic]
+    pub escrow_id: String,
+    pub vault: Address,
+    pub amount: i128,
+}
+
 /// Event emitted when accrued interest is distributed
 /// Topics: ["interest_distributed", escrow_id: String]
 #[contractevent(topics = ["interest_distributed"])]
@@ -183,15 +231,8 @@ pub struct InterestDistributed {
     pub escrow_id: String,
     pub user_share: i128,
     pub admin_share: i128,
-}
-
-/// Event emitted when a contract operation results in an error
-/// Topics: ["error_occurred"]
-#[contractevent(topics = ["error_occurred"])]
-pub struct ErrorOccurred {
-    pub error_code: u32,
-    pub operation: String,
-    pub timestamp: u64,
+    pub platform_share: i128,
+    pub compounded: i128,
 }
 
 // ─── Royalty Events ───────────────────────────────────────────────────────────
@@ -218,6 +259,68 @@ pub struct RoyaltyPaid {
     pub amount: i128,
 }
 
+// ─── Marketplace Events ───────────────────────────────────────────────────────
+
+/// Event emitted when a landlord position is listed for sale
+/// Topics: ["position_listed", agreement_id: String, seller: Address]
+#[contractevent(topics = ["position_listed"])]
+pub struct PositionListed {
+    #[topic]
+    pub agreement_id: String,
+    #[topic]
+    pub seller: Address,
+    pub listing_id: u32,
+    pub ask_price: i128,
+    pub expires_at: u64,
+}
+
+/// Event emitted when a listing is withdrawn by its seller
+/// Topics: ["listing_cancelled", agreement_id: String]
+#[contractevent(topics = ["listing_cancelled"])]
+pub struct ListingCancelled {
+    #[topic]
+    pub agreement_id: String,
+    pub listing_id: u32,
+}
+
+/// Event emitted when a bid is placed on a listing
+/// Topics: ["bid_placed", agreement_id: String, bidder: Address]
+#[contractevent(topics = ["bid_placed"])]
+pub struct BidPlaced {
+    #[topic]
+    pub agreement_id: String,
+    #[topic]
+    pub bidder: Address,
+    pub bid_id: u32,
+    pub amount: i128,
+}
+
+/// Event emitted when an open bid is withdrawn and refunded
+/// Topics: ["bid_withdrawn", agreement_id: String, bidder: Address]
+#[contractevent(topics = ["bid_withdrawn"])]
+pub struct BidWithdrawn {
+    #[topic]
+    pub agreement_id: String,
+    #[topic]
+    pub bidder: Address,
+    pub bid_id: u32,
+    pub amount: i128,
+}
+
+/// Event emitted when a landlord position changes hands through the
+/// marketplace; the tenant is a topic so it can watch for new landlords
+/// Topics: ["position_sold", agreement_id: String, tenant: Address]
+#[contractevent(topics = ["position_sold"])]
+pub struct PositionSold {
+    #[topic]
+    pub agreement_id: String,
+    #[topic]
+    pub tenant: Address,
+    pub seller: Address,
+    pub buyer: Address,
+    pub price: i128,
+}
+
 // ─── Rate Limiting Events ─────────────────────────────────────────────────────
 
 /// Event emitted when a rate limit is exceeded
@@ -240,6 +343,34 @@ pub struct RateLimitConfigUpdated {
     pub cooldown_blocks: u32,
 }
 
+/// Event emitted when a function's rate limit override is set
+/// Topics: ["fn_rate_limit_set", function_name: String]
+#[contractevent(topics = ["fn_rate_limit_set"])]
+pub struct FunctionRateLimitSet {
+    #[topic]
+    pub function_name: String,
+    pub max_calls_per_block: u32,
+    pub max_calls_per_user_per_day: u32,
+    pub cooldown_blocks: u32,
+}
+
+/// Event emitted when a function's rate limit override is removed
+/// Topics: ["fn_rate_limit_removed", function_name: String]
+#[contractevent(topics = ["fn_rate_limit_removed"])]
+pub struct FunctionRateLimitRemoved {
+    #[topic]
+    pub function_name: String,
+}
+
+/// Event emitted when an account is exempted from, or returned to, rate limiting
+/// Topics: ["rate_limit_exempt", account: Address]
+#[contractevent(topics = ["rate_limit_exempt"])]
+pub struct RateLimitExemptionUpdated {
+    #[topic]
+    pub account: Address,
+    pub exempt: bool,
+}
+
 // ─── Multi-Sig Events ─────────────────────────────────────────────────────────
 
 /// Event emitted when the multi-sig system is initialized
@@ -270,6 +401,7 @@ pub struct ActionApproved {
     #[topic]
     pub approver: Address,
     pub approval_count: u32,
+    pub approval_weight: u32,
 }
 
 /// Event emitted when a multi-sig action is executed
@@ -281,6 +413,18 @@ pub struct ActionExecuted {
     pub action_type: crate::types::ActionType,
 }
 
+/// Event emitted when an executed multi-sig action takes effect; `before` and
+/// `after` are XDR snapshots of the value the action changed
+/// Topics: ["action_applied", proposal_id: String]
+#[contractevent(topics = ["action_applied"])]
+pub struct ActionApplied {
+    #[topic]
+    pub proposal_id: String,
+    pub action_type: crate::types::ActionType,
+    pub before: Bytes,
+    pub after: Bytes,
+}
+
 /// Event emitted when a multi-sig action is rejected
 /// Topics: ["action_rejected", proposal_id: String]
 #[contractevent(topics = ["action_rejected"])]
@@ -315,6 +459,71 @@ pub struct RequiredSignaturesUpdated {
     pub new_required: u32,
 }
 
+/// Event emitted when an action type's approval threshold changes
+/// Topics: ["threshold_updated"]
+#[contractevent(topics = ["threshold_updated"])]
+pub struct ActionThresholdUpdated {
+    pub action_type: crate::types::ActionType,
+    pub old_threshold: u32,
+    pub new_threshold: u32,
+}
+
+/// Event emitted when an admin's approval weight changes
+/// Topics: ["weight_updated", admin: Address]
+#[contractevent(topics = ["weight_updated"])]
+pub struct AdminWeightUpdated {
+    #[topic]
+    pub admin: Address,
+    pub old_weight: u32,
+    pub new_weight: u32,
+}
+
+/// Event emitted when a role is granted
+/// Topics: ["role_granted", account: Address]
+#[contractevent(topics = ["role_granted"])]
+pub struct RoleGranted {
+    #[topic]
+    pub account: Address,
+    pub role: crate::types::Role,
+}
+
+/// Event emitted when a role is revoked
+/// Topics: ["role_revoked", account: Address]
+#[contractevent(topics = ["role_revoked"])]
+pub struct RoleRevoked {
+    #[topic]
+    pub account: Address,
+    pub role: crate::types::Role,
+}
+
+/// Event emitted when one module is paused
+/// Topics: ["module_paused", paused_by: Address]
+#[contractevent(topics = ["module_paused"])]
+pub struct ModulePaused {
+    #[topic]
+    pub paused_by: Address,
+    pub module: crate::types::PauseModule,
+    pub reason: String,
+    pub auto_unpause_at: Option<u64>,
+}
+
+/// Event emitted when one module is unpaused
+/// Topics: ["module_unpaused", unpaused_by: Address]
+#[contractevent(topics = ["module_unpaused"])]
+pub struct ModuleUnpaused {
+    #[topic]
+    pub unpaused_by: Address,
+    pub module: crate::types::PauseModule,
+}
+
+/// Event emitted when the pause guardian is set or removed
+/// Topics: ["guardian_updated"]
+#[contractevent(topics = ["guardian_updated"])]
+pub struct GuardianUpdated {
+    pub old_guardian: Option<Address>,
+    pub new_guardian: Option<Address>,
+}
+
 // ─── Timelock Events ──────────────────────────────────────────────────────────
 
 /// Event emitted when a timelocked action is queued
@@ -342,6 +551,13 @@ pub struct TimelockActionCancelled {
     pub action_id: String,
 }
 
+/// Event emitted when the timelock is enabled or disabled
+/// Topics: ["timelock_status"]
+#[contractevent(topics = ["timelock_status"])]
+pub struct TimelockStatusChanged {
+    pub enabled: bool,
+}
+
 // ─── Versioning Events ────────────────────────────────────────────────────────
 
 /// Event emitted when the contract version is updated
@@ -425,6 +641,36 @@ pub struct UpgradeApproved {
 pub struct UpgradeExecuted {
     #[topic]
     pub proposal_id: String,
+    pub wasm_hash: BytesN<32>,
+}
+
+/// Event emitted when stored data is migrated after an upgrade
+/// Topics: ["storage_migrated"]
+#[contractevent(topics = ["storage_migrated"])]
+pub struct StorageMigrated {
+    pub version: ContractVersion,
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+/// Event emitted when the protocol directory peers are resolved through is set
+/// Topics: ["directory_set", admin: Address]
+#[contractevent(topics = ["directory_set"])]
+pub struct DirectorySet {
+    #[topic]
+    pub admin: Address,
+    pub directory: Address,
+}
+
+/// Event emitted when activation moves the tenant's deposit into an escrow
+/// Topics: ["deposit_escrowed", user: Address]
+#[contractevent(topics = ["deposit_escrowed"])]
+pub struct DepositEscrowed {
+    #[topic]
+    pub user: Address,
+    pub agreement_id: String,
+    pub escrow_id: BytesN<32>,
+    pub security_deposit: i128,
 }
 
 // ─── Helper Functions ─────────────────────────────────────────────────────────
@@ -598,25 +844,76 @@ pub(crate) fn interest_accrued(env: &Env, escrow_id: String, amount: i128, total
     .publish(env);
 }
 
+pub(crate) fn deposit_rate_scheduled(
+    env: &Env,
+    agreement_id: String,
+    effective_from: u64,
+    annual_rate: u32,
+) {
+    DepositRateScheduled {
+        agreement_id,
+        effective_from,
+        annual_rate,
+    }
+    .publish(env);
+}
+
+pub(crate) fn statutory_rate_published(env: &Env, effective_from: u64, annual_rate: u32) {
+    StatutoryRatePublished {
+        effective_from,
+        annual_rate,
+    }
+    .publish(env);
+}
+
+pub(crate) fn yield_vault_set(env: &Env, token: Address, vault: Address) {
+    YieldVaultSet { token, vault }.publish(env);
+}
+
+pub(crate) fn deposit_routed_to_vault(
+    env: &Env,
+    escrow_id: String,
+    vault: Address,
+    amount: i128,
+    shares: i128,
+) {
+    DepositRoutedToVault {
+        escrow_id,
+        vault,
+        amount,
+        shares,
+    }
+    .publish(env);
+}
+
+pub(crate) fn deposit_withdrawn_from_vault(
+    env: &Env,
+    escrow_id: String,
+    vault: Address,
+    amount: i128,
+) {
+    DepositWithdrawnFromVault {
+        escrow_id,
+        vault,
+        amount,
+    }
+    .publish(env);
+}
+
 pub(crate) fn interest_distributed(
     env: &Env,
     escrow_id: String,
     user_share: i128,
     admin_share: i128,
+    platform_share: i128,
+    compounded: i128,
 ) {
     InterestDistributed {
         escrow_id,
         user_share,
         admin_share,
-    }
-    .publish(env);
-}
-
-pub(crate) fn error_occurred(env: &Env, error_code: u32, operation: String, timestamp: u64) {
-    ErrorOccurred {
-        error_code,
-        operation,
-        timestamp,
+        platform_share,
+        compounded,
     }
     .publish(env);
 }
@@ -639,6 +936,82 @@ pub(crate) fn royalty_paid(env: &Env, token_id: String, amount: i128, recipient:
     .publish(env);
 }
 
+pub(crate) fn position_listed(
+    env: &Env,
+    agreement_id: String,
+    seller: Address,
+    listing_id: u32,
+    ask_price: i128,
+    expires_at: u64,
+) {
+    PositionListed {
+        agreement_id,
+        seller,
+        listing_id,
+        ask_price,
+        expires_at,
+    }
+    .publish(env);
+}
+
+pub(crate) fn listing_cancelled(env: &Env, agreement_id: String, listing_id: u32) {
+    ListingCancelled {
+        agreement_id,
+        listing_id,
+    }
+    .publish(env);
+}
+
+pub(crate) fn bid_placed(
+    env: &Env,
+    agreement_id: String,
+    bidder: Address,
+    bid_id: u32,
+    amount: i128,
+) {
+    BidPlaced {
+        agreement_id,
+        bidder,
+        bid_id,
+        amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn bid_withdrawn(
+    env: &Env,
+    agreement_id: String,
+    bidder: Address,
+    bid_id: u32,
+    amount: i128,
+) {
+    BidWithdrawn {
+        agreement_id,
+        bidder,
+        bid_id,
+        amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn position_sold(
+    env: &Env,
+    agreement_id: String,
+    tenant: Address,
+    seller: Address,
+    buyer: Address,
+    price: i128,
+) {
+    PositionSold {
+        agreement_id,
+        tenant,
+        seller,
+        buyer,
+        price,
+    }
+    .publish(env);
+}
+
 pub(crate) fn rate_limit_exceeded(
     env: &Env,
     user: Address,
@@ -668,6 +1041,30 @@ pub(crate) fn rate_limit_config_updated(
     .publish(env);
 }
 
+pub(crate) fn function_rate_limit_set(
+    env: &Env,
+    function_name: String,
+    max_calls_per_block: u32,
+    max_calls_per_user_per_day: u32,
+    cooldown_blocks: u32,
+) {
+    FunctionRateLimitSet {
+        function_name,
+        max_calls_per_block,
+        max_calls_per_user_per_day,
+        cooldown_blocks,
+    }
+    .publish(env);
+}
+
+pub(crate) fn function_rate_limit_removed(env: &Env, function_name: String) {
+    FunctionRateLimitRemoved { function_name }.publish(env);
+}
+
+pub(crate) fn rate_limit_exemption_updated(env: &Env, account: Address, exempt: bool) {
+    RateLimitExemptionUpdated { account, exempt }.publish(env);
+}
+
 pub(crate) fn multisig_initialized(env: &Env, admins: u32, required_signatures: u32) {
     MultiSigInitialized {
         admins,
@@ -695,11 +1092,13 @@ pub(crate) fn action_approved(
     proposal_id: String,
     approver: Address,
     approval_count: u32,
+    approval_weight: u32,
 ) {
     ActionApproved {
         proposal_id,
         approver,
         approval_count,
+        approval_weight,
     }
     .publish(env);
 }
@@ -716,6 +1115,22 @@ pub(crate) fn action_executed(
     .publish(env);
 }
 
+pub(crate) fn action_applied(
+    env: &Env,
+    proposal_id: String,
+    action_type: crate::types::ActionType,
+    before: Bytes,
+    after: Bytes,
+) {
+    ActionApplied {
+        proposal_id,
+        action_type,
+        before,
+        after,
+    }
+    .publish(env);
+}
+
 pub(crate) fn action_rejected(env: &Env, proposal_id: String) {
     ActionRejected { proposal_id }.publish(env);
 }
@@ -744,6 +1159,73 @@ pub(crate) fn required_signatures_updated(env: &Env, old_required: u32, new_requ
     .publish(env);
 }
 
+pub(crate) fn action_threshold_updated(
+    env: &Env,
+    action_type: crate::types::ActionType,
+    old_threshold: u32,
+    new_threshold: u32,
+) {
+    ActionThresholdUpdated {
+        action_type,
+        old_threshold,
+        new_threshold,
+    }
+    .publish(env);
+}
+
+pub(crate) fn admin_weight_updated(env: &Env, admin: Address, old_weight: u32, new_weight: u32) {
+    AdminWeightUpdated {
+        admin,
+        old_weight,
+        new_weight,
+    }
+    .publish(env);
+}
+
+pub(crate) fn role_granted(env: &Env, role: crate::types::Role, account: Address) {
+    RoleGranted { account, role }.publish(env);
+}
+
+pub(crate) fn role_revoked(env: &Env, role: crate::types::Role, account: Address) {
+    RoleRevoked { account, role }.publish(env);
+}
+
+pub(crate) fn module_paused(
+    env: &Env,
+    module: crate::types::PauseModule,
+    paused_by: Address,
+    reason: String,
+    auto_unpause_at: Option<u64>,
+) {
+    ModulePaused {
+        paused_by,
+        module,
+        reason,
+        auto_unpause_at,
+    }
+    .publish(env);
+}
+
+pub(crate) fn module_unpaused(env: &Env, module: crate::types::PauseModule, unpaused_by: Address) {
+    ModuleUnpaused {
+        unpaused_by,
+        module,
+    }
+    .publish(env);
+}
+
+pub(crate) fn guardian_updated(
+    env: &Env,
+    old_guardian: Option<Address>,
+    new_guardian: Option<Address>,
+) {
+    GuardianUpdated {
+        old_guardian,
+        new_guardian,
+    }
+    .publish(env);
+}
+
 pub(crate) fn timelock_action_queued(env: &Env, action_id: String, eta: u64) {
     TimelockActionQueued { action_id, eta }.publish(env);
 }
@@ -756,6 +1238,10 @@ pub(crate) fn timelock_action_cancelled(env: &Env, action_id: String) {
     TimelockActionCancelled { action_id }.publish(env);
 }
 
+pub(crate) fn timelock_status_changed(env: &Env, enabled: bool) {
+    TimelockStatusChanged { enabled }.publish(env);
+}
+
 pub(crate) fn version_updated(env: &Env, major: u32, minor: u32, patch: u32) {
     VersionUpdated {
         major,
@@ -815,6 +1301,44 @@ pub(crate) fn upgrade_approved(env: &Env, proposal_id: String, approvals: u32) {
     .publish(env);
 }
 
-pub(crate) fn upgrade_executed(env: &Env, proposal_id: String) {
-    UpgradeExecuted { proposal_id }.publish(env);
+pub(crate) fn upgrade_executed(env: &Env, proposal_id: String, wasm_hash: BytesN<32>) {
+    UpgradeExecuted {
+        proposal_id,
+        wasm_hash,
+    }
+    .publish(env);
+}
+
+pub(crate) fn storage_migrated(
+    env: &Env,
+    version: ContractVersion,
+    from_version: u32,
+    to_version: u32,
+) {
+    StorageMigrated {
+        version,
+        from_version,
+        to_version,
+    }
+    .publish(env);
+}
+
+pub(crate) fn directory_set(env: &Env, admin: Address, directory: Address) {
+    DirectorySet { admin, directory }.publish(env);
+}
+
+pub(crate) fn deposit_escrowed(
+    env: &Env,
+    agreement_id: String,
+    user: Address,
+    escrow_id: BytesN<32>,
+    security_deposit: i128,
+) {
+    DepositEscrowed {
+        user,
+        agreement_id,
+        escrow_id,
+        security_deposit,
+    }
+    .publish(env);
 }
@@ -1,7 +1,9 @@
 //! Gas optimization utilities for the Chioma contract.
 //!
-//! Provides benchmarking structs and helper functions to estimate, track, and
-//! reduce the computational cost of on-chain operations.
+//! Resource metrics are measured off-chain by the workspace `benchmarks`
+//! crate, which runs each entrypoint under the soroban testutils cost model,
+//! and published here with `record_gas_metrics`. Estimates are read back from
+//! those measurements rather than computed on-chain.
 //!
 //! Resolves: https://github.com/chioma-housing-protocol-I/chioma/issues/478
 
@@ -10,22 +12,31 @@ use soroban_sdk::{contracttype, Env, String, Vec};
 use crate::errors::RentalError;
 use crate::storage::DataKey;
 
+/// Most metrics accepted by one `record_gas_metrics` call.
+pub const MAX_METRICS_BATCH: u32 = 50;
+
 // ─── Gas Metrics ─────────────────────────────────────────────────────────────
 
-/// Tracks gas usage statistics for a named operation.
+/// Resources one invocation of an operation consumed in a benchmark run.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GasMetrics {
-    /// Human-readable operation name (e.g. "make_payment_with_token").
+    /// Entrypoint name (e.g. "make_payment_with_token").
     pub operation: String,
-    /// Estimated average gas units consumed.
-    pub average_gas: u64,
-    /// Minimum observed gas units.
-    pub min_gas: u64,
-    /// Maximum observed gas units.
-    pub max_gas: u64,
-    /// Estimated optimisation potential as a percentage (0–100).
-    pub optimization_potential: u32,
+    /// Modelled CPU instructions.
+    pub cpu_instructions: u64,
+    /// Modelled memory in bytes.
+    pub memory_bytes: u64,
+    /// Ledger entries read.
+    pub read_entries: u32,
+    /// Ledger entries written.
+    pub write_entries: u32,
+    /// Bytes read from disk.
+    pub read_bytes: u32,
+    /// Bytes written to the ledger.
+    pub write_bytes: u32,
+    /// Ledger timestamp the metrics were recorded at; set by the contract.
+    pub recorded_at: u64,
 }
 
 /// A concrete suggestion for reducing gas on a given operation.
@@ -48,76 +59,58 @@ pub enum OperationType {
     ProposeExtension,
 }
 
+/// Operation types `get_gas_metrics` reports on.
+pub const TRACKED_OPERATIONS: [OperationType; 5] = [
+    OperationType::CreateAgreement,
+    OperationType::MakePayment,
+    OperationType::ReleaseEscrow,
+    OperationType::ResolveDispute,
+    OperationType::ProposeExtension,
+];
+
 // ─── Public API ───────────────────────────────────────────────────────────────
 
-/// Return a static gas-cost estimate for the given operation type.
-///
-/// Values are conservative upper-bound estimates based on the number of
-/// storage reads/writes each operation performs.
-pub fn estimate_gas_cost(env: Env, operation: OperationType) -> Result<u64, RentalError> {
-    // Each persistent storage read ≈ 5 000 gas units (Soroban approximation).
-    // Each persistent storage write ≈ 10 000 gas units.
-    // Token transfer (cross-contract call) ≈ 25 000 gas units.
-    let estimate = match operation {
-        OperationType::CreateAgreement => {
-            // 1 duplicate-check read + 1 write + 1 TTL extend + 1 counter r/w
-            5_000u64 + 10_000 + 2_000 + 5_000 + 10_000
-        }
-        OperationType::MakePayment => {
-            // 1 agreement read + optional token-rate read + 1 token transfer
-            // + 1 payment-record write + 1 agreement write + 2 TTL extends
-            5_000u64 + 5_000 + 25_000 + 10_000 + 10_000 + 4_000
-        }
-        OperationType::ReleaseEscrow => {
-            // 1 agreement read + 1 frozen-flag read + 1 token balance read
-            // + 1 token transfer + 1 event
-            5_000u64 + 5_000 + 5_000 + 25_000 + 1_000
-        }
-        OperationType::ResolveDispute => {
-            // 2 reads + 1 write + 1 token transfer + 1 event
-            10_000u64 + 10_000 + 25_000 + 1_000
-        }
-        OperationType::ProposeExtension => {
-            // 1 agreement read + 1 extension write + 1 history r/w + 1 event
-            5_000u64 + 10_000 + 5_000 + 10_000 + 1_000
+/// Store measured metrics, replacing any recorded for the same operation.
+/// Callers are responsible for authorisation.
+pub fn record_gas_metrics(env: &Env, metrics: Vec<GasMetrics>) -> Result<(), RentalError> {
+    if metrics.is_empty() || metrics.len() > MAX_METRICS_BATCH {
+        return Err(RentalError::InvalidInput);
+    }
+
+    let now = env.ledger().timestamp();
+    for mut entry in metrics.iter() {
+        if entry.operation.is_empty() {
+            return Err(RentalError::InvalidInput);
         }
-    };
+        entry.recorded_at = now;
+        env.storage()
+            .persistent()
+            .set(&DataKey::GasMetrics(entry.operation.clone()), &entry);
+    }
+    Ok(())
+}
 
-    // Persist the estimate so get_gas_metrics can surface it.
-    let op_name = operation_name(&env, &operation);
-    let metrics = GasMetrics {
-        operation: op_name.clone(),
-        average_gas: estimate,
-        min_gas: estimate.saturating_sub(estimate / 5),
-        max_gas: estimate + estimate / 5,
-        optimization_potential: optimization_potential_for(&operation),
-    };
-    // Use the operation name string directly as the storage key.
+/// Metrics last recorded for `operation`, if any.
+pub fn get_operation_metrics(env: &Env, operation: String) -> Option<GasMetrics> {
     env.storage()
-        .instance()
-        .set(&DataKey::GasMetrics(op_name), &metrics);
+        .persistent()
+        .get(&DataKey::GasMetrics(operation))
+}
 
-    Ok(estimate)
+/// Measured CPU instructions for the given operation type.
+///
+/// Fails with `InvalidState` until metrics for the operation are recorded.
+pub fn estimate_gas_cost(env: Env, operation: OperationType) -> Result<u64, RentalError> {
+    get_operation_metrics(&env, operation_name(&env, &operation))
+        .map(|metrics| metrics.cpu_instructions)
+        .ok_or(RentalError::InvalidState)
 }
 
-/// Return all persisted gas metrics.
+/// Return the recorded metrics of every tracked operation type.
 pub fn get_gas_metrics(env: Env) -> Result<Vec<GasMetrics>, RentalError> {
-    let ops = [
-        OperationType::CreateAgreement,
-        OperationType::MakePayment,
-        OperationType::ReleaseEscrow,
-        OperationType::ResolveDispute,
-        OperationType::ProposeExtension,
-    ];
-
     let mut result = Vec::new(&env);
-    for op in ops.iter() {
-        let op_name = operation_name(&env, op);
-        if let Some(m) = env
-            .storage()
-            .instance()
-            .get::<DataKey, GasMetrics>(&DataKey::GasMetrics(op_name))
-        {
+    for op in TRACKED_OPERATIONS.iter() {
+        if let Some(m) = get_operation_metrics(&env, operation_name(&env, op)) {
             result.push_back(m);
         }
     }
@@ -162,7 +155,7 @@ pub fn optimize_operation(
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
-fn operation_name(env: &Env, op: &OperationType) -> String {
+pub(crate) fn operation_name(env: &Env, op: &OperationType) -> String {
     match op {
         OperationType::CreateAgreement => String::from_str(env, "create_agreement"),
         OperationType::MakePayment => String::from_str(env, "make_payment_with_token"),
@@ -171,13 +164,3 @@ fn operation_name(env: &Env, op: &OperationType) -> String {
         OperationType::ProposeExtension => String::from_str(env, "propose_extension"),
     }
 }
-
-fn optimization_potential_for(op: &OperationType) -> u32 {
-    match op {
-        OperationType::CreateAgreement => 15,
-        OperationType::MakePayment => 25,
-        OperationType::ReleaseEscrow => 20,
-        OperationType::ResolveDispute => 20,
-        OperationType::ProposeExtension => 15,
-    }
-}
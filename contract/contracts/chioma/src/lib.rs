@@ -7,20 +7,27 @@
 //! @notice On-chain rental agreement lifecycle: create, sign, submit, cancel, and query agreements.
 //! Optimized for gas efficiency and security.
 
-use soroban_sdk::{contract, contractimpl, Address, Bytes, Env, String, Vec};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, String, Vec};
 
+mod access;
+mod admin;
 mod agreement;
+mod audit;
 mod deposit_interest;
 mod errors;
 mod events;
 mod gas_optimization;
+mod marketplace;
+mod migration;
 mod multi_sig;
 mod multi_token;
+mod peers;
 mod rate_limit;
 mod royalties;
 mod storage;
 mod timelock;
 mod types;
+mod yield_adapter;
 
 #[cfg(test)]
 mod tests;
@@ -61,15 +68,28 @@ mod tests_lease_negotiation;
 #[cfg(test)]
 mod tests_property;
 
+#[cfg(test)]
+mod tests_yield_adapter;
+
+#[cfg(test)]
+mod tests_marketplace;
+
+#[cfg(test)]
+mod tests_upgrade;
+
+#[cfg(test)]
+mod tests_gas;
+
 pub use agreement::{
-    accept_extension, activate_extension, approve_agreement, cancel_agreement, cancel_extension,
-    create_agreement, create_agreement_with_token, get_agreement, get_agreement_count,
-    get_agreement_token, get_current_agreement_end, get_extension, get_extension_history,
-    get_payment_history, get_payment_split, has_agreement, is_escrow_frozen,
-    make_payment_with_token, propose_extension, reject_extension, release_escrow_with_token,
-    set_escrow_frozen, sign_agreement, submit_agreement, update_metadata,
-    validate_agreement_params,
+    accept_extension, activate_extension, approve_agreement, approve_with_first_rent,
+    cancel_agreement, cancel_extension, create_agreement, create_agreement_with_token,
+    get_agreement, get_agreement_count, get_agreement_token, get_current_agreement_end,
+    get_extension, get_extension_history, get_payment_history, get_payment_split, has_agreement,
+    is_escrow_frozen, make_payment_with_token, propose_extension, reject_extension,
+    release_escrow_with_token, set_escrow_frozen, sign_agreement, submit_agreement,
+    update_metadata, validate_agreement_params,
 };
+pub use audit::MAX_AUDIT_CAPACITY;
 pub use errors::RentalError;
 pub use gas_optimization::{
     estimate_gas_cost, get_gas_metrics, optimize_operation, GasMetrics, OperationType,
@@ -79,15 +99,23 @@ pub use multi_token::{
     add_supported_token, convert_amount, get_exchange_rate, get_supported_tokens,
     is_token_supported, remove_supported_token, set_exchange_rate,
 };
-pub use storage::DataKey;
+pub use peers::{
+    Component, DirectoryClient, DirectoryInterface, EscrowClient, EscrowInterface, EscrowStatus,
+    VaultPosition,
+};
+pub use storage::{DataKey, GovernanceKey, MigrationKey, RateLimitKey};
+pub use timelock::get_min_delay;
 pub use types::{
-    ActionType, AdminProposal, AgreementExtension, AgreementInput, AgreementStatus, AgreementTerms,
-    AgreementWithToken, Attribute, CompoundingFrequency, Config, ContractState,
-    ContractUpgradeProposal, ContractVersion, DepositInterest, DepositInterestConfig, ErrorContext,
-    ExtensionHistory, ExtensionStatus, InterestAccrual, InterestRecipient, MultiSigConfig,
-    PauseState, PaymentSplit, RateLimitConfig, RateLimitReason, RentAgreement, RoyaltyConfig,
-    RoyaltyPayment, SupportedToken, TimelockAction, TimelockActionType, TokenExchangeRate,
-    UserCallCount, VersionStatus,
+    ActionThreshold, ActionType, AdminProposal, AgreementExtension, AgreementInput,
+    AgreementStatus, AgreementTerms, AgreementWithToken, Attribute, AuditEntry, AuditFilter,
+    AuditPage, BidStatus, CompoundingFrequency, Config, ContractState, ContractUpgradeProposal,
+    ContractVersion, DepositInterest, DepositInterestConfig, ExtensionHistory, ExtensionStatus,
+    InterestAccrual, InterestAccrualBatch, InterestRateSource, InterestRecipient, InterestSplit,
+    MultiSigConfig, PauseModule, PauseState, PaymentSplit, PositionBid, PositionListing,
+    RateLimitConfig, RateLimitMode, RateLimitReason, RateSegment, RecordMigrationBatch,
+    RentAgreement, Role, RoyaltyConfig, RoyaltyLeg, RoyaltyPayment, RoyaltyShare, SupportedToken,
+    TimelockAction, TimelockActionType, TokenExchangeRate, UserCallCount, VersionStatus,
+    VersionedAgreement, YieldPosition,
 };
 
 /// Chioma rental agreement contract.
@@ -117,11 +145,20 @@ impl Contract {
             })
     }
 
-    /// Record a new contract version (admin only).
-    pub fn record_version(env: Env, version: ContractVersion) -> Result<(), RentalError> {
-        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-        state.admin.require_auth();
+    /// Record a new contract version (`Upgrader` role).
+    pub fn record_version(
+        env: Env,
+        caller: Address,
+        version: ContractVersion,
+    ) -> Result<(), RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Upgrader)?;
 
+        Self::store_version(&env, version);
+        Ok(())
+    }
+
+    fn store_version(env: &Env, version: ContractVersion) {
         env.storage()
             .instance()
             .set(&DataKey::CurrentVersion, &version);
@@ -130,7 +167,7 @@ impl Contract {
             .storage()
             .instance()
             .get(&DataKey::VersionHistory)
-            .unwrap_or(Vec::new(&env));
+            .unwrap_or(Vec::new(env));
 
         history.push_back(version.clone());
         env.storage()
@@ -138,21 +175,20 @@ impl Contract {
             .set(&DataKey::VersionHistory, &history);
         env.storage().instance().extend_ttl(500000, 500000);
 
-        events::version_updated(&env, version.major, version.minor, version.patch);
-
-        Ok(())
+        events::version_updated(env, version.major, version.minor, version.patch);
     }
 
-    /// Update the status of an existing version (admin only).
+    /// Update the status of an existing version (`Upgrader` role).
     pub fn update_version_status(
         env: Env,
+        caller: Address,
         major: u32,
         minor: u32,
         patch: u32,
         status: VersionStatus,
     ) -> Result<(), RentalError> {
-        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-        state.admin.require_auth();
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Upgrader)?;
 
         let mut history: Vec<ContractVersion> = env
             .storage()
@@ -201,12 +237,13 @@ impl Contract {
         env: Env,
         proposer: Address,
         proposal_id: String,
-        wasm_hash: Bytes,
+        wasm_hash: BytesN<32>,
         notes: String,
         delay_seconds: u64,
     ) -> Result<(), RentalError> {
         proposer.require_auth();
         multi_sig::require_admin(&env, &proposer)?;
+        access::require_role(&env, &proposer, Role::Upgrader)?;
 
         if env
             .storage()
@@ -286,6 +323,10 @@ impl Contract {
             return Err(RentalError::ProposalAlreadyExecuted);
         }
 
+        if proposal.cancelled {
+            return Err(RentalError::TimelockAlreadyCancelled);
+        }
+
         for approved in proposal.approvals.iter() {
             if approved == approver {
                 return Err(RentalError::AlreadyApproved);
@@ -303,7 +344,11 @@ impl Contract {
         Ok(approvals)
     }
 
-    /// Execute an approved upgrade proposal after ETA and record new version metadata.
+    /// Execute an approved upgrade proposal after ETA, install its WASM and
+    /// record `new_version`, which must be newer than the current version.
+    /// The approving admins' combined weight must meet `required_signatures`,
+    /// and a cancelled proposal cannot run. Call `migrate` once the new code
+    /// is live.
     pub fn execute_contract_upgrade(
         env: Env,
         executor: Address,
@@ -311,7 +356,7 @@ impl Contract {
         mut new_version: ContractVersion,
     ) -> Result<(), RentalError> {
         executor.require_auth();
-        multi_sig::require_admin(&env, &executor)?;
+        access::require_role(&env, &executor, Role::Upgrader)?;
 
         let mut proposal: ContractUpgradeProposal = env
             .storage()
@@ -323,14 +368,26 @@ impl Contract {
             return Err(RentalError::ProposalAlreadyExecuted);
         }
 
+        if proposal.cancelled {
+            return Err(RentalError::TimelockAlreadyCancelled);
+        }
+
         if env.ledger().timestamp() < proposal.eta {
             return Err(RentalError::TimelockEtaNotReached);
         }
 
-        if proposal.approvals.len() < proposal.required_signatures {
+        // Approvals count by admin weight, like any other multi-sig action.
+        let config = multi_sig::get_multisig_config(&env)?;
+        if multi_sig::weight_of_all(&env, &config, &proposal.approvals)
+            < multi_sig::upgrade_threshold(&env, &config)
+        {
             return Err(RentalError::InsufficientApprovals);
         }
 
+        if migration::semver(&new_version) <= migration::semver(&Self::get_version(env.clone())) {
+            return Err(RentalError::InvalidInput);
+        }
+
         proposal.executed = true;
         env.storage()
             .persistent()
@@ -353,11 +410,15 @@ impl Contract {
             .instance()
             .set(&DataKey::ActiveUpgradeProposals, &active);
 
-        new_version.hash = proposal.wasm_hash;
+        new_version.hash = proposal.wasm_hash.clone().into();
         new_version.updated_at = env.ledger().timestamp();
 
-        Self::record_version(env.clone(), new_version)?;
-        events::upgrade_executed(&env, proposal_id);
+        Self::store_version(&env, new_version);
+        events::upgrade_executed(&env, proposal_id, proposal.wasm_hash.clone());
+
+        // The new code takes over once this invocation returns.
+        env.deployer()
+            .update_current_contract_wasm(proposal.wasm_hash);
 
         Ok(())
     }
@@ -386,12 +447,73 @@ impl Contract {
             .unwrap_or(0)
     }
 
+    /// Run pending storage migrations after an upgrade (`Upgrader` role).
+    ///
+    /// Runs once per recorded version and returns the resulting storage layout.
+    pub fn migrate(env: Env, caller: Address) -> Result<u32, RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Upgrader)?;
+
+        migration::migrate(&env, &Self::get_version(env.clone()))
+    }
+
+    /// Get the layout version of the stored data.
+    pub fn get_storage_version(env: Env) -> u32 {
+        migration::get_storage_version(&env)
+    }
+
+    /// Rewrite the next batch of agreements in the current record layout,
+    /// starting at `cursor` (`Upgrader` role).
+    pub fn migrate_records(
+        env: Env,
+        caller: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<RecordMigrationBatch, RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Upgrader)?;
+
+        migration::migrate_records(&env, cursor, limit)
+    }
+
+    /// Rewrite the listed agreements in the current record layout, for
+    /// agreements created before the creation-order index (`Upgrader` role).
+    pub fn migrate_records_by_id(
+        env: Env,
+        caller: Address,
+        agreement_ids: Vec<String>,
+    ) -> Result<u32, RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Upgrader)?;
+
+        migration::migrate_listed_records(&env, &agreement_ids)
+    }
+
+    /// Resolve peer contracts, such as the escrow deposits are moved into,
+    /// through `directory` from now on (`Upgrader` role).
+    ///
+    /// @custom:error Unauthorized If the timelock is enabled.
+    pub fn set_directory(env: Env, caller: Address, directory: Address) -> Result<(), RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Upgrader)?;
+        timelock::require_disabled(&env)?;
+
+        peers::set_directory(&env, caller, directory);
+        Ok(())
+    }
+
+    /// Get the protocol directory peers are resolved through, if set.
+    pub fn get_directory(env: Env) -> Option<Address> {
+        peers::get_directory(&env)
+    }
+
     /// Initialize the contract with an admin and configuration.
     ///
     /// @notice One-time setup: sets admin and config. Callable only once.
     /// @param env The Soroban environment.
     /// @param admin Address that will have admin privileges.
     /// @param config Initial configuration (e.g. fee_bps, paused).
+    ///   The admin is granted every `Role`.
     /// @return Ok(()) on success.
     /// @custom:error AlreadyInitialized If the contract has already been initialized.
     /// @custom:error InvalidConfig If config.fee_bps > 10000.
@@ -416,50 +538,42 @@ impl Contract {
             config: config.clone(),
             initialized: true,
         };
-
-        env.storage().instance().set(&DataKey::State, &state);
-        env.storage().instance().extend_ttl(500000, 500000);
+        admin::save_state(&env, &state);
 
         if config.paused {
-            Self::set_pause_state(
+            admin::set_pause_state(
                 &env,
                 admin.clone(),
                 String::from_str(&env, "Initialized in paused mode"),
+                None,
             );
         }
 
+        access::grant_all_roles(&env, &admin);
+        migration::init_storage_version(&env);
+
         events::contract_initialized(&env, admin, config);
 
         Ok(())
     }
 
+    /// Whether `account` holds `role`.
+    pub fn has_role(env: Env, account: Address, role: Role) -> bool {
+        access::has_role(&env, &account, role)
+    }
+
     /// Get the current state of the contract.
     ///
     /// @notice Returns admin, config, and initialized flag if the contract has been initialized.
+    /// `config.paused` reports whether a pause is in effect now, so it clears when a timed pause lifts.
     /// @param env The Soroban environment.
     /// @return The contract state if initialized, otherwise None.
     pub fn get_state(env: Env) -> Option<ContractState> {
-        env.storage().instance().get(&DataKey::State)
-    }
-
-    fn set_pause_state(env: &Env, admin: Address, reason: String) -> PauseState {
-        let pause_state = PauseState {
-            is_paused: true,
-            paused_at: env.ledger().timestamp(),
-            paused_by: admin,
-            pause_reason: reason,
-        };
-
-        env.storage()
-            .instance()
-            .set(&DataKey::PauseState, &pause_state);
-        env.storage().instance().extend_ttl(500000, 500000);
-
-        pause_state
+        admin::get_state(&env).ok()
     }
 
-    fn check_paused(env: &Env) -> Result<(), RentalError> {
-        if Self::is_paused(env.clone()) {
+    fn check_paused(env: &Env, module: PauseModule) -> Result<(), RentalError> {
+        if admin::is_paused(env) || admin::is_module_paused(env, module) {
             return Err(RentalError::ContractPaused);
         }
         Ok(())
@@ -467,115 +581,111 @@ impl Contract {
 
     /// Update contract configuration.
     ///
-    /// @notice Admin-only: updates fee and paused state. Emits config_updated event.
+    /// @notice Requires the `Treasurer` role; updates fee and paused state. Emits config_updated event.
     /// @param env The Soroban environment.
+    /// @param caller Holder of the `Treasurer` role.
     /// @param new_config New configuration (e.g. fee_bps, paused).
     /// @return Ok(()) on success.
-    /// @custom:error InvalidState If contract state is missing.
+    /// @custom:error Unauthorized If caller lacks the `Treasurer` role.
     /// @custom:error InvalidConfig If new_config.fee_bps > 10000.
-    pub fn update_config(env: Env, new_config: Config) -> Result<(), RentalError> {
-        let mut state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-
-        state.admin.require_auth();
-
-        let was_paused = Self::is_paused(env.clone());
-
-        if new_config.fee_bps > 10_000 {
-            return Err(RentalError::InvalidConfig);
-        }
-
-        let old_config = state.config.clone();
-        state.config = new_config.clone();
-
-        env.storage().instance().set(&DataKey::State, &state);
-        env.storage().instance().extend_ttl(500000, 500000);
-
-        if new_config.paused && !was_paused {
-            let reason = String::from_str(&env, "Paused via config update");
-            Self::set_pause_state(&env, state.admin.clone(), reason.clone());
-            events::paused(&env, reason, state.admin.clone());
-        } else if !new_config.paused && was_paused {
-            env.storage().instance().remove(&DataKey::PauseState);
-            events::unpaused(&env, state.admin.clone());
-        }
-
-        events::config_updated(&env, state.admin, old_config, new_config);
+    /// @custom:error Unauthorized If the timelock is enabled; queue an UpdateConfig action instead.
+    pub fn update_config(env: Env, caller: Address, new_config: Config) -> Result<(), RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Treasurer)?;
+        timelock::require_disabled(&env)?;
 
+        admin::apply_config(&env, caller, new_config)?;
         Ok(())
     }
 
-    pub fn pause(env: Env, reason: String) -> Result<(), RentalError> {
-        let mut state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-
-        state.admin.require_auth();
-
-        if Self::is_paused(env.clone()) {
-            return Err(RentalError::AlreadyPaused);
-        }
+    /// Pause every module. Callable by a `Pauser`, whose pause lasts until
+    /// `unpause`, or by the guardian, whose pause lifts on its own after
+    /// `GUARDIAN_PAUSE_SECONDS`.
+    pub fn pause(env: Env, caller: Address, reason: String) -> Result<(), RentalError> {
+        caller.require_auth();
+        let auto_unpause_at = access::pause_deadline(&env, &caller, 0)?;
 
-        Self::set_pause_state(&env, state.admin.clone(), reason.clone());
+        admin::apply_pause(&env, caller, reason, auto_unpause_at)
+    }
 
-        if !state.config.paused {
-            state.config.paused = true;
-            env.storage().instance().set(&DataKey::State, &state);
-            env.storage().instance().extend_ttl(500000, 500000);
-        }
+    /// Pausing stays immediate so it can be used in an emergency; lifting a
+    /// pause must be queued while the timelock is enabled.
+    pub fn unpause(env: Env, caller: Address) -> Result<(), RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Pauser)?;
+        timelock::require_disabled(&env)?;
 
-        events::paused(&env, reason, state.admin);
-        Ok(())
+        admin::apply_unpause(&env, caller)
     }
 
-    pub fn unpause(env: Env) -> Result<(), RentalError> {
-        let mut state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
+    pub fn is_paused(env: Env) -> bool {
+        admin::is_paused(&env)
+    }
 
-        state.admin.require_auth();
+    /// Get the contract-wide pause record, including one that has expired.
+    pub fn get_pause_state(env: Env) -> Option<PauseState> {
+        admin::get_pause_state(&env)
+    }
 
-        if !Self::is_paused(env.clone()) {
-            return Err(RentalError::NotPaused);
-        }
+    /// Pause one module for `duration` seconds, or until `unpause_module`
+    /// when zero. The guardian may pause too, for at most
+    /// `GUARDIAN_PAUSE_SECONDS`.
+    pub fn pause_module(
+        env: Env,
+        caller: Address,
+        module: PauseModule,
+        reason: String,
+        duration: u64,
+    ) -> Result<(), RentalError> {
+        caller.require_auth();
+        let auto_unpause_at = access::pause_deadline(&env, &caller, duration)?;
 
-        env.storage().instance().remove(&DataKey::PauseState);
+        admin::apply_module_pause(&env, module, caller, reason, auto_unpause_at)
+    }
 
-        if state.config.paused {
-            state.config.paused = false;
-            env.storage().instance().set(&DataKey::State, &state);
-            env.storage().instance().extend_ttl(500000, 500000);
-        }
+    /// Lift a module pause (`Pauser` role). Must be queued as an
+    /// `UnpauseModule` action while the timelock is enabled.
+    pub fn unpause_module(
+        env: Env,
+        caller: Address,
+        module: PauseModule,
+    ) -> Result<(), RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Pauser)?;
+        timelock::require_disabled(&env)?;
 
-        events::unpaused(&env, state.admin);
-        Ok(())
+        admin::apply_module_unpause(&env, module, caller)
     }
 
-    pub fn is_paused(env: Env) -> bool {
-        if let Some(pause_state) = env
-            .storage()
-            .instance()
-            .get::<DataKey, PauseState>(&DataKey::PauseState)
-        {
-            return pause_state.is_paused;
-        }
+    /// Whether `module` is paused on its own, ignoring a contract-wide pause.
+    pub fn is_module_paused(env: Env, module: PauseModule) -> bool {
+        admin::is_module_paused(&env, module)
+    }
 
-        if let Some(state) = Self::get_state(env) {
-            return state.config.paused;
-        }
+    /// Get a module's pause record, including one that has expired.
+    pub fn get_module_pause_state(env: Env, module: PauseModule) -> Option<PauseState> {
+        admin::get_module_pause_state(&env, module)
+    }
 
-        false
+    /// Get the address allowed to pause but never unpause, if any.
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        access::get_guardian(&env)
     }
 
     // --- Token Management Functions ---
 
     pub fn add_supported_token(
         env: Env,
+        caller: Address,
         token_address: Address,
         symbol: String,
         decimals: u32,
         min_amount: i128,
         max_amount: i128,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
-        // Only admin can add tokens
-        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-        state.admin.require_auth();
+        Self::check_paused(&env, PauseModule::Tokens)?;
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::TokenManager)?;
 
         multi_token::add_supported_token(
             env,
@@ -587,10 +697,14 @@ impl Contract {
         )
     }
 
-    pub fn remove_supported_token(env: Env, token_address: Address) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
-        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-        state.admin.require_auth();
+    pub fn remove_supported_token(
+        env: Env,
+        caller: Address,
+        token_address: Address,
+    ) -> Result<(), RentalError> {
+        Self::check_paused(&env, PauseModule::Tokens)?;
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::TokenManager)?;
 
         multi_token::remove_supported_token(env, token_address)
     }
@@ -607,13 +721,15 @@ impl Contract {
 
     pub fn set_exchange_rate(
         env: Env,
+        caller: Address,
         from_token: Address,
         to_token: Address,
         rate: i128,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
-        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-        state.admin.require_auth();
+        Self::check_paused(&env, PauseModule::Tokens)?;
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::RateSetter)?;
+        timelock::require_disabled(&env)?;
 
         multi_token::set_exchange_rate(env, from_token, to_token, rate)
     }
@@ -628,11 +744,13 @@ impl Contract {
 
     pub fn update_exchange_rates(
         env: Env,
+        caller: Address,
         rates: Vec<(Address, Address, i128)>,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
-        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-        state.admin.require_auth();
+        Self::check_paused(&env, PauseModule::Tokens)?;
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::RateSetter)?;
+        timelock::require_disabled(&env)?;
 
         for (from, to, rate) in rates.iter() {
             multi_token::set_exchange_rate(env.clone(), from, to, rate)?;
@@ -655,7 +773,7 @@ impl Contract {
         env: Env,
         input: crate::types::AgreementInput,
     ) -> Result<String, RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::create_agreement_with_token(&env, input)
     }
 
@@ -671,7 +789,7 @@ impl Contract {
         amount: i128,
         token: Address,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Payments)?;
         agreement::make_payment_with_token(&env, agreement_id, amount, token)
     }
 
@@ -680,44 +798,32 @@ impl Contract {
         escrow_id: String,
         token: Address,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Escrow)?;
         agreement::release_escrow_with_token(&env, escrow_id, token)
     }
 
     /// Freeze escrow funds for a specific agreement.
     ///
-    /// Can be called by system admin or a configured multi-sig admin (DAO-voted entity).
+    /// Requires the `Arbitrator` role.
     pub fn freeze_escrow(env: Env, caller: Address, escrow_id: String) -> Result<(), RentalError> {
         caller.require_auth();
-        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-        let is_system_admin = caller == state.admin;
-        let is_dao_admin = multi_sig::is_admin(&env, &caller).unwrap_or(false);
-
-        if !is_system_admin && !is_dao_admin {
-            return Err(RentalError::Unauthorized);
-        }
+        access::require_role(&env, &caller, Role::Arbitrator)?;
 
-        agreement::set_escrow_frozen(&env, escrow_id, true)
+        agreement::set_escrow_frozen(&env, caller, escrow_id, true)
     }
 
     /// Unfreeze escrow funds for a specific agreement.
     ///
-    /// Can be called by system admin or a configured multi-sig admin (DAO-voted entity).
+    /// Requires the `Arbitrator` role.
     pub fn unfreeze_escrow(
         env: Env,
         caller: Address,
         escrow_id: String,
     ) -> Result<(), RentalError> {
         caller.require_auth();
-        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-        let is_system_admin = caller == state.admin;
-        let is_dao_admin = multi_sig::is_admin(&env, &caller).unwrap_or(false);
-
-        if !is_system_admin && !is_dao_admin {
-            return Err(RentalError::Unauthorized);
-        }
+        access::require_role(&env, &caller, Role::Arbitrator)?;
 
-        agreement::set_escrow_frozen(&env, escrow_id, false)
+        agreement::set_escrow_frozen(&env, caller, escrow_id, false)
     }
 
     /// Check whether escrow funds are currently frozen for an agreement.
@@ -745,7 +851,7 @@ impl Contract {
         env: Env,
         input: crate::types::AgreementInput,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::create_agreement(&env, input)
     }
 
@@ -762,28 +868,55 @@ impl Contract {
         user: Address,
         agreement_id: String,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::sign_agreement(&env, user, agreement_id)
     }
 
     /// Approve a pending agreement as a witness (PendingApproval → Active).
     ///
     /// @notice Admin or designated agent witnesses the agreement, permanently recording
-    ///         their ID and activating the escrow. Payouts cannot be triggered before
+    ///         their ID and activating it. In the same call the tenant's security
+    ///         deposit is moved into a Funded escrow linked to the agreement, resolved
+    ///         through the protocol directory. Payouts cannot be triggered before
     ///         this call succeeds.
     /// @param env The Soroban environment.
     /// @param approver Address of the approving witness (must authorize).
     /// @param agreement_id Identifier of the agreement to approve.
     /// @return Ok(()) on success.
+    /// @custom:error InvalidConfig If a deposit must be escrowed and no escrow
+    ///        contract is registered in the directory.
+    /// @custom:error PaymentFailed If the escrow could not be funded; the
+    ///        agreement is left PendingApproval.
     pub fn approve_agreement(
         env: Env,
         approver: Address,
         agreement_id: String,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::approve_agreement(&env, approver, agreement_id)
     }
 
+    /// Approve a pending agreement and pay its first month's rent.
+    ///
+    /// @notice Does what `approve_agreement` does, then takes the first month's
+    ///         rent from the tenant as a regular `make_payment_with_token` payment
+    ///         in the agreement's token. The rent is not part of the deposit escrow.
+    /// @param env The Soroban environment.
+    /// @param approver Address of the approving witness (must authorize).
+    /// @param agreement_id Identifier of the agreement to approve.
+    /// @return Ok(()) on success.
+    /// @custom:error PaymentFailed If the escrow could not be funded; the
+    ///        agreement is left PendingApproval, as it is if the rent cannot be paid.
+    pub fn approve_with_first_rent(
+        env: Env,
+        approver: Address,
+        agreement_id: String,
+    ) -> Result<(), RentalError> {
+        Self::check_paused(&env, PauseModule::Agreements)?;
+        Self::check_paused(&env, PauseModule::Payments)?;
+        agreement::approve_with_first_rent(&env, approver, agreement_id)
+    }
+
     /// Submit a draft agreement for tenant signature (Draft → Pending).
     ///
     /// @notice Landlord submits a draft so the tenant can sign. Landlord must authorize.
@@ -796,7 +929,7 @@ impl Contract {
         admin: Address,
         agreement_id: String,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::submit_agreement(&env, admin, agreement_id)
     }
 
@@ -812,7 +945,7 @@ impl Contract {
         caller: Address,
         agreement_id: String,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::cancel_agreement(&env, caller, agreement_id)
     }
 
@@ -824,7 +957,7 @@ impl Contract {
         new_rent: Option<i128>,
         new_deposit: Option<i128>,
     ) -> Result<String, RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::propose_extension(
             &env,
             caller,
@@ -840,7 +973,7 @@ impl Contract {
         caller: Address,
         extension_id: String,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::accept_extension(&env, caller, extension_id)
     }
 
@@ -850,7 +983,7 @@ impl Contract {
         extension_id: String,
         reason: String,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::reject_extension(&env, caller, extension_id, reason)
     }
 
@@ -859,7 +992,7 @@ impl Contract {
         caller: Address,
         extension_id: String,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::activate_extension(&env, caller, extension_id)
     }
 
@@ -869,7 +1002,7 @@ impl Contract {
         extension_id: String,
         reason: String,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::cancel_extension(&env, caller, extension_id, reason)
     }
 
@@ -947,7 +1080,7 @@ impl Contract {
         metadata_uri: String,
         attributes: Vec<Attribute>,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Agreements)?;
         agreement::update_metadata(&env, agreement_id, metadata_uri, attributes)
     }
 
@@ -955,17 +1088,18 @@ impl Contract {
 
     /// Set the interest configuration for a security deposit.
     ///
-    /// Admin-only. Also initialises the DepositInterest record on first call.
+    /// Requires the `Treasurer` role. Also initialises the DepositInterest record on first call.
     pub fn set_deposit_interest_config(
         env: Env,
+        caller: Address,
         agreement_id: String,
         annual_rate: u32,
         compounding_frequency: CompoundingFrequency,
         interest_recipient: InterestRecipient,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
-        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-        state.admin.require_auth();
+        Self::check_paused(&env, PauseModule::Interest)?;
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Treasurer)?;
         deposit_interest::set_deposit_interest_config(
             env,
             agreement_id,
@@ -983,6 +1117,59 @@ impl Contract {
         deposit_interest::get_deposit_interest_config(env, agreement_id)
     }
 
+    /// Schedule an agreement-specific deposit rate change (`RateSetter` role).
+    ///
+    /// The rate applies from `effective_from`, which may not precede the
+    /// deposit's last accrual.
+    pub fn add_deposit_rate_segment(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        effective_from: u64,
+        annual_rate: u32,
+    ) -> Result<(), RentalError> {
+        Self::check_paused(&env, PauseModule::Interest)?;
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::RateSetter)?;
+        deposit_interest::add_deposit_rate_segment(env, agreement_id, effective_from, annual_rate)
+    }
+
+    /// Publish the statutory deposit rate effective from `effective_from` (`RateSetter` role).
+    pub fn publish_statutory_rate(
+        env: Env,
+        caller: Address,
+        effective_from: u64,
+        annual_rate: u32,
+    ) -> Result<(), RentalError> {
+        Self::check_paused(&env, PauseModule::Interest)?;
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::RateSetter)?;
+        deposit_interest::publish_statutory_rate(env, effective_from, annual_rate)
+    }
+
+    /// Switch a deposit between its own rate schedule and the statutory one (`RateSetter` role).
+    pub fn set_deposit_rate_source(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        rate_source: InterestRateSource,
+    ) -> Result<(), RentalError> {
+        Self::check_paused(&env, PauseModule::Interest)?;
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::RateSetter)?;
+        deposit_interest::set_deposit_rate_source(env, agreement_id, rate_source)
+    }
+
+    /// Get the agreement-specific rate schedule for a deposit.
+    pub fn get_deposit_rate_schedule(env: Env, agreement_id: String) -> Vec<RateSegment> {
+        deposit_interest::get_deposit_rate_schedule(&env, agreement_id)
+    }
+
+    /// Get the statutory deposit rate schedule.
+    pub fn get_statutory_rate_schedule(env: Env) -> Vec<RateSegment> {
+        deposit_interest::get_statutory_rate_schedule(&env)
+    }
+
     /// Calculate (but do not persist) the interest accrued so far.
     pub fn calculate_accrued_interest(env: Env, escrow_id: String) -> Result<i128, RentalError> {
         deposit_interest::calculate_accrued_interest(env, escrow_id)
@@ -990,7 +1177,7 @@ impl Contract {
 
     /// Accrue interest up to the current ledger time and persist the update.
     pub fn accrue_interest(env: Env, escrow_id: String) -> Result<InterestAccrual, RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Interest)?;
         deposit_interest::accrue_interest(env, escrow_id)
     }
 
@@ -1002,39 +1189,148 @@ impl Contract {
         deposit_interest::get_deposit_interest(env, escrow_id)
     }
 
-    /// Get the accrual history for a deposit.
+    /// Page through the accrual history for a deposit, oldest first.
     pub fn get_accrual_history(
         env: Env,
         escrow_id: String,
+        cursor: u32,
+        limit: u32,
     ) -> Result<Vec<InterestAccrual>, RentalError> {
-        deposit_interest::get_accrual_history(env, escrow_id)
+        deposit_interest::get_accrual_history(env, escrow_id, cursor, limit)
     }
 
-    /// Distribute all accrued interest to tenant / landlord per configuration.
+    /// Set a custom basis-point split (tenant / landlord / platform) for an
+    /// agreement's interest, with per-recipient compounding (`Treasurer` role).
+    pub fn set_interest_split(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        split: InterestSplit,
+    ) -> Result<(), RentalError> {
+        Self::check_paused(&env, PauseModule::Interest)?;
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Treasurer)?;
+        deposit_interest::set_interest_split(env, agreement_id, split)
+    }
+
+    /// Get the split applied when distributing an agreement's interest.
+    pub fn get_interest_split(
+        env: Env,
+        agreement_id: String,
+    ) -> Result<InterestSplit, RentalError> {
+        deposit_interest::get_interest_split(env, agreement_id)
+    }
+
+    /// Distribute all accrued interest per the agreement's split, compounding
+    /// shares whose recipient opted in.
     pub fn distribute_interest(env: Env, escrow_id: String) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Interest)?;
         deposit_interest::distribute_interest(env, escrow_id)
     }
 
-    /// (Keeper / oracle entry-point) Accrue interest for all deposits.
-    pub fn process_interest_accruals(env: Env) -> Result<Vec<String>, RentalError> {
-        Self::check_paused(&env)?;
-        deposit_interest::process_interest_accruals(env)
-    }
+    // ─── Yield Adapter Functions ──────────────────────────────────────────────
 
-    /// Log an error context for diagnostics.
-    pub fn log_error(
+    /// Set the yield vault that idle deposits in `token` are routed to (`Treasurer` role).
+    pub fn set_yield_vault(
         env: Env,
-        error: RentalError,
-        operation: String,
-        details: String,
+        caller: Address,
+        token: Address,
+        vault: Address,
     ) -> Result<(), RentalError> {
-        errors::log_error(&env, error, operation, details)
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Treasurer)?;
+        yield_adapter::set_yield_vault(&env, token, vault)
+    }
+
+    /// Get the yield vault configured for `token`.
+    pub fn get_yield_vault(env: Env, token: Address) -> Option<Address> {
+        yield_adapter::get_yield_vault(&env, token)
+    }
+
+    /// Have the escrow holding a deposit move it into its token's yield
+    /// vault (`Treasurer` role).
+    pub fn route_deposit_to_vault(
+        env: Env,
+        caller: Address,
+        escrow_id: String,
+    ) -> Result<YieldPosition, RentalError> {
+        Self::check_paused(&env, PauseModule::Interest)?;
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Treasurer)?;
+        yield_adapter::route_deposit_to_vault(&env, escrow_id)
+    }
+
+    /// Bring a deposit and its unpaid yield back from the vault into its
+    /// escrow (`Treasurer` role).
+    pub fn withdraw_deposit_from_vault(
+        env: Env,
+        caller: Address,
+        escrow_id: String,
+    ) -> Result<i128, RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Treasurer)?;
+        yield_adapter::close_position(&env, escrow_id)
+    }
+
+    /// Get the vault position backing a deposit.
+    pub fn get_yield_position(env: Env, escrow_id: String) -> Result<YieldPosition, RentalError> {
+        yield_adapter::get_yield_position(&env, escrow_id).ok_or(RentalError::EscrowNotFound)
+    }
+
+    /// Get the yield a vault-held deposit has earned and not yet withdrawn.
+    pub fn get_vault_yield(env: Env, escrow_id: String) -> Result<i128, RentalError> {
+        yield_adapter::get_yield_position(&env, escrow_id.clone())
+            .ok_or(RentalError::EscrowNotFound)?;
+        Ok(yield_adapter::vault_yield(&env, escrow_id))
+    }
+
+    /// (Keeper / oracle entry-point) Accrue interest for the next batch of
+    /// registered deposits, starting at `cursor`.
+    pub fn process_interest_accruals(
+        env: Env,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<InterestAccrualBatch, RentalError> {
+        Self::check_paused(&env, PauseModule::Interest)?;
+        deposit_interest::process_interest_accruals(env, cursor, limit)
+    }
+
+    /// Take a deposit off the interest registry once the escrow holding it
+    /// has released or refunded it. Anyone can call this.
+    pub fn end_deposit_interest(env: Env, agreement_id: String) -> Result<(), RentalError> {
+        Self::check_paused(&env, PauseModule::Interest)?;
+        deposit_interest::end_deposit_interest(env, agreement_id)
+    }
+
+    /// Get the number of deposits registered for interest accrual.
+    pub fn get_interest_deposit_count(env: Env) -> u32 {
+        deposit_interest::get_interest_deposit_count(&env)
+    }
+
+    /// Page through the escrow ids registered for interest accrual.
+    pub fn get_interest_deposits(env: Env, cursor: u32, limit: u32) -> Vec<String> {
+        deposit_interest::get_interest_deposits(&env, cursor, limit)
+    }
+
+    /// Page through the audit log, oldest first, keeping entries that match
+    /// `filter`. Pass the returned `next_cursor` back until `done` is set.
+    pub fn get_audit_log(
+        env: Env,
+        filter: AuditFilter,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<AuditPage, RentalError> {
+        audit::get_audit_log(&env, filter, cursor, limit)
     }
 
-    /// Retrieve the most recent error logs.
-    pub fn get_error_logs(env: Env, limit: u32) -> Result<Vec<ErrorContext>, RentalError> {
-        errors::get_error_logs(&env, limit)
+    /// Get the number of audit entries ever written.
+    pub fn get_audit_log_count(env: Env) -> u32 {
+        audit::get_count(&env)
+    }
+
+    /// Get how many audit entries are retained before the oldest is overwritten.
+    pub fn get_audit_log_capacity(env: Env) -> u32 {
+        audit::get_capacity(&env)
     }
 
     // ─── Royalty Functions ───────────────────────────────────────────────────
@@ -1046,10 +1342,21 @@ impl Contract {
         royalty_percentage: u32,
         royalty_recipient: Address,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Royalties)?;
         royalties::set_royalty(env, token_id, royalty_percentage, royalty_recipient)
     }
 
+    /// Set several royalty recipients for a token, each with its own basis
+    /// points. The combined share is capped at 25 %.
+    pub fn set_royalty_recipients(
+        env: Env,
+        token_id: String,
+        recipients: Vec<RoyaltyShare>,
+    ) -> Result<(), RentalError> {
+        Self::check_paused(&env, PauseModule::Royalties)?;
+        royalties::set_royalty_recipients(env, token_id, recipients)
+    }
+
     /// Retrieve the royalty configuration for a token.
     pub fn get_royalty(env: Env, token_id: String) -> Result<RoyaltyConfig, RentalError> {
         royalties::get_royalty(env, token_id)
@@ -1071,7 +1378,7 @@ impl Contract {
         to: Address,
         sale_price: i128,
     ) -> Result<(), RentalError> {
-        Self::check_paused(&env)?;
+        Self::check_paused(&env, PauseModule::Royalties)?;
         royalties::transfer_with_royalty(env, token_id, to, sale_price)
     }
 
@@ -1083,12 +1390,92 @@ impl Contract {
         royalties::get_royalty_payments(env, token_id)
     }
 
+    // ─── Marketplace Functions ───────────────────────────────────────────────
+
+    /// List the caller's landlord position on an active agreement at an ask
+    /// price. The listing expires after `duration` seconds (at most 90 days).
+    pub fn list_position(
+        env: Env,
+        agreement_id: String,
+        ask_price: i128,
+        duration: u64,
+    ) -> Result<PositionListing, RentalError> {
+        Self::check_paused(&env, PauseModule::Royalties)?;
+        marketplace::list_position(&env, agreement_id, ask_price, duration)
+    }
+
+    /// Withdraw a listing. Open bids remain refundable.
+    pub fn cancel_listing(env: Env, agreement_id: String) -> Result<(), RentalError> {
+        Self::check_paused(&env, PauseModule::Royalties)?;
+        marketplace::cancel_listing(&env, agreement_id)
+    }
+
+    /// Get the live listing for an agreement, if any.
+    pub fn get_position_listing(env: Env, agreement_id: String) -> Option<PositionListing> {
+        marketplace::get_position_listing(&env, agreement_id)
+    }
+
+    /// Buy a listed position at its ask price.
+    pub fn buy_position(
+        env: Env,
+        agreement_id: String,
+        buyer: Address,
+    ) -> Result<RoyaltyPayment, RentalError> {
+        Self::check_paused(&env, PauseModule::Royalties)?;
+        marketplace::buy_position(&env, agreement_id, buyer)
+    }
+
+    /// Place an escrowed bid on a listed position. Returns the bid id.
+    pub fn place_bid(
+        env: Env,
+        agreement_id: String,
+        bidder: Address,
+        amount: i128,
+    ) -> Result<u32, RentalError> {
+        Self::check_paused(&env, PauseModule::Royalties)?;
+        marketplace::place_bid(&env, agreement_id, bidder, amount)
+    }
+
+    /// Withdraw an open bid and refund its escrowed funds.
+    pub fn withdraw_bid(env: Env, agreement_id: String, bid_id: u32) -> Result<(), RentalError> {
+        Self::check_paused(&env, PauseModule::Royalties)?;
+        marketplace::withdraw_bid(&env, agreement_id, bid_id)
+    }
+
+    /// Accept a bid on the caller's listing and settle the sale.
+    pub fn accept_bid(
+        env: Env,
+        agreement_id: String,
+        bid_id: u32,
+    ) -> Result<RoyaltyPayment, RentalError> {
+        Self::check_paused(&env, PauseModule::Royalties)?;
+        marketplace::accept_bid(&env, agreement_id, bid_id)
+    }
+
+    /// Get a bid by id.
+    pub fn get_position_bid(
+        env: Env,
+        agreement_id: String,
+        bid_id: u32,
+    ) -> Result<PositionBid, RentalError> {
+        marketplace::get_position_bid(&env, agreement_id, bid_id).ok_or(RentalError::InvalidInput)
+    }
+
+    /// Number of bids placed on an agreement's listings.
+    pub fn get_position_bid_count(env: Env, agreement_id: String) -> u32 {
+        marketplace::get_position_bid_count(&env, agreement_id)
+    }
+
     // ─── Rate Limiting Functions ──────────────────────────────────────────────
 
-    /// Set rate limit configuration (admin only).
-    pub fn set_rate_limit_config(env: Env, config: RateLimitConfig) -> Result<(), RentalError> {
-        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-        state.admin.require_auth();
+    /// Set rate limit configuration (`RateLimitAdmin` role).
+    pub fn set_rate_limit_config(
+        env: Env,
+        caller: Address,
+        config: RateLimitConfig,
+    ) -> Result<(), RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::RateLimitAdmin)?;
 
         rate_limit::set_rate_limit_config(&env, config.clone())?;
 
@@ -1121,18 +1508,82 @@ impl Contract {
         rate_limit::get_block_call_count(&env, function_name)
     }
 
-    /// Reset rate limits for a user (admin only, emergency use).
+    /// Reset rate limits for a user (`RateLimitAdmin` role, emergency use).
     pub fn reset_user_rate_limit(
         env: Env,
+        caller: Address,
         user: Address,
         function_name: String,
     ) -> Result<(), RentalError> {
-        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
-        state.admin.require_auth();
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::RateLimitAdmin)?;
 
         rate_limit::reset_user_rate_limit(&env, &user, function_name)
     }
 
+    /// Override the rate limit for one function name (`RateLimitAdmin` role).
+    pub fn set_function_rate_limit(
+        env: Env,
+        caller: Address,
+        function_name: String,
+        config: RateLimitConfig,
+    ) -> Result<(), RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::RateLimitAdmin)?;
+
+        rate_limit::set_function_rate_limit(&env, function_name, config)
+    }
+
+    /// Remove a function's rate limit override (`RateLimitAdmin` role).
+    pub fn remove_function_rate_limit(
+        env: Env,
+        caller: Address,
+        function_name: String,
+    ) -> Result<(), RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::RateLimitAdmin)?;
+
+        rate_limit::remove_function_rate_limit(&env, function_name)
+    }
+
+    /// Get the override configured for a function, if any.
+    pub fn get_function_rate_limit(env: Env, function_name: String) -> Option<RateLimitConfig> {
+        rate_limit::get_function_rate_limit(&env, function_name)
+    }
+
+    /// List the function names that have an override.
+    pub fn get_function_rate_limits(env: Env) -> Vec<String> {
+        rate_limit::get_function_rate_limits(&env)
+    }
+
+    /// Get the configuration enforced for a function (override or global).
+    pub fn get_effective_rate_limit(env: Env, function_name: String) -> RateLimitConfig {
+        rate_limit::get_effective_rate_limit(&env, function_name)
+    }
+
+    /// Exempt an account from rate limiting, or revoke the exemption (`RateLimitAdmin` role).
+    pub fn set_rate_limit_exempt(
+        env: Env,
+        caller: Address,
+        account: Address,
+        exempt: bool,
+    ) -> Result<(), RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::RateLimitAdmin)?;
+
+        rate_limit::set_rate_limit_exempt(&env, account, exempt)
+    }
+
+    /// Whether an account is exempt from rate limiting.
+    pub fn is_rate_limit_exempt(env: Env, account: Address) -> bool {
+        rate_limit::is_rate_limit_exempt(&env, &account)
+    }
+
+    /// List all accounts exempt from rate limiting.
+    pub fn get_rate_limit_exemptions(env: Env) -> Vec<Address> {
+        rate_limit::get_rate_limit_exemptions(&env)
+    }
+
     // ─── Multi-Sig Admin Functions ───────────────────────────────────────────
 
     /// Initialize multi-sig configuration with initial admins and required signatures
@@ -1158,6 +1609,21 @@ impl Contract {
         multi_sig::is_admin(&env, &address)
     }
 
+    /// Get an admin's approval weight (0 for non-admins)
+    pub fn get_admin_weight(env: Env, admin: Address) -> Result<u32, RentalError> {
+        multi_sig::get_admin_weight(&env, &admin)
+    }
+
+    /// Get the approval weight a proposal of `action_type` needs
+    pub fn get_action_threshold(env: Env, action_type: ActionType) -> Result<u32, RentalError> {
+        multi_sig::get_action_threshold(&env, action_type)
+    }
+
+    /// Get the combined weight of a proposal's approvers
+    pub fn get_approval_weight(env: Env, proposal_id: String) -> Result<u32, RentalError> {
+        multi_sig::get_approval_weight(&env, proposal_id)
+    }
+
     /// Propose an admin action (pause, unpause, config update, etc.)
     pub fn propose_action(
         env: Env,
@@ -1196,27 +1662,6 @@ impl Contract {
         multi_sig::reject_action(&env, caller, proposal_id)
     }
 
-    /// Add a new admin (must be called through proposal execution)
-    pub fn add_admin(env: Env, new_admin: Address) -> Result<(), RentalError> {
-        // This should only be called through execute_action after approval
-        // For now, we'll add a check for multi-sig admin
-        let caller = new_admin.clone(); // In real scenario, get from context
-        multi_sig::require_admin(&env, &caller)?;
-        multi_sig::add_admin_internal(&env, new_admin)
-    }
-
-    /// Remove an admin (must be called through proposal execution)
-    pub fn remove_admin(env: Env, admin_to_remove: Address) -> Result<(), RentalError> {
-        // This should only be called through execute_action after approval
-        multi_sig::remove_admin_internal(&env, admin_to_remove)
-    }
-
-    /// Update required signatures (must be called through proposal execution)
-    pub fn update_required_signatures(env: Env, new_required: u32) -> Result<(), RentalError> {
-        // This should only be called through execute_action after approval
-        multi_sig::update_required_signatures_internal(&env, new_required)
-    }
-
     /// Get a proposal by ID
     pub fn get_proposal(env: Env, proposal_id: String) -> Result<AdminProposal, RentalError> {
         multi_sig::get_proposal(&env, proposal_id)
@@ -1238,7 +1683,10 @@ impl Contract {
     ///
     /// `delay` is in seconds and must meet the minimum for the given `action_type`:
     /// UpdateAdmin (7 days), UpdateConfig (3 days), UpdateRates (2 days),
-    /// PauseContract (1 day), UnpauseContract (1 hour).
+    /// PauseContract (1 day), UnpauseContract (1 hour), UnpauseModule (1 hour),
+    /// DisableTimelock (7 days).
+    /// `data` is the XDR-encoded payload for the action type and is validated
+    /// here; the change itself is applied by `execute_timelock_action`.
     pub fn queue_timelock_action(
         env: Env,
         caller: Address,
@@ -1259,7 +1707,7 @@ impl Contract {
         timelock::execute_action(&env, caller, action_id)
     }
 
-    /// Cancel a queued timelock action (admin only).
+    /// Cancel a queued timelock action (the role that may queue it).
     pub fn cancel_timelock_action(
         env: Env,
         caller: Address,
@@ -1283,16 +1731,45 @@ impl Contract {
         timelock::get_action_count(&env)
     }
 
+    /// Require config, rate and unpause changes to be queued through the
+    /// timelock (`Upgrader` role). Disabling it again is itself a timelocked action.
+    pub fn enable_timelock(env: Env, caller: Address) -> Result<(), RentalError> {
+        timelock::enable(&env, caller)
+    }
+
+    /// Whether admin changes must currently go through the timelock.
+    pub fn is_timelock_enabled(env: Env) -> bool {
+        timelock::is_enabled(&env)
+    }
+
     // ─── Gas Optimisation ─────────────────────────────────────────────────────
 
-    /// Estimate the gas cost for a given operation type.
+    /// Publish resource metrics measured by the benchmark suite (`Upgrader`
+    /// role), at most `MAX_METRICS_BATCH` per call.
+    pub fn record_gas_metrics(
+        env: Env,
+        caller: Address,
+        metrics: Vec<GasMetrics>,
+    ) -> Result<(), RentalError> {
+        caller.require_auth();
+        access::require_role(&env, &caller, Role::Upgrader)?;
+
+        gas_optimization::record_gas_metrics(&env, metrics)
+    }
+
+    /// Return the metrics last recorded for an entrypoint, if any.
+    pub fn get_operation_metrics(env: Env, operation: String) -> Option<GasMetrics> {
+        gas_optimization::get_operation_metrics(&env, operation)
+    }
+
+    /// Return the measured CPU instructions for a given operation type.
     ///
-    /// Returns a conservative upper-bound estimate in Soroban gas units.
+    /// Fails with `InvalidState` until its metrics have been recorded.
     pub fn estimate_gas_cost(env: Env, operation: OperationType) -> Result<u64, RentalError> {
         gas_optimization::estimate_gas_cost(env, operation)
     }
 
-    /// Return persisted gas metrics for all tracked operations.
+    /// Return recorded metrics for all tracked operation types.
     pub fn get_gas_metrics(env: Env) -> Result<Vec<GasMetrics>, RentalError> {
         gas_optimization::get_gas_metrics(env)
     }
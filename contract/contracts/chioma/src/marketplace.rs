@@ -0,0 +1,308 @@
+//! Marketplace for landlord positions.
+//!
+//! A landlord lists an agreement at an ask price for a limited time. Buyers
+//! either pay the ask outright or place bids whose funds are held by the
+//! contract until the bid is accepted or withdrawn. Every sale settles
+//! through the royalty logic, which also hands the position to the buyer.
+
+use soroban_sdk::{token, Address, Env, String};
+
+use crate::agreement;
+use crate::errors::RentalError;
+use crate::events;
+use crate::royalties;
+use crate::storage::DataKey;
+use crate::types::{
+    AgreementStatus, BidStatus, PositionBid, PositionListing, RentAgreement, RoyaltyPayment,
+};
+
+/// Longest a listing may stay open (90 days).
+pub const MAX_LISTING_DURATION: u64 = 90 * 86_400;
+
+fn load_agreement(env: &Env, agreement_id: &String) -> Result<RentAgreement, RentalError> {
+    agreement::load_agreement(env, agreement_id).ok_or(RentalError::AgreementNotFound)
+}
+
+fn is_live(env: &Env, listing: &PositionListing) -> bool {
+    env.ledger().timestamp() < listing.expires_at
+}
+
+/// Listing that is still open for the given agreement.
+fn live_listing(env: &Env, agreement_id: &String) -> Result<PositionListing, RentalError> {
+    let listing: PositionListing = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PositionListing(agreement_id.clone()))
+        .ok_or(RentalError::InvalidState)?;
+    if !is_live(env, &listing) {
+        return Err(RentalError::Expired);
+    }
+    Ok(listing)
+}
+
+fn save_bid(env: &Env, bid: &PositionBid) {
+    env.storage().persistent().set(
+        &DataKey::PositionBid(bid.agreement_id.clone(), bid.bid_id),
+        bid,
+    );
+}
+
+fn adjust_escrowed_bids(env: &Env, token: &Address, delta: i128) {
+    let total = escrowed_bids(env, token).saturating_add(delta);
+    env.storage()
+        .instance()
+        .set(&DataKey::EscrowedBids(token.clone()), &total);
+}
+
+/// Total amount of `token` the contract holds for open bids.
+pub fn escrowed_bids(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::EscrowedBids(token.clone()))
+        .unwrap_or(0)
+}
+
+// ─── Listings ─────────────────────────────────────────────────────────────────
+
+/// List an active agreement's landlord position at `ask_price` for
+/// `duration` seconds. Replaces an expired listing, but not a live one.
+pub fn list_position(
+    env: &Env,
+    agreement_id: String,
+    ask_price: i128,
+    duration: u64,
+) -> Result<PositionListing, RentalError> {
+    if ask_price <= 0 {
+        return Err(RentalError::InvalidAmount);
+    }
+    if duration == 0 || duration > MAX_LISTING_DURATION {
+        return Err(RentalError::InvalidInput);
+    }
+
+    let agreement = load_agreement(env, &agreement_id)?;
+    agreement.admin.require_auth();
+    if agreement.status != AgreementStatus::Active {
+        return Err(RentalError::AgreementNotActive);
+    }
+    if live_listing(env, &agreement_id).is_ok() {
+        return Err(RentalError::InvalidState);
+    }
+
+    let listing_id: u32 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PositionListingCount)
+        .unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&DataKey::PositionListingCount, &(listing_id + 1));
+
+    let now = env.ledger().timestamp();
+    let listing = PositionListing {
+        listing_id,
+        agreement_id: agreement_id.clone(),
+        seller: agreement.admin.clone(),
+        ask_price,
+        token: agreement.payment_token,
+        created_at: now,
+        expires_at: now.saturating_add(duration),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::PositionListing(agreement_id.clone()), &listing);
+
+    events::position_listed(
+        env,
+        agreement_id,
+        agreement.admin,
+        listing_id,
+        ask_price,
+        listing.expires_at,
+    );
+    Ok(listing)
+}
+
+/// Withdraw a listing. Open bids stay refundable through `withdraw_bid`.
+pub fn cancel_listing(env: &Env, agreement_id: String) -> Result<(), RentalError> {
+    let listing: PositionListing = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PositionListing(agreement_id.clone()))
+        .ok_or(RentalError::InvalidState)?;
+    listing.seller.require_auth();
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PositionListing(agreement_id.clone()));
+    events::listing_cancelled(env, agreement_id, listing.listing_id);
+    Ok(())
+}
+
+/// Open listing for an agreement; expired listings are reported as `None`.
+pub fn get_position_listing(env: &Env, agreement_id: String) -> Option<PositionListing> {
+    live_listing(env, &agreement_id).ok()
+}
+
+/// Buy a listed position outright at its ask price.
+pub fn buy_position(
+    env: &Env,
+    agreement_id: String,
+    buyer: Address,
+) -> Result<RoyaltyPayment, RentalError> {
+    buyer.require_auth();
+    let listing = live_listing(env, &agreement_id)?;
+    if buyer == listing.seller {
+        return Err(RentalError::InvalidInput);
+    }
+
+    settle(env, listing.clone(), &buyer, &buyer, listing.ask_price)
+}
+
+// ─── Bids ─────────────────────────────────────────────────────────────────────
+
+/// Place a bid on a live listing, moving `amount` from the bidder into the
+/// contract. Returns the bid id.
+pub fn place_bid(
+    env: &Env,
+    agreement_id: String,
+    bidder: Address,
+    amount: i128,
+) -> Result<u32, RentalError> {
+    bidder.require_auth();
+    if amount <= 0 {
+        return Err(RentalError::InvalidAmount);
+    }
+    let listing = live_listing(env, &agreement_id)?;
+    if bidder == listing.seller {
+        return Err(RentalError::InvalidInput);
+    }
+
+    token::Client::new(env, &listing.token).transfer(
+        &bidder,
+        env.current_contract_address(),
+        &amount,
+    );
+    adjust_escrowed_bids(env, &listing.token, amount);
+
+    let count_key = DataKey::PositionBidCount(agreement_id.clone());
+    let bid_id: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    env.storage().persistent().set(&count_key, &(bid_id + 1));
+
+    let bid = PositionBid {
+        bid_id,
+        listing_id: listing.listing_id,
+        agreement_id: agreement_id.clone(),
+        bidder: bidder.clone(),
+        amount,
+        placed_at: env.ledger().timestamp(),
+        status: BidStatus::Open,
+    };
+    save_bid(env, &bid);
+
+    events::bid_placed(env, agreement_id, bidder, bid_id, amount);
+    Ok(bid_id)
+}
+
+/// Refund an open bid to its bidder. Allowed at any time, including after
+/// the listing has expired or been cancelled.
+pub fn withdraw_bid(env: &Env, agreement_id: String, bid_id: u32) -> Result<(), RentalError> {
+    let mut bid =
+        get_position_bid(env, agreement_id.clone(), bid_id).ok_or(RentalError::InvalidInput)?;
+    bid.bidder.require_auth();
+    if bid.status != BidStatus::Open {
+        return Err(RentalError::InvalidState);
+    }
+
+    let agreement = load_agreement(env, &agreement_id)?;
+    token::Client::new(env, &agreement.payment_token).transfer(
+        &env.current_contract_address(),
+        &bid.bidder,
+        &bid.amount,
+    );
+    adjust_escrowed_bids(env, &agreement.payment_token, -bid.amount);
+
+    bid.status = BidStatus::Withdrawn;
+    save_bid(env, &bid);
+
+    events::bid_withdrawn(env, agreement_id, bid.bidder, bid_id, bid.amount);
+    Ok(())
+}
+
+/// Accept an open bid on the seller's live listing, settling the sale from
+/// the escrowed bid funds.
+pub fn accept_bid(
+    env: &Env,
+    agreement_id: String,
+    bid_id: u32,
+) -> Result<RoyaltyPayment, RentalError> {
+    let listing = live_listing(env, &agreement_id)?;
+    listing.seller.require_auth();
+
+    let mut bid =
+        get_position_bid(env, agreement_id.clone(), bid_id).ok_or(RentalError::InvalidInput)?;
+    if bid.status != BidStatus::Open || bid.listing_id != listing.listing_id {
+        return Err(RentalError::InvalidState);
+    }
+
+    bid.status = BidStatus::Accepted;
+    save_bid(env, &bid);
+    adjust_escrowed_bids(env, &listing.token, -bid.amount);
+
+    let payer = env.current_contract_address();
+    settle(env, listing, &payer, &bid.bidder, bid.amount)
+}
+
+/// A bid by id, whatever its status.
+pub fn get_position_bid(env: &Env, agreement_id: String, bid_id: u32) -> Option<PositionBid> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PositionBid(agreement_id, bid_id))
+}
+
+/// Number of bids ever placed on an agreement's listings.
+pub fn get_position_bid_count(env: &Env, agreement_id: String) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PositionBidCount(agreement_id))
+        .unwrap_or(0)
+}
+
+// ─── Settlement ───────────────────────────────────────────────────────────────
+
+fn settle(
+    env: &Env,
+    listing: PositionListing,
+    payer: &Address,
+    buyer: &Address,
+    price: i128,
+) -> Result<RoyaltyPayment, RentalError> {
+    let agreement = load_agreement(env, &listing.agreement_id)?;
+    // The position may have changed hands since it was listed.
+    if agreement.admin != listing.seller {
+        return Err(RentalError::Unauthorized);
+    }
+    // Or the lease may have ended.
+    if agreement.status != AgreementStatus::Active {
+        return Err(RentalError::AgreementNotActive);
+    }
+    let tenant = agreement.user.clone();
+
+    let payment = royalties::settle_sale(
+        env,
+        listing.agreement_id.clone(),
+        agreement,
+        payer,
+        buyer,
+        price,
+    )?;
+
+    events::position_sold(
+        env,
+        listing.agreement_id,
+        tenant,
+        listing.seller,
+        buyer.clone(),
+        price,
+    );
+    Ok(payment)
+}
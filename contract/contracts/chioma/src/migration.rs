@@ -0,0 +1,219 @@
+//! Post-upgrade storage migrations.
+//!
+//! `STORAGE_VERSION` is the layout this build reads and writes. After an
+//! upgrade installs new code, `migrate` converts stored data one layout at a
+//! time until it matches, and records the contract version it ran for so it
+//! runs once per upgrade.
+//!
+//! Agreements are persisted in a `VersionedAgreement` envelope and upgraded
+//! as they are read, so a layout change never breaks decoding of existing
+//! entries. `migrate_records` rewrites them in the current layout in
+//! batches, walking the creation-order index with a cursor, together with
+//! their deposit-interest records. Agreements created before the index
+//! existed are rewritten from a list of ids by `migrate_listed_records`.
+
+use soroban_sdk::{vec, Env, IntoVal, String, Symbol, Val, Vec};
+
+use crate::access;
+use crate::admin;
+use crate::agreement;
+use crate::deposit_interest;
+use crate::errors::RentalError;
+use crate::events;
+use crate::gas_optimization;
+use crate::storage::{DataKey, MigrationKey};
+use crate::timelock;
+use crate::types::{ContractVersion, RecordMigrationBatch, Role};
+
+/// Storage layout this build reads and writes. Bump it, and add a step to
+/// `run_migration`, whenever persisted data changes shape.
+///
+/// 2: privileged entrypoints check roles instead of the contract admin.
+/// 3: the public error log is replaced by the internal audit log.
+/// 4: gas metrics hold measured resources and live in persistent storage.
+/// 5: deposit accrual history lives in buckets outside `DepositInterest`.
+/// 6: the timelock queue is nested under `DataKey::Timelock`.
+/// 7: whether the contract is paused lives in the pause record alone.
+/// 8: rate limiting is gated on its own role instead of `Pauser`.
+pub const STORAGE_VERSION: u32 = 8;
+
+/// Most agreements `migrate_records` rewrites in one call.
+pub const MAX_MIGRATION_BATCH: u32 = 50;
+
+/// Version a fresh deployment reports before any version is recorded.
+const INITIAL_VERSION: (u32, u32, u32) = (0, 1, 0);
+
+pub fn semver(version: &ContractVersion) -> (u32, u32, u32) {
+    (version.major, version.minor, version.patch)
+}
+
+/// Layout of the stored data. Data written before layouts were tracked is
+/// in layout 1.
+pub fn get_storage_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::Migration(MigrationKey::StorageVersion))
+        .unwrap_or(1)
+}
+
+/// Bring stored data up to `STORAGE_VERSION` for the `current` contract
+/// version. Callers are responsible for authorisation.
+pub fn migrate(env: &Env, current: &ContractVersion) -> Result<u32, RentalError> {
+    let migrated: (u32, u32, u32) = env
+        .storage()
+        .instance()
+        .get(&DataKey::Migration(MigrationKey::MigratedVersion))
+        .unwrap_or(INITIAL_VERSION);
+    let from = get_storage_version(env);
+    if migrated >= semver(current) || from > STORAGE_VERSION {
+        return Err(RentalError::InvalidState);
+    }
+
+    for step in (from + 1)..=STORAGE_VERSION {
+        run_migration(env, step)?;
+    }
+
+    env.storage().instance().set(
+        &DataKey::Migration(MigrationKey::StorageVersion),
+        &STORAGE_VERSION,
+    );
+    env.storage().instance().set(
+        &DataKey::Migration(MigrationKey::MigratedVersion),
+        &semver(current),
+    );
+
+    events::storage_migrated(env, current.clone(), from, STORAGE_VERSION);
+    Ok(STORAGE_VERSION)
+}
+
+/// Mark a fresh deployment as already in the current layout.
+pub fn init_storage_version(env: &Env) {
+    env.storage().instance().set(
+        &DataKey::Migration(MigrationKey::StorageVersion),
+        &STORAGE_VERSION,
+    );
+}
+
+/// Convert stored data from layout `step - 1` to layout `step`.
+fn run_migration(env: &Env, step: u32) -> Result<(), RentalError> {
+    match step {
+        // Layout 1 is the original one; nothing to convert.
+        1 => Ok(()),
+        // The contract admin keeps every privilege it had as a role.
+        2 => {
+            let state = admin::get_state(env)?;
+            access::grant_all_roles(env, &state.admin);
+            Ok(())
+        }
+        // Drop the old error log counter. Its persistent `ErrorLog(u32)`
+        // rows are left to expire.
+        3 => {
+            let legacy_count: Vec<Val> = vec![env, Symbol::new(env, "ErrorLogCount").into_val(env)];
+            env.storage().instance().remove(&legacy_count);
+            Ok(())
+        }
+        // The old hard-coded estimates no longer decode; drop them.
+        4 => {
+            for op in gas_optimization::TRACKED_OPERATIONS.iter() {
+                env.storage().instance().remove(&DataKey::GasMetrics(
+                    gas_optimization::operation_name(env, op),
+                ));
+            }
+            Ok(())
+        }
+        // Deposits are only reachable through their agreements, so they are
+        // converted as they are read, or eagerly by `migrate_records`.
+        5 => Ok(()),
+        // Actions queued under the old keys would otherwise be unreachable.
+        6 => {
+            timelock::migrate_legacy_keys(env);
+            Ok(())
+        }
+        7 => admin::migrate_config_pause(env),
+        // The contract admin keeps control of rate limiting; other `Pauser`
+        // holders lose it.
+        8 => {
+            let state = admin::get_state(env)?;
+            if !access::has_role(env, &state.admin, Role::RateLimitAdmin) {
+                access::grant_role(env, Role::RateLimitAdmin, state.admin)?;
+            }
+            Ok(())
+        }
+        _ => Err(RentalError::InvalidState),
+    }
+}
+
+/// Record `agreement_id` at position `index` of the creation-order index.
+pub fn index_agreement(env: &Env, index: u32, agreement_id: &String) {
+    env.storage().persistent().set(
+        &DataKey::Migration(MigrationKey::AgreementIndex(index)),
+        agreement_id,
+    );
+}
+
+/// Rewrite the next batch of agreements in the current layout.
+///
+/// Visits at most `limit` index entries (capped at `MAX_MIGRATION_BATCH`)
+/// from `cursor`. Agreements created before the index existed have no entry;
+/// rewrite those with `migrate_listed_records`. Callers are responsible for
+/// authorisation.
+pub fn migrate_records(
+    env: &Env,
+    cursor: u32,
+    limit: u32,
+) -> Result<RecordMigrationBatch, RentalError> {
+    if limit == 0 {
+        return Err(RentalError::InvalidInput);
+    }
+
+    let count = agreement::get_agreement_count(env);
+    let end = cursor
+        .saturating_add(limit.min(MAX_MIGRATION_BATCH))
+        .min(count);
+
+    let mut migrated = 0;
+    for index in cursor..end {
+        let Some(agreement_id) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, String>(&DataKey::Migration(MigrationKey::AgreementIndex(index)))
+        else {
+            continue;
+        };
+        if let Some(record) = agreement::load_agreement(env, &agreement_id) {
+            agreement::save_agreement(env, &agreement_id, &record);
+            deposit_interest::migrate_deposit_interest(env, &agreement_id);
+            migrated += 1;
+        }
+    }
+
+    let next_cursor = end.max(cursor);
+    Ok(RecordMigrationBatch {
+        migrated,
+        next_cursor,
+        done: next_cursor >= count,
+    })
+}
+
+/// Rewrite the listed agreements, and their deposit-interest records, in the
+/// current layout.
+///
+/// For agreements created before the creation-order index existed, whose
+/// ids are only known from their creation events. Takes at most
+/// `MAX_MIGRATION_BATCH` ids; unknown ids are skipped. Returns how many
+/// agreements were rewritten. Callers are responsible for authorisation.
+pub fn migrate_listed_records(env: &Env, agreement_ids: &Vec<String>) -> Result<u32, RentalError> {
+    if agreement_ids.is_empty() || agreement_ids.len() > MAX_MIGRATION_BATCH {
+        return Err(RentalError::InvalidInput);
+    }
+
+    let mut migrated = 0;
+    for agreement_id in agreement_ids.iter() {
+        if let Some(record) = agreement::load_agreement(env, &agreement_id) {
+            agreement::save_agreement(env, &agreement_id, &record);
+            deposit_interest::migrate_deposit_interest(env, &agreement_id);
+            migrated += 1;
+        }
+    }
+    Ok(migrated)
+}
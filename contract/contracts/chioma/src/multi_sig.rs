@@ -1,13 +1,41 @@
 use crate::{
+    access, admin, audit,
     errors::RentalError,
-    events,
-    storage::DataKey,
-    types::{ActionType, AdminProposal, MultiSigConfig},
+    events, multi_token, rate_limit,
+    storage::{DataKey, GovernanceKey},
+    timelock,
+    types::{
+        ActionThreshold, ActionType, AdminProposal, Config, MultiSigConfig, RateLimitConfig, Role,
+        SupportedToken, TokenExchangeRate,
+    },
 };
-use soroban_sdk::{Address, Bytes, Env, String, Vec};
+use rate_limiter::read_config;
+use soroban_sdk::xdr::{FromXdr, ToXdr};
+use soroban_sdk::{Address, Bytes, Env, String, Val, Vec};
 
 const PROPOSAL_EXPIRY_SECONDS: u64 = 7 * 24 * 60 * 60; // 7 days
 
+/// `prop_<n>`, without heap formatting.
+fn proposal_id_for(env: &Env, n: u32) -> String {
+    let mut buf = [0u8; 15];
+    buf[..5].copy_from_slice(b"prop_");
+    let mut digits = [0u8; 10];
+    let mut len = 0;
+    let mut v = n;
+    loop {
+        digits[len] = b'0' + (v % 10) as u8;
+        len += 1;
+        v /= 10;
+        if v == 0 {
+            break;
+        }
+    }
+    for i in 0..len {
+        buf[5 + i] = digits[len - 1 - i];
+    }
+    String::from_bytes(env, &buf[..5 + len])
+}
+
 /// Initialize multi-sig configuration
 pub fn initialize_multisig(
     env: &Env,
@@ -85,6 +113,80 @@ pub fn require_admin(env: &Env, caller: &Address) -> Result<(), RentalError> {
     Ok(())
 }
 
+// ─── Weights and thresholds ──────────────────────────────────────────────────
+//
+// Each admin carries a weight (1 unless set through `SetAdminWeight`) and each
+// action type a threshold (`required_signatures` unless set through
+// `SetActionThreshold`). A proposal executes once the weights of its approvers
+// add up to its action type's threshold.
+
+/// Approval weight of `admin`, or 0 if it is not an admin.
+pub fn get_admin_weight(env: &Env, admin: &Address) -> Result<u32, RentalError> {
+    let config = get_multisig_config(env)?;
+    Ok(weight_of(env, &config, admin))
+}
+
+fn weight_of(env: &Env, config: &MultiSigConfig, address: &Address) -> u32 {
+    if !config.admins.contains(address) {
+        return 0;
+    }
+    env.storage()
+        .instance()
+        .get(&DataKey::Governance(GovernanceKey::AdminWeight(
+            address.clone(),
+        )))
+        .unwrap_or(1)
+}
+
+/// Combined weight of every admin.
+fn total_weight(env: &Env, config: &MultiSigConfig) -> u32 {
+    config.admins.iter().fold(0u32, |total, admin| {
+        total.saturating_add(weight_of(env, config, &admin))
+    })
+}
+
+/// Weight a proposal of `action_type` needs, capped at the combined weight
+/// of the admins so removing one never strands a proposal.
+pub fn get_action_threshold(env: &Env, action_type: ActionType) -> Result<u32, RentalError> {
+    let config = get_multisig_config(env)?;
+    Ok(threshold_for(env, &config, action_type))
+}
+
+fn threshold_for(env: &Env, config: &MultiSigConfig, action_type: ActionType) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::Governance(GovernanceKey::ActionThreshold(
+            action_type,
+        )))
+        .unwrap_or(config.required_signatures)
+        .min(total_weight(env, config))
+}
+
+/// Weight a contract upgrade needs. Upgrades have no action type of their
+/// own, so they take the default threshold.
+pub(crate) fn upgrade_threshold(env: &Env, config: &MultiSigConfig) -> u32 {
+    config.required_signatures.min(total_weight(env, config))
+}
+
+/// Combined weight of the admins that have approved a proposal.
+pub fn get_approval_weight(env: &Env, proposal_id: String) -> Result<u32, RentalError> {
+    let config = get_multisig_config(env)?;
+    let proposal = get_proposal(env, proposal_id)?;
+    Ok(approval_weight(env, &config, &proposal))
+}
+
+fn approval_weight(env: &Env, config: &MultiSigConfig, proposal: &AdminProposal) -> u32 {
+    weight_of_all(env, config, &proposal.approvals)
+}
+
+/// Combined weight of `approvers`; addresses that are no longer admins count
+/// for nothing.
+pub(crate) fn weight_of_all(env: &Env, config: &MultiSigConfig, approvers: &Vec<Address>) -> u32 {
+    approvers.iter().fold(0u32, |total, approver| {
+        total.saturating_add(weight_of(env, config, &approver))
+    })
+}
+
 /// Propose an admin action
 pub fn propose_action(
     env: &Env,
@@ -95,6 +197,7 @@ pub fn propose_action(
 ) -> Result<String, RentalError> {
     proposer.require_auth();
     require_admin(env, &proposer)?;
+    validate_payload(env, &action_type, &target, &data)?;
 
     // Generate proposal ID
     let mut proposal_count: u32 = env
@@ -104,11 +207,7 @@ pub fn propose_action(
         .unwrap_or(0);
 
     proposal_count += 1;
-    // Create proposal ID using count + timestamp for uniqueness
-    // In Soroban, we can use a simple prefix + counter approach
-    let proposal_id = String::from_str(env, "prop_");
-    // Since we can't use format!, we use the counter as part of storage key
-    // The DataKey::AdminProposal will ensure uniqueness with the counter
+    let proposal_id = proposal_id_for(env, proposal_count);
 
     // Create proposal with single approval from proposer
     let mut approvals = Vec::new(env);
@@ -199,7 +298,8 @@ pub fn approve_action(
         .persistent()
         .set(&DataKey::AdminProposal(proposal_id.clone()), &proposal);
 
-    events::action_approved(env, proposal_id, approver, proposal.approval_count);
+    let weight = approval_weight(env, &get_multisig_config(env)?, &proposal);
+    events::action_approved(env, proposal_id, approver, proposal.approval_count, weight);
 
     Ok(())
 }
@@ -229,12 +329,16 @@ pub fn execute_action(
         return Err(RentalError::ProposalExpired);
     }
 
-    // Check if has enough approvals
+    // Check the approvers' combined weight against the action's threshold
     let config = get_multisig_config(env)?;
-    if proposal.approval_count < config.required_signatures {
+    let threshold = threshold_for(env, &config, proposal.action_type.clone());
+    if approval_weight(env, &config, &proposal) < threshold {
         return Err(RentalError::InsufficientApprovals);
     }
 
+    // Apply the action; any failure reverts the whole execution
+    let (before, after) = apply_action(env, &executor, &proposal)?;
+
     // Mark as executed
     proposal.executed = true;
     env.storage()
@@ -258,11 +362,269 @@ pub fn execute_action(
         .instance()
         .set(&DataKey::ActiveProposals, &new_active);
 
+    events::action_applied(
+        env,
+        proposal_id.clone(),
+        proposal.action_type.clone(),
+        before,
+        after,
+    );
     events::action_executed(env, proposal_id, proposal.action_type);
 
     Ok(())
 }
 
+// ─── Action payloads ─────────────────────────────────────────────────────────
+//
+// `data` holds the XDR encoding of the action's argument:
+//
+// | Action                     | `target`      | `data`                         |
+// | -------------------------- | ------------- | ------------------------------ |
+// | `Pause`                    | -             | `String` reason, or empty      |
+// | `Unpause`                  | -             | empty                          |
+// | `UpdateConfig`             | -             | `Config`                       |
+// | `UpdateRate`               | -             | `TokenExchangeRate`            |
+// | `AddAdmin` / `RemoveAdmin` | admin         | empty                          |
+// | `UpdateRequiredSignatures` | -             | `u32`                          |
+// | `EmergencyAction`          | new admin     | empty                          |
+// | `SetRateLimit`             | -             | `RateLimitConfig`              |
+// | `AddToken`                 | -             | `SupportedToken`               |
+// | `RemoveToken`              | token         | empty                          |
+// | `SetActionThreshold`       | -             | `ActionThreshold`              |
+// | `SetAdminWeight`           | admin         | `u32` weight                   |
+// | `GrantRole` / `RevokeRole` | account       | `Role`                         |
+// | `SetGuardian`              | guardian      | empty                          |
+// | `SetAuditLogCapacity`      | -             | `u32` capacity                 |
+//
+// `SetGuardian` without a target removes the guardian.
+
+/// Decode a payload. Bytes that are not valid XDR at all abort in the host,
+/// which still reverts the call.
+pub(crate) fn decode<T: FromXdr>(env: &Env, data: &Bytes) -> Result<T, RentalError> {
+    if data.is_empty() {
+        return Err(RentalError::InvalidInput);
+    }
+    T::from_xdr(env, data).map_err(|_| RentalError::InvalidInput)
+}
+
+fn require_target(target: &Option<Address>) -> Result<Address, RentalError> {
+    target.clone().ok_or(RentalError::InvalidInput)
+}
+
+/// Reject proposals whose payload does not decode for their action type.
+fn validate_payload(
+    env: &Env,
+    action_type: &ActionType,
+    target: &Option<Address>,
+    data: &Bytes,
+) -> Result<(), RentalError> {
+    match action_type {
+        ActionType::Pause => {
+            if !data.is_empty() {
+                decode::<String>(env, data)?;
+            }
+        }
+        ActionType::Unpause => {}
+        ActionType::UpdateConfig => {
+            let config: Config = decode(env, data)?;
+            if config.fee_bps > 10_000 {
+                return Err(RentalError::InvalidConfig);
+            }
+        }
+        ActionType::UpdateRate => {
+            let rate: TokenExchangeRate = decode(env, data)?;
+            if rate.rate <= 0 {
+                return Err(RentalError::InvalidAmount);
+            }
+        }
+        ActionType::UpdateRequiredSignatures => {
+            decode::<u32>(env, data)?;
+        }
+        ActionType::SetRateLimit => {
+            decode::<RateLimitConfig>(env, data)?;
+        }
+        ActionType::AddToken => {
+            decode::<SupportedToken>(env, data)?;
+        }
+        ActionType::SetActionThreshold => {
+            let update: ActionThreshold = decode(env, data)?;
+            let config = get_multisig_config(env)?;
+            if update.threshold == 0 || update.threshold > total_weight(env, &config) {
+                return Err(RentalError::InvalidConfig);
+            }
+        }
+        ActionType::SetAdminWeight => {
+            if !is_admin(env, &require_target(target)?)? {
+                return Err(RentalError::InvalidInput);
+            }
+            if decode::<u32>(env, data)? == 0 {
+                return Err(RentalError::InvalidConfig);
+            }
+        }
+        ActionType::GrantRole => {
+            let role: Role = decode(env, data)?;
+            if access::has_role(env, &require_target(target)?, role) {
+                return Err(RentalError::InvalidInput);
+            }
+        }
+        ActionType::RevokeRole => {
+            let role: Role = decode(env, data)?;
+            if !access::has_role(env, &require_target(target)?, role) {
+                return Err(RentalError::InvalidInput);
+            }
+        }
+        ActionType::SetGuardian => {}
+        ActionType::SetAuditLogCapacity => {
+            audit::validate_capacity(decode(env, data)?)?;
+        }
+        ActionType::AddAdmin
+        | ActionType::RemoveAdmin
+        | ActionType::EmergencyAction
+        | ActionType::RemoveToken => {
+            require_target(target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Apply an approved proposal, returning XDR snapshots of the affected value
+/// before and after the change.
+///
+/// Unpausing, config, rate and admin changes are rejected while the timelock
+/// is enabled, as their direct entrypoints are; they must be queued there.
+fn apply_action(
+    env: &Env,
+    executor: &Address,
+    proposal: &AdminProposal,
+) -> Result<(Bytes, Bytes), RentalError> {
+    let data = &proposal.data;
+    let target = &proposal.target;
+
+    match proposal.action_type {
+        ActionType::Pause => {
+            let reason = if data.is_empty() {
+                String::from_str(env, "Paused by multi-sig")
+            } else {
+                decode(env, data)?
+            };
+            admin::apply_pause(env, executor.clone(), reason, None)?;
+            Ok((false.to_xdr(env), true.to_xdr(env)))
+        }
+        ActionType::Unpause => {
+            timelock::require_disabled(env)?;
+            admin::apply_unpause(env, executor.clone())?;
+            Ok((true.to_xdr(env), false.to_xdr(env)))
+        }
+        ActionType::UpdateConfig => {
+            timelock::require_disabled(env)?;
+            let new_config: Config = decode(env, data)?;
+            let old_config = admin::apply_config(env, executor.clone(), new_config.clone())?;
+            Ok((old_config.to_xdr(env), new_config.to_xdr(env)))
+        }
+        ActionType::UpdateRate => {
+            timelock::require_disabled(env)?;
+            let rate: TokenExchangeRate = decode(env, data)?;
+            let old_rate: Option<i128> = env
+                .storage()
+                .persistent()
+                .get::<DataKey, TokenExchangeRate>(&DataKey::ExchangeRate(
+                    rate.from_token.clone(),
+                    rate.to_token.clone(),
+                ))
+                .map(|r| r.rate);
+            multi_token::set_exchange_rate(env.clone(), rate.from_token, rate.to_token, rate.rate)?;
+            Ok((old_rate.to_xdr(env), Some(rate.rate).to_xdr(env)))
+        }
+        ActionType::AddAdmin => {
+            let before = get_multisig_config(env)?.admins;
+            add_admin_internal(env, require_target(target)?)?;
+            let after = get_multisig_config(env)?.admins;
+            Ok((before.to_xdr(env), after.to_xdr(env)))
+        }
+        ActionType::RemoveAdmin => {
+            let before = get_multisig_config(env)?.admins;
+            remove_admin_internal(env, require_target(target)?)?;
+            let after = get_multisig_config(env)?.admins;
+            Ok((before.to_xdr(env), after.to_xdr(env)))
+        }
+        ActionType::UpdateRequiredSignatures => {
+            let before = get_multisig_config(env)?.required_signatures;
+            let new_required: u32 = decode(env, data)?;
+            update_required_signatures_internal(env, new_required)?;
+            Ok((before.to_xdr(env), new_required.to_xdr(env)))
+        }
+        ActionType::EmergencyAction => {
+            timelock::require_disabled(env)?;
+            let new_admin = require_target(target)?;
+            let old_admin = admin::apply_admin(env, new_admin.clone())?;
+            Ok((old_admin.to_xdr(env), new_admin.to_xdr(env)))
+        }
+        ActionType::SetRateLimit => {
+            let before = rate_limit::get_rate_limit_config(env);
+            // Proposals queued before the timestamp-based modes carry the
+            // older layout.
+            let config: RateLimitConfig = read_config(env, &decode::<Val>(env, data)?);
+            rate_limit::set_rate_limit_config(env, config.clone())?;
+            events::rate_limit_config_updated(
+                env,
+                config.max_calls_per_block,
+                config.max_calls_per_user_per_day,
+                config.cooldown_blocks,
+            );
+            Ok((before.to_xdr(env), config.to_xdr(env)))
+        }
+        ActionType::AddToken => {
+            let token: SupportedToken = decode(env, data)?;
+            let before = multi_token::is_token_supported(env.clone(), token.token_address.clone())?;
+            multi_token::add_supported_token(
+                env.clone(),
+                token.token_address,
+                token.symbol,
+                token.decimals,
+                token.min_amount,
+                token.max_amount,
+            )?;
+            Ok((before.to_xdr(env), true.to_xdr(env)))
+        }
+        ActionType::RemoveToken => {
+            let token = require_target(target)?;
+            let before = multi_token::is_token_supported(env.clone(), token.clone())?;
+            multi_token::remove_supported_token(env.clone(), token)?;
+            Ok((before.to_xdr(env), false.to_xdr(env)))
+        }
+        ActionType::SetActionThreshold => {
+            let update: ActionThreshold = decode(env, data)?;
+            let before = set_action_threshold_internal(env, update.action_type, update.threshold)?;
+            Ok((before.to_xdr(env), update.threshold.to_xdr(env)))
+        }
+        ActionType::SetAdminWeight => {
+            let admin = require_target(target)?;
+            let weight: u32 = decode(env, data)?;
+            let before = set_admin_weight_internal(env, admin, weight)?;
+            Ok((before.to_xdr(env), weight.to_xdr(env)))
+        }
+        ActionType::GrantRole => {
+            let role: Role = decode(env, data)?;
+            access::grant_role(env, role, require_target(target)?)?;
+            Ok((false.to_xdr(env), true.to_xdr(env)))
+        }
+        ActionType::RevokeRole => {
+            let role: Role = decode(env, data)?;
+            access::revoke_role(env, role, require_target(target)?)?;
+            Ok((true.to_xdr(env), false.to_xdr(env)))
+        }
+        ActionType::SetGuardian => {
+            let before = access::set_guardian(env, target.clone());
+            Ok((before.to_xdr(env), target.to_xdr(env)))
+        }
+        ActionType::SetAuditLogCapacity => {
+            let capacity: u32 = decode(env, data)?;
+            let before = audit::set_capacity(env, capacity)?;
+            Ok((before.to_xdr(env), capacity.to_xdr(env)))
+        }
+    }
+}
+
 /// Reject/cancel a proposal (only proposer can do this before execution)
 pub fn reject_action(env: &Env, caller: Address, proposal_id: String) -> Result<(), RentalError> {
     caller.require_auth();
@@ -363,10 +725,16 @@ pub fn remove_admin_internal(env: &Env, admin_to_remove: Address) -> Result<(),
 
     config.admins = new_admins;
     config.total_admins -= 1;
+    env.storage()
+        .instance()
+        .remove(&DataKey::Governance(GovernanceKey::AdminWeight(
+            admin_to_remove.clone(),
+        )));
 
     // Adjust required signatures if needed
-    if config.required_signatures > config.total_admins {
-        config.required_signatures = config.total_admins;
+    let remaining_weight = total_weight(env, &config);
+    if config.required_signatures > remaining_weight {
+        config.required_signatures = remaining_weight;
     }
 
     // Update storage
@@ -387,7 +755,7 @@ pub fn update_required_signatures_internal(
 ) -> Result<(), RentalError> {
     let mut config = get_multisig_config(env)?;
 
-    if new_required == 0 || new_required > config.total_admins {
+    if new_required == 0 || new_required > total_weight(env, &config) {
         return Err(RentalError::InvalidConfig);
     }
 
@@ -405,6 +773,55 @@ pub fn update_required_signatures_internal(
     Ok(())
 }
 
+/// Set the weight an action type needs through multi-sig proposal
+/// execution, returning the previous threshold.
+pub fn set_action_threshold_internal(
+    env: &Env,
+    action_type: ActionType,
+    threshold: u32,
+) -> Result<u32, RentalError> {
+    let config = get_multisig_config(env)?;
+    if threshold == 0 || threshold > total_weight(env, &config) {
+        return Err(RentalError::InvalidConfig);
+    }
+
+    let old_threshold = threshold_for(env, &config, action_type.clone());
+    env.storage().instance().set(
+        &DataKey::Governance(GovernanceKey::ActionThreshold(action_type.clone())),
+        &threshold,
+    );
+
+    events::action_threshold_updated(env, action_type, old_threshold, threshold);
+
+    Ok(old_threshold)
+}
+
+/// Set an admin's approval weight through multi-sig proposal execution,
+/// returning the previous weight.
+pub fn set_admin_weight_internal(
+    env: &Env,
+    admin: Address,
+    weight: u32,
+) -> Result<u32, RentalError> {
+    let config = get_multisig_config(env)?;
+    if !config.admins.contains(&admin) {
+        return Err(RentalError::InvalidInput);
+    }
+    if weight == 0 {
+        return Err(RentalError::InvalidConfig);
+    }
+
+    let old_weight = weight_of(env, &config, &admin);
+    env.storage().instance().set(
+        &DataKey::Governance(GovernanceKey::AdminWeight(admin.clone())),
+        &weight,
+    );
+
+    events::admin_weight_updated(env, admin, old_weight, weight);
+
+    Ok(old_weight)
+}
+
 /// Get a proposal by ID
 pub fn get_proposal(env: &Env, proposal_id: String) -> Result<AdminProposal, RentalError> {
     env.storage()
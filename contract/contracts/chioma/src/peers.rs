@@ -0,0 +1,137 @@
+//! Peer contracts chioma calls, resolved through the protocol directory: the
+//! escrow contract that holds deposits taken at activation, hands them to a
+//! new landlord when a position is sold, moves them into yield vaults on
+//! chioma's behalf and reports when they have been paid out.
+use soroban_sdk::{contractclient, contracttype, Address, BytesN, Env, String};
+
+use crate::errors::RentalError;
+use crate::events;
+use crate::storage::{DataKey, GovernanceKey};
+
+/// The protocol directory's `Component`, mirrored to name peers.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Component {
+    AgentRegistry,
+    Chioma,
+    DisputeResolution,
+    Escrow,
+    Payment,
+    PropertyRegistry,
+    RentObligation,
+    UserProfile,
+}
+
+/// The part of the protocol directory peers are resolved through.
+#[contractclient(name = "DirectoryClient")]
+pub trait DirectoryInterface {
+    fn resolve(env: Env, component: Component) -> Option<Address>;
+}
+
+/// The escrow contract's `VaultPosition`, mirrored to read positions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaultPosition {
+    pub vault: Address,
+    pub principal: i128,
+    pub shares: i128,
+    pub routed_at: u64,
+}
+
+/// The escrow contract's `EscrowStatus`, mirrored to read where a deposit
+/// stands.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowStatus {
+    Pending = 0,
+    Funded = 1,
+    Released = 2,
+    Refunded = 3,
+    Disputed = 4,
+    PartiallyFunded = 5,
+}
+
+/// The escrow contract's `EscrowError::InstallmentsOutstanding`: the
+/// agreement's escrow is still being funded in installments.
+pub(crate) const INSTALLMENTS_OUTSTANDING: soroban_sdk::Error =
+    soroban_sdk::Error::from_contract_error(31);
+
+/// The part of the escrow contract deposits are moved into.
+#[contractclient(name = "EscrowClient")]
+pub trait EscrowInterface {
+    fn create_funded(
+        env: Env,
+        agreement_id: String,
+        depositor: Address,
+        beneficiary: Address,
+        arbiter: Address,
+        platform_governance: Address,
+        agent_referral: Address,
+        amount: i128,
+        token: Address,
+    ) -> BytesN<32>;
+    fn get_agreement_escrow(env: Env, agreement_id: String) -> Option<BytesN<32>>;
+    fn get_escrow_status(env: Env, escrow_id: BytesN<32>) -> EscrowStatus;
+    fn transfer_beneficiary(env: Env, agreement_id: String, beneficiary: Address);
+    fn route_to_vault(env: Env, escrow_id: BytesN<32>, vault: Address) -> VaultPosition;
+    fn withdraw_yield(env: Env, escrow_id: BytesN<32>, to: Address, amount: i128);
+    fn compound_yield(env: Env, escrow_id: BytesN<32>, amount: i128);
+    fn close_vault_position(env: Env, escrow_id: BytesN<32>) -> i128;
+    fn get_vault_position(env: Env, escrow_id: BytesN<32>) -> Option<VaultPosition>;
+    fn get_vault_yield(env: Env, escrow_id: BytesN<32>) -> i128;
+}
+
+pub(crate) fn set_directory(env: &Env, admin: Address, directory: Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::Governance(GovernanceKey::Directory), &directory);
+    events::directory_set(env, admin, directory);
+}
+
+pub(crate) fn get_directory(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Governance(GovernanceKey::Directory))
+}
+
+/// The escrow contract registered in the directory.
+pub(crate) fn escrow(env: &Env) -> Result<Address, RentalError> {
+    let directory = get_directory(env).ok_or(RentalError::InvalidConfig)?;
+    DirectoryClient::new(env, &directory)
+        .resolve(&Component::Escrow)
+        .ok_or(RentalError::InvalidConfig)
+}
+
+/// Make `beneficiary` the landlord the escrow holding `agreement_id`'s
+/// deposit pays out to. Agreements without an escrowed deposit are left as
+/// they are.
+pub(crate) fn transfer_deposit(
+    env: &Env,
+    agreement_id: &String,
+    beneficiary: &Address,
+) -> Result<(), RentalError> {
+    let Ok(escrow) = escrow(env) else {
+        return Ok(());
+    };
+    let escrow = EscrowClient::new(env, &escrow);
+    if escrow.get_agreement_escrow(agreement_id).is_none() {
+        return Ok(());
+    }
+    match escrow.try_transfer_beneficiary(agreement_id, beneficiary) {
+        Ok(Ok(())) => Ok(()),
+        _ => Err(RentalError::InvalidState),
+    }
+}
+
+/// Whether the escrow holding `agreement_id`'s deposit has paid it out,
+/// to the landlord or back to the tenant.
+pub(crate) fn deposit_settled(env: &Env, agreement_id: &String) -> Result<bool, RentalError> {
+    let escrow = EscrowClient::new(env, &escrow(env)?);
+    let escrow_id = escrow
+        .get_agreement_escrow(agreement_id)
+        .ok_or(RentalError::EscrowNotFound)?;
+    Ok(matches!(
+        escrow.get_escrow_status(&escrow_id),
+        EscrowStatus::Released | EscrowStatus::Refunded
+    ))
+}
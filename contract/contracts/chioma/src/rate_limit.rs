@@ -2,17 +2,28 @@
 //!
 //! Implements rate limiting to prevent spam attacks and DOS vulnerabilities.
 
-use soroban_sdk::{Address, Env, String};
+use rate_limiter::{consume_window, load_window, read_config, LegacyRateLimitConfig, Strategy};
+use soroban_sdk::{Address, Env, String, Val, Vec};
 
 use crate::errors::RentalError;
 use crate::events;
-use crate::storage::DataKey;
-use crate::types::{RateLimitConfig, UserCallCount};
+use crate::storage::{DataKey, RateLimitKey};
+use crate::types::{RateLimitConfig, RateLimitMode, UserCallCount};
 
 const BLOCKS_PER_DAY: u64 = 17280; // Assuming ~5 second blocks
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Timestamp-based modes need a non-empty window.
+fn validate_config(config: &RateLimitConfig) -> Result<(), RentalError> {
+    if config.mode != RateLimitMode::LedgerBlocks && config.window_seconds == 0 {
+        return Err(RentalError::InvalidConfig);
+    }
+    Ok(())
+}
 
 /// Initialize rate limit configuration (admin only).
 pub fn set_rate_limit_config(env: &Env, config: RateLimitConfig) -> Result<(), RentalError> {
+    validate_config(&config)?;
     env.storage()
         .persistent()
         .set(&DataKey::RateLimitConfig, &config);
@@ -22,26 +33,169 @@ pub fn set_rate_limit_config(env: &Env, config: RateLimitConfig) -> Result<(), R
     Ok(())
 }
 
+impl From<LegacyRateLimitConfig> for RateLimitConfig {
+    /// Configs stored before the timestamp-based modes limited by ledger
+    /// blocks over a day.
+    fn from(legacy: LegacyRateLimitConfig) -> Self {
+        RateLimitConfig {
+            max_calls_per_block: legacy.max_calls_per_block,
+            max_calls_per_user_per_day: legacy.max_calls_per_user_per_day,
+            cooldown_blocks: legacy.cooldown_blocks,
+            mode: RateLimitMode::LedgerBlocks,
+            window_seconds: SECONDS_PER_DAY,
+        }
+    }
+}
+
 /// Get current rate limit configuration.
 pub fn get_rate_limit_config(env: &Env) -> RateLimitConfig {
     env.storage()
         .persistent()
-        .get(&DataKey::RateLimitConfig)
+        .get::<_, Val>(&DataKey::RateLimitConfig)
+        .map(|raw| read_config(env, &raw))
         .unwrap_or(RateLimitConfig {
             max_calls_per_block: 10,
             max_calls_per_user_per_day: 100,
             cooldown_blocks: 0,
+            mode: RateLimitMode::LedgerBlocks,
+            window_seconds: SECONDS_PER_DAY,
         })
 }
 
+// ─── Per-function overrides ──────────────────────────────────────────────────
+
+/// Override the global configuration for one function name.
+pub fn set_function_rate_limit(
+    env: &Env,
+    function_name: String,
+    config: RateLimitConfig,
+) -> Result<(), RentalError> {
+    validate_config(&config)?;
+    let key = DataKey::RateLimitPolicy(RateLimitKey::FunctionLimit(function_name.clone()));
+    env.storage().persistent().set(&key, &config);
+    env.storage().persistent().extend_ttl(&key, 500000, 500000);
+
+    let mut names = get_function_rate_limits(env);
+    if !names.contains(&function_name) {
+        names.push_back(function_name.clone());
+        env.storage().persistent().set(
+            &DataKey::RateLimitPolicy(RateLimitKey::FunctionLimits),
+            &names,
+        );
+    }
+
+    events::function_rate_limit_set(
+        env,
+        function_name,
+        config.max_calls_per_block,
+        config.max_calls_per_user_per_day,
+        config.cooldown_blocks,
+    );
+    Ok(())
+}
+
+/// Drop a function's override so it falls back to the global configuration.
+pub fn remove_function_rate_limit(env: &Env, function_name: String) -> Result<(), RentalError> {
+    let key = DataKey::RateLimitPolicy(RateLimitKey::FunctionLimit(function_name.clone()));
+    if !env.storage().persistent().has(&key) {
+        return Err(RentalError::InvalidInput);
+    }
+    env.storage().persistent().remove(&key);
+
+    let mut names = get_function_rate_limits(env);
+    if let Some(index) = names.first_index_of(&function_name) {
+        names.remove(index);
+        env.storage().persistent().set(
+            &DataKey::RateLimitPolicy(RateLimitKey::FunctionLimits),
+            &names,
+        );
+    }
+
+    events::function_rate_limit_removed(env, function_name);
+    Ok(())
+}
+
+/// Override configured for a function, if any.
+pub fn get_function_rate_limit(env: &Env, function_name: String) -> Option<RateLimitConfig> {
+    env.storage()
+        .persistent()
+        .get::<_, Val>(&DataKey::RateLimitPolicy(RateLimitKey::FunctionLimit(
+            function_name,
+        )))
+        .map(|raw| read_config(env, &raw))
+}
+
+/// Names of all functions with an override.
+pub fn get_function_rate_limits(env: &Env) -> Vec<String> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RateLimitPolicy(RateLimitKey::FunctionLimits))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Configuration that applies to a function: its override, or the global one.
+pub fn get_effective_rate_limit(env: &Env, function_name: String) -> RateLimitConfig {
+    get_function_rate_limit(env, function_name).unwrap_or_else(|| get_rate_limit_config(env))
+}
+
+// ─── Exemptions ──────────────────────────────────────────────────────────────
+
+/// Exempt an account from all rate limits, or revoke its exemption.
+pub fn set_rate_limit_exempt(env: &Env, account: Address, exempt: bool) -> Result<(), RentalError> {
+    let key = DataKey::RateLimitPolicy(RateLimitKey::Exempt(account.clone()));
+    let mut accounts = get_rate_limit_exemptions(env);
+    if exempt {
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, 500000, 500000);
+        if !accounts.contains(&account) {
+            accounts.push_back(account.clone());
+        }
+    } else {
+        env.storage().persistent().remove(&key);
+        if let Some(index) = accounts.first_index_of(&account) {
+            accounts.remove(index);
+        }
+    }
+    env.storage().persistent().set(
+        &DataKey::RateLimitPolicy(RateLimitKey::Exemptions),
+        &accounts,
+    );
+
+    events::rate_limit_exemption_updated(env, account, exempt);
+    Ok(())
+}
+
+/// Whether an account bypasses rate limiting.
+pub fn is_rate_limit_exempt(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::RateLimitPolicy(RateLimitKey::Exempt(
+            account.clone(),
+        )))
+}
+
+/// All accounts currently exempt from rate limiting.
+pub fn get_rate_limit_exemptions(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RateLimitPolicy(RateLimitKey::Exemptions))
+        .unwrap_or(Vec::new(env))
+}
+
+// ─── Enforcement ─────────────────────────────────────────────────────────────
+
 /// Check and enforce rate limits for a user calling a specific function.
 ///
-/// This implements:
+/// Exempt accounts are let through untouched. Otherwise the function's
+/// override (or the global configuration) is applied:
 /// 1. Per-block global rate limiting
 /// 2. Per-user daily rate limiting
 /// 3. Cooldown period enforcement
 pub fn check_rate_limit(env: &Env, user: &Address, function_name: &str) -> Result<(), RentalError> {
-    let config = get_rate_limit_config(env);
+    if is_rate_limit_exempt(env, user) {
+        return Ok(());
+    }
+    let config = get_effective_rate_limit(env, String::from_str(env, function_name));
     let current_block = env.ledger().sequence() as u64;
 
     // Get or initialize user call count
@@ -79,16 +233,44 @@ pub fn check_rate_limit(env: &Env, user: &Address, function_name: &str) -> Resul
         user_calls.daily_reset_block = current_block;
     }
 
-    // Check daily limit
-    if user_calls.daily_count >= config.max_calls_per_user_per_day {
-        events::rate_limit_exceeded(
-            env,
-            user.clone(),
-            String::from_str(env, function_name),
-            crate::types::RateLimitReason::DailyLimitExceeded,
-        );
-        return Err(RentalError::RateLimitExceeded);
-    }
+    // Check the per-user limit in the configured mode
+    let window_key = DataKey::RateLimitPolicy(RateLimitKey::Window(
+        user.clone(),
+        String::from_str(env, function_name),
+    ));
+    let window = match config.mode {
+        RateLimitMode::LedgerBlocks => {
+            if user_calls.daily_count >= config.max_calls_per_user_per_day {
+                events::rate_limit_exceeded(
+                    env,
+                    user.clone(),
+                    String::from_str(env, function_name),
+                    crate::types::RateLimitReason::DailyLimitExceeded,
+                );
+                return Err(RentalError::RateLimitExceeded);
+            }
+            None
+        }
+        RateLimitMode::SlidingWindow | RateLimitMode::TokenBucket => {
+            let strategy = if config.mode == RateLimitMode::TokenBucket {
+                Strategy::TokenBucket
+            } else {
+                Strategy::SlidingWindow
+            };
+            let limit = config.max_calls_per_user_per_day;
+            let mut state = load_window(env, &window_key, limit, config.window_seconds);
+            if !consume_window(env, strategy, limit, config.window_seconds, &mut state) {
+                events::rate_limit_exceeded(
+                    env,
+                    user.clone(),
+                    String::from_str(env, function_name),
+                    crate::types::RateLimitReason::WindowLimitExceeded,
+                );
+                return Err(RentalError::RateLimitExceeded);
+            }
+            Some(state)
+        }
+    };
 
     // Check per-block limit
     let block_key = DataKey::BlockCallCount(current_block, String::from_str(env, function_name));
@@ -112,6 +294,12 @@ pub fn check_rate_limit(env: &Env, user: &Address, function_name: &str) -> Resul
     // Save user call count
     env.storage().persistent().set(&key, &user_calls);
     env.storage().persistent().extend_ttl(&key, 500000, 500000);
+    if let Some(state) = window {
+        env.storage().persistent().set(&window_key, &state);
+        env.storage()
+            .persistent()
+            .extend_ttl(&window_key, 500000, 500000);
+    }
 
     // Update block call count (use temporary storage, expires after ~1 day)
     env.storage()
@@ -1,11 +1,43 @@
 //! NFT Royalty Mechanism for Chioma rental agreements.
 
-use soroban_sdk::{token, Address, Env, String, Vec};
+use soroban_sdk::{
+    contracttype, token, unwrap::UnwrapOptimized, Address, Env, String, TryFromVal, Val, Vec,
+};
 
+use crate::agreement;
 use crate::errors::RentalError;
 use crate::events;
-use crate::storage::DataKey;
-use crate::types::{RoyaltyConfig, RoyaltyPayment};
+use crate::peers;
+use crate::storage::{self, DataKey};
+use crate::types::{RentAgreement, RoyaltyConfig, RoyaltyLeg, RoyaltyPayment, RoyaltyShare};
+
+/// Cap on the combined royalty across all recipients (25 %).
+pub const MAX_TOTAL_ROYALTY_BPS: u32 = 2500;
+
+/// Cap on the number of royalty recipients per token.
+pub const MAX_ROYALTY_RECIPIENTS: u32 = 10;
+
+/// `RoyaltyConfig` as stored before a token could have several recipients.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacyRoyaltyConfig {
+    pub token_id: String,
+    pub creator: Address,
+    pub royalty_percentage: u32,
+    pub royalty_recipient: Address,
+}
+
+/// `RoyaltyPayment` as stored before each recipient's leg was recorded.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacyRoyaltyPayment {
+    pub token_id: String,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub royalty_amount: i128,
+    pub timestamp: u64,
+}
 
 /// Set the royalty configuration for a specific token (agreement).
 ///
@@ -16,41 +48,104 @@ pub fn set_royalty(
     royalty_percentage: u32,
     royalty_recipient: Address,
 ) -> Result<(), RentalError> {
-    if royalty_percentage > 2500 {
+    let mut recipients = Vec::new(&env);
+    recipients.push_back(RoyaltyShare {
+        recipient: royalty_recipient,
+        percentage: royalty_percentage,
+    });
+    set_royalty_recipients(env, token_id, recipients)
+}
+
+/// Set a list of royalty recipients, each with its own basis points.
+///
+/// The combined percentage is capped at `MAX_TOTAL_ROYALTY_BPS`. Only the
+/// agreement's landlord (creator) can set this.
+pub fn set_royalty_recipients(
+    env: Env,
+    token_id: String,
+    recipients: Vec<RoyaltyShare>,
+) -> Result<(), RentalError> {
+    if recipients.is_empty() || recipients.len() > MAX_ROYALTY_RECIPIENTS {
+        return Err(RentalError::InvalidInput);
+    }
+
+    let mut total: u32 = 0;
+    for (i, share) in recipients.iter().enumerate() {
+        for other in recipients.iter().skip(i + 1) {
+            if other.recipient == share.recipient {
+                return Err(RentalError::InvalidInput);
+            }
+        }
+        total = total.saturating_add(share.percentage);
+    }
+    if total > MAX_TOTAL_ROYALTY_BPS {
         // Limit to 25% (2500 bps)
         return Err(RentalError::InvalidAmount);
     }
 
     // Read agreement to verify landlord (creator)
-    let agreement = env
-        .storage()
-        .persistent()
-        .get::<DataKey, crate::types::RentAgreement>(&DataKey::Agreement(token_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement =
+        agreement::load_agreement(&env, &token_id).ok_or(RentalError::AgreementNotFound)?;
 
     agreement.admin.require_auth();
 
+    let first = recipients.get(0).unwrap();
     let config = RoyaltyConfig {
         token_id: token_id.clone(),
         creator: agreement.admin.clone(),
-        royalty_percentage,
-        royalty_recipient: royalty_recipient.clone(),
+        royalty_percentage: total,
+        royalty_recipient: first.recipient.clone(),
+        recipients: recipients.clone(),
     };
 
     env.storage()
         .persistent()
         .set(&DataKey::RoyaltyConfig(token_id.clone()), &config);
 
-    events::royalty_set(&env, token_id, royalty_percentage, royalty_recipient);
+    for share in recipients.iter() {
+        events::royalty_set(&env, token_id.clone(), share.percentage, share.recipient);
+    }
     Ok(())
 }
 
 /// Retrieve the royalty configuration for a token.
+///
+/// A configuration written before multiple recipients existed reads as a
+/// single recipient holding the whole percentage.
 pub fn get_royalty(env: Env, token_id: String) -> Result<RoyaltyConfig, RentalError> {
-    env.storage()
+    let raw: Val = env
+        .storage()
         .persistent()
         .get(&DataKey::RoyaltyConfig(token_id))
-        .ok_or(RentalError::InternalError) // Or add specific RoyaltyNotFound
+        .ok_or(RentalError::InternalError)?; // Or add specific RoyaltyNotFound
+    if storage::has_field(&env, &raw, "recipients") {
+        return Ok(RoyaltyConfig::try_from_val(&env, &raw).unwrap_optimized());
+    }
+    let legacy = LegacyRoyaltyConfig::try_from_val(&env, &raw).unwrap_optimized();
+    let mut recipients = Vec::new(&env);
+    recipients.push_back(RoyaltyShare {
+        recipient: legacy.royalty_recipient.clone(),
+        percentage: legacy.royalty_percentage,
+    });
+    Ok(RoyaltyConfig {
+        token_id: legacy.token_id,
+        creator: legacy.creator,
+        royalty_percentage: legacy.royalty_percentage,
+        royalty_recipient: legacy.royalty_recipient,
+        recipients,
+    })
+}
+
+/// Split `sale_price` into one leg per recipient.
+fn royalty_legs(env: &Env, config: &RoyaltyConfig, sale_price: i128) -> Vec<RoyaltyLeg> {
+    let mut legs = Vec::new(env);
+    for share in config.recipients.iter() {
+        legs.push_back(RoyaltyLeg {
+            recipient: share.recipient,
+            amount: (sale_price * share.percentage as i128) / 10000,
+        });
+    }
+    legs
 }
 
 /// Calculate the royalty amount for a given sale price.
@@ -59,15 +154,18 @@ pub fn calculate_royalty(
     token_id: String,
     sale_price: i128,
 ) -> Result<i128, RentalError> {
-    let config = get_royalty(env, token_id)?;
-    Ok((sale_price * config.royalty_percentage as i128) / 10000)
+    let config = get_royalty(env.clone(), token_id)?;
+    Ok(royalty_legs(&env, &config, sale_price)
+        .iter()
+        .map(|leg| leg.amount)
+        .sum())
 }
 
 /// Perform a transfer of the "agreement" ownership with royalty payment.
 ///
 /// The current landlord MUST call this and authorize the transfer.
 /// The sale price is paid by the new landlord (`to`) to the current landlord,
-/// with the royalty percentage deducted and sent to the royalty recipient.
+/// with each recipient's royalty deducted and sent to it.
 pub fn transfer_with_royalty(
     env: Env,
     token_id: String,
@@ -78,65 +176,83 @@ pub fn transfer_with_royalty(
         return Err(RentalError::InvalidAmount);
     }
 
-    let mut agreement = env
-        .storage()
-        .persistent()
-        .get::<DataKey, crate::types::RentAgreement>(&DataKey::Agreement(token_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement =
+        agreement::load_agreement(&env, &token_id).ok_or(RentalError::AgreementNotFound)?;
 
-    let current_landlord = agreement.admin.clone();
-    current_landlord.require_auth();
+    agreement.admin.require_auth();
     to.require_auth(); // Buyer must authorize the payment
 
-    let config = get_royalty(env.clone(), token_id.clone())?;
-    let royalty_amount = (sale_price * config.royalty_percentage as i128) / 10000;
+    get_royalty(env.clone(), token_id.clone())?;
+    settle_sale(&env, token_id, agreement, &to, &to, sale_price)?;
+    Ok(())
+}
+
+/// Pay royalties and the seller out of `payer`'s funds, then hand the
+/// landlord position, and the escrowed deposit with it, to `buyer`.
+///
+/// All legs are computed before any transfer and the whole settlement runs
+/// in one invocation, so it either completes or reverts as a unit. A token
+/// without a royalty configuration is settled with no royalty legs.
+pub(crate) fn settle_sale(
+    env: &Env,
+    token_id: String,
+    mut agreement: RentAgreement,
+    payer: &Address,
+    buyer: &Address,
+    sale_price: i128,
+) -> Result<RoyaltyPayment, RentalError> {
+    let current_landlord = agreement.admin.clone();
+
+    let legs = match get_royalty(env.clone(), token_id.clone()) {
+        Ok(config) => royalty_legs(env, &config, sale_price),
+        Err(_) => Vec::new(env),
+    };
+    let royalty_amount: i128 = legs.iter().map(|leg| leg.amount).sum();
     let seller_amount = sale_price - royalty_amount;
 
-    let token_client = token::Client::new(&env, &agreement.payment_token);
-
-    // 1. Transfer royalty to recipient
-    if royalty_amount > 0 {
-        token_client.transfer(&to, &config.royalty_recipient, &royalty_amount);
-        events::royalty_paid(
-            &env,
-            token_id.clone(),
-            royalty_amount,
-            config.royalty_recipient,
-        );
+    let token_client = token::Client::new(env, &agreement.payment_token);
+
+    // 1. Transfer each royalty leg to its recipient
+    for leg in legs.iter() {
+        if leg.amount > 0 {
+            token_client.transfer(payer, &leg.recipient, &leg.amount);
+            events::royalty_paid(env, token_id.clone(), leg.amount, leg.recipient);
+        }
     }
 
     // 2. Transfer remainder to current landlord
     if seller_amount > 0 {
-        token_client.transfer(&to, &current_landlord, &seller_amount);
+        token_client.transfer(payer, &current_landlord, &seller_amount);
     }
 
     // 3. Record payment history
     let payment = RoyaltyPayment {
         token_id: token_id.clone(),
         from: current_landlord,
-        to: to.clone(),
+        to: buyer.clone(),
         amount: sale_price,
         royalty_amount,
+        legs,
         timestamp: env.ledger().timestamp(),
     };
 
-    let mut payments: Vec<RoyaltyPayment> = env
-        .storage()
-        .persistent()
-        .get(&DataKey::RoyaltyPayments(token_id.clone()))
-        .unwrap_or(Vec::new(&env));
-    payments.push_back(payment);
+    let mut payments = load_payments(env, &token_id);
+    payments.push_back(payment.clone());
     env.storage()
         .persistent()
         .set(&DataKey::RoyaltyPayments(token_id.clone()), &payments);
 
-    // 4. Update agreement landlord
-    agreement.admin = to;
+    // 4. Update agreement landlord; any listing was the previous landlord's
+    agreement.admin = buyer.clone();
+    agreement::save_agreement(env, &token_id, &agreement);
     env.storage()
         .persistent()
-        .set(&DataKey::Agreement(token_id), &agreement);
+        .remove(&DataKey::PositionListing(token_id.clone()));
 
-    Ok(())
+    // 5. The escrowed deposit now belongs to the new landlord
+    peers::transfer_deposit(env, &token_id, buyer)?;
+
+    Ok(payment)
 }
 
 /// Get the royalty payment history for a token.
@@ -144,9 +260,34 @@ pub fn get_royalty_payments(
     env: Env,
     token_id: String,
 ) -> Result<Vec<RoyaltyPayment>, RentalError> {
-    Ok(env
+    Ok(load_payments(&env, &token_id))
+}
+
+/// Read a token's payment history, entry by entry, since sales settled
+/// before legs were recorded share the list with newer ones. Those older
+/// entries carry no legs: which recipient was paid was never stored.
+fn load_payments(env: &Env, token_id: &String) -> Vec<RoyaltyPayment> {
+    let raw: Vec<Val> = env
         .storage()
         .persistent()
-        .get(&DataKey::RoyaltyPayments(token_id))
-        .unwrap_or(Vec::new(&env)))
+        .get(&DataKey::RoyaltyPayments(token_id.clone()))
+        .unwrap_or(Vec::new(env));
+    let mut payments = Vec::new(env);
+    for entry in raw.iter() {
+        if storage::has_field(env, &entry, "legs") {
+            payments.push_back(RoyaltyPayment::try_from_val(env, &entry).unwrap_optimized());
+            continue;
+        }
+        let legacy = LegacyRoyaltyPayment::try_from_val(env, &entry).unwrap_optimized();
+        payments.push_back(RoyaltyPayment {
+            token_id: legacy.token_id,
+            from: legacy.from,
+            to: legacy.to,
+            amount: legacy.amount,
+            royalty_amount: legacy.royalty_amount,
+            legs: Vec::new(env),
+            timestamp: legacy.timestamp,
+        });
+    }
+    payments
 }
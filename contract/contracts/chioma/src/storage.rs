@@ -1,4 +1,6 @@
-use soroban_sdk::{contracttype, String};
+use soroban_sdk::{contracttype, Env, Map, String, Symbol, TryFromVal, Val};
+
+use crate::types::{ActionType, PauseModule, Role};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -14,11 +16,29 @@ pub enum DataKey {
     AgreementToken(String),
     DepositInterestConfig(String),
     DepositInterest(String),
-    ErrorLog(u32),
-    ErrorLogCount,
+    // Registry of interest-bearing deposits, walked by keepers with a cursor
+    InterestDeposit(u32),
+    InterestDepositCount,
+    InterestDepositRegistered(String),
+    // Rate schedules (Vec<RateSegment>, ascending by effective_from)
+    DepositRateSchedule(String),
+    StatutoryRateSchedule,
+    InterestSplit(String),
+    AccrualBucket(String, u32), // (escrow_id, bucket index) -> Vec<InterestAccrual>
+    // Yield adapter keys
+    YieldVault(soroban_sdk::Address), // token -> vault
+    AuditLog(u32),                    // ring slot (seq % capacity) -> AuditEntry
+    AuditLogCount,                    // u32 entries ever written, i.e. the next seq
     RoyaltyConfig(String),
     RoyaltyPayments(String),
+    // Marketplace keys
+    PositionListing(String),
+    PositionListingCount,
+    PositionBid(String, u32), // (agreement_id, bid_id)
+    PositionBidCount(String),
+    EscrowedBids(soroban_sdk::Address), // token -> total held for open bids
     RateLimitConfig,
+    RateLimitPolicy(RateLimitKey),
     UserCallCount(soroban_sdk::Address, String), // (user, function_name)
     BlockCallCount(u64, String),                 // (block_number, function_name)
     PaymentRecord(String, u32),
@@ -27,10 +47,8 @@ pub enum DataKey {
     AdminProposal(String),
     ProposalCount,
     ActiveProposals,
-    // Timelock keys
-    TimelockAction(String),
-    TimelockActionCount,
-    ActiveTimelockActions,
+    Governance(GovernanceKey),
+    Timelock(TimelockKey),
     // Versioning keys
     CurrentVersion,
     VersionHistory,
@@ -40,6 +58,67 @@ pub enum DataKey {
     UpgradeProposal(String),
     UpgradeProposalCount,
     ActiveUpgradeProposals,
+    Migration(MigrationKey),
     // Gas optimisation metrics (keyed by operation name string)
     GasMetrics(String),
 }
+
+/// Per-function rate-limit overrides and exemptions.
+///
+/// Nested under `DataKey::RateLimitPolicy`, since a contract type may only
+/// have 50 variants.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RateLimitKey {
+    FunctionLimit(String), // function_name -> RateLimitConfig override
+    FunctionLimits,        // Vec<String> of overridden function names
+    Exempt(soroban_sdk::Address),
+    Exemptions,                           // Vec<Address>
+    Window(soroban_sdk::Address, String), // (user, function_name) -> RateWindow
+}
+
+/// Multi-sig policy beyond `MultiSigConfig`, roles and pause controls.
+///
+/// Nested under `DataKey::Governance` for the same reason as `RateLimitKey`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GovernanceKey {
+    ActionThreshold(ActionType), // u32 weight; defaults to required_signatures
+    AdminWeight(soroban_sdk::Address), // u32; defaults to 1
+    Role(Role, soroban_sdk::Address), // bool; granted through multi-sig
+    ModulePause(PauseModule),    // PauseState
+    Guardian,                    // Address that may pause but never unpause
+    AuditLogCapacity,            // u32 ring size of the audit log
+    Directory,                   // Address of the protocol directory
+}
+
+/// Timelock queue and switch.
+///
+/// Nested under `DataKey::Timelock` for the same reason as `RateLimitKey`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimelockKey {
+    Action(String),
+    ActionCount,
+    Active,  // Vec<String> of pending action ids
+    Enabled, // bool; when set, admin changes must be queued
+}
+
+/// Post-upgrade migration progress.
+///
+/// Nested under `DataKey::Migration` for the same reason as `RateLimitKey`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MigrationKey {
+    StorageVersion,      // u32 layout of the stored data
+    MigratedVersion,     // (major, minor, patch) `migrate` last ran for
+    AgreementIndex(u32), // creation order -> agreement_id, walked by `migrate_records`
+}
+
+/// Whether a stored struct has `field`, telling apart layouts written by
+/// older builds. Decoding a struct of the wrong shape traps rather than
+/// failing, so the layout has to be known before decoding.
+pub(crate) fn has_field(env: &Env, raw: &Val, field: &str) -> bool {
+    Map::<Symbol, Val>::try_from_val(env, raw)
+        .is_ok_and(|fields| fields.contains_key(Symbol::new(env, field)))
+}
@@ -1,7 +1,9 @@
 use super::*;
 use soroban_sdk::{
+    contract, contractimpl, contracttype,
     testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
-    Address, Env, IntoVal, String,
+    xdr::ToXdr,
+    Address, BytesN, Env, IntoVal, String,
 };
 
 #[test]
@@ -131,6 +133,245 @@ fn create_contract(env: &Env) -> ContractClient<'_> {
     ContractClient::new(env, &contract_id)
 }
 
+// ─── mock peers ───────────────────────────────────────────────────────────────
+
+#[contracttype]
+enum PeerKey {
+    Escrow,
+    Rejecting,
+    FailingWith, // contract error every create_funded call fails with
+    Funded(String),
+    Linked(String),        // agreement_id -> escrow_id
+    Agreement(BytesN<32>), // escrow_id -> agreement_id
+    Position(BytesN<32>),  // escrow_id -> VaultPosition
+    Yield(BytesN<32>),     // escrow_id -> i128 earned and not paid out
+    Status(BytesN<32>),    // escrow_id -> EscrowStatus once settled
+}
+
+/// Resolves `Component::Escrow` to whatever `wire_escrow` registered.
+#[contract]
+pub struct MockDirectory;
+
+#[contractimpl]
+impl MockDirectory {
+    pub fn resolve(env: Env, component: Component) -> Option<Address> {
+        match component {
+            Component::Escrow => env.storage().instance().get(&PeerKey::Escrow),
+            _ => None,
+        }
+    }
+}
+
+/// The arguments of a `create_funded` call, as the mock escrow saw them.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundedEscrow {
+    pub depositor: Address,
+    pub beneficiary: Address,
+    pub arbiter: Address,
+    pub amount: i128,
+    pub token: Address,
+}
+
+/// Records each `create_funded` call, or fails them all once rejecting.
+/// Vault positions are bookkeeping only: yield is whatever `earn` credits,
+/// and is paid out of the mock's own token balance.
+#[contract]
+pub struct MockEscrow;
+
+#[contractimpl]
+impl MockEscrow {
+    pub fn create_funded(
+        env: Env,
+        agreement_id: String,
+        depositor: Address,
+        beneficiary: Address,
+        arbiter: Address,
+        _platform_governance: Address,
+        _agent_referral: Address,
+        amount: i128,
+        token: Address,
+    ) -> BytesN<32> {
+        if env.storage().instance().has(&PeerKey::Rejecting) {
+            panic!("escrow rejected the deposit");
+        }
+        if let Some(error) = env.storage().instance().get(&PeerKey::FailingWith) {
+            soroban_sdk::panic_with_error!(&env, soroban_sdk::Error::from_contract_error(error));
+        }
+        let funded = FundedEscrow {
+            depositor,
+            beneficiary,
+            arbiter,
+            amount,
+            token,
+        };
+        let escrow_id: BytesN<32> = env
+            .crypto()
+            .sha256(&agreement_id.clone().to_xdr(&env))
+            .into();
+        let storage = env.storage().instance();
+        storage.set(&PeerKey::Funded(agreement_id.clone()), &funded);
+        storage.set(&PeerKey::Linked(agreement_id.clone()), &escrow_id);
+        storage.set(&PeerKey::Agreement(escrow_id.clone()), &agreement_id);
+        escrow_id
+    }
+
+    pub fn get_agreement_escrow(env: Env, agreement_id: String) -> Option<BytesN<32>> {
+        env.storage().instance().get(&PeerKey::Linked(agreement_id))
+    }
+
+    pub fn get_escrow_status(env: Env, escrow_id: BytesN<32>) -> EscrowStatus {
+        env.storage()
+            .instance()
+            .get(&PeerKey::Status(escrow_id))
+            .unwrap_or(EscrowStatus::Funded)
+    }
+
+    pub fn transfer_beneficiary(env: Env, agreement_id: String, beneficiary: Address) {
+        let mut funded = Self::funded(env.clone(), agreement_id.clone()).unwrap();
+        funded.beneficiary = beneficiary;
+        env.storage()
+            .instance()
+            .set(&PeerKey::Funded(agreement_id), &funded);
+    }
+
+    pub fn route_to_vault(env: Env, escrow_id: BytesN<32>, vault: Address) -> VaultPosition {
+        let funded = Self::funded_escrow(&env, &escrow_id);
+        let position = VaultPosition {
+            vault,
+            principal: funded.amount,
+            shares: funded.amount,
+            routed_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .instance()
+            .set(&PeerKey::Position(escrow_id), &position);
+        position
+    }
+
+    pub fn withdraw_yield(env: Env, escrow_id: BytesN<32>, to: Address, amount: i128) {
+        let earned = Self::get_vault_yield(env.clone(), escrow_id.clone());
+        assert!(amount <= earned, "not enough yield");
+        let token = Self::funded_escrow(&env, &escrow_id).token;
+        soroban_sdk::token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount,
+        );
+        env.storage()
+            .instance()
+            .set(&PeerKey::Yield(escrow_id), &(earned - amount));
+    }
+
+    pub fn compound_yield(env: Env, escrow_id: BytesN<32>, amount: i128) {
+        let earned = Self::get_vault_yield(env.clone(), escrow_id.clone());
+        assert!(amount <= earned, "not enough yield");
+        let mut position = Self::get_vault_position(env.clone(), escrow_id.clone()).unwrap();
+        position.principal += amount;
+        let storage = env.storage().instance();
+        storage.set(&PeerKey::Position(escrow_id.clone()), &position);
+        storage.set(&PeerKey::Yield(escrow_id), &(earned - amount));
+    }
+
+    pub fn close_vault_position(env: Env, escrow_id: BytesN<32>) -> i128 {
+        let position = Self::get_vault_position(env.clone(), escrow_id.clone()).unwrap();
+        let amount = position.principal + Self::get_vault_yield(env.clone(), escrow_id.clone());
+        let storage = env.storage().instance();
+        storage.remove(&PeerKey::Position(escrow_id.clone()));
+        storage.remove(&PeerKey::Yield(escrow_id));
+        amount
+    }
+
+    pub fn get_vault_position(env: Env, escrow_id: BytesN<32>) -> Option<VaultPosition> {
+        env.storage().instance().get(&PeerKey::Position(escrow_id))
+    }
+
+    pub fn get_vault_yield(env: Env, escrow_id: BytesN<32>) -> i128 {
+        if !env
+            .storage()
+            .instance()
+            .has(&PeerKey::Position(escrow_id.clone()))
+        {
+            return 0;
+        }
+        env.storage()
+            .instance()
+            .get(&PeerKey::Yield(escrow_id))
+            .unwrap_or(0)
+    }
+
+    /// Mark the deposit of `agreement_id` as paid out with `status`.
+    pub fn settle(env: Env, agreement_id: String, status: EscrowStatus) {
+        let escrow_id = Self::get_agreement_escrow(env.clone(), agreement_id).unwrap();
+        env.storage()
+            .instance()
+            .set(&PeerKey::Status(escrow_id), &status);
+    }
+
+    pub fn reject(env: Env) {
+        env.storage().instance().set(&PeerKey::Rejecting, &true);
+    }
+
+    /// Fail every `create_funded` call with the escrow contract's `error`.
+    pub fn fail_with(env: Env, error: u32) {
+        env.storage().instance().set(&PeerKey::FailingWith, &error);
+    }
+
+    pub fn funded(env: Env, agreement_id: String) -> Option<FundedEscrow> {
+        env.storage().instance().get(&PeerKey::Funded(agreement_id))
+    }
+
+    /// Credit a routed deposit with `amount` of vault yield.
+    pub fn earn(env: Env, agreement_id: String, amount: i128) {
+        let escrow_id = Self::get_agreement_escrow(env.clone(), agreement_id).unwrap();
+        let earned = Self::get_vault_yield(env.clone(), escrow_id.clone());
+        env.storage()
+            .instance()
+            .set(&PeerKey::Yield(escrow_id), &(earned + amount));
+    }
+}
+
+impl MockEscrow {
+    fn funded_escrow(env: &Env, escrow_id: &BytesN<32>) -> FundedEscrow {
+        let agreement_id: String = env
+            .storage()
+            .instance()
+            .get(&PeerKey::Agreement(escrow_id.clone()))
+            .unwrap();
+        Self::funded(env.clone(), agreement_id).unwrap()
+    }
+}
+
+/// Point `client` at a mock directory listing a mock escrow, initializing
+/// it first if needed so activation has an arbiter. Once wired, later calls
+/// return the same mock escrow.
+pub(crate) fn wire_escrow<'a>(env: &'a Env, client: &ContractClient<'_>) -> MockEscrowClient<'a> {
+    if client.get_state().is_none() {
+        let config = Config {
+            fee_bps: 0,
+            fee_collector: Address::generate(env),
+            paused: false,
+        };
+        client.initialize(&Address::generate(env), &config);
+    }
+    if let Some(directory) = client.get_directory() {
+        let escrow: Option<Address> = env.as_contract(&directory, || {
+            env.storage().instance().get(&PeerKey::Escrow)
+        });
+        if let Some(escrow) = escrow {
+            return MockEscrowClient::new(env, &escrow);
+        }
+    }
+    let escrow = env.register(MockEscrow, ());
+    let directory = env.register(MockDirectory, ());
+    env.as_contract(&directory, || {
+        env.storage().instance().set(&PeerKey::Escrow, &escrow);
+    });
+    let admin = client.get_state().unwrap().admin;
+    client.set_directory(&admin, &directory);
+    MockEscrowClient::new(env, &escrow)
+}
+
 fn initialize_contract_state(env: &Env, client: &ContractClient<'_>, admin: &Address) {
     let config = Config {
         fee_bps: 100,
@@ -170,7 +411,7 @@ fn test_update_config_success() {
         paused: true,
     };
 
-    client.update_config(&new_config);
+    client.update_config(&admin, &new_config);
 
     let updated_state = client.get_state().unwrap();
     assert_eq!(updated_state.config, new_config);
@@ -198,11 +439,11 @@ fn test_update_config_unauthorized() {
             invoke: &MockAuthInvoke {
                 contract: &client.address,
                 fn_name: "update_config",
-                args: (new_config.clone(),).into_val(&env),
+                args: (attacker.clone(), new_config.clone()).into_val(&env),
                 sub_invokes: &[],
             },
         }])
-        .update_config(&new_config);
+        .update_config(&attacker, &new_config);
 }
 
 #[test]
@@ -226,11 +467,11 @@ fn test_update_config_invalid_fee_bps() {
             invoke: &MockAuthInvoke {
                 contract: &client.address,
                 fn_name: "update_config",
-                args: (bad_config.clone(),).into_val(&env),
+                args: (admin.clone(), bad_config.clone()).into_val(&env),
                 sub_invokes: &[],
             },
         }])
-        .update_config(&bad_config);
+        .update_config(&admin, &bad_config);
 }
 
 #[test]
@@ -990,6 +1231,8 @@ fn test_cancel_agreement_invalid_state() {
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
 
+    wire_escrow(&env, &client);
+
     let agreement_id = "CANCEL_INVALID";
     create_pending_agreement(&env, &client, agreement_id, &tenant, &landlord);
 
@@ -1003,6 +1246,174 @@ fn test_cancel_agreement_invalid_state() {
     client.cancel_agreement(&landlord, &String::from_str(&env, agreement_id));
 }
 
+/// Sign `agreement_id` so it is ready for `approve_agreement`.
+fn pending_approval(env: &Env, client: &ContractClient<'_>, agreement_id: &str) -> RentAgreement {
+    let tenant = Address::generate(env);
+    let landlord = Address::generate(env);
+    create_pending_agreement(env, client, agreement_id, &tenant, &landlord);
+    let id = String::from_str(env, agreement_id);
+    client.sign_agreement(&tenant, &id);
+    client.get_agreement(&id).unwrap()
+}
+
+#[test]
+fn test_approve_agreement_escrows_security_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let escrow = wire_escrow(&env, &client);
+    let agreement = pending_approval(&env, &client, "DEPOSIT_ESCROWED");
+    let id = agreement.agreement_id.clone();
+
+    client.approve_agreement(&agreement.admin, &id);
+
+    let funded = escrow.funded(&id).unwrap();
+    assert_eq!(funded.depositor, agreement.user);
+    assert_eq!(funded.beneficiary, agreement.admin);
+    assert_eq!(funded.arbiter, client.get_state().unwrap().admin);
+    assert_eq!(funded.amount, 2000);
+    assert_eq!(funded.token, agreement.payment_token);
+
+    let active = client.get_agreement(&id).unwrap();
+    assert_eq!(active.status, AgreementStatus::Active);
+    assert_eq!(active.payment_count, 0);
+}
+
+/// Like `pending_approval`, with the agreement paid in a real token the
+/// tenant holds `balance` of.
+fn pending_approval_with_token(
+    env: &Env,
+    client: &ContractClient<'_>,
+    agreement_id: &str,
+    balance: i128,
+) -> RentAgreement {
+    let mut agreement = pending_approval(env, client, agreement_id);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(env, &token).mint(&agreement.user, &balance);
+    agreement.payment_token = token;
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &storage::DataKey::Agreement(agreement.agreement_id.clone()),
+            &agreement,
+        );
+    });
+    agreement
+}
+
+#[test]
+fn test_approve_with_first_rent_pays_it_outside_the_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let escrow = wire_escrow(&env, &client);
+    let agreement = pending_approval_with_token(&env, &client, "FIRST_RENT_PAID", 1000);
+    let id = agreement.agreement_id.clone();
+
+    client.approve_with_first_rent(&agreement.admin, &id);
+
+    // The escrow holds the deposit alone; the rent is an ordinary payment.
+    assert_eq!(escrow.funded(&id).unwrap().amount, 2000);
+    let active = client.get_agreement(&id).unwrap();
+    assert_eq!(active.status, AgreementStatus::Active);
+    assert_eq!(active.payment_count, 1);
+    assert_eq!(active.total_rent_paid, 1000);
+    let history = client.get_payment_history(&id);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().payer, agreement.user);
+    let token = soroban_sdk::token::Client::new(&env, &agreement.payment_token);
+    assert_eq!(token.balance(&client.address), 1000);
+    assert_eq!(token.balance(&agreement.user), 0);
+}
+
+#[test]
+fn test_approve_with_first_rent_fails_without_the_rent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let escrow = wire_escrow(&env, &client);
+    let agreement = pending_approval_with_token(&env, &client, "FIRST_RENT_UNPAID", 0);
+    let id = agreement.agreement_id.clone();
+
+    let result = client.try_approve_with_first_rent(&agreement.admin, &id);
+    assert!(result.is_err());
+
+    let pending = client.get_agreement(&id).unwrap();
+    assert_eq!(pending.status, AgreementStatus::PendingApproval);
+    assert_eq!(pending.payment_count, 0);
+    assert_eq!(escrow.funded(&id), None);
+}
+
+#[test]
+fn test_approve_agreement_fails_when_escrow_rejects_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let escrow = wire_escrow(&env, &client);
+    escrow.reject();
+    let agreement = pending_approval(&env, &client, "DEPOSIT_REJECTED");
+    let id = agreement.agreement_id.clone();
+
+    let result = client.try_approve_agreement(&agreement.admin, &id);
+    assert_eq!(result, Err(Ok(RentalError::PaymentFailed)));
+
+    let pending = client.get_agreement(&id).unwrap();
+    assert_eq!(pending.status, AgreementStatus::PendingApproval);
+    assert_eq!(pending.witness_id, None);
+    assert_eq!(pending.payment_count, 0);
+}
+
+#[test]
+fn test_approve_agreement_waits_for_outstanding_installments() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let escrow = wire_escrow(&env, &client);
+    escrow.fail_with(&crate::peers::INSTALLMENTS_OUTSTANDING.get_code());
+    let agreement = pending_approval(&env, &client, "DEPOSIT_IN_INSTALLMENTS");
+    let id = agreement.agreement_id.clone();
+
+    let result = client.try_approve_agreement(&agreement.admin, &id);
+    assert_eq!(result, Err(Ok(RentalError::EscrowInsufficientFunds)));
+    assert_eq!(
+        client.get_agreement(&id).unwrap().status,
+        AgreementStatus::PendingApproval
+    );
+}
+
+#[test]
+fn test_approve_agreement_requires_registered_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    initialize_contract_state(&env, &client, &admin);
+    let agreement = pending_approval(&env, &client, "NO_ESCROW");
+
+    let result = client.try_approve_agreement(&agreement.admin, &agreement.agreement_id);
+    assert_eq!(result, Err(Ok(RentalError::InvalidConfig)));
+}
+
+#[test]
+fn test_set_directory_requires_upgrader() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    initialize_contract_state(&env, &client, &admin);
+    let directory = Address::generate(&env);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_directory(&stranger, &directory),
+        Err(Ok(RentalError::Unauthorized))
+    );
+
+    client.set_directory(&admin, &directory);
+    assert_eq!(client.get_directory(), Some(directory));
+}
+
 #[test]
 fn test_get_agreement() {
     let env = Env::default();
@@ -1192,7 +1603,7 @@ fn test_contract_paused_operations() {
     };
     client.initialize(&admin, &config);
 
-    client.pause(&String::from_str(&env, "incident response"));
+    client.pause(&admin, &String::from_str(&env, "incident response"));
 
     assert!(client.is_paused());
 
@@ -1232,7 +1643,7 @@ fn test_contract_paused_operations() {
     });
     assert_eq!(res, Err(Ok(RentalError::ContractPaused)));
 
-    client.unpause();
+    client.unpause(&admin);
     assert!(!client.is_paused());
 
     let agreement_id_str = "agreement-active";
@@ -1264,12 +1675,12 @@ fn test_contract_paused_operations() {
         );
     });
 
-    client.pause(&String::from_str(&env, "second incident"));
+    client.pause(&admin, &String::from_str(&env, "second incident"));
 
     let res_sign = client.try_sign_agreement(&tenant, &agreement_id);
     assert_eq!(res_sign, Err(Ok(RentalError::ContractPaused)));
 
-    client.unpause();
+    client.unpause(&admin);
     let res_sign_success = client.try_sign_agreement(&tenant, &agreement_id);
     assert!(res_sign_success.is_ok());
 }
@@ -1289,12 +1700,12 @@ fn test_pause_unpause_events_emitted() {
 
     client.initialize(&admin, &config);
 
-    let pause_res = client.try_pause(&String::from_str(&env, "security patching"));
+    let pause_res = client.try_pause(&admin, &String::from_str(&env, "security patching"));
     assert!(pause_res.is_ok());
     let events_after_pause = env.events().all();
     assert_eq!(events_after_pause.len(), 1);
 
-    let unpause_res = client.try_unpause();
+    let unpause_res = client.try_unpause(&admin);
     assert!(unpause_res.is_ok());
     let events_after_unpause = env.events().all();
     assert_eq!(events_after_unpause.len(), 1);
@@ -1314,9 +1725,9 @@ fn test_pause_double_call_fails() {
     };
 
     client.initialize(&admin, &config);
-    client.pause(&String::from_str(&env, "maintenance"));
+    client.pause(&admin, &String::from_str(&env, "maintenance"));
 
-    let result = client.try_pause(&String::from_str(&env, "maintenance again"));
+    let result = client.try_pause(&admin, &String::from_str(&env, "maintenance again"));
     assert_eq!(result, Err(Ok(RentalError::AlreadyPaused)));
 }
 
@@ -1334,7 +1745,7 @@ fn test_unpause_when_not_paused_fails() {
     };
 
     client.initialize(&admin, &config);
-    let result = client.try_unpause();
+    let result = client.try_unpause(&admin);
     assert_eq!(result, Err(Ok(RentalError::NotPaused)));
 }
 
@@ -1356,11 +1767,11 @@ fn test_pause_unauthorized() {
             invoke: &MockAuthInvoke {
                 contract: &client.address,
                 fn_name: "pause",
-                args: (reason.clone(),).into_val(&env),
+                args: (attacker.clone(), reason.clone()).into_val(&env),
                 sub_invokes: &[],
             },
         }])
-        .pause(&reason);
+        .pause(&attacker, &reason);
 }
 
 #[test]
@@ -1378,11 +1789,11 @@ fn test_unpause_unauthorized() {
             invoke: &MockAuthInvoke {
                 contract: &client.address,
                 fn_name: "pause",
-                args: (reason.clone(),).into_val(&env),
+                args: (admin.clone(), reason.clone()).into_val(&env),
                 sub_invokes: &[],
             },
         }])
-        .pause(&reason);
+        .pause(&admin, &reason);
 
     let attacker = Address::generate(&env);
 
@@ -1392,11 +1803,11 @@ fn test_unpause_unauthorized() {
             invoke: &MockAuthInvoke {
                 contract: &client.address,
                 fn_name: "unpause",
-                args: ().into_val(&env),
+                args: (attacker.clone(),).into_val(&env),
                 sub_invokes: &[],
             },
         }])
-        .unpause();
+        .unpause(&attacker);
 }
 
 #[test]
@@ -2,7 +2,7 @@ use super::*;
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
     token::StellarAssetClient as TokenAdminClient,
-    Address, Env, String,
+    Address, Env, IntoVal, String,
 };
 
 // ─── helpers ──────────────────────────────────────────────────────────────────
@@ -41,11 +41,6 @@ fn create_agreement_helper(
     let token_admin = Address::generate(env);
     let token = create_token_mock(env, &token_admin);
 
-    // Mint tokens to the contract for distribution
-    let token_admin_client = TokenAdminClient::new(env, &token);
-    let contract_self = client.address.clone();
-    token_admin_client.mint(&contract_self, &(deposit * 2));
-
     client.create_agreement(&AgreementInput {
         agreement_id: id.clone(),
         admin: admin.clone(),
@@ -62,22 +57,54 @@ fn create_agreement_helper(
         metadata_uri: String::from_str(env, "").clone(),
         attributes: Vec::new(env).clone(),
     });
+    back_with_vault_yield(env, client, &id, user, admin, &token, deposit);
     id
 }
 
+/// Escrow `deposit` for `id` and route it to a vault whose yield never
+/// limits accruals, holding twice the deposit for distribution.
+fn back_with_vault_yield(
+    env: &Env,
+    client: &ContractClient<'_>,
+    id: &String,
+    user: &Address,
+    admin: &Address,
+    token: &Address,
+    deposit: i128,
+) {
+    let escrow = crate::tests::wire_escrow(env, client);
+    let escrow_id = escrow.create_funded(
+        id,
+        user,
+        admin,
+        &Address::generate(env),
+        &Address::generate(env),
+        &Address::generate(env),
+        &deposit,
+        token,
+    );
+    escrow.route_to_vault(&escrow_id, &Address::generate(env));
+    escrow.earn(id, &UNCAPPED_YIELD);
+    TokenAdminClient::new(env, token).mint(&escrow.address, &(deposit * 2));
+}
+
+/// Vault yield credited to every helper deposit, far above any accrual.
+const UNCAPPED_YIELD: i128 = 1_000_000_000_000;
+
 // ─── tests ────────────────────────────────────────────────────────────────────
 
 #[test]
 fn test_set_deposit_interest_config() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, 5_000);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &500, // 5 % per year
         &CompoundingFrequency::Monthly,
@@ -90,11 +117,104 @@ fn test_set_deposit_interest_config() {
     assert_eq!(cfg.compounding_frequency, CompoundingFrequency::Monthly);
 }
 
+#[test]
+fn test_config_stored_before_rate_sources_is_still_read() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let id = create_agreement_helper(
+        &env,
+        &client,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        5_000,
+    );
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &500,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Landlord,
+    );
+
+    let legacy = crate::deposit_interest::LegacyDepositInterestConfig {
+        agreement_id: id.clone(),
+        annual_rate: 500,
+        compounding_frequency: CompoundingFrequency::Monthly,
+        interest_recipient: InterestRecipient::Landlord,
+    };
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::DepositInterestConfig(id.clone()), &legacy);
+    });
+
+    let cfg = client.get_deposit_interest_config(&id);
+    assert_eq!(cfg.rate_source, InterestRateSource::Agreement);
+    assert_eq!(cfg.interest_recipient, InterestRecipient::Landlord);
+
+    client.set_deposit_rate_source(&admin, &id, &InterestRateSource::Statutory);
+    assert_eq!(
+        client.get_deposit_interest_config(&id).rate_source,
+        InterestRateSource::Statutory
+    );
+}
+
+#[test]
+fn test_rate_change_on_legacy_config_keeps_interest_earned_so_far() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (client, admin) = setup(&env);
+    let id = create_agreement_helper(
+        &env,
+        &client,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        12_000,
+    );
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &1200,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Tenant,
+    );
+
+    // A config written before rate schedules existed.
+    let legacy = crate::deposit_interest::LegacyDepositInterestConfig {
+        agreement_id: id.clone(),
+        annual_rate: 1200,
+        compounding_frequency: CompoundingFrequency::Monthly,
+        interest_recipient: InterestRecipient::Tenant,
+    };
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::DepositInterestConfig(id.clone()), &legacy);
+        storage.remove(&DataKey::DepositRateSchedule(id.clone()));
+    });
+
+    // Halfway through the month the rate doubles.
+    env.ledger().with_mut(|li| li.timestamp = 15 * 86_400);
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &2400,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Tenant,
+    );
+
+    // 15 days at 12 % and 15 days at 24 % → 60 + 120.
+    env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
+    assert_eq!(client.calculate_accrued_interest(&id), 180);
+    assert_eq!(client.accrue_interest(&id).amount, 180);
+}
+
 #[test]
 fn test_deposit_interest_initialised_with_principal() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -102,6 +222,7 @@ fn test_deposit_interest_initialised_with_principal() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1000,
         &CompoundingFrequency::Daily,
@@ -118,13 +239,14 @@ fn test_deposit_interest_initialised_with_principal() {
 fn test_calculate_accrued_interest_no_time() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, 10_000);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &500,
         &CompoundingFrequency::Monthly,
@@ -144,7 +266,7 @@ fn test_calculate_accrued_interest_after_30_days() {
     // Start at ledger timestamp 0.
     env.ledger().with_mut(|li| li.timestamp = 0);
 
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -152,6 +274,7 @@ fn test_calculate_accrued_interest_after_30_days() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1200, // 12 % per year
         &CompoundingFrequency::Monthly,
@@ -172,7 +295,7 @@ fn test_accrue_interest_persists_state() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -180,6 +303,7 @@ fn test_accrue_interest_persists_state() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1200,
         &CompoundingFrequency::Monthly,
@@ -196,7 +320,7 @@ fn test_accrue_interest_persists_state() {
     let di = client.get_deposit_interest(&id);
     assert_eq!(di.accrued_interest, 120);
     assert_eq!(di.total_with_interest, deposit + 120);
-    assert_eq!(di.accrual_history.len(), 1);
+    assert_eq!(di.history_count, 1);
 }
 
 #[test]
@@ -205,7 +329,7 @@ fn test_accrue_interest_multiple_periods() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -213,6 +337,7 @@ fn test_accrue_interest_multiple_periods() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1200,
         &CompoundingFrequency::Monthly,
@@ -228,7 +353,7 @@ fn test_accrue_interest_multiple_periods() {
     client.accrue_interest(&id);
 
     let di = client.get_deposit_interest(&id);
-    assert_eq!(di.accrual_history.len(), 2);
+    assert_eq!(di.history_count, 2);
     // total accrued ≥ 240 (compounding slightly more is fine)
     assert!(di.accrued_interest >= 240);
 }
@@ -239,12 +364,13 @@ fn test_get_accrual_history() {
     env.mock_all_auths();
     env.ledger().with_mut(|li| li.timestamp = 0);
 
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, 5_000);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &600,
         &CompoundingFrequency::Monthly,
@@ -254,7 +380,7 @@ fn test_get_accrual_history() {
     env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
     client.accrue_interest(&id);
 
-    let history = client.get_accrual_history(&id);
+    let history = client.get_accrual_history(&id, &0, &10);
     assert_eq!(history.len(), 1);
     assert!(history.get(0).unwrap().amount > 0);
 }
@@ -274,13 +400,14 @@ fn test_config_not_found_error() {
 fn test_invalid_rate_rejected() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, 5_000);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &10_001, // > 10 000 bps — invalid
         &CompoundingFrequency::Daily,
@@ -294,13 +421,14 @@ fn test_invalid_rate_rejected() {
 fn test_set_deposit_interest_config_landlord_recipient() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, 5_000);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &500,
         &CompoundingFrequency::Monthly,
@@ -315,13 +443,14 @@ fn test_set_deposit_interest_config_landlord_recipient() {
 fn test_set_deposit_interest_config_split_recipient() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, 5_000);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &500,
         &CompoundingFrequency::Monthly,
@@ -336,13 +465,14 @@ fn test_set_deposit_interest_config_split_recipient() {
 fn test_get_deposit_interest_config_all_fields() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, 5_000);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &750,
         &CompoundingFrequency::Quarterly,
@@ -361,7 +491,7 @@ fn test_calculate_accrued_interest_365_days_equals_annual_rate() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -369,6 +499,7 @@ fn test_calculate_accrued_interest_365_days_equals_annual_rate() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1000, // 10% per year
         &CompoundingFrequency::Monthly,
@@ -391,7 +522,7 @@ fn test_compound_interest_daily_exceeds_simple() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -399,6 +530,7 @@ fn test_compound_interest_daily_exceeds_simple() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1000, // 10% per year
         &CompoundingFrequency::Daily,
@@ -424,7 +556,7 @@ fn test_compound_interest_monthly() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -432,6 +564,7 @@ fn test_compound_interest_monthly() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1200, // 12% per year
         &CompoundingFrequency::Monthly,
@@ -456,7 +589,7 @@ fn test_compound_interest_quarterly() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -464,6 +597,7 @@ fn test_compound_interest_quarterly() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &800, // 8% per year
         &CompoundingFrequency::Quarterly,
@@ -487,7 +621,7 @@ fn test_compound_interest_annually() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -495,6 +629,7 @@ fn test_compound_interest_annually() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &500, // 5% per year
         &CompoundingFrequency::Annually,
@@ -518,7 +653,7 @@ fn test_distribute_interest_to_tenant() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -526,6 +661,7 @@ fn test_distribute_interest_to_tenant() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1000,
         &CompoundingFrequency::Monthly,
@@ -552,7 +688,7 @@ fn test_distribute_interest_to_landlord() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -560,6 +696,7 @@ fn test_distribute_interest_to_landlord() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1000,
         &CompoundingFrequency::Monthly,
@@ -585,7 +722,7 @@ fn test_distribute_interest_split_50_50() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -593,6 +730,7 @@ fn test_distribute_interest_split_50_50() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1000,
         &CompoundingFrequency::Monthly,
@@ -619,12 +757,13 @@ fn test_get_accrual_history_multiple_entries() {
     env.mock_all_auths();
     env.ledger().with_mut(|li| li.timestamp = 0);
 
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, 5_000);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &600,
         &CompoundingFrequency::Monthly,
@@ -638,7 +777,7 @@ fn test_get_accrual_history_multiple_entries() {
         client.accrue_interest(&id);
     }
 
-    let history = client.get_accrual_history(&id);
+    let history = client.get_accrual_history(&id, &0, &10);
     assert_eq!(history.len(), 3);
 }
 
@@ -648,7 +787,7 @@ fn test_get_deposit_interest_state_all_fields() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -656,6 +795,7 @@ fn test_get_deposit_interest_state_all_fields() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1000,
         &CompoundingFrequency::Monthly,
@@ -678,7 +818,7 @@ fn test_process_interest_accruals_batch() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     // Create multiple agreements
     let mut agreement_ids = Vec::new(&env);
@@ -691,6 +831,7 @@ fn test_process_interest_accruals_batch() {
             _ => String::from_str(&env, "AGR002"),
         };
 
+        let token = create_token_mock(&env, &Address::generate(&env));
         client.create_agreement(&AgreementInput {
             agreement_id: id.clone(),
             admin: landlord.clone(),
@@ -703,12 +844,14 @@ fn test_process_interest_accruals_batch() {
                 end_date: 1_000_000,
                 agent_commission_rate: 0,
             },
-            payment_token: Address::generate(&env),
+            payment_token: token.clone(),
             metadata_uri: String::from_str(&env, "").clone(),
             attributes: Vec::new(&env).clone(),
         });
+        back_with_vault_yield(&env, &client, &id, &tenant, &landlord, &token, 5_000);
 
         client.set_deposit_interest_config(
+            &admin,
             &id,
             &600,
             &CompoundingFrequency::Monthly,
@@ -722,10 +865,10 @@ fn test_process_interest_accruals_batch() {
     env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
 
     // Batch process all accruals
-    for i in 0..agreement_ids.len() {
-        let id = agreement_ids.get(i).unwrap();
-        client.accrue_interest(&id);
-    }
+    let batch = client.process_interest_accruals(&0, &10);
+    assert_eq!(batch.processed, agreement_ids);
+    assert_eq!(batch.next_cursor, 3);
+    assert!(batch.done);
 
     // Verify all were processed
     for i in 0..agreement_ids.len() {
@@ -735,19 +878,185 @@ fn test_process_interest_accruals_batch() {
     }
 }
 
+/// Creates `n` agreements with interest configured, ids "AGR000".."AGR00{n-1}".
+fn register_deposits(env: &Env, client: &ContractClient<'_>, n: u32) -> Vec<String> {
+    let admin = client.get_state().unwrap().admin;
+    let ids = ["AGR000", "AGR001", "AGR002", "AGR003", "AGR004"];
+    let mut out = Vec::new(env);
+    for i in 0..n {
+        let id = String::from_str(env, ids[i as usize]);
+        let landlord = Address::generate(env);
+        let tenant = Address::generate(env);
+        let token = create_token_mock(env, &Address::generate(env));
+        client.create_agreement(&AgreementInput {
+            agreement_id: id.clone(),
+            admin: landlord.clone(),
+            user: tenant.clone(),
+            agent: None,
+            terms: AgreementTerms {
+                monthly_rent: 1000,
+                security_deposit: 5_000,
+                start_date: 100,
+                end_date: 1_000_000,
+                agent_commission_rate: 0,
+            },
+            payment_token: token.clone(),
+            metadata_uri: String::from_str(env, ""),
+            attributes: Vec::new(env),
+        });
+        back_with_vault_yield(env, client, &id, &tenant, &landlord, &token, 5_000);
+        client.set_deposit_interest_config(
+            &admin,
+            &id,
+            &600,
+            &CompoundingFrequency::Monthly,
+            &InterestRecipient::Tenant,
+        );
+        out.push_back(id);
+    }
+    out
+}
+
+#[test]
+fn test_process_interest_accruals_walks_registry_with_cursor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (client, _admin) = setup(&env);
+
+    let ids = register_deposits(&env, &client, 5);
+    assert_eq!(client.get_interest_deposit_count(), 5);
+
+    env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
+
+    let first = client.process_interest_accruals(&0, &2);
+    assert_eq!(first.processed.len(), 2);
+    assert_eq!(first.next_cursor, 2);
+    assert!(!first.done);
+
+    let second = client.process_interest_accruals(&first.next_cursor, &2);
+    assert_eq!(second.processed.len(), 2);
+    assert!(!second.done);
+
+    let third = client.process_interest_accruals(&second.next_cursor, &2);
+    assert_eq!(third.processed.len(), 1);
+    assert_eq!(third.next_cursor, 5);
+    assert!(third.done);
+
+    for id in ids.iter() {
+        assert!(client.get_deposit_interest(&id).accrued_interest > 0);
+    }
+
+    // Past the end: nothing to do.
+    let empty = client.process_interest_accruals(&5, &2);
+    assert_eq!(empty.processed.len(), 0);
+    assert!(empty.done);
+}
+
+#[test]
+fn test_reconfiguring_interest_does_not_duplicate_registry_entry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let ids = register_deposits(&env, &client, 2);
+    let id = ids.get(0).unwrap();
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &900,
+        &CompoundingFrequency::Daily,
+        &InterestRecipient::Landlord,
+    );
+
+    assert_eq!(client.get_interest_deposit_count(), 2);
+    assert_eq!(client.get_interest_deposits(&0, &10), ids);
+    assert_eq!(client.get_interest_deposits(&1, &10).len(), 1);
+}
+
+#[test]
+fn test_settled_deposit_leaves_interest_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin) = setup(&env);
+    let escrow = crate::tests::wire_escrow(&env, &client);
+
+    let ids = register_deposits(&env, &client, 3);
+    escrow.settle(&ids.get(0).unwrap(), &EscrowStatus::Released);
+    client.end_deposit_interest(&ids.get(0).unwrap());
+
+    // The last deposit takes the settled one's slot.
+    assert_eq!(client.get_interest_deposit_count(), 2);
+    assert_eq!(
+        client.get_interest_deposits(&0, &10),
+        Vec::from_array(&env, [ids.get(2).unwrap(), ids.get(1).unwrap()])
+    );
+
+    escrow.settle(&ids.get(1).unwrap(), &EscrowStatus::Refunded);
+    client.end_deposit_interest(&ids.get(1).unwrap());
+    client.end_deposit_interest(&ids.get(1).unwrap());
+    assert_eq!(
+        client.get_interest_deposits(&0, &10),
+        Vec::from_array(&env, [ids.get(2).unwrap()])
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
+    let batch = client.process_interest_accruals(&0, &10);
+    assert_eq!(batch.processed, client.get_interest_deposits(&0, &10));
+    assert!(batch.done);
+}
+
+#[test]
+fn test_unsettled_deposit_stays_in_interest_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin) = setup(&env);
+    let token = create_token_mock(&env, &Address::generate(&env));
+    let ids = register_deposits(&env, &client, 2);
+    let id = ids.get(0).unwrap();
+
+    // The deposit is still held by its escrow.
+    assert_eq!(
+        client.try_end_deposit_interest(&id),
+        Err(Ok(RentalError::InvalidState))
+    );
+
+    // Releasing through the legacy path does not end accrual either.
+    client.release_escrow_with_token(&id, &token);
+    assert_eq!(client.get_interest_deposit_count(), 2);
+
+    // Nor can an unescrowed agreement be taken off.
+    let unescrowed = String::from_str(&env, "AGR-NONE");
+    assert_eq!(
+        client.try_end_deposit_interest(&unescrowed),
+        Err(Ok(RentalError::EscrowNotFound))
+    );
+}
+
+#[test]
+fn test_process_interest_accruals_rejects_zero_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin) = setup(&env);
+
+    let result = client.try_process_interest_accruals(&0, &0);
+    assert_eq!(result, Err(Ok(RentalError::InvalidInput)));
+}
+
 #[test]
 fn test_zero_interest_rate() {
     let env = Env::default();
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, 5_000);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &0, // 0% interest
         &CompoundingFrequency::Monthly,
@@ -765,7 +1074,7 @@ fn test_high_interest_rate_100_percent() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -773,6 +1082,7 @@ fn test_high_interest_rate_100_percent() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &10_000, // 100% per year
         &CompoundingFrequency::Monthly,
@@ -791,13 +1101,14 @@ fn test_very_small_principal() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, 1); // 1 unit
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1000,
         &CompoundingFrequency::Monthly,
@@ -816,7 +1127,7 @@ fn test_very_large_principal() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -824,6 +1135,7 @@ fn test_very_large_principal() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, large_deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &100, // 1% per year
         &CompoundingFrequency::Monthly,
@@ -842,7 +1154,7 @@ fn test_multiple_accruals_sum_correctly() {
     env.mock_all_auths();
 
     env.ledger().with_mut(|li| li.timestamp = 0);
-    let (client, _admin) = setup(&env);
+    let (client, admin) = setup(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -850,6 +1162,7 @@ fn test_multiple_accruals_sum_correctly() {
     let id = create_agreement_helper(&env, &client, &tenant, &landlord, deposit);
 
     client.set_deposit_interest_config(
+        &admin,
         &id,
         &1200,
         &CompoundingFrequency::Monthly,
@@ -867,7 +1180,7 @@ fn test_multiple_accruals_sum_correctly() {
     client.accrue_interest(&id);
 
     let di = client.get_deposit_interest(&id);
-    let history = client.get_accrual_history(&id);
+    let history = client.get_accrual_history(&id, &0, &10);
 
     // Total should equal sum of accruals
     let mut total_accrued = 0i128;
@@ -877,3 +1190,403 @@ fn test_multiple_accruals_sum_correctly() {
 
     assert_eq!(di.accrued_interest, total_accrued);
 }
+
+// ─── Rate schedules ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_rate_change_is_not_retroactive() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (client, admin) = setup(&env);
+
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let id = create_agreement_helper(&env, &client, &tenant, &landlord, 12_000);
+
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &1200,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Tenant,
+    );
+
+    // Halfway through the first month the rate drops to zero.
+    env.ledger().with_mut(|li| li.timestamp = 15 * 86_400);
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &0,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Tenant,
+    );
+
+    // 15 days at 12 % and 15 days at 0 % → half of the 120 a full month earns.
+    env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
+    assert_eq!(client.calculate_accrued_interest(&id), 60);
+
+    let schedule = client.get_deposit_rate_schedule(&id);
+    assert_eq!(schedule.len(), 2);
+    assert_eq!(schedule.get(1).unwrap().effective_from, 15 * 86_400);
+    assert_eq!(schedule.get(1).unwrap().annual_rate, 0);
+}
+
+#[test]
+fn test_scheduled_rate_change_applies_piecewise() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (client, admin) = setup(&env);
+
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let id = create_agreement_helper(&env, &client, &tenant, &landlord, 12_000);
+
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &1200,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Tenant,
+    );
+    client.add_deposit_rate_segment(&admin, &id, &(30 * 86_400), &2400);
+
+    // Month 1: 12_000 × 1 % = 120. Month 2: 12_120 × 2 % = 242.
+    env.ledger().with_mut(|li| li.timestamp = 60 * 86_400);
+    let accrual = client.accrue_interest(&id);
+    assert_eq!(accrual.amount, 362);
+    assert_eq!(accrual.rate, 2400);
+}
+
+#[test]
+fn test_rate_segment_before_last_accrual_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (client, admin) = setup(&env);
+
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let id = create_agreement_helper(&env, &client, &tenant, &landlord, 12_000);
+
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &1200,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Tenant,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
+    client.accrue_interest(&id);
+
+    let result = client.try_add_deposit_rate_segment(&admin, &id, &(10 * 86_400), &2400);
+    assert_eq!(result, Err(Ok(RentalError::InvalidDate)));
+
+    let result = client.try_add_deposit_rate_segment(&admin, &id, &(40 * 86_400), &10_001);
+    assert_eq!(result, Err(Ok(RentalError::InvalidAmount)));
+}
+
+#[test]
+fn test_statutory_rate_schedule_accrual() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (client, admin) = setup(&env);
+
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let id = create_agreement_helper(&env, &client, &tenant, &landlord, 12_000);
+
+    client.publish_statutory_rate(&admin, &0, &600);
+    client.publish_statutory_rate(&admin, &(30 * 86_400), &1200);
+    assert_eq!(client.get_statutory_rate_schedule().len(), 2);
+
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &5000,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Tenant,
+    );
+    client.set_deposit_rate_source(&admin, &id, &InterestRateSource::Statutory);
+    assert_eq!(
+        client.get_deposit_interest_config(&id).rate_source,
+        InterestRateSource::Statutory
+    );
+
+    // Month 1: 12_000 × 0.5 % = 60. Month 2: 12_060 × 1 % = 120.
+    env.ledger().with_mut(|li| li.timestamp = 60 * 86_400);
+    assert_eq!(client.calculate_accrued_interest(&id), 180);
+}
+
+#[test]
+fn test_statutory_rate_must_not_start_in_past_or_out_of_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let (client, admin) = setup(&env);
+
+    let result = client.try_publish_statutory_rate(&admin, &500, &600);
+    assert_eq!(result, Err(Ok(RentalError::InvalidDate)));
+
+    client.publish_statutory_rate(&admin, &2_000, &600);
+    let result = client.try_publish_statutory_rate(&admin, &2_000, &700);
+    assert_eq!(result, Err(Ok(RentalError::InvalidDate)));
+}
+
+// ─── Interest split & compounding ─────────────────────────────────────────────
+
+fn split(
+    tenant_bps: u32,
+    landlord_bps: u32,
+    platform_bps: u32,
+    tenant_compounds: bool,
+) -> InterestSplit {
+    InterestSplit {
+        tenant_bps,
+        landlord_bps,
+        platform_bps,
+        tenant_compounds,
+        landlord_compounds: false,
+        platform_compounds: false,
+    }
+}
+
+#[test]
+fn test_default_interest_split_follows_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let id = create_agreement_helper(&env, &client, &tenant, &landlord, 10_000);
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &1000,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Split,
+    );
+
+    assert_eq!(
+        client.get_interest_split(&id),
+        split(5_000, 5_000, 0, false)
+    );
+}
+
+#[test]
+fn test_custom_interest_split_pays_three_recipients() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (client, admin) = setup(&env);
+
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let id = create_agreement_helper(&env, &client, &tenant, &landlord, 12_000);
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &1200,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Tenant,
+    );
+    client.set_interest_split(&admin, &id, &split(6_000, 3_000, 1_000, false));
+
+    env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
+    assert_eq!(client.accrue_interest(&id).amount, 120);
+    client.distribute_interest(&id);
+
+    let token =
+        soroban_sdk::token::Client::new(&env, &client.get_agreement(&id).unwrap().payment_token);
+    let platform = client.get_state().unwrap().config.fee_collector;
+    assert_eq!(token.balance(&tenant), 72);
+    assert_eq!(token.balance(&landlord), 36);
+    assert_eq!(token.balance(&platform), 12);
+    assert_eq!(client.get_deposit_interest(&id).principal, 12_000);
+}
+
+#[test]
+fn test_interest_split_must_total_10000_bps() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let id = create_agreement_helper(&env, &client, &tenant, &landlord, 10_000);
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &1000,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Tenant,
+    );
+
+    let result = client.try_set_interest_split(&admin, &id, &split(6_000, 3_000, 500, false));
+    assert_eq!(result, Err(Ok(RentalError::InvalidConfig)));
+}
+
+#[test]
+fn test_compounding_share_grows_principal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (client, admin) = setup(&env);
+
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let id = create_agreement_helper(&env, &client, &tenant, &landlord, 12_000);
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &1200,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Tenant,
+    );
+    // Tenant compounds half; landlord is paid the other half.
+    client.set_interest_split(&admin, &id, &split(5_000, 5_000, 0, true));
+
+    env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
+    client.accrue_interest(&id);
+    client.distribute_interest(&id);
+
+    let token =
+        soroban_sdk::token::Client::new(&env, &client.get_agreement(&id).unwrap().payment_token);
+    assert_eq!(token.balance(&tenant), 0);
+    assert_eq!(token.balance(&landlord), 60);
+
+    let di = client.get_deposit_interest(&id);
+    assert_eq!(di.principal, 12_060);
+    assert_eq!(di.total_with_interest, 12_060);
+    assert_eq!(di.accrued_interest, 0);
+    assert_eq!(di.history_count, 2);
+    let last = client.get_accrual_history(&id, &1, &1).get(0).unwrap();
+    assert_eq!(last.compounded, 60);
+    assert_eq!(last.balance, 12_060);
+
+    // The next month accrues on the compounded principal.
+    env.ledger().with_mut(|li| li.timestamp = 60 * 86_400);
+    assert_eq!(client.accrue_interest(&id).amount, 120);
+}
+
+// ─── Bucketed history ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_accrual_history_spans_buckets_and_pages() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (client, admin) = setup(&env);
+
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let id = create_agreement_helper(&env, &client, &tenant, &landlord, 100_000);
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &1000,
+        &CompoundingFrequency::Daily,
+        &InterestRecipient::Tenant,
+    );
+
+    // 70 daily accruals fill two full buckets and part of a third.
+    for day in 1..=70u64 {
+        env.ledger().with_mut(|li| li.timestamp = day * 86_400);
+        client.accrue_interest(&id);
+    }
+    assert_eq!(client.get_deposit_interest(&id).history_count, 70);
+
+    // A page straddling a bucket boundary comes back in order.
+    let page = client.get_accrual_history(&id, &30, &5);
+    assert_eq!(page.len(), 5);
+    for (offset, entry) in page.iter().enumerate() {
+        assert_eq!(entry.accrued_at, (31 + offset as u64) * 86_400);
+    }
+
+    // Walking the whole history with a cursor yields every snapshot once.
+    let mut cursor = 0;
+    let mut seen = 0;
+    loop {
+        let page = client.get_accrual_history(&id, &cursor, &25);
+        if page.is_empty() {
+            break;
+        }
+        seen += page.len();
+        cursor += page.len();
+    }
+    assert_eq!(seen, 70);
+
+    // Pages are capped.
+    assert_eq!(client.get_accrual_history(&id, &0, &1_000).len(), 70);
+    assert_eq!(client.get_accrual_history(&id, &70, &10).len(), 0);
+}
+
+#[test]
+fn test_deposit_stored_with_inline_history_moves_it_into_buckets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (client, admin) = setup(&env);
+    let id = create_agreement_helper(
+        &env,
+        &client,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        100_000,
+    );
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &1000,
+        &CompoundingFrequency::Daily,
+        &InterestRecipient::Tenant,
+    );
+    for day in 1..=2u64 {
+        env.ledger().with_mut(|li| li.timestamp = day * 86_400);
+        client.accrue_interest(&id);
+    }
+    let current = client.get_deposit_interest(&id);
+    let history = client.get_accrual_history(&id, &0, &10);
+
+    // Store the deposit the way it was stored before history buckets.
+    // The first snapshot predates compounding being recorded.
+    let first = history.get(0).unwrap();
+    let mut accrual_history = Vec::new(&env);
+    accrual_history.push_back(
+        crate::deposit_interest::LegacyInterestAccrual {
+            accrued_at: first.accrued_at,
+            amount: first.amount,
+            rate: first.rate,
+            balance: first.balance,
+        }
+        .into_val(&env),
+    );
+    accrual_history.push_back(history.get(1).unwrap().into_val(&env));
+    let legacy = crate::deposit_interest::LegacyDepositInterest {
+        escrow_id: id.clone(),
+        principal: current.principal,
+        accrued_interest: current.accrued_interest,
+        total_with_interest: current.total_with_interest,
+        last_accrual_date: current.last_accrual_date,
+        accrual_history,
+    };
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AccrualBucket(id.clone(), 0));
+        env.storage()
+            .persistent()
+            .set(&DataKey::DepositInterest(id.clone()), &legacy);
+    });
+
+    assert_eq!(client.get_deposit_interest(&id), current);
+    assert_eq!(client.get_accrual_history(&id, &0, &10), history);
+
+    env.ledger().with_mut(|li| li.timestamp = 3 * 86_400);
+    client.accrue_interest(&id);
+    assert_eq!(client.get_deposit_interest(&id).history_count, 3);
+}
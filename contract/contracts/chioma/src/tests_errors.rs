@@ -1,7 +1,8 @@
 use super::*;
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
-    Address, Env, String,
+    xdr::ToXdr,
+    Address, Env, String, Symbol, Vec,
 };
 
 fn create_contract(env: &Env) -> ContractClient<'_> {
@@ -9,7 +10,7 @@ fn create_contract(env: &Env) -> ContractClient<'_> {
     ContractClient::new(env, &contract_id)
 }
 
-fn setup(env: &Env) -> ContractClient<'_> {
+fn setup(env: &Env) -> (ContractClient<'_>, Address) {
     let client = create_contract(env);
     let admin = Address::generate(env);
     let config = Config {
@@ -19,7 +20,49 @@ fn setup(env: &Env) -> ContractClient<'_> {
     };
     client.initialize(&admin, &config);
     env.ledger().with_mut(|li| li.timestamp = 100);
-    client
+    (client, admin)
+}
+
+fn agreement_input(env: &Env, id: &str) -> AgreementInput {
+    AgreementInput {
+        agreement_id: String::from_str(env, id),
+        admin: Address::generate(env),
+        user: Address::generate(env),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1000,
+            security_deposit: 2000,
+            start_date: 100,
+            end_date: 1000,
+            agent_commission_rate: 0,
+        },
+        payment_token: Address::generate(env),
+        metadata_uri: String::from_str(env, ""),
+        attributes: Vec::new(env),
+    }
+}
+
+fn no_filter() -> AuditFilter {
+    AuditFilter {
+        agreement_id: None,
+        actor: None,
+        from: None,
+        to: None,
+    }
+}
+
+fn set_capacity(env: &Env, client: &ContractClient<'_>, capacity: u32) {
+    let signer = Address::generate(env);
+    let mut signers = Vec::new(env);
+    signers.push_back(signer.clone());
+    client.initialize_multisig(&signers, &1);
+    let id = client.propose_action(
+        &signer,
+        &ActionType::SetAuditLogCapacity,
+        &None,
+        &capacity.to_xdr(env),
+    );
+    client.execute_action(&signer, &id);
 }
 
 #[test]
@@ -47,424 +90,195 @@ fn test_error_codes_and_messages() {
 }
 
 #[test]
-fn test_log_and_get_errors() {
+fn test_operations_are_recorded_in_audit_log() {
     let env = Env::default();
     env.mock_all_auths();
-    let client = setup(&env);
-
-    let op = String::from_str(&env, "create_agreement");
-    let details = String::from_str(&env, "Missing ID");
-
-    client.log_error(&RentalError::AgreementNotFound, &op, &details);
-
-    let logs = client.get_error_logs(&10);
-    assert_eq!(logs.len(), 1);
-
-    let log = logs.get(0).unwrap();
-    assert_eq!(log.error_code, 13);
-    assert_eq!(log.operation, op);
-    assert_eq!(log.details, details);
-    assert!(log.timestamp > 0);
+    let (client, admin) = setup(&env);
+
+    let input = agreement_input(&env, "ag1");
+    client.create_agreement(&input);
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.submit_agreement(&input.admin, &input.agreement_id);
+
+    let page = client.get_audit_log(&no_filter(), &0, &10);
+    assert_eq!(page.entries.len(), 2);
+    assert_eq!(page.next_cursor, 2);
+    assert!(page.done);
+
+    let created = page.entries.get(0).unwrap();
+    assert_eq!(created.seq, 0);
+    assert_eq!(created.actor, input.user);
+    assert_eq!(created.operation, Symbol::new(&env, "create_agreement"));
+    assert_eq!(created.agreement_id, Some(input.agreement_id.clone()));
+    assert_eq!(created.error_code, 0);
+    assert_eq!(created.timestamp, 100);
+
+    let submitted = page.entries.get(1).unwrap();
+    assert_eq!(submitted.seq, 1);
+    assert_eq!(submitted.actor, input.admin);
+    assert_eq!(submitted.operation, Symbol::new(&env, "submit_agreement"));
+    assert_eq!(submitted.timestamp, 200);
+
+    client.pause(&admin, &String::from_str(&env, "maintenance"));
+    let page = client.get_audit_log(&no_filter(), &2, &10);
+    let paused = page.entries.get(0).unwrap();
+    assert_eq!(paused.actor, admin);
+    assert_eq!(paused.operation, Symbol::new(&env, "pause"));
+    assert_eq!(paused.agreement_id, None);
+    assert_eq!(client.get_audit_log_count(), 3);
 }
 
 #[test]
-fn test_multiple_logs_limit() {
+fn test_failed_call_leaves_no_audit_entry() {
     let env = Env::default();
     env.mock_all_auths();
-    let client = setup(&env);
+    let (client, _) = setup(&env);
 
-    let op = String::from_str(&env, "op");
-    let details = String::from_str(&env, "details");
+    let input = agreement_input(&env, "ag1");
+    client.create_agreement(&input);
+    assert!(client.try_create_agreement(&input).is_err());
 
-    for _i in 0..15 {
-        client.log_error(&RentalError::InternalError, &op, &details);
-    }
-
-    // Test limit
-    let logs_limit_5 = client.get_error_logs(&5);
-    assert_eq!(logs_limit_5.len(), 5);
-
-    let logs_limit_20 = client.get_error_logs(&20);
-    assert_eq!(logs_limit_20.len(), 15);
+    assert_eq!(client.get_audit_log_count(), 1);
 }
 
 #[test]
-fn test_error_log_context_fields() {
+fn test_audit_log_filters() {
     let env = Env::default();
     env.mock_all_auths();
-    let client = setup(&env);
-
-    let op = String::from_str(&env, "create_agreement");
-    let details = String::from_str(&env, "Agreement ID not found in storage");
-
-    client.log_error(&RentalError::AgreementNotFound, &op, &details);
-
-    let logs = client.get_error_logs(&10);
-    assert_eq!(logs.len(), 1);
-
-    let log = logs.get(0).unwrap();
-    assert_eq!(log.error_code, 13);
+    let (client, _) = setup(&env);
+
+    let first = agreement_input(&env, "ag1");
+    let second = agreement_input(&env, "ag2");
+    client.create_agreement(&first);
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.create_agreement(&second);
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    client.submit_agreement(&first.admin, &first.agreement_id);
+
+    let by_agreement = AuditFilter {
+        agreement_id: Some(first.agreement_id.clone()),
+        ..no_filter()
+    };
+    let page = client.get_audit_log(&by_agreement, &0, &10);
+    assert_eq!(page.entries.len(), 2);
+    assert_eq!(page.entries.get(0).unwrap().seq, 0);
+    assert_eq!(page.entries.get(1).unwrap().seq, 2);
+
+    let by_actor = AuditFilter {
+        actor: Some(second.user.clone()),
+        ..no_filter()
+    };
+    let page = client.get_audit_log(&by_actor, &0, &10);
+    assert_eq!(page.entries.len(), 1);
     assert_eq!(
-        log.error_message,
-        String::from_str(&env, "Agreement not found. Please check the ID.")
+        page.entries.get(0).unwrap().agreement_id,
+        Some(second.agreement_id)
     );
-    assert_eq!(log.details, details);
-    assert_eq!(log.operation, op);
-    assert!(log.timestamp > 0);
-}
-
-#[test]
-fn test_error_log_timestamp() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let client = setup(&env);
-
-    env.ledger().with_mut(|li| li.timestamp = 99999);
 
-    let op = String::from_str(&env, "test_op");
-    let details = String::from_str(&env, "test details");
-    client.log_error(&RentalError::InternalError, &op, &details);
-
-    let logs = client.get_error_logs(&10);
-    let log = logs.get(0).unwrap();
-    assert_eq!(log.timestamp, 99999);
+    let by_time = AuditFilter {
+        from: Some(150),
+        to: Some(250),
+        ..no_filter()
+    };
+    let page = client.get_audit_log(&by_time, &0, &10);
+    assert_eq!(page.entries.len(), 1);
+    assert_eq!(page.entries.get(0).unwrap().seq, 1);
 }
 
 #[test]
-fn test_error_log_operation_name() {
+fn test_audit_log_pages_with_cursor() {
     let env = Env::default();
     env.mock_all_auths();
-    let client = setup(&env);
-
-    let details = String::from_str(&env, "details");
-
-    client.log_error(
-        &RentalError::AgreementNotFound,
-        &String::from_str(&env, "create_agreement"),
-        &details,
-    );
-    client.log_error(
-        &RentalError::PaymentFailed,
-        &String::from_str(&env, "make_payment"),
-        &details,
-    );
+    let (client, _) = setup(&env);
 
-    let logs = client.get_error_logs(&10);
-    assert_eq!(logs.len(), 2);
-    assert_eq!(
-        logs.get(0).unwrap().operation,
-        String::from_str(&env, "create_agreement")
-    );
-    assert_eq!(
-        logs.get(1).unwrap().operation,
-        String::from_str(&env, "make_payment")
-    );
-}
+    for id in ["ag1", "ag2", "ag3", "ag4", "ag5"] {
+        client.create_agreement(&agreement_input(&env, id));
+    }
 
-#[test]
-fn test_error_log_details_completeness() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let client = setup(&env);
+    let first = client.get_audit_log(&no_filter(), &0, &2);
+    assert_eq!(first.entries.len(), 2);
+    assert_eq!(first.next_cursor, 2);
+    assert!(!first.done);
 
-    let long_details = String::from_str(
-        &env,
-        "This is a very long error detail string that contains extensive information about what went wrong during the operation including context about the agreement state and the specific validation that failed during processing of the request",
-    );
-    let op = String::from_str(&env, "validate");
+    let second = client.get_audit_log(&no_filter(), &first.next_cursor, &2);
+    assert_eq!(second.entries.get(0).unwrap().seq, 2);
+    assert_eq!(second.next_cursor, 4);
 
-    client.log_error(&RentalError::InvalidInput, &op, &long_details);
+    let last = client.get_audit_log(&no_filter(), &second.next_cursor, &2);
+    assert_eq!(last.entries.len(), 1);
+    assert_eq!(last.next_cursor, 5);
+    assert!(last.done);
 
-    let logs = client.get_error_logs(&10);
-    let log = logs.get(0).unwrap();
-    assert_eq!(log.details, long_details);
+    let past_end = client.get_audit_log(&no_filter(), &10, &2);
+    assert_eq!(past_end.entries.len(), 0);
+    assert_eq!(past_end.next_cursor, 10);
+    assert!(past_end.done);
 }
 
 #[test]
-fn test_error_log_persistence() {
+fn test_audit_log_zero_limit_is_rejected() {
     let env = Env::default();
     env.mock_all_auths();
-    let client = setup(&env);
+    let (client, _) = setup(&env);
 
-    let op = String::from_str(&env, "create_agreement");
-    let details = String::from_str(&env, "persistence test");
-
-    client.log_error(&RentalError::AgreementNotFound, &op, &details);
-
-    // Perform unrelated read operation
-    let _config = client.get_rate_limit_config();
-
-    // Verify error log persisted
-    let logs = client.get_error_logs(&10);
-    assert_eq!(logs.len(), 1);
-    assert_eq!(logs.get(0).unwrap().operation, op);
+    let result = client.try_get_audit_log(&no_filter(), &0, &0);
+    assert_eq!(result, Err(Ok(RentalError::InvalidInput)));
 }
 
 #[test]
-fn test_error_log_various_types() {
+fn test_audit_log_empty_history() {
     let env = Env::default();
     env.mock_all_auths();
-    let client = setup(&env);
-
-    let details = String::from_str(&env, "test");
+    let (client, _) = setup(&env);
 
-    client.log_error(
-        &RentalError::AgreementNotFound,
-        &String::from_str(&env, "core"),
-        &details,
-    );
-    client.log_error(
-        &RentalError::PaymentInsufficientFunds,
-        &String::from_str(&env, "payment"),
-        &details,
-    );
-    client.log_error(
-        &RentalError::EscrowNotFound,
-        &String::from_str(&env, "escrow"),
-        &details,
-    );
-    client.log_error(
-        &RentalError::RateLimitExceeded,
-        &String::from_str(&env, "rate_limit"),
-        &details,
-    );
-
-    let logs = client.get_error_logs(&10);
-    assert_eq!(logs.len(), 4);
-    assert_eq!(logs.get(0).unwrap().error_code, 13);
-    assert_eq!(logs.get(1).unwrap().error_code, 201);
-    assert_eq!(logs.get(2).unwrap().error_code, 401);
-    assert_eq!(logs.get(3).unwrap().error_code, 801);
+    let page = client.get_audit_log(&no_filter(), &0, &10);
+    assert_eq!(page.entries.len(), 0);
+    assert!(page.done);
+    assert_eq!(client.get_audit_log_capacity(), 1_000);
 }
 
 #[test]
-fn test_error_log_ordering() {
+fn test_audit_log_ring_overwrites_oldest_entries() {
     let env = Env::default();
     env.mock_all_auths();
-    let client = setup(&env);
-
-    let details = String::from_str(&env, "d");
-
-    client.log_error(
-        &RentalError::InternalError,
-        &String::from_str(&env, "op1"),
-        &details,
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &String::from_str(&env, "op2"),
-        &details,
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &String::from_str(&env, "op3"),
-        &details,
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &String::from_str(&env, "op4"),
-        &details,
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &String::from_str(&env, "op5"),
-        &details,
-    );
+    let (client, _) = setup(&env);
+    set_capacity(&env, &client, 3);
+    assert_eq!(client.get_audit_log_capacity(), 3);
 
-    let logs = client.get_error_logs(&10);
-    assert_eq!(logs.len(), 5);
-    assert_eq!(
-        logs.get(0).unwrap().operation,
-        String::from_str(&env, "op1")
-    );
-    assert_eq!(
-        logs.get(1).unwrap().operation,
-        String::from_str(&env, "op2")
-    );
-    assert_eq!(
-        logs.get(2).unwrap().operation,
-        String::from_str(&env, "op3")
-    );
-    assert_eq!(
-        logs.get(3).unwrap().operation,
-        String::from_str(&env, "op4")
-    );
-    assert_eq!(
-        logs.get(4).unwrap().operation,
-        String::from_str(&env, "op5")
-    );
-}
-
-#[test]
-fn test_error_log_limit_returns_most_recent() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let client = setup(&env);
-
-    let op = String::from_str(&env, "op");
-
-    // Log 15 errors with distinguishable details
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_0"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_1"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_2"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_3"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_4"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_5"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_6"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_7"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_8"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_9"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_10"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_11"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_12"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_13"),
-    );
-    client.log_error(
-        &RentalError::InternalError,
-        &op,
-        &String::from_str(&env, "error_14"),
-    );
+    for id in ["ag1", "ag2", "ag3", "ag4", "ag5"] {
+        client.create_agreement(&agreement_input(&env, id));
+    }
 
-    let logs = client.get_error_logs(&5);
-    assert_eq!(logs.len(), 5);
-    // Should return the 5 most recent (indices 10-14)
-    assert_eq!(
-        logs.get(0).unwrap().details,
-        String::from_str(&env, "error_10")
-    );
+    assert_eq!(client.get_audit_log_count(), 5);
+    let page = client.get_audit_log(&no_filter(), &0, &10);
+    assert_eq!(page.entries.len(), 3);
+    assert_eq!(page.entries.get(0).unwrap().seq, 2);
     assert_eq!(
-        logs.get(1).unwrap().details,
-        String::from_str(&env, "error_11")
+        page.entries.get(0).unwrap().agreement_id,
+        Some(String::from_str(&env, "ag3"))
     );
-    assert_eq!(
-        logs.get(2).unwrap().details,
-        String::from_str(&env, "error_12")
-    );
-    assert_eq!(
-        logs.get(3).unwrap().details,
-        String::from_str(&env, "error_13")
-    );
-    assert_eq!(
-        logs.get(4).unwrap().details,
-        String::from_str(&env, "error_14")
-    );
-}
-
-#[test]
-fn test_get_error_logs_empty_history_returns_empty_vec() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let client = setup(&env);
-
-    let logs = client.get_error_logs(&10);
-    assert_eq!(logs.len(), 0);
+    assert_eq!(page.entries.get(2).unwrap().seq, 4);
+    assert!(page.done);
 }
 
 #[test]
-fn test_get_error_logs_zero_limit_returns_empty_vec() {
+fn test_audit_log_capacity_bounds() {
     let env = Env::default();
     env.mock_all_auths();
-    let client = setup(&env);
-
-    client.log_error(
-        &RentalError::InternalError,
-        &String::from_str(&env, "op"),
-        &String::from_str(&env, "details"),
-    );
-
-    let logs = client.get_error_logs(&0);
-    assert_eq!(logs.len(), 0);
-}
-
-#[test]
-fn test_get_error_logs_limit_above_count_returns_all_logs() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let client = setup(&env);
-
-    client.log_error(
-        &RentalError::AgreementNotFound,
-        &String::from_str(&env, "op_a"),
-        &String::from_str(&env, "a"),
-    );
-    client.log_error(
-        &RentalError::PaymentFailed,
-        &String::from_str(&env, "op_b"),
-        &String::from_str(&env, "b"),
-    );
-    client.log_error(
-        &RentalError::EscrowNotFound,
-        &String::from_str(&env, "op_c"),
-        &String::from_str(&env, "c"),
-    );
-
-    let logs = client.get_error_logs(&100);
-    assert_eq!(logs.len(), 3);
-    assert_eq!(
-        logs.get(0).unwrap().operation,
-        String::from_str(&env, "op_a")
-    );
-    assert_eq!(
-        logs.get(1).unwrap().operation,
-        String::from_str(&env, "op_b")
-    );
-    assert_eq!(
-        logs.get(2).unwrap().operation,
-        String::from_str(&env, "op_c")
-    );
+    let (client, _) = setup(&env);
+
+    let signer = Address::generate(&env);
+    let mut signers = Vec::new(&env);
+    signers.push_back(signer.clone());
+    client.initialize_multisig(&signers, &1);
+
+    for capacity in [0u32, 10_001] {
+        let result = client.try_propose_action(
+            &signer,
+            &ActionType::SetAuditLogCapacity,
+            &None,
+            &capacity.to_xdr(&env),
+        );
+        assert_eq!(result, Err(Ok(RentalError::InvalidConfig)));
+    }
 }
@@ -0,0 +1,103 @@
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String,
+};
+
+fn setup(env: &Env) -> (ContractClient<'_>, Address) {
+    env.mock_all_auths();
+    let client = ContractClient::new(env, &env.register(Contract, ()));
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(env),
+            paused: false,
+        },
+    );
+    (client, admin)
+}
+
+fn metrics(env: &Env, operation: &str, cpu_instructions: u64) -> GasMetrics {
+    GasMetrics {
+        operation: String::from_str(env, operation),
+        cpu_instructions,
+        memory_bytes: 40_000,
+        read_entries: 4,
+        write_entries: 2,
+        read_bytes: 0,
+        write_bytes: 900,
+        recorded_at: 0,
+    }
+}
+
+#[test]
+fn test_estimates_come_from_recorded_metrics() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    assert_eq!(
+        client.try_estimate_gas_cost(&OperationType::MakePayment),
+        Err(Ok(RentalError::InvalidState))
+    );
+    assert_eq!(client.get_gas_metrics().len(), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    client.record_gas_metrics(
+        &admin,
+        &vec![
+            &env,
+            metrics(&env, "make_payment_with_token", 812_345),
+            metrics(&env, "get_agreement", 61_000),
+        ],
+    );
+
+    assert_eq!(
+        client.estimate_gas_cost(&OperationType::MakePayment),
+        812_345
+    );
+    let recorded = client
+        .get_operation_metrics(&String::from_str(&env, "get_agreement"))
+        .unwrap();
+    assert_eq!(recorded.cpu_instructions, 61_000);
+    assert_eq!(recorded.recorded_at, 5_000);
+
+    // Only tracked operation types are listed.
+    let tracked = client.get_gas_metrics();
+    assert_eq!(tracked.len(), 1);
+    assert_eq!(
+        tracked.get(0).unwrap().operation,
+        String::from_str(&env, "make_payment_with_token")
+    );
+
+    // A later run replaces the earlier figures.
+    client.record_gas_metrics(
+        &admin,
+        &vec![&env, metrics(&env, "make_payment_with_token", 790_000)],
+    );
+    assert_eq!(
+        client.estimate_gas_cost(&OperationType::MakePayment),
+        790_000
+    );
+}
+
+#[test]
+fn test_record_gas_metrics_requires_upgrader_and_valid_batch() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_record_gas_metrics(&outsider, &vec![&env, metrics(&env, "pause", 1)]),
+        Err(Ok(RentalError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_record_gas_metrics(&admin, &Vec::new(&env)),
+        Err(Ok(RentalError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_record_gas_metrics(&admin, &vec![&env, metrics(&env, "", 1)]),
+        Err(Ok(RentalError::InvalidInput))
+    );
+}
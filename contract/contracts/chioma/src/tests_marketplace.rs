@@ -0,0 +1,241 @@
+use super::*;
+use crate::tests::{wire_escrow, MockEscrowClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient as TokenAdminClient},
+    Address, Env, String,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup<'a> {
+    client: ContractClient<'a>,
+    escrow: MockEscrowClient<'a>,
+    token: Address,
+    landlord: Address,
+    id: String,
+}
+
+fn setup(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let client = ContractClient::new(env, &env.register(Contract, ()));
+    client.initialize(
+        &Address::generate(env),
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(env),
+            paused: false,
+        },
+    );
+    let escrow = wire_escrow(env, &client);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    let tenant = Address::generate(env);
+    let landlord = Address::generate(env);
+    let id = String::from_str(env, "AGR001");
+    client.create_agreement(&AgreementInput {
+        agreement_id: id.clone(),
+        admin: landlord.clone(),
+        user: tenant.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1000,
+            security_deposit: 2000,
+            start_date: 100,
+            end_date: 10_000_000,
+            agent_commission_rate: 0,
+        },
+        payment_token: token.clone(),
+        metadata_uri: String::from_str(env, ""),
+        attributes: Vec::new(env),
+    });
+    client.submit_agreement(&landlord, &id);
+    client.sign_agreement(&tenant, &id);
+    client.approve_agreement(&landlord, &id);
+
+    Setup {
+        client,
+        escrow,
+        token,
+        landlord,
+        id,
+    }
+}
+
+fn funded_buyer(env: &Env, s: &Setup, amount: i128) -> Address {
+    let buyer = Address::generate(env);
+    TokenAdminClient::new(env, &s.token).mint(&buyer, &amount);
+    buyer
+}
+
+#[test]
+fn test_list_position_and_buy_at_ask() {
+    let env = Env::default();
+    let s = setup(&env);
+    let token = TokenClient::new(&env, &s.token);
+    let royalty_recipient = Address::generate(&env);
+    s.client.set_royalty(&s.id, &500, &royalty_recipient);
+
+    let listing = s.client.list_position(&s.id, &10_000, &(7 * DAY));
+    assert_eq!(listing.seller, s.landlord);
+    assert_eq!(listing.expires_at, 7 * DAY);
+
+    let buyer = funded_buyer(&env, &s, 10_000);
+    let payment = s.client.buy_position(&s.id, &buyer);
+
+    assert_eq!(payment.royalty_amount, 500);
+    assert_eq!(token.balance(&royalty_recipient), 500);
+    assert_eq!(token.balance(&s.landlord), 9_500);
+    assert_eq!(s.client.get_agreement(&s.id).unwrap().admin, buyer);
+    assert_eq!(s.client.get_position_listing(&s.id), None);
+    // The escrowed deposit follows the landlord position.
+    assert_eq!(s.escrow.funded(&s.id).unwrap().beneficiary, buyer);
+}
+
+#[test]
+fn test_accept_bid_settles_from_escrow() {
+    let env = Env::default();
+    let s = setup(&env);
+    let token = TokenClient::new(&env, &s.token);
+    s.client.list_position(&s.id, &10_000, &(7 * DAY));
+
+    let low = funded_buyer(&env, &s, 8_000);
+    let high = funded_buyer(&env, &s, 9_000);
+    let low_bid = s.client.place_bid(&s.id, &low, &8_000);
+    let high_bid = s.client.place_bid(&s.id, &high, &9_000);
+    assert_eq!(token.balance(&s.client.address), 17_000);
+
+    // No royalty configured: the seller receives the full bid.
+    let payment = s.client.accept_bid(&s.id, &high_bid);
+    assert_eq!(payment.royalty_amount, 0);
+    assert_eq!(token.balance(&s.landlord), 9_000);
+    assert_eq!(s.client.get_agreement(&s.id).unwrap().admin, high);
+    assert_eq!(s.escrow.funded(&s.id).unwrap().beneficiary, high);
+    assert_eq!(
+        s.client.get_position_bid(&s.id, &high_bid).status,
+        BidStatus::Accepted
+    );
+
+    // The losing bidder gets a refund.
+    s.client.withdraw_bid(&s.id, &low_bid);
+    assert_eq!(token.balance(&low), 8_000);
+    assert_eq!(token.balance(&s.client.address), 0);
+    assert_eq!(s.client.get_position_bid_count(&s.id), 2);
+}
+
+#[test]
+fn test_position_of_an_ended_lease_cannot_be_sold() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client.list_position(&s.id, &10_000, &(7 * DAY));
+    let bidder = funded_buyer(&env, &s, 9_000);
+    let bid = s.client.place_bid(&s.id, &bidder, &9_000);
+
+    // The lease ends while the listing is still open.
+    env.as_contract(&s.client.address, || {
+        let mut agreement = crate::agreement::load_agreement(&env, &s.id).unwrap();
+        agreement.status = AgreementStatus::Terminated;
+        crate::agreement::save_agreement(&env, &s.id, &agreement);
+    });
+
+    let buyer = funded_buyer(&env, &s, 10_000);
+    assert_eq!(
+        s.client.try_buy_position(&s.id, &buyer),
+        Err(Ok(RentalError::AgreementNotActive))
+    );
+    assert_eq!(
+        s.client.try_accept_bid(&s.id, &bid),
+        Err(Ok(RentalError::AgreementNotActive))
+    );
+    assert_eq!(s.client.get_agreement(&s.id).unwrap().admin, s.landlord);
+
+    // The bidder can still take the bid back.
+    s.client.withdraw_bid(&s.id, &bid);
+    assert_eq!(TokenClient::new(&env, &s.token).balance(&bidder), 9_000);
+}
+
+#[test]
+fn test_listing_expires() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client.list_position(&s.id, &10_000, &DAY);
+    let bidder = funded_buyer(&env, &s, 5_000);
+    let bid_id = s.client.place_bid(&s.id, &bidder, &5_000);
+
+    env.ledger().with_mut(|li| li.timestamp = DAY);
+    assert_eq!(s.client.get_position_listing(&s.id), None);
+    assert_eq!(
+        s.client.try_accept_bid(&s.id, &bid_id),
+        Err(Ok(RentalError::Expired))
+    );
+    let buyer = funded_buyer(&env, &s, 10_000);
+    assert_eq!(
+        s.client.try_buy_position(&s.id, &buyer),
+        Err(Ok(RentalError::Expired))
+    );
+
+    // Escrowed funds stay refundable, and the position can be relisted.
+    s.client.withdraw_bid(&s.id, &bid_id);
+    s.client.list_position(&s.id, &12_000, &DAY);
+}
+
+#[test]
+fn test_bids_from_a_previous_listing_cannot_be_accepted() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client.list_position(&s.id, &10_000, &DAY);
+    let bidder = funded_buyer(&env, &s, 5_000);
+    let bid_id = s.client.place_bid(&s.id, &bidder, &5_000);
+
+    s.client.cancel_listing(&s.id);
+    s.client.list_position(&s.id, &10_000, &DAY);
+
+    assert_eq!(
+        s.client.try_accept_bid(&s.id, &bid_id),
+        Err(Ok(RentalError::InvalidState))
+    );
+}
+
+#[test]
+fn test_listing_validation() {
+    let env = Env::default();
+    let s = setup(&env);
+
+    assert_eq!(
+        s.client.try_list_position(&s.id, &0, &DAY),
+        Err(Ok(RentalError::InvalidAmount))
+    );
+    assert_eq!(
+        s.client.try_list_position(&s.id, &10_000, &(91 * DAY)),
+        Err(Ok(RentalError::InvalidInput))
+    );
+    s.client.list_position(&s.id, &10_000, &DAY);
+    assert_eq!(
+        s.client.try_list_position(&s.id, &10_000, &DAY),
+        Err(Ok(RentalError::InvalidState))
+    );
+    assert_eq!(
+        s.client.try_place_bid(&s.id, &s.landlord, &1_000),
+        Err(Ok(RentalError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_escrow_release_leaves_open_bids_untouched() {
+    let env = Env::default();
+    let s = setup(&env);
+    let token = TokenClient::new(&env, &s.token);
+    TokenAdminClient::new(&env, &s.token).mint(&s.client.address, &2_000);
+
+    s.client.list_position(&s.id, &10_000, &DAY);
+    let bidder = funded_buyer(&env, &s, 5_000);
+    s.client.place_bid(&s.id, &bidder, &5_000);
+
+    s.client.release_escrow_with_token(&s.id, &s.token);
+    assert_eq!(token.balance(&s.landlord), 2_000);
+    assert_eq!(token.balance(&s.client.address), 5_000);
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::tests::wire_escrow;
 use soroban_sdk::{testutils::Address as _, Address, Env, String};
 
 fn create_contract(env: &Env) -> ContractClient<'_> {
@@ -26,7 +27,7 @@ fn test_add_supported_token() {
     let token_addr = Address::generate(&env);
     let symbol = String::from_str(&env, "USDC");
 
-    client.add_supported_token(&token_addr, &symbol, &6, &1, &1000000);
+    client.add_supported_token(&admin, &token_addr, &symbol, &6, &1, &1000000);
 
     assert!(client.is_token_supported(&token_addr));
 
@@ -46,10 +47,10 @@ fn test_remove_supported_token() {
     let token_addr = Address::generate(&env);
     let symbol = String::from_str(&env, "USDC");
 
-    client.add_supported_token(&token_addr, &symbol, &6, &1, &1000000);
+    client.add_supported_token(&admin, &token_addr, &symbol, &6, &1, &1000000);
     assert!(client.is_token_supported(&token_addr));
 
-    client.remove_supported_token(&token_addr);
+    client.remove_supported_token(&admin, &token_addr);
     assert!(!client.is_token_supported(&token_addr));
 }
 
@@ -65,7 +66,7 @@ fn test_exchange_rates() {
     let token2 = Address::generate(&env);
     let rate = 1_500_000_000_000_000_000; // 1.5
 
-    client.set_exchange_rate(&token1, &token2, &rate);
+    client.set_exchange_rate(&admin, &token1, &token2, &rate);
 
     let fetched_rate = client.get_exchange_rate(&token1, &token2);
     assert_eq!(fetched_rate, rate);
@@ -88,6 +89,7 @@ fn test_create_agreement_with_token() {
     let token_addr = Address::generate(&env);
 
     client.add_supported_token(
+        &admin,
         &token_addr,
         &String::from_str(&env, "USDC"),
         &6,
@@ -136,6 +138,7 @@ fn test_make_payment_with_different_token() {
         .address();
 
     client.add_supported_token(
+        &admin,
         &base_token,
         &String::from_str(&env, "USDC"),
         &6,
@@ -143,6 +146,7 @@ fn test_make_payment_with_different_token() {
         &1000000000,
     );
     client.add_supported_token(
+        &admin,
         &pay_token,
         &String::from_str(&env, "EURT"),
         &6,
@@ -152,7 +156,7 @@ fn test_make_payment_with_different_token() {
 
     // Set rate: 1 EURT = 1.1 USDC
     let rate = 1_100_000_000_000_000_000;
-    client.set_exchange_rate(&pay_token, &base_token, &rate);
+    client.set_exchange_rate(&admin, &pay_token, &base_token, &rate);
 
     let agreement_id = client.create_agreement_with_token(&AgreementInput {
         agreement_id: String::from_str(&env, "PROP1").clone(),
@@ -174,6 +178,7 @@ fn test_make_payment_with_different_token() {
     // Sign agreement (moves to PendingApproval) then witness-approve to activate
     client.submit_agreement(&landlord, &agreement_id);
     client.sign_agreement(&tenant, &agreement_id);
+    wire_escrow(&env, &client);
     client.approve_agreement(&admin, &agreement_id);
 
     // Give tenant some pay_token
@@ -488,7 +493,7 @@ fn test_add_supported_token_success() {
     let token_addr = Address::generate(&env);
     let symbol = String::from_str(&env, "USDC");
 
-    client.add_supported_token(&token_addr, &symbol, &6, &1, &1000000);
+    client.add_supported_token(&admin, &token_addr, &symbol, &6, &1, &1000000);
 
     assert!(client.is_token_supported(&token_addr));
 }
@@ -504,10 +509,10 @@ fn test_remove_supported_token_success() {
     let token_addr = Address::generate(&env);
     let symbol = String::from_str(&env, "USDC");
 
-    client.add_supported_token(&token_addr, &symbol, &6, &1, &1000000);
+    client.add_supported_token(&admin, &token_addr, &symbol, &6, &1, &1000000);
     assert!(client.is_token_supported(&token_addr));
 
-    client.remove_supported_token(&token_addr);
+    client.remove_supported_token(&admin, &token_addr);
     assert!(!client.is_token_supported(&token_addr));
 }
 
@@ -522,8 +527,22 @@ fn test_get_supported_tokens_list() {
     let token1 = Address::generate(&env);
     let token2 = Address::generate(&env);
 
-    client.add_supported_token(&token1, &String::from_str(&env, "USDC"), &6, &1, &1000000);
-    client.add_supported_token(&token2, &String::from_str(&env, "EURT"), &6, &1, &1000000);
+    client.add_supported_token(
+        &admin,
+        &token1,
+        &String::from_str(&env, "USDC"),
+        &6,
+        &1,
+        &1000000,
+    );
+    client.add_supported_token(
+        &admin,
+        &token2,
+        &String::from_str(&env, "EURT"),
+        &6,
+        &1,
+        &1000000,
+    );
 
     let tokens = client.get_supported_tokens();
     assert_eq!(tokens.len(), 2);
@@ -541,7 +560,7 @@ fn test_set_exchange_rate_success() {
     let token2 = Address::generate(&env);
     let rate = 1_500_000_000_000_000_000; // 1.5
 
-    client.set_exchange_rate(&token1, &token2, &rate);
+    client.set_exchange_rate(&admin, &token1, &token2, &rate);
 
     let fetched_rate = client.get_exchange_rate(&token1, &token2);
     assert_eq!(fetched_rate, rate);
@@ -576,7 +595,7 @@ fn test_convert_amount_calculation() {
     let token2 = Address::generate(&env);
     let rate = 2_000_000_000_000_000_000; // 2.0
 
-    client.set_exchange_rate(&token1, &token2, &rate);
+    client.set_exchange_rate(&admin, &token1, &token2, &rate);
 
     let amount = 1000;
     let converted = client.convert_amount(&token1, &token2, &amount);
@@ -597,6 +616,7 @@ fn test_create_agreement_with_token_stores_token() {
     let agreement_id = String::from_str(&env, "AGR-TOKEN-TEST");
 
     client.add_supported_token(
+        &admin,
         &token_addr,
         &String::from_str(&env, "USDC"),
         &6,
@@ -1,11 +1,13 @@
 //! Role-Based Access Control tests for the Chioma contract.
 //!
-//! Verifies that privileged functions enforce admin-only access and that
-//! agreement signing is restricted to the specific tenant on each agreement.
+//! Verifies that privileged functions enforce their role, that roles move
+//! through multi-sig, and that agreement signing is restricted to the
+//! specific tenant on each agreement.
 
 use super::*;
 use soroban_sdk::{
     testutils::{Address as _, MockAuth, MockAuthInvoke},
+    xdr::ToXdr,
     Address, Env, IntoVal, String, Vec,
 };
 
@@ -92,7 +94,7 @@ fn test_record_version_admin_succeeds() {
         updated_at: env.ledger().timestamp(),
     };
 
-    let result = client.try_record_version(&version);
+    let result = client.try_record_version(&admin, &version);
     assert!(result.is_ok(), "admin should be able to record a version");
 }
 
@@ -122,11 +124,11 @@ fn test_record_version_non_admin_rejected() {
             invoke: &MockAuthInvoke {
                 contract: &client.address,
                 fn_name: "record_version",
-                args: (version.clone(),).into_val(&env),
+                args: (attacker.clone(), version.clone()).into_val(&env),
                 sub_invokes: &[],
             },
         }])
-        .record_version(&version);
+        .record_version(&attacker, &version);
 }
 
 // ── freeze_escrow / unfreeze_escrow ────────────────────────────────────────
@@ -352,11 +354,11 @@ fn test_update_config_specific_auth_required() {
             invoke: &MockAuthInvoke {
                 contract: &client.address,
                 fn_name: "update_config",
-                args: (new_config.clone(),).into_val(&env),
+                args: (attacker.clone(), new_config.clone()).into_val(&env),
                 sub_invokes: &[],
             },
         }])
-        .update_config(&new_config);
+        .update_config(&attacker, &new_config);
 }
 
 // ── pause / unpause: admin-only ──────────────────────────────────────────────
@@ -378,9 +380,172 @@ fn test_pause_specific_auth_required() {
             invoke: &MockAuthInvoke {
                 contract: &client.address,
                 fn_name: "pause",
-                args: (reason.clone(),).into_val(&env),
+                args: (attacker.clone(), reason.clone()).into_val(&env),
                 sub_invokes: &[],
             },
         }])
-        .pause(&reason);
+        .pause(&attacker, &reason);
+}
+
+// ── roles ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_initial_admin_holds_every_role() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let client = initialized_client(&env, &admin);
+    let other = Address::generate(&env);
+
+    for role in [
+        Role::Pauser,
+        Role::Treasurer,
+        Role::RateSetter,
+        Role::TokenManager,
+        Role::Arbitrator,
+        Role::Upgrader,
+        Role::RateLimitAdmin,
+    ] {
+        assert!(client.has_role(&admin, &role));
+        assert!(!client.has_role(&other, &role));
+    }
+}
+
+#[test]
+fn test_roles_are_granted_and_revoked_through_multisig() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let client = initialized_client(&env, &admin);
+    env.mock_all_auths();
+
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let mut signers = Vec::new(&env);
+    signers.push_back(signer1.clone());
+    signers.push_back(signer2.clone());
+    client.initialize_multisig(&signers, &2);
+
+    let pass = |action_type: ActionType, account: &Address, role: Role| {
+        let id = client.propose_action(
+            &signer1,
+            &action_type,
+            &Some(account.clone()),
+            &role.to_xdr(&env),
+        );
+        client.approve_action(&signer2, &id);
+        client.execute_action(&signer1, &id);
+    };
+
+    let operator = Address::generate(&env);
+    let reason = String::from_str(&env, "incident");
+    assert_eq!(
+        client.try_pause(&operator, &reason),
+        Err(Ok(RentalError::Unauthorized))
+    );
+
+    pass(ActionType::GrantRole, &operator, Role::Pauser);
+    assert!(client.has_role(&operator, &Role::Pauser));
+    client.pause(&operator, &reason);
+    client.unpause(&operator);
+
+    // A role only unlocks its own entrypoints.
+    let token = Address::generate(&env);
+    assert_eq!(
+        client.try_set_exchange_rate(&operator, &token, &Address::generate(&env), &1),
+        Err(Ok(RentalError::Unauthorized))
+    );
+
+    // Granting a held role, or revoking one that is not held, is rejected
+    // at proposal time.
+    assert_eq!(
+        client.try_propose_action(
+            &signer1,
+            &ActionType::GrantRole,
+            &Some(operator.clone()),
+            &Role::Pauser.to_xdr(&env),
+        ),
+        Err(Ok(RentalError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_propose_action(
+            &signer1,
+            &ActionType::RevokeRole,
+            &Some(operator.clone()),
+            &Role::Treasurer.to_xdr(&env),
+        ),
+        Err(Ok(RentalError::InvalidInput))
+    );
+
+    pass(ActionType::RevokeRole, &operator, Role::Pauser);
+    assert!(!client.has_role(&operator, &Role::Pauser));
+    assert_eq!(
+        client.try_pause(&operator, &reason),
+        Err(Ok(RentalError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_rate_limiting_needs_its_own_role() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let client = initialized_client(&env, &admin);
+    env.mock_all_auths();
+
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    client.initialize_multisig(
+        &Vec::from_array(&env, [signer1.clone(), signer2.clone()]),
+        &2,
+    );
+    let grant = |account: &Address, role: Role| {
+        let id = client.propose_action(
+            &signer1,
+            &ActionType::GrantRole,
+            &Some(account.clone()),
+            &role.to_xdr(&env),
+        );
+        client.approve_action(&signer2, &id);
+        client.execute_action(&signer1, &id);
+    };
+
+    let config = RateLimitConfig {
+        max_calls_per_block: 1,
+        max_calls_per_user_per_day: 1,
+        cooldown_blocks: 0,
+        mode: RateLimitMode::LedgerBlocks,
+        window_seconds: 0,
+    };
+    let function_name = String::from_str(&env, "create_agreement");
+    let user = Address::generate(&env);
+
+    // A pauser can halt the contract but not loosen its rate limits.
+    let pauser = Address::generate(&env);
+    grant(&pauser, Role::Pauser);
+    assert_eq!(
+        client.try_set_rate_limit_config(&pauser, &config),
+        Err(Ok(RentalError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_function_rate_limit(&pauser, &function_name, &config),
+        Err(Ok(RentalError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_rate_limit_exempt(&pauser, &user, &true),
+        Err(Ok(RentalError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_reset_user_rate_limit(&pauser, &user, &function_name),
+        Err(Ok(RentalError::Unauthorized))
+    );
+
+    let operator = Address::generate(&env);
+    grant(&operator, Role::RateLimitAdmin);
+    client.set_rate_limit_config(&operator, &config);
+    client.set_function_rate_limit(&operator, &function_name, &config);
+    client.set_rate_limit_exempt(&operator, &user, &true);
+    assert_eq!(client.get_rate_limit_config(), config);
+    assert!(client.is_rate_limit_exempt(&user));
+    assert_eq!(
+        client.try_pause(&operator, &String::from_str(&env, "incident")),
+        Err(Ok(RentalError::Unauthorized))
+    );
 }
@@ -489,3 +489,167 @@ fn test_transfer_with_negative_sale_price_fails() {
 
     client.transfer_with_royalty(&agreement_id, &Address::generate(&env), &-1);
 }
+
+fn share(recipient: &Address, percentage: u32) -> RoyaltyShare {
+    RoyaltyShare {
+        recipient: recipient.clone(),
+        percentage,
+    }
+}
+
+#[test]
+fn test_transfer_pays_every_royalty_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin) = setup(&env);
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let (token_address, token_admin) = create_token_mock(&env, &Address::generate(&env));
+    let id = String::from_str(&env, "T1");
+    create_agreement_with_token(&client, &env, &id, &landlord, &tenant, &token_address);
+
+    let developer = Address::generate(&env);
+    let agency = Address::generate(&env);
+    let platform = Address::generate(&env);
+    let mut recipients = Vec::new(&env);
+    recipients.push_back(share(&developer, 1000));
+    recipients.push_back(share(&agency, 500));
+    recipients.push_back(share(&platform, 250));
+    client.set_royalty_recipients(&id, &recipients);
+
+    let config = client.get_royalty(&id);
+    assert_eq!(config.royalty_percentage, 1750);
+    assert_eq!(config.royalty_recipient, developer);
+    assert_eq!(config.recipients, recipients);
+    assert_eq!(client.calculate_royalty(&id, &10_000), 1750);
+
+    let buyer = Address::generate(&env);
+    token_admin.mint(&buyer, &10_000);
+    client.transfer_with_royalty(&id, &buyer, &10_000);
+
+    let token = soroban_sdk::token::Client::new(&env, &token_address);
+    assert_eq!(token.balance(&developer), 1000);
+    assert_eq!(token.balance(&agency), 500);
+    assert_eq!(token.balance(&platform), 250);
+    assert_eq!(token.balance(&landlord), 8250);
+    assert_eq!(token.balance(&buyer), 0);
+
+    let payment = client.get_royalty_payments(&id).get(0).unwrap();
+    assert_eq!(payment.royalty_amount, 1750);
+    assert_eq!(payment.legs.len(), 3);
+    assert_eq!(payment.legs.get(1).unwrap().recipient, agency);
+    assert_eq!(payment.legs.get(1).unwrap().amount, 500);
+}
+
+#[test]
+fn test_royalty_recipients_total_is_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin) = setup(&env);
+
+    let id = String::from_str(&env, "T1");
+    let token = Address::generate(&env);
+    create_agreement_with_token(
+        &client,
+        &env,
+        &id,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &token,
+    );
+
+    let mut recipients = Vec::new(&env);
+    recipients.push_back(share(&Address::generate(&env), 2000));
+    recipients.push_back(share(&Address::generate(&env), 501));
+    let result = client.try_set_royalty_recipients(&id, &recipients);
+    assert_eq!(result, Err(Ok(RentalError::InvalidAmount)));
+}
+
+#[test]
+fn test_royalty_recipients_reject_empty_and_duplicates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin) = setup(&env);
+
+    let id = String::from_str(&env, "T1");
+    let token = Address::generate(&env);
+    create_agreement_with_token(
+        &client,
+        &env,
+        &id,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &token,
+    );
+
+    let result = client.try_set_royalty_recipients(&id, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(RentalError::InvalidInput)));
+
+    let agency = Address::generate(&env);
+    let mut recipients = Vec::new(&env);
+    recipients.push_back(share(&agency, 100));
+    recipients.push_back(share(&agency, 200));
+    let result = client.try_set_royalty_recipients(&id, &recipients);
+    assert_eq!(result, Err(Ok(RentalError::InvalidInput)));
+}
+
+#[test]
+fn test_royalty_records_stored_before_multiple_recipients_are_still_read() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin) = setup(&env);
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let (token_address, token_admin) = create_token_mock(&env, &Address::generate(&env));
+    let id = String::from_str(&env, "T1");
+    create_agreement_with_token(&client, &env, &id, &landlord, &tenant, &token_address);
+
+    let recipient = Address::generate(&env);
+    let earlier_buyer = Address::generate(&env);
+    let legacy_config = crate::royalties::LegacyRoyaltyConfig {
+        token_id: id.clone(),
+        creator: landlord.clone(),
+        royalty_percentage: 1000,
+        royalty_recipient: recipient.clone(),
+    };
+    let legacy_payment = crate::royalties::LegacyRoyaltyPayment {
+        token_id: id.clone(),
+        from: earlier_buyer.clone(),
+        to: landlord.clone(),
+        amount: 5_000,
+        royalty_amount: 500,
+        timestamp: 7,
+    };
+    let mut payments: Vec<crate::royalties::LegacyRoyaltyPayment> = Vec::new(&env);
+    payments.push_back(legacy_payment);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::RoyaltyConfig(id.clone()), &legacy_config);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RoyaltyPayments(id.clone()), &payments);
+    });
+
+    let config = client.get_royalty(&id);
+    assert_eq!(config.royalty_percentage, 1000);
+    assert_eq!(config.recipients.len(), 1);
+    assert_eq!(config.recipients.get(0).unwrap(), share(&recipient, 1000));
+
+    let buyer = Address::generate(&env);
+    token_admin.mint(&buyer, &10_000);
+    client.transfer_with_royalty(&id, &buyer, &10_000);
+
+    let token = soroban_sdk::token::Client::new(&env, &token_address);
+    assert_eq!(token.balance(&recipient), 1000);
+
+    let history = client.get_royalty_payments(&id);
+    assert_eq!(history.len(), 2);
+    let earlier = history.get(0).unwrap();
+    assert_eq!(earlier.from, earlier_buyer);
+    assert_eq!(earlier.royalty_amount, 500);
+    assert!(earlier.legs.is_empty());
+    assert_eq!(history.get(1).unwrap().legs.len(), 1);
+}
@@ -1,9 +1,15 @@
 use crate::{
+    access,
     errors::RentalError,
-    types::{Config, TimelockActionType},
+    timelock,
+    types::{ActionType, Config, Role, TimelockAction, TimelockActionType, TokenExchangeRate},
     Contract, ContractClient,
 };
-use soroban_sdk::{testutils::Address as _, testutils::Ledger as _, Address, Bytes, Env, String};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{
+    testutils::Address as _, testutils::Ledger as _, vec, Address, Bytes, Env, IntoVal, String,
+    Symbol, Val,
+};
 
 // ─── Minimum delays (seconds) – must match timelock.rs constants ──────────────
 const MIN_DELAY_UPDATE_ADMIN: u64 = 7 * 24 * 60 * 60;
@@ -34,6 +40,32 @@ fn setup() -> (Env, ContractClient<'static>, Address) {
     (env, client, admin)
 }
 
+fn config_with_fee(env: &Env, fee_bps: u32) -> Config {
+    Config {
+        fee_bps,
+        fee_collector: Address::generate(env),
+        paused: false,
+    }
+}
+
+/// A valid payload for each action type.
+fn payload(env: &Env, action_type: &TimelockActionType) -> Bytes {
+    match action_type {
+        TimelockActionType::UpdateConfig => config_with_fee(env, 250).to_xdr(env),
+        TimelockActionType::UpdateRates => vec![
+            env,
+            TokenExchangeRate {
+                from_token: Address::generate(env),
+                to_token: Address::generate(env),
+                rate: 2_000_000_000_000_000_000,
+                updated_at: 0,
+            },
+        ]
+        .to_xdr(env),
+        _ => Bytes::new(env),
+    }
+}
+
 // ─── Queue Action Tests ───────────────────────────────────────────────────────
 
 #[test]
@@ -41,13 +73,12 @@ fn test_queue_action_success() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let result = client.try_queue_timelock_action(
         &admin,
         &TimelockActionType::UpdateConfig,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UpdateConfig),
         &MIN_DELAY_UPDATE_CONFIG,
     );
     assert!(result.is_ok());
@@ -61,7 +92,6 @@ fn test_queue_all_action_types() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let cases: &[(TimelockActionType, u64)] = &[
         (TimelockActionType::UpdateAdmin, MIN_DELAY_UPDATE_ADMIN),
@@ -72,7 +102,13 @@ fn test_queue_all_action_types() {
     ];
 
     for (action_type, delay) in cases {
-        let result = client.try_queue_timelock_action(&admin, action_type, &target, &data, delay);
+        let result = client.try_queue_timelock_action(
+            &admin,
+            action_type,
+            &target,
+            &payload(&env, action_type),
+            delay,
+        );
         assert!(result.is_ok(), "queue failed for {action_type:?}");
     }
 
@@ -84,14 +120,13 @@ fn test_queue_delay_too_short_rejected() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     // 1 second below the minimum for UpdateAdmin
     let result = client.try_queue_timelock_action(
         &admin,
         &TimelockActionType::UpdateAdmin,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UpdateAdmin),
         &(MIN_DELAY_UPDATE_ADMIN - 1),
     );
     assert_eq!(result, Err(Ok(RentalError::TimelockDelayTooShort)));
@@ -103,13 +138,12 @@ fn test_queue_requires_admin() {
 
     let non_admin = Address::generate(&env);
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let result = client.try_queue_timelock_action(
         &non_admin,
         &TimelockActionType::UpdateConfig,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UpdateConfig),
         &MIN_DELAY_UPDATE_CONFIG,
     );
     assert_eq!(result, Err(Ok(RentalError::Unauthorized)));
@@ -122,13 +156,12 @@ fn test_get_action_after_queue() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let action_id = client.queue_timelock_action(
         &admin,
         &TimelockActionType::UpdateRates,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UpdateRates),
         &MIN_DELAY_UPDATE_RATES,
     );
 
@@ -155,13 +188,12 @@ fn test_execute_before_eta_fails() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let action_id = client.queue_timelock_action(
         &admin,
         &TimelockActionType::UpdateConfig,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UpdateConfig),
         &MIN_DELAY_UPDATE_CONFIG,
     );
 
@@ -175,13 +207,12 @@ fn test_execute_after_eta_succeeds() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let action_id = client.queue_timelock_action(
         &admin,
         &TimelockActionType::UpdateConfig,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UpdateConfig),
         &MIN_DELAY_UPDATE_CONFIG,
     );
 
@@ -204,13 +235,12 @@ fn test_execute_already_executed_fails() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let action_id = client.queue_timelock_action(
         &admin,
         &TimelockActionType::UpdateConfig,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UpdateConfig),
         &MIN_DELAY_UPDATE_CONFIG,
     );
 
@@ -230,13 +260,12 @@ fn test_execute_cancelled_action_fails() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let action_id = client.queue_timelock_action(
         &admin,
         &TimelockActionType::UpdateConfig,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UpdateConfig),
         &MIN_DELAY_UPDATE_CONFIG,
     );
 
@@ -258,13 +287,12 @@ fn test_cancel_action_success() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let action_id = client.queue_timelock_action(
         &admin,
         &TimelockActionType::PauseContract,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::PauseContract),
         &MIN_DELAY_PAUSE,
     );
 
@@ -281,13 +309,12 @@ fn test_cancel_requires_admin() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let action_id = client.queue_timelock_action(
         &admin,
         &TimelockActionType::PauseContract,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::PauseContract),
         &MIN_DELAY_PAUSE,
     );
 
@@ -301,13 +328,12 @@ fn test_cancel_already_cancelled_fails() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let action_id = client.queue_timelock_action(
         &admin,
         &TimelockActionType::PauseContract,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::PauseContract),
         &MIN_DELAY_PAUSE,
     );
 
@@ -320,15 +346,15 @@ fn test_cancel_already_cancelled_fails() {
 #[test]
 fn test_cancel_executed_action_fails() {
     let (env, client, admin) = setup();
+    client.pause(&admin, &String::from_str(&env, "maintenance"));
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let action_id = client.queue_timelock_action(
         &admin,
         &TimelockActionType::UnpauseContract,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UnpauseContract),
         &MIN_DELAY_UNPAUSE,
     );
 
@@ -349,7 +375,6 @@ fn test_active_actions_tracking() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     assert_eq!(client.get_active_timelock_actions().len(), 0);
 
@@ -357,7 +382,7 @@ fn test_active_actions_tracking() {
         &admin,
         &TimelockActionType::UpdateConfig,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UpdateConfig),
         &MIN_DELAY_UPDATE_CONFIG,
     );
     assert_eq!(client.get_active_timelock_actions().len(), 1);
@@ -366,7 +391,7 @@ fn test_active_actions_tracking() {
         &admin,
         &TimelockActionType::UpdateRates,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UpdateRates),
         &MIN_DELAY_UPDATE_RATES,
     );
     assert_eq!(client.get_active_timelock_actions().len(), 2);
@@ -390,14 +415,13 @@ fn test_exact_minimum_delay_accepted() {
     let (env, client, admin) = setup();
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     // Exactly at the minimum should succeed
     let result = client.try_queue_timelock_action(
         &admin,
         &TimelockActionType::UnpauseContract,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UnpauseContract),
         &MIN_DELAY_UNPAUSE,
     );
     assert!(result.is_ok());
@@ -406,15 +430,15 @@ fn test_exact_minimum_delay_accepted() {
 #[test]
 fn test_execute_at_exact_eta() {
     let (env, client, admin) = setup();
+    client.pause(&admin, &String::from_str(&env, "maintenance"));
 
     let target = Address::generate(&env);
-    let data = Bytes::new(&env);
 
     let action_id = client.queue_timelock_action(
         &admin,
         &TimelockActionType::UnpauseContract,
         &target,
-        &data,
+        &payload(&env, &TimelockActionType::UnpauseContract),
         &MIN_DELAY_UNPAUSE,
     );
 
@@ -426,3 +450,324 @@ fn test_execute_at_exact_eta() {
     let result = client.try_execute_timelock_action(&admin, &action_id);
     assert!(result.is_ok());
 }
+
+// ─── Payload Application Tests ────────────────────────────────────────────────
+
+#[test]
+fn test_queue_rejects_invalid_payloads() {
+    let (env, client, admin) = setup();
+    let target = Address::generate(&env);
+
+    let result = client.try_queue_timelock_action(
+        &admin,
+        &TimelockActionType::UpdateConfig,
+        &target,
+        &Bytes::new(&env),
+        &MIN_DELAY_UPDATE_CONFIG,
+    );
+    assert_eq!(result, Err(Ok(RentalError::InvalidInput)));
+
+    let result = client.try_queue_timelock_action(
+        &admin,
+        &TimelockActionType::UpdateConfig,
+        &target,
+        &config_with_fee(&env, 10_001).to_xdr(&env),
+        &MIN_DELAY_UPDATE_CONFIG,
+    );
+    assert_eq!(result, Err(Ok(RentalError::InvalidConfig)));
+
+    let no_rates: soroban_sdk::Vec<TokenExchangeRate> = soroban_sdk::Vec::new(&env);
+    let result = client.try_queue_timelock_action(
+        &admin,
+        &TimelockActionType::UpdateRates,
+        &target,
+        &no_rates.to_xdr(&env),
+        &MIN_DELAY_UPDATE_RATES,
+    );
+    assert_eq!(result, Err(Ok(RentalError::InvalidInput)));
+
+    let result = client.try_queue_timelock_action(
+        &admin,
+        &TimelockActionType::UpdateAdmin,
+        &admin,
+        &Bytes::new(&env),
+        &MIN_DELAY_UPDATE_ADMIN,
+    );
+    assert_eq!(result, Err(Ok(RentalError::InvalidInput)));
+
+    assert_eq!(client.get_timelock_action_count(), 0);
+}
+
+#[test]
+fn test_executed_actions_apply_their_payload() {
+    let (env, client, admin) = setup();
+    let new_admin = Address::generate(&env);
+    let config = config_with_fee(&env, 250);
+    let rate = TokenExchangeRate {
+        from_token: Address::generate(&env),
+        to_token: Address::generate(&env),
+        rate: 3_000_000_000_000_000_000,
+        updated_at: 0,
+    };
+
+    let config_id = client.queue_timelock_action(
+        &admin,
+        &TimelockActionType::UpdateConfig,
+        &new_admin,
+        &config.clone().to_xdr(&env),
+        &MIN_DELAY_UPDATE_CONFIG,
+    );
+    let rates_id = client.queue_timelock_action(
+        &admin,
+        &TimelockActionType::UpdateRates,
+        &new_admin,
+        &vec![&env, rate.clone()].to_xdr(&env),
+        &MIN_DELAY_UPDATE_RATES,
+    );
+    let pause_id = client.queue_timelock_action(
+        &admin,
+        &TimelockActionType::PauseContract,
+        &new_admin,
+        &String::from_str(&env, "upgrade window").to_xdr(&env),
+        &MIN_DELAY_PAUSE,
+    );
+    let admin_id = client.queue_timelock_action(
+        &admin,
+        &TimelockActionType::UpdateAdmin,
+        &new_admin,
+        &Bytes::new(&env),
+        &MIN_DELAY_UPDATE_ADMIN,
+    );
+
+    // Nothing changes before execution.
+    assert_eq!(client.get_state().unwrap().config.fee_bps, 100);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += MIN_DELAY_UPDATE_ADMIN;
+    });
+    client.execute_timelock_action(&admin, &config_id);
+    client.execute_timelock_action(&admin, &rates_id);
+    client.execute_timelock_action(&admin, &pause_id);
+    client.execute_timelock_action(&admin, &admin_id);
+
+    let state = client.get_state().unwrap();
+    assert_eq!(state.config.fee_bps, 250);
+    assert_eq!(state.admin, new_admin);
+    assert_eq!(
+        client.get_exchange_rate(&rate.from_token, &rate.to_token),
+        rate.rate
+    );
+    assert!(client.is_paused());
+}
+
+#[test]
+fn test_failed_execution_leaves_action_pending() {
+    let (env, client, admin) = setup();
+    let target = Address::generate(&env);
+
+    // The contract is not paused, so the unpause cannot apply yet.
+    let action_id = client.queue_timelock_action(
+        &admin,
+        &TimelockActionType::UnpauseContract,
+        &target,
+        &Bytes::new(&env),
+        &MIN_DELAY_UNPAUSE,
+    );
+    env.ledger().with_mut(|li| {
+        li.timestamp += MIN_DELAY_UNPAUSE;
+    });
+    let result = client.try_execute_timelock_action(&admin, &action_id);
+    assert_eq!(result, Err(Ok(RentalError::NotPaused)));
+    assert!(!client.get_timelock_action(&action_id).executed);
+
+    client.pause(&admin, &String::from_str(&env, "maintenance"));
+    client.execute_timelock_action(&admin, &action_id);
+    assert!(!client.is_paused());
+}
+
+#[test]
+fn test_enabled_timelock_blocks_direct_admin_changes() {
+    let (env, client, admin) = setup();
+    let target = Address::generate(&env);
+
+    client.enable_timelock(&admin);
+    assert!(client.is_timelock_enabled());
+    assert_eq!(
+        client.try_enable_timelock(&admin),
+        Err(Ok(RentalError::InvalidState))
+    );
+
+    assert_eq!(
+        client.try_update_config(&admin, &config_with_fee(&env, 200)),
+        Err(Ok(RentalError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_exchange_rate(&admin, &target, &admin, &1),
+        Err(Ok(RentalError::Unauthorized))
+    );
+
+    // Pausing stays immediate; lifting it must be queued.
+    client.pause(&admin, &String::from_str(&env, "incident"));
+    assert_eq!(
+        client.try_unpause(&admin),
+        Err(Ok(RentalError::Unauthorized))
+    );
+
+    let action_id = client.queue_timelock_action(
+        &admin,
+        &TimelockActionType::DisableTimelock,
+        &target,
+        &Bytes::new(&env),
+        &(7 * 24 * 60 * 60),
+    );
+    env.ledger().with_mut(|li| {
+        li.timestamp += 7 * 24 * 60 * 60;
+    });
+    client.execute_timelock_action(&admin, &action_id);
+
+    assert!(!client.is_timelock_enabled());
+    client.unpause(&admin);
+    client.update_config(&admin, &config_with_fee(&env, 200));
+}
+
+#[test]
+fn test_enabled_timelock_blocks_the_same_changes_through_multisig() {
+    let (env, client, admin) = setup();
+    let admins = vec![&env, Address::generate(&env), Address::generate(&env)];
+    client.initialize_multisig(&admins, &1);
+    let proposer = admins.get(0).unwrap();
+    client.enable_timelock(&admin);
+
+    let proposals = [
+        (ActionType::Unpause, None, Bytes::new(&env)),
+        (
+            ActionType::UpdateConfig,
+            None,
+            config_with_fee(&env, 200).to_xdr(&env),
+        ),
+        (
+            ActionType::UpdateRate,
+            None,
+            TokenExchangeRate {
+                from_token: Address::generate(&env),
+                to_token: Address::generate(&env),
+                rate: 2,
+                updated_at: 0,
+            }
+            .to_xdr(&env),
+        ),
+        (
+            ActionType::EmergencyAction,
+            Some(Address::generate(&env)),
+            Bytes::new(&env),
+        ),
+    ];
+    client.pause(&admin, &String::from_str(&env, "incident"));
+    for (action_type, target, data) in proposals {
+        let proposal_id = client.propose_action(&proposer, &action_type, &target, &data);
+        assert_eq!(
+            client.try_execute_action(&proposer, &proposal_id),
+            Err(Ok(RentalError::Unauthorized))
+        );
+    }
+    assert!(client.is_paused());
+    assert_eq!(client.get_state().unwrap().admin, admin);
+}
+
+#[test]
+fn test_queue_and_cancel_check_the_role_of_the_action() {
+    let (env, client, admin) = setup();
+    let treasurer = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        access::grant_role(&env, Role::Treasurer, treasurer.clone()).unwrap();
+    });
+    let target = Address::generate(&env);
+
+    let action_id = client.queue_timelock_action(
+        &treasurer,
+        &TimelockActionType::UpdateConfig,
+        &target,
+        &payload(&env, &TimelockActionType::UpdateConfig),
+        &MIN_DELAY_UPDATE_CONFIG,
+    );
+    assert_eq!(
+        client.try_queue_timelock_action(
+            &treasurer,
+            &TimelockActionType::UpdateAdmin,
+            &target,
+            &Bytes::new(&env),
+            &MIN_DELAY_UPDATE_ADMIN,
+        ),
+        Err(Ok(RentalError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_enable_timelock(&treasurer),
+        Err(Ok(RentalError::Unauthorized))
+    );
+
+    let admin_change = client.queue_timelock_action(
+        &admin,
+        &TimelockActionType::UpdateAdmin,
+        &target,
+        &Bytes::new(&env),
+        &MIN_DELAY_UPDATE_ADMIN,
+    );
+    assert_eq!(
+        client.try_cancel_timelock_action(&treasurer, &admin_change),
+        Err(Ok(RentalError::Unauthorized))
+    );
+    client.cancel_timelock_action(&treasurer, &action_id);
+}
+
+#[test]
+fn test_actions_queued_under_the_old_keys_are_moved() {
+    let (env, client, admin) = setup();
+    let action_id = String::from_str(&env, "tl_00000001");
+    let legacy = |name: &str| -> soroban_sdk::Vec<Val> {
+        vec![&env, Symbol::new(&env, name).into_val(&env)]
+    };
+    env.as_contract(&client.address, || {
+        let mut action_key = legacy("TimelockAction");
+        action_key.push_back(action_id.clone().into_val(&env));
+        env.storage().persistent().set(
+            &action_key,
+            &TimelockAction {
+                id: action_id.clone(),
+                action_type: TimelockActionType::UpdateConfig,
+                target: admin.clone(),
+                data: payload(&env, &TimelockActionType::UpdateConfig),
+                eta: MIN_DELAY_UPDATE_CONFIG,
+                executed: false,
+                cancelled: false,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&legacy("TimelockActionCount"), &1u32);
+        env.storage().instance().set(
+            &legacy("ActiveTimelockActions"),
+            &vec![&env, action_id.clone()],
+        );
+        timelock::migrate_legacy_keys(&env);
+    });
+
+    assert_eq!(client.get_timelock_action_count(), 1);
+    assert_eq!(
+        client.get_active_timelock_actions(),
+        vec![&env, action_id.clone()]
+    );
+    env.ledger()
+        .with_mut(|li| li.timestamp = MIN_DELAY_UPDATE_CONFIG);
+    client.execute_timelock_action(&admin, &action_id);
+    assert_eq!(client.get_state().unwrap().config.fee_bps, 250);
+
+    // The next action does not reuse the migrated id.
+    let next = client.queue_timelock_action(
+        &admin,
+        &TimelockActionType::UnpauseContract,
+        &admin,
+        &Bytes::new(&env),
+        &MIN_DELAY_UNPAUSE,
+    );
+    assert_eq!(next, String::from_str(&env, "tl_00000002"));
+}
@@ -0,0 +1,388 @@
+use super::*;
+use soroban_sdk::{
+    testutils::Address as _, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, String, Symbol,
+    Val,
+};
+
+/// Minimal WASM module the host accepts as contract code.
+const UPGRADE_WASM: &[u8] = b"\0asm\x01\0\0\0\0\x1e\x11contractenvmetav0\0\0\0\0\0\0\0\x17\0\0\0\0";
+
+fn setup(env: &Env) -> (ContractClient<'_>, Address) {
+    let (client, admin) = deploy(env);
+    client.initialize_multisig(&vec![env, admin.clone()], &1);
+    (client, admin)
+}
+
+/// Initialized contract whose multi-sig is still to be set up.
+fn deploy(env: &Env) -> (ContractClient<'_>, Address) {
+    env.mock_all_auths();
+    let client = ContractClient::new(env, &env.register(Contract, ()));
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(env),
+            paused: false,
+        },
+    );
+    (client, admin)
+}
+
+fn version(env: &Env, major: u32, minor: u32) -> ContractVersion {
+    ContractVersion {
+        major,
+        minor,
+        patch: 0,
+        label: String::from_str(env, "release"),
+        status: VersionStatus::Active,
+        hash: Bytes::new(env),
+        updated_at: 0,
+    }
+}
+
+fn propose(env: &Env, client: &ContractClient, admin: &Address) -> (String, BytesN<32>) {
+    let wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(Bytes::from_slice(env, UPGRADE_WASM));
+    let id = String::from_str(env, "UPG-001");
+    client.propose_contract_upgrade(admin, &id, &wasm_hash, &String::from_str(env, "v1"), &0);
+    (id, wasm_hash)
+}
+
+#[test]
+fn test_execute_upgrade_installs_wasm_and_records_version() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (id, wasm_hash) = propose(&env, &client, &admin);
+
+    client.execute_contract_upgrade(&admin, &id, &version(&env, 1, 0));
+
+    // The installed module exports nothing; reinstall the native contract to
+    // stand in for the new code.
+    assert!(client.try_get_version().is_err());
+    env.register_at(&client.address, Contract, ());
+
+    let current = client.get_version();
+    assert_eq!((current.major, current.minor), (1, 0));
+    assert_eq!(current.hash, Bytes::from(wasm_hash));
+    assert!(client.get_upgrade_proposal(&id).executed);
+}
+
+#[test]
+fn test_execute_upgrade_requires_newer_version() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (id, _) = propose(&env, &client, &admin);
+
+    // The default version is 0.1.0.
+    let result = client.try_execute_contract_upgrade(&admin, &id, &version(&env, 0, 1));
+    assert_eq!(result, Err(Ok(RentalError::InvalidInput)));
+    assert!(!client.get_upgrade_proposal(&id).executed);
+}
+
+#[test]
+fn test_cancelled_upgrade_cannot_be_approved_or_executed() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let (id, _) = propose(&env, &client, &admin);
+
+    env.as_contract(&client.address, || {
+        let key = DataKey::UpgradeProposal(id.clone());
+        let mut proposal: ContractUpgradeProposal = env.storage().persistent().get(&key).unwrap();
+        proposal.cancelled = true;
+        env.storage().persistent().set(&key, &proposal);
+    });
+
+    assert_eq!(
+        client.try_approve_contract_upgrade(&admin, &id),
+        Err(Ok(RentalError::TimelockAlreadyCancelled))
+    );
+    assert_eq!(
+        client.try_execute_contract_upgrade(&admin, &id, &version(&env, 1, 0)),
+        Err(Ok(RentalError::TimelockAlreadyCancelled))
+    );
+    assert!(!client.get_upgrade_proposal(&id).executed);
+}
+
+#[test]
+fn test_upgrade_approvals_count_by_admin_weight() {
+    let env = Env::default();
+    let (client, admin) = deploy(&env);
+    let other = Address::generate(&env);
+    client.initialize_multisig(&vec![&env, admin.clone(), other.clone()], &2);
+    let (id, _) = propose(&env, &client, &admin);
+
+    // One approval of weight 1 is short of the two signatures required.
+    assert_eq!(
+        client.try_execute_contract_upgrade(&admin, &id, &version(&env, 1, 0)),
+        Err(Ok(RentalError::InsufficientApprovals))
+    );
+
+    let weight = client.propose_action(
+        &admin,
+        &ActionType::SetAdminWeight,
+        &Some(admin.clone()),
+        &2u32.to_xdr(&env),
+    );
+    client.approve_action(&other, &weight);
+    client.execute_action(&admin, &weight);
+
+    // The proposer's approval now carries the threshold alone.
+    client.execute_contract_upgrade(&admin, &id, &version(&env, 1, 0));
+    env.register_at(&client.address, Contract, ());
+    assert!(client.get_upgrade_proposal(&id).executed);
+}
+
+#[test]
+fn test_migrate_runs_once_per_upgrade() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(RentalError::InvalidState))
+    );
+
+    let (id, _) = propose(&env, &client, &admin);
+    client.execute_contract_upgrade(&admin, &id, &version(&env, 1, 0));
+    env.register_at(&client.address, Contract, ());
+
+    assert_eq!(client.migrate(&admin), 8);
+    assert_eq!(client.get_storage_version(), 8);
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(RentalError::InvalidState))
+    );
+}
+
+#[test]
+fn test_migrate_grants_roles_to_admin_of_pre_role_deployment() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    // Roll the deployment back to the layout from before roles existed,
+    // keeping only the role needed to run the upgrade.
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::Migration(MigrationKey::StorageVersion), &1u32);
+        for role in [Role::Pauser, Role::Treasurer, Role::Arbitrator] {
+            env.storage()
+                .instance()
+                .remove(&DataKey::Governance(GovernanceKey::Role(
+                    role,
+                    admin.clone(),
+                )));
+        }
+    });
+    assert!(!client.has_role(&admin, &Role::Pauser));
+
+    let (id, _) = propose(&env, &client, &admin);
+    client.execute_contract_upgrade(&admin, &id, &version(&env, 1, 0));
+    env.register_at(&client.address, Contract, ());
+
+    assert_eq!(client.migrate(&admin), 8);
+    for role in [Role::Pauser, Role::Treasurer, Role::Arbitrator] {
+        assert!(client.has_role(&admin, &role));
+    }
+}
+
+#[test]
+fn test_migrate_gives_rate_limiting_to_admin_alone() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let pauser = Address::generate(&env);
+
+    // Roll back to layout 7, where pausers also tuned rate limiting.
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::Migration(MigrationKey::StorageVersion), &7u32);
+        env.storage()
+            .instance()
+            .remove(&DataKey::Governance(GovernanceKey::Role(
+                Role::RateLimitAdmin,
+                admin.clone(),
+            )));
+        env.storage().instance().set(
+            &DataKey::Governance(GovernanceKey::Role(Role::Pauser, pauser.clone())),
+            &true,
+        );
+    });
+
+    let (id, _) = propose(&env, &client, &admin);
+    client.execute_contract_upgrade(&admin, &id, &version(&env, 1, 0));
+    env.register_at(&client.address, Contract, ());
+
+    assert_eq!(client.migrate(&admin), 8);
+    assert!(client.has_role(&admin, &Role::RateLimitAdmin));
+    assert!(!client.has_role(&pauser, &Role::RateLimitAdmin));
+}
+
+#[test]
+fn test_migrate_drops_legacy_error_log_counter() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let legacy_count: Vec<Val> = vec![&env, Symbol::new(&env, "ErrorLogCount").into_val(&env)];
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::Migration(MigrationKey::StorageVersion), &2u32);
+        env.storage().instance().set(&legacy_count, &7u32);
+    });
+
+    let (id, _) = propose(&env, &client, &admin);
+    client.execute_contract_upgrade(&admin, &id, &version(&env, 1, 0));
+    env.register_at(&client.address, Contract, ());
+
+    assert_eq!(client.migrate(&admin), 8);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().instance().has(&legacy_count));
+    });
+}
+
+#[test]
+fn test_migrate_gives_a_config_only_pause_its_own_record() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    env.as_contract(&client.address, || {
+        let mut state: ContractState = env.storage().instance().get(&DataKey::State).unwrap();
+        state.config.paused = true;
+        env.storage().instance().set(&DataKey::State, &state);
+        env.storage()
+            .instance()
+            .set(&DataKey::Migration(MigrationKey::StorageVersion), &6u32);
+    });
+    assert!(!client.is_paused());
+
+    let (id, _) = propose(&env, &client, &admin);
+    client.execute_contract_upgrade(&admin, &id, &version(&env, 1, 0));
+    env.register_at(&client.address, Contract, ());
+
+    assert_eq!(client.migrate(&admin), 8);
+    assert!(client.is_paused());
+    assert!(client.get_state().unwrap().config.paused);
+    client.unpause(&admin);
+    assert!(!client.get_state().unwrap().config.paused);
+}
+
+#[test]
+fn test_unversioned_agreements_are_upgraded_on_read_and_by_batch_migration() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    for id in ["AGR-001", "AGR-002", "AGR-003"] {
+        client.create_agreement(&AgreementInput {
+            agreement_id: String::from_str(&env, id),
+            admin: Address::generate(&env),
+            user: Address::generate(&env),
+            agent: None,
+            terms: AgreementTerms {
+                monthly_rent: 1000,
+                security_deposit: 2000,
+                start_date: 100,
+                end_date: 200,
+                agent_commission_rate: 0,
+            },
+            payment_token: Address::generate(&env),
+            metadata_uri: String::from_str(&env, ""),
+            attributes: Vec::new(&env),
+        });
+    }
+
+    // Store the first agreement the way it was stored before records were
+    // versioned.
+    let legacy_id = String::from_str(&env, "AGR-001");
+    let legacy = client.get_agreement(&legacy_id).unwrap();
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Agreement(legacy_id.clone()), &legacy);
+    });
+    assert_eq!(client.get_agreement(&legacy_id), Some(legacy.clone()));
+
+    assert_eq!(
+        client.try_migrate_records(&admin, &0, &0),
+        Err(Ok(RentalError::InvalidInput))
+    );
+
+    let batch = client.migrate_records(&admin, &0, &2);
+    assert_eq!(
+        batch,
+        RecordMigrationBatch {
+            migrated: 2,
+            next_cursor: 2,
+            done: false,
+        }
+    );
+    let batch = client.migrate_records(&admin, &batch.next_cursor, &10);
+    assert_eq!(
+        batch,
+        RecordMigrationBatch {
+            migrated: 1,
+            next_cursor: 3,
+            done: true,
+        }
+    );
+
+    env.as_contract(&client.address, || {
+        let stored: VersionedAgreement = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Agreement(legacy_id.clone()))
+            .unwrap();
+        assert_eq!(stored.into_current(), legacy);
+    });
+
+    // An agreement created before the index existed is out of the walk's
+    // reach and is rewritten by id instead.
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::Agreement(legacy_id.clone()), &legacy);
+        storage.remove(&DataKey::Migration(MigrationKey::AgreementIndex(0)));
+    });
+    assert_eq!(client.migrate_records(&admin, &0, &10).migrated, 2);
+    let ids = vec![&env, legacy_id.clone(), String::from_str(&env, "AGR-404")];
+    assert_eq!(client.migrate_records_by_id(&admin, &ids), 1);
+    env.as_contract(&client.address, || {
+        let stored: VersionedAgreement = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Agreement(legacy_id.clone()))
+            .unwrap();
+        assert_eq!(stored.into_current(), legacy);
+    });
+    assert_eq!(
+        client.try_migrate_records_by_id(&admin, &Vec::new(&env)),
+        Err(Ok(RentalError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_migrate_drops_hard_coded_gas_estimates() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let legacy_key = DataKey::GasMetrics(String::from_str(&env, "make_payment_with_token"));
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::Migration(MigrationKey::StorageVersion), &3u32);
+        env.storage().instance().set(&legacy_key, &59_000u64);
+    });
+
+    let (id, _) = propose(&env, &client, &admin);
+    client.execute_contract_upgrade(&admin, &id, &version(&env, 1, 0));
+    env.register_at(&client.address, Contract, ());
+
+    assert_eq!(client.migrate(&admin), 8);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().instance().has(&legacy_key));
+    });
+    assert_eq!(
+        client.try_estimate_gas_cost(&OperationType::MakePayment),
+        Err(Ok(RentalError::InvalidState))
+    );
+}
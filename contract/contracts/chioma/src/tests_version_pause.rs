@@ -63,7 +63,7 @@ fn test_record_version_success() {
         updated_at: env.ledger().timestamp(),
     };
 
-    client.record_version(&new_version);
+    client.record_version(&admin, &new_version);
 
     let version = client.get_version();
     assert_eq!(version.major, 1);
@@ -88,7 +88,7 @@ fn test_record_version_semantic_updates() {
         hash: Bytes::new(&env),
         updated_at: env.ledger().timestamp(),
     };
-    client.record_version(&v1);
+    client.record_version(&admin, &v1);
 
     // Minor update
     let v1_1 = ContractVersion {
@@ -100,7 +100,7 @@ fn test_record_version_semantic_updates() {
         hash: Bytes::new(&env),
         updated_at: env.ledger().timestamp(),
     };
-    client.record_version(&v1_1);
+    client.record_version(&admin, &v1_1);
 
     // Patch update
     let v1_1_1 = ContractVersion {
@@ -112,7 +112,7 @@ fn test_record_version_semantic_updates() {
         hash: Bytes::new(&env),
         updated_at: env.ledger().timestamp(),
     };
-    client.record_version(&v1_1_1);
+    client.record_version(&admin, &v1_1_1);
 
     let history = client.get_version_history();
     assert_eq!(history.len(), 3);
@@ -141,7 +141,7 @@ fn test_record_version_requires_admin_auth() {
     };
 
     // No auth mocked for attacker
-    client.record_version(&new_version);
+    client.record_version(&admin, &new_version);
 }
 
 #[test]
@@ -167,7 +167,7 @@ fn test_version_metadata_recording() {
         updated_at: 123456789,
     };
 
-    client.record_version(&version);
+    client.record_version(&admin, &version);
 
     let current = client.get_version();
     assert_eq!(current.hash, test_hash);
@@ -186,15 +186,18 @@ fn test_version_history_order_and_length() {
     initialize_contract(&env, &client, &admin);
 
     for i in 1..=5 {
-        client.record_version(&ContractVersion {
-            major: i,
-            minor: 0,
-            patch: 0,
-            label: String::from_str(&env, "ver"),
-            status: VersionStatus::Active,
-            hash: Bytes::new(&env),
-            updated_at: env.ledger().timestamp(),
-        });
+        client.record_version(
+            &admin,
+            &ContractVersion {
+                major: i,
+                minor: 0,
+                patch: 0,
+                label: String::from_str(&env, "ver"),
+                status: VersionStatus::Active,
+                hash: Bytes::new(&env),
+                updated_at: env.ledger().timestamp(),
+            },
+        );
     }
 
     let history = client.get_version_history();
@@ -221,7 +224,7 @@ fn test_version_history_immutability() {
         hash: Bytes::new(&env),
         updated_at: env.ledger().timestamp(),
     };
-    client.record_version(&v1);
+    client.record_version(&admin, &v1);
 
     let _history_before = client.get_version_history();
 
@@ -236,7 +239,7 @@ fn test_version_history_immutability() {
         hash: Bytes::new(&env),
         updated_at: env.ledger().timestamp(),
     };
-    client.record_version(&v2);
+    client.record_version(&admin, &v2);
 
     let history_after = client.get_version_history();
     assert_eq!(history_after.len(), 2);
@@ -263,19 +266,19 @@ fn test_update_version_status_lifecycle() {
         hash: Bytes::new(&env),
         updated_at: env.ledger().timestamp(),
     };
-    client.record_version(&v1);
+    client.record_version(&admin, &v1);
 
     // Update to Deprecated
-    client.update_version_status(&1, &0, &0, &VersionStatus::Deprecated);
+    client.update_version_status(&admin, &1, &0, &0, &VersionStatus::Deprecated);
     let history = client.get_version_history();
     assert_eq!(history.get(0).unwrap().status, VersionStatus::Deprecated);
     assert_eq!(client.get_version().status, VersionStatus::Deprecated);
 
     // Update to Archived - Mismatching version should return error
-    let res = client.try_update_version_status(&1, &1, &0, &VersionStatus::Archived);
+    let res = client.try_update_version_status(&admin, &1, &1, &0, &VersionStatus::Archived);
     assert!(res.is_err());
 
-    client.update_version_status(&1, &0, &0, &VersionStatus::Archived);
+    client.update_version_status(&admin, &1, &0, &0, &VersionStatus::Archived);
     assert_eq!(client.get_version().status, VersionStatus::Archived);
 }
 
@@ -287,28 +290,34 @@ fn test_update_non_current_version_status() {
     let admin = Address::generate(&env);
     initialize_contract(&env, &client, &admin);
 
-    client.record_version(&ContractVersion {
-        major: 1,
-        minor: 0,
-        patch: 0,
-        label: String::from_str(&env, "v1"),
-        status: VersionStatus::Active,
-        hash: Bytes::new(&env),
-        updated_at: env.ledger().timestamp(),
-    });
+    client.record_version(
+        &admin,
+        &ContractVersion {
+            major: 1,
+            minor: 0,
+            patch: 0,
+            label: String::from_str(&env, "v1"),
+            status: VersionStatus::Active,
+            hash: Bytes::new(&env),
+            updated_at: env.ledger().timestamp(),
+        },
+    );
 
-    client.record_version(&ContractVersion {
-        major: 2,
-        minor: 0,
-        patch: 0,
-        label: String::from_str(&env, "v2"),
-        status: VersionStatus::Active,
-        hash: Bytes::new(&env),
-        updated_at: env.ledger().timestamp(),
-    });
+    client.record_version(
+        &admin,
+        &ContractVersion {
+            major: 2,
+            minor: 0,
+            patch: 0,
+            label: String::from_str(&env, "v2"),
+            status: VersionStatus::Active,
+            hash: Bytes::new(&env),
+            updated_at: env.ledger().timestamp(),
+        },
+    );
 
     // Update v1 status
-    client.update_version_status(&1, &0, &0, &VersionStatus::Deprecated);
+    client.update_version_status(&admin, &1, &0, &0, &VersionStatus::Deprecated);
 
     // Current version (v2) should remain Active
     let current = client.get_version();
@@ -334,31 +343,37 @@ fn test_update_version_status_admin_auth() {
             invoke: &MockAuthInvoke {
                 contract: &client.address,
                 fn_name: "record_version",
-                args: (ContractVersion {
-                    major: 1,
-                    minor: 0,
-                    patch: 0,
-                    label: String::from_str(&env, "v1"),
-                    status: VersionStatus::Active,
-                    hash: Bytes::new(&env),
-                    updated_at: env.ledger().timestamp(),
-                },)
+                args: (
+                    admin.clone(),
+                    ContractVersion {
+                        major: 1,
+                        minor: 0,
+                        patch: 0,
+                        label: String::from_str(&env, "v1"),
+                        status: VersionStatus::Active,
+                        hash: Bytes::new(&env),
+                        updated_at: env.ledger().timestamp(),
+                    },
+                )
                     .into_val(&env),
                 sub_invokes: &[],
             },
         }])
-        .record_version(&ContractVersion {
-            major: 1,
-            minor: 0,
-            patch: 0,
-            label: String::from_str(&env, "v1"),
-            status: VersionStatus::Active,
-            hash: Bytes::new(&env),
-            updated_at: env.ledger().timestamp(),
-        });
+        .record_version(
+            &admin,
+            &ContractVersion {
+                major: 1,
+                minor: 0,
+                patch: 0,
+                label: String::from_str(&env, "v1"),
+                status: VersionStatus::Active,
+                hash: Bytes::new(&env),
+                updated_at: env.ledger().timestamp(),
+            },
+        );
 
     let _attacker = Address::generate(&env);
-    client.update_version_status(&1, &0, &0, &VersionStatus::Deprecated);
+    client.update_version_status(&admin, &1, &0, &0, &VersionStatus::Deprecated);
 }
 
 // --- Pause State Management Tests ---
@@ -374,13 +389,13 @@ fn test_pause_and_unpause_success() {
     assert!(!client.is_paused());
 
     let reason = String::from_str(&env, "Emergency maintenance");
-    client.pause(&reason);
+    client.pause(&admin, &reason);
 
     assert!(client.is_paused());
     let state = client.get_state().unwrap();
     assert!(state.config.paused);
 
-    client.unpause();
+    client.unpause(&admin);
     assert!(!client.is_paused());
 }
 
@@ -396,7 +411,7 @@ fn test_pause_metadata_and_reasons() {
     let timestamp = 999888777;
     env.ledger().with_mut(|li| li.timestamp = timestamp);
 
-    client.pause(&reason);
+    client.pause(&admin, &reason);
 
     // PauseState is not directly exposed in Contract struct,
     // but we can check it indirectly or by looking at events if needed.
@@ -412,8 +427,8 @@ fn test_prevent_double_pause() {
     let admin = Address::generate(&env);
     initialize_contract(&env, &client, &admin);
 
-    client.pause(&String::from_str(&env, "one"));
-    client.pause(&String::from_str(&env, "two"));
+    client.pause(&admin, &String::from_str(&env, "one"));
+    client.pause(&admin, &String::from_str(&env, "two"));
 }
 
 #[test]
@@ -425,7 +440,7 @@ fn test_prevent_unpause_when_not_paused() {
     let admin = Address::generate(&env);
     initialize_contract(&env, &client, &admin);
 
-    client.unpause();
+    client.unpause(&admin);
 }
 
 #[test]
@@ -437,7 +452,7 @@ fn test_pause_requires_admin_auth() {
     initialize_contract(&env, &client, &admin);
 
     let _attacker = Address::generate(&env);
-    client.pause(&String::from_str(&env, "malicious"));
+    client.pause(&admin, &String::from_str(&env, "malicious"));
 }
 
 // --- Pause State Enforcement Tests ---
@@ -451,7 +466,7 @@ fn test_operations_blocked_when_paused() {
     let admin = Address::generate(&env);
     initialize_contract(&env, &client, &admin);
 
-    client.pause(&String::from_str(&env, "halt"));
+    client.pause(&admin, &String::from_str(&env, "halt"));
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -484,21 +499,192 @@ fn test_admin_bypass_pause_for_maintenance() {
     let admin = Address::generate(&env);
     initialize_contract(&env, &client, &admin);
 
-    client.pause(&String::from_str(&env, "maintenance"));
+    client.pause(&admin, &String::from_str(&env, "maintenance"));
 
     // Admin should still be able to record version even if paused
-    let res = client.try_record_version(&ContractVersion {
-        major: 3,
-        minor: 0,
-        patch: 0,
-        label: String::from_str(&env, "v3"),
-        status: VersionStatus::Active,
-        hash: Bytes::new(&env),
-        updated_at: env.ledger().timestamp(),
-    });
+    let res = client.try_record_version(
+        &admin,
+        &ContractVersion {
+            major: 3,
+            minor: 0,
+            patch: 0,
+            label: String::from_str(&env, "v3"),
+            status: VersionStatus::Active,
+            hash: Bytes::new(&env),
+            updated_at: env.ledger().timestamp(),
+        },
+    );
     assert!(res.is_ok());
 }
 
+fn agreement_input(env: &Env, id: &str) -> AgreementInput {
+    AgreementInput {
+        agreement_id: String::from_str(env, id),
+        admin: Address::generate(env),
+        user: Address::generate(env),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1000,
+            security_deposit: 2000,
+            start_date: 100,
+            end_date: 1000,
+            agent_commission_rate: 0,
+        },
+        payment_token: Address::generate(env),
+        metadata_uri: String::from_str(env, ""),
+        attributes: Vec::new(env),
+    }
+}
+
+#[test]
+fn test_module_pause_blocks_only_that_module() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    initialize_contract(&env, &client, &admin);
+
+    client.pause_module(
+        &admin,
+        &PauseModule::Agreements,
+        &String::from_str(&env, "incident"),
+        &0,
+    );
+    assert!(client.is_module_paused(&PauseModule::Agreements));
+    assert!(!client.is_paused());
+
+    let result = client.try_create_agreement(&agreement_input(&env, "AGR1"));
+    assert_eq!(result, Err(Ok(RentalError::ContractPaused)));
+
+    // Other modules keep working.
+    client.add_supported_token(
+        &admin,
+        &Address::generate(&env),
+        &String::from_str(&env, "USDC"),
+        &7,
+        &1,
+        &1_000_000,
+    );
+
+    assert_eq!(
+        client.try_pause_module(
+            &admin,
+            &PauseModule::Agreements,
+            &String::from_str(&env, "again"),
+            &0,
+        ),
+        Err(Ok(RentalError::AlreadyPaused))
+    );
+
+    client.unpause_module(&admin, &PauseModule::Agreements);
+    assert!(!client.is_module_paused(&PauseModule::Agreements));
+    client.create_agreement(&agreement_input(&env, "AGR1"));
+}
+
+#[test]
+fn test_guardian_pauses_expire_and_cannot_be_lifted_by_guardian() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    initialize_contract(&env, &client, &admin);
+
+    let guardian = Address::generate(&env);
+    let reason = String::from_str(&env, "token incident");
+    assert_eq!(
+        client.try_pause(&guardian, &reason),
+        Err(Ok(RentalError::Unauthorized))
+    );
+
+    let signer = Address::generate(&env);
+    let mut signers = Vec::new(&env);
+    signers.push_back(signer.clone());
+    client.initialize_multisig(&signers, &1);
+    let id = client.propose_action(
+        &signer,
+        &ActionType::SetGuardian,
+        &Some(guardian.clone()),
+        &Bytes::new(&env),
+    );
+    client.execute_action(&signer, &id);
+    assert_eq!(client.get_guardian(), Some(guardian.clone()));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    // Requests longer than the cap are shortened.
+    client.pause_module(&guardian, &PauseModule::Payments, &reason, &(30 * 86_400));
+    let pause_state = client
+        .get_module_pause_state(&PauseModule::Payments)
+        .unwrap();
+    assert_eq!(pause_state.paused_by, guardian);
+    assert_eq!(pause_state.auto_unpause_at, Some(1_000 + 3 * 86_400));
+
+    client.pause(&guardian, &reason);
+    assert!(client.is_paused());
+    assert!(client.get_state().unwrap().config.paused);
+    assert_eq!(
+        client.get_pause_state().unwrap().auto_unpause_at,
+        Some(1_000 + 3 * 86_400)
+    );
+
+    assert_eq!(
+        client.try_unpause(&guardian),
+        Err(Ok(RentalError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_unpause_module(&guardian, &PauseModule::Payments),
+        Err(Ok(RentalError::Unauthorized))
+    );
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1_000 + 3 * 86_400);
+    assert!(!client.is_paused());
+    assert!(!client.get_state().unwrap().config.paused);
+    assert!(!client.is_module_paused(&PauseModule::Payments));
+    client.add_supported_token(
+        &admin,
+        &Address::generate(&env),
+        &String::from_str(&env, "USDC"),
+        &7,
+        &1,
+        &1_000_000,
+    );
+}
+
+#[test]
+fn test_pause_records_stored_before_expiry_are_still_read() {
+    #[soroban_sdk::contracttype]
+    struct OldPauseState {
+        is_paused: bool,
+        paused_at: u64,
+        paused_by: Address,
+        pause_reason: String,
+    }
+
+    let env = Env::default();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    initialize_contract(&env, &client, &admin);
+
+    let reason = String::from_str(&env, "maintenance");
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(
+            &DataKey::PauseState,
+            &OldPauseState {
+                is_paused: true,
+                paused_at: 5,
+                paused_by: admin.clone(),
+                pause_reason: reason.clone(),
+            },
+        );
+    });
+
+    assert!(client.is_paused());
+    let pause_state = client.get_pause_state().unwrap();
+    assert_eq!(pause_state.pause_reason, reason);
+    assert_eq!(pause_state.auto_unpause_at, None);
+}
+
 // --- Integration & Edge Cases ---
 
 #[test]
@@ -510,9 +696,9 @@ fn test_multiple_pause_unpause_cycles() {
     initialize_contract(&env, &client, &admin);
 
     for _ in 0..5 {
-        client.pause(&String::from_str(&env, "cycling"));
+        client.pause(&admin, &String::from_str(&env, "cycling"));
         assert!(client.is_paused());
-        client.unpause();
+        client.unpause(&admin);
         assert!(!client.is_paused());
     }
 }
@@ -526,13 +712,13 @@ fn test_pause_with_empty_and_long_reasons() {
     initialize_contract(&env, &client, &admin);
 
     // Empty reason
-    client.pause(&String::from_str(&env, ""));
+    client.pause(&admin, &String::from_str(&env, ""));
     assert!(client.is_paused());
-    client.unpause();
+    client.unpause(&admin);
 
     // Long reason
     let long_reason = String::from_str(&env, "This is a very long reason for pausing the contract to ensure that the storage can handle large strings even in the pause state as specified in the test cases of issue 658 development");
-    client.pause(&long_reason);
+    client.pause(&admin, &long_reason);
     assert!(client.is_paused());
 }
 
@@ -544,29 +730,35 @@ fn test_version_history_persists_through_pause() {
     let admin = Address::generate(&env);
     initialize_contract(&env, &client, &admin);
 
-    client.record_version(&ContractVersion {
-        major: 1,
-        minor: 0,
-        patch: 0,
-        label: String::from_str(&env, "v1"),
-        status: VersionStatus::Active,
-        hash: Bytes::new(&env),
-        updated_at: env.ledger().timestamp(),
-    });
+    client.record_version(
+        &admin,
+        &ContractVersion {
+            major: 1,
+            minor: 0,
+            patch: 0,
+            label: String::from_str(&env, "v1"),
+            status: VersionStatus::Active,
+            hash: Bytes::new(&env),
+            updated_at: env.ledger().timestamp(),
+        },
+    );
 
-    client.pause(&String::from_str(&env, "halt"));
+    client.pause(&admin, &String::from_str(&env, "halt"));
 
-    client.record_version(&ContractVersion {
-        major: 2,
-        minor: 0,
-        patch: 0,
-        label: String::from_str(&env, "v2"),
-        status: VersionStatus::Active,
-        hash: Bytes::new(&env),
-        updated_at: env.ledger().timestamp(),
-    });
+    client.record_version(
+        &admin,
+        &ContractVersion {
+            major: 2,
+            minor: 0,
+            patch: 0,
+            label: String::from_str(&env, "v2"),
+            status: VersionStatus::Active,
+            hash: Bytes::new(&env),
+            updated_at: env.ledger().timestamp(),
+        },
+    );
 
-    client.unpause();
+    client.unpause(&admin);
 
     let history = client.get_version_history();
     assert_eq!(history.len(), 2);
@@ -0,0 +1,256 @@
+use super::*;
+use crate::tests::{wire_escrow, MockEscrowClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient as TokenAdminClient},
+    Address, Env, String,
+};
+
+// ─── helpers ──────────────────────────────────────────────────────────────────
+
+struct Setup<'a> {
+    client: ContractClient<'a>,
+    escrow: MockEscrowClient<'a>,
+    admin: Address,
+    token: Address,
+    vault: Address,
+    tenant: Address,
+    id: String,
+}
+
+const DEPOSIT: i128 = 12_000;
+
+/// An agreement whose deposit is held by the mock escrow, with a vault
+/// configured for its token but nothing routed yet.
+fn setup(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let client = ContractClient::new(env, &env.register(Contract, ()));
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(env),
+            paused: false,
+        },
+    );
+    let escrow = wire_escrow(env, &client);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    let vault = Address::generate(env);
+
+    let tenant = Address::generate(env);
+    let landlord = Address::generate(env);
+    let id = String::from_str(env, "AGR001");
+    client.create_agreement(&AgreementInput {
+        agreement_id: id.clone(),
+        admin: landlord.clone(),
+        user: tenant.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1000,
+            security_deposit: DEPOSIT,
+            start_date: 100,
+            end_date: 10_000_000,
+            agent_commission_rate: 0,
+        },
+        payment_token: token.clone(),
+        metadata_uri: String::from_str(env, ""),
+        attributes: Vec::new(env),
+    });
+    client.set_deposit_interest_config(
+        &admin,
+        &id,
+        &1200,
+        &CompoundingFrequency::Monthly,
+        &InterestRecipient::Tenant,
+    );
+
+    // The deposit is held by the escrow contract, not by chioma.
+    escrow.create_funded(
+        &id,
+        &tenant,
+        &landlord,
+        &Address::generate(env),
+        &Address::generate(env),
+        &Address::generate(env),
+        &DEPOSIT,
+        &token,
+    );
+    TokenAdminClient::new(env, &token).mint(&escrow.address, &DEPOSIT);
+    client.set_yield_vault(&admin, &token, &vault);
+
+    Setup {
+        client,
+        escrow,
+        admin,
+        token,
+        vault,
+        tenant,
+        id,
+    }
+}
+
+fn simulate_yield(env: &Env, s: &Setup, amount: i128) {
+    s.escrow.earn(&s.id, &amount);
+    TokenAdminClient::new(env, &s.token).mint(&s.escrow.address, &amount);
+}
+
+// ─── tests ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_route_deposit_to_vault_moves_the_escrowed_deposit() {
+    let env = Env::default();
+    let s = setup(&env);
+
+    assert_eq!(s.client.get_yield_vault(&s.token), Some(s.vault.clone()));
+
+    let position = s.client.route_deposit_to_vault(&s.admin, &s.id);
+    assert_eq!(position.principal, DEPOSIT);
+    assert_eq!(position.shares, DEPOSIT);
+    assert_eq!(position.vault, s.vault);
+    assert_eq!(position.token, s.token);
+    let escrow_id = s.escrow.get_agreement_escrow(&s.id).unwrap();
+    assert_eq!(
+        s.escrow.get_vault_position(&escrow_id).unwrap().vault,
+        s.vault
+    );
+    assert_eq!(s.client.get_yield_position(&s.id), position);
+    assert_eq!(s.client.get_vault_yield(&s.id), 0);
+}
+
+#[test]
+fn test_route_deposit_requires_vault_escrow_and_single_position() {
+    let env = Env::default();
+    let s = setup(&env);
+
+    let other_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    assert_eq!(s.client.get_yield_vault(&other_token), None);
+
+    // No escrow holds a deposit for this agreement.
+    let unescrowed = String::from_str(&env, "AGR002");
+    s.client.create_agreement(&AgreementInput {
+        agreement_id: unescrowed.clone(),
+        admin: Address::generate(&env),
+        user: Address::generate(&env),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1000,
+            security_deposit: DEPOSIT,
+            start_date: 100,
+            end_date: 10_000_000,
+            agent_commission_rate: 0,
+        },
+        payment_token: s.token.clone(),
+        metadata_uri: String::from_str(&env, ""),
+        attributes: Vec::new(&env),
+    });
+    let result = s.client.try_route_deposit_to_vault(&s.admin, &unescrowed);
+    assert_eq!(result, Err(Ok(RentalError::EscrowNotFound)));
+
+    s.client.route_deposit_to_vault(&s.admin, &s.id);
+    let result = s.client.try_route_deposit_to_vault(&s.admin, &s.id);
+    assert_eq!(result, Err(Ok(RentalError::InvalidState)));
+}
+
+#[test]
+fn test_deposit_without_vault_position_accrues_nothing() {
+    let env = Env::default();
+    let s = setup(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
+    assert_eq!(s.client.calculate_accrued_interest(&s.id), 0);
+    assert_eq!(s.client.accrue_interest(&s.id).amount, 0);
+
+    // Nothing unbacked to pay out.
+    s.client.distribute_interest(&s.id);
+    let token = TokenClient::new(&env, &s.token);
+    assert_eq!(token.balance(&s.tenant), 0);
+    assert_eq!(token.balance(&s.escrow.address), DEPOSIT);
+}
+
+#[test]
+fn test_accrual_is_capped_by_vault_yield() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client.route_deposit_to_vault(&s.admin, &s.id);
+
+    // A month passes but the vault has earned nothing yet.
+    env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
+    assert_eq!(s.client.calculate_accrued_interest(&s.id), 0);
+
+    // The vault earns 50; the contractual 120 is capped to what is backed.
+    simulate_yield(&env, &s, 50);
+    assert_eq!(s.client.get_vault_yield(&s.id), 50);
+    let accrual = s.client.accrue_interest(&s.id);
+    assert_eq!(accrual.amount, 50);
+}
+
+#[test]
+fn test_distribute_interest_is_paid_from_vault_yield() {
+    let env = Env::default();
+    let s = setup(&env);
+    let token = TokenClient::new(&env, &s.token);
+    s.client.route_deposit_to_vault(&s.admin, &s.id);
+
+    env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
+    simulate_yield(&env, &s, 200);
+    assert_eq!(s.client.accrue_interest(&s.id).amount, 120);
+
+    s.client.distribute_interest(&s.id);
+    assert_eq!(token.balance(&s.tenant), 120);
+    // Chioma never held any of it.
+    assert_eq!(token.balance(&s.client.address), 0);
+    assert_eq!(s.client.get_vault_yield(&s.id), 80);
+    assert_eq!(s.client.get_deposit_interest(&s.id).accrued_interest, 0);
+}
+
+#[test]
+fn test_withdraw_deposit_from_vault_returns_funds_to_the_escrow() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client.route_deposit_to_vault(&s.admin, &s.id);
+    simulate_yield(&env, &s, 40);
+
+    let withdrawn = s.client.withdraw_deposit_from_vault(&s.admin, &s.id);
+    assert_eq!(withdrawn, DEPOSIT + 40);
+    let result = s.client.try_get_yield_position(&s.id);
+    assert_eq!(result, Err(Ok(RentalError::EscrowNotFound)));
+    let result = s.client.try_get_vault_yield(&s.id);
+    assert_eq!(result, Err(Ok(RentalError::EscrowNotFound)));
+}
+
+#[test]
+fn test_compounded_interest_stays_in_vault() {
+    let env = Env::default();
+    let s = setup(&env);
+    let token = TokenClient::new(&env, &s.token);
+    s.client.route_deposit_to_vault(&s.admin, &s.id);
+    s.client.set_interest_split(
+        &s.admin,
+        &s.id,
+        &InterestSplit {
+            tenant_bps: 10_000,
+            landlord_bps: 0,
+            platform_bps: 0,
+            tenant_compounds: true,
+            landlord_compounds: false,
+            platform_compounds: false,
+        },
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 30 * 86_400);
+    simulate_yield(&env, &s, 200);
+    s.client.accrue_interest(&s.id);
+    s.client.distribute_interest(&s.id);
+
+    assert_eq!(token.balance(&s.tenant), 0);
+    assert_eq!(s.client.get_yield_position(&s.id).principal, DEPOSIT + 120);
+    assert_eq!(s.client.get_vault_yield(&s.id), 80);
+}
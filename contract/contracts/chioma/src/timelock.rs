@@ -1,10 +1,13 @@
 use crate::{
+    access, admin,
     errors::RentalError,
     events,
-    storage::DataKey,
-    types::{ContractState, TimelockAction, TimelockActionType},
+    multi_sig::decode,
+    multi_token,
+    storage::{DataKey, TimelockKey},
+    types::{Config, PauseModule, Role, TimelockAction, TimelockActionType, TokenExchangeRate},
 };
-use soroban_sdk::{Address, Bytes, Env, String, Vec};
+use soroban_sdk::{vec, Address, Bytes, Env, IntoVal, String, Symbol, Val, Vec};
 
 // ─── ID Generation ────────────────────────────────────────────────────────────
 
@@ -41,6 +44,8 @@ const MIN_DELAY_UPDATE_RATES: u64 = 2 * 24 * 60 * 60;
 const MIN_DELAY_PAUSE: u64 = 24 * 60 * 60;
 /// 1 hour
 const MIN_DELAY_UNPAUSE: u64 = 60 * 60;
+/// 7 days
+const MIN_DELAY_DISABLE: u64 = 7 * 24 * 60 * 60;
 
 /// Returns the minimum required delay (seconds) for a given action type.
 pub fn get_min_delay(action_type: &TimelockActionType) -> u64 {
@@ -49,29 +54,33 @@ pub fn get_min_delay(action_type: &TimelockActionType) -> u64 {
         TimelockActionType::UpdateConfig => MIN_DELAY_UPDATE_CONFIG,
         TimelockActionType::UpdateRates => MIN_DELAY_UPDATE_RATES,
         TimelockActionType::PauseContract => MIN_DELAY_PAUSE,
-        TimelockActionType::UnpauseContract => MIN_DELAY_UNPAUSE,
+        TimelockActionType::UnpauseContract | TimelockActionType::UnpauseModule => {
+            MIN_DELAY_UNPAUSE
+        }
+        TimelockActionType::DisableTimelock => MIN_DELAY_DISABLE,
     }
 }
 
 // ─── Internal Helpers ─────────────────────────────────────────────────────────
 
-fn require_admin(env: &Env, caller: &Address) -> Result<(), RentalError> {
-    let state: ContractState = env
-        .storage()
-        .instance()
-        .get(&DataKey::State)
-        .ok_or(RentalError::InvalidState)?;
-    if state.admin != *caller {
-        return Err(RentalError::Unauthorized);
+/// Role that may queue or cancel an action, matching the entrypoint the
+/// action stands in for.
+fn role_for(action_type: &TimelockActionType) -> Role {
+    match action_type {
+        TimelockActionType::UpdateConfig => Role::Treasurer,
+        TimelockActionType::UpdateRates => Role::RateSetter,
+        TimelockActionType::PauseContract
+        | TimelockActionType::UnpauseContract
+        | TimelockActionType::UnpauseModule => Role::Pauser,
+        TimelockActionType::UpdateAdmin | TimelockActionType::DisableTimelock => Role::Upgrader,
     }
-    Ok(())
 }
 
 fn remove_from_active(env: &Env, action_id: &String) {
     let active: Vec<String> = env
         .storage()
         .instance()
-        .get(&DataKey::ActiveTimelockActions)
+        .get(&DataKey::Timelock(TimelockKey::Active))
         .unwrap_or(Vec::new(env));
 
     let mut new_active = Vec::new(env);
@@ -82,16 +91,158 @@ fn remove_from_active(env: &Env, action_id: &String) {
     }
     env.storage()
         .instance()
-        .set(&DataKey::ActiveTimelockActions, &new_active);
+        .set(&DataKey::Timelock(TimelockKey::Active), &new_active);
+}
+
+// ─── Action Payloads ──────────────────────────────────────────────────────────
+//
+// `data` holds the XDR encoding of the action's argument:
+//
+// | Action            | `target`  | `data`                             |
+// | ----------------- | --------- | ---------------------------------- |
+// | `UpdateAdmin`     | new admin | empty                              |
+// | `UpdateConfig`    | -         | `Config`                           |
+// | `UpdateRates`     | -         | non-empty `Vec<TokenExchangeRate>` |
+// | `PauseContract`   | -         | `String` reason, or empty          |
+// | `UnpauseContract` | -         | empty                              |
+// | `DisableTimelock` | -         | empty                              |
+// | `UnpauseModule`   | -         | `PauseModule`                      |
+//
+// `updated_at` in a queued rate is ignored; the execution time is recorded.
+
+fn validate_payload(
+    env: &Env,
+    action_type: &TimelockActionType,
+    target: &Address,
+    data: &Bytes,
+) -> Result<(), RentalError> {
+    match action_type {
+        TimelockActionType::UpdateAdmin => {
+            if *target == admin::get_state(env)?.admin {
+                return Err(RentalError::InvalidInput);
+            }
+        }
+        TimelockActionType::UpdateConfig => {
+            let config: Config = decode(env, data)?;
+            if config.fee_bps > 10_000 {
+                return Err(RentalError::InvalidConfig);
+            }
+        }
+        TimelockActionType::UpdateRates => {
+            let rates: Vec<TokenExchangeRate> = decode(env, data)?;
+            if rates.is_empty() {
+                return Err(RentalError::InvalidInput);
+            }
+            for rate in rates.iter() {
+                if rate.rate <= 0 {
+                    return Err(RentalError::InvalidAmount);
+                }
+            }
+        }
+        TimelockActionType::PauseContract => {
+            if !data.is_empty() {
+                decode::<String>(env, data)?;
+            }
+        }
+        TimelockActionType::UnpauseContract | TimelockActionType::DisableTimelock => {}
+        TimelockActionType::UnpauseModule => {
+            decode::<PauseModule>(env, data)?;
+        }
+    }
+    Ok(())
+}
+
+fn apply_action(env: &Env, executor: &Address, action: &TimelockAction) -> Result<(), RentalError> {
+    let data = &action.data;
+
+    match action.action_type {
+        TimelockActionType::UpdateAdmin => {
+            admin::apply_admin(env, action.target.clone())?;
+        }
+        TimelockActionType::UpdateConfig => {
+            admin::apply_config(env, executor.clone(), decode(env, data)?)?;
+        }
+        TimelockActionType::UpdateRates => {
+            let rates: Vec<TokenExchangeRate> = decode(env, data)?;
+            for rate in rates.iter() {
+                multi_token::set_exchange_rate(
+                    env.clone(),
+                    rate.from_token,
+                    rate.to_token,
+                    rate.rate,
+                )?;
+            }
+        }
+        TimelockActionType::PauseContract => {
+            let reason = if data.is_empty() {
+                String::from_str(env, "Paused by timelock")
+            } else {
+                decode(env, data)?
+            };
+            admin::apply_pause(env, executor.clone(), reason, None)?;
+        }
+        TimelockActionType::UnpauseContract => {
+            admin::apply_unpause(env, executor.clone())?;
+        }
+        TimelockActionType::DisableTimelock => {
+            set_enabled(env, false);
+        }
+        TimelockActionType::UnpauseModule => {
+            admin::apply_module_unpause(env, decode(env, data)?, executor.clone())?;
+        }
+    }
+    Ok(())
+}
+
+// ─── Enforcement ──────────────────────────────────────────────────────────────
+
+fn set_enabled(env: &Env, enabled: bool) {
+    env.storage()
+        .instance()
+        .set(&DataKey::Timelock(TimelockKey::Enabled), &enabled);
+    events::timelock_status_changed(env, enabled);
+}
+
+/// Whether admin changes must go through the timelock.
+pub fn is_enabled(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::Timelock(TimelockKey::Enabled))
+        .unwrap_or(false)
+}
+
+/// Route admin changes through the timelock from now on.
+///
+/// Requires the `Upgrader` role. Turning the timelock off again requires a
+/// queued `DisableTimelock` action.
+pub fn enable(env: &Env, caller: Address) -> Result<(), RentalError> {
+    caller.require_auth();
+    access::require_role(env, &caller, Role::Upgrader)?;
+    if is_enabled(env) {
+        return Err(RentalError::InvalidState);
+    }
+    set_enabled(env, true);
+    Ok(())
+}
+
+/// Reject a direct admin call while the timelock is enabled.
+pub fn require_disabled(env: &Env) -> Result<(), RentalError> {
+    if is_enabled(env) {
+        return Err(RentalError::Unauthorized);
+    }
+    Ok(())
 }
 
 // ─── Public Functions ─────────────────────────────────────────────────────────
 
 /// Queue a new admin action with a mandatory delay.
 ///
-/// Only the contract admin may call this. `delay` (seconds) must be at or
-/// above the minimum enforced for the given `action_type`. Returns the
-/// action ID that can be used to execute or cancel the action later.
+/// The caller needs the role of the entrypoint the action stands in for:
+/// `Treasurer` for config, `RateSetter` for rates, `Pauser` for pausing and
+/// `Upgrader` for the admin and the timelock itself. `delay` (seconds) must be at or
+/// above the minimum enforced for the given `action_type`, and `data` must
+/// decode as that type's payload. Returns the action ID that can be used to
+/// execute or cancel the action later.
 pub fn queue_action(
     env: &Env,
     caller: Address,
@@ -101,13 +252,14 @@ pub fn queue_action(
     delay: u64,
 ) -> Result<String, RentalError> {
     caller.require_auth();
-    require_admin(env, &caller)?;
+    access::require_role(env, &caller, role_for(&action_type))?;
 
     // Enforce minimum delay for the action type
     let min_delay = get_min_delay(&action_type);
     if delay < min_delay {
         return Err(RentalError::TimelockDelayTooShort);
     }
+    validate_payload(env, &action_type, &target, &data)?;
 
     let now = env.ledger().timestamp();
     let eta = now + delay;
@@ -116,7 +268,7 @@ pub fn queue_action(
     let mut action_count: u32 = env
         .storage()
         .instance()
-        .get(&DataKey::TimelockActionCount)
+        .get(&DataKey::Timelock(TimelockKey::ActionCount))
         .unwrap_or(0);
     action_count += 1;
 
@@ -133,11 +285,12 @@ pub fn queue_action(
     };
 
     // Persist the action
-    env.storage()
-        .persistent()
-        .set(&DataKey::TimelockAction(action_id.clone()), &action);
+    env.storage().persistent().set(
+        &DataKey::Timelock(TimelockKey::Action(action_id.clone())),
+        &action,
+    );
     env.storage().persistent().extend_ttl(
-        &DataKey::TimelockAction(action_id.clone()),
+        &DataKey::Timelock(TimelockKey::Action(action_id.clone())),
         500000,
         500000,
     );
@@ -145,19 +298,19 @@ pub fn queue_action(
     // Update counter
     env.storage()
         .instance()
-        .set(&DataKey::TimelockActionCount, &action_count);
+        .set(&DataKey::Timelock(TimelockKey::ActionCount), &action_count);
     env.storage().instance().extend_ttl(500000, 500000);
 
     // Track in active list
     let mut active: Vec<String> = env
         .storage()
         .instance()
-        .get(&DataKey::ActiveTimelockActions)
+        .get(&DataKey::Timelock(TimelockKey::Active))
         .unwrap_or(Vec::new(env));
     active.push_back(action_id.clone());
     env.storage()
         .instance()
-        .set(&DataKey::ActiveTimelockActions, &active);
+        .set(&DataKey::Timelock(TimelockKey::Active), &active);
 
     events::timelock_action_queued(env, action_id.clone(), eta);
 
@@ -167,14 +320,15 @@ pub fn queue_action(
 /// Execute a queued action once its ETA has been reached.
 ///
 /// Any caller may trigger execution once the ETA has passed. The action must
-/// not have been previously executed or cancelled.
+/// not have been previously executed or cancelled. If applying the queued
+/// change fails, the action stays pending.
 pub fn execute_action(env: &Env, caller: Address, action_id: String) -> Result<(), RentalError> {
     caller.require_auth();
 
     let mut action: TimelockAction = env
         .storage()
         .persistent()
-        .get(&DataKey::TimelockAction(action_id.clone()))
+        .get(&DataKey::Timelock(TimelockKey::Action(action_id.clone())))
         .ok_or(RentalError::TimelockNotFound)?;
 
     if action.executed {
@@ -189,10 +343,13 @@ pub fn execute_action(env: &Env, caller: Address, action_id: String) -> Result<(
         return Err(RentalError::TimelockEtaNotReached);
     }
 
+    apply_action(env, &caller, &action)?;
+
     action.executed = true;
-    env.storage()
-        .persistent()
-        .set(&DataKey::TimelockAction(action_id.clone()), &action);
+    env.storage().persistent().set(
+        &DataKey::Timelock(TimelockKey::Action(action_id.clone())),
+        &action,
+    );
 
     remove_from_active(env, &action_id);
 
@@ -203,17 +360,17 @@ pub fn execute_action(env: &Env, caller: Address, action_id: String) -> Result<(
 
 /// Cancel a queued action before it has been executed.
 ///
-/// Only the contract admin may cancel. The action must not have been
-/// previously executed or cancelled.
+/// Requires the role that may queue the action. The action must not have
+/// been previously executed or cancelled.
 pub fn cancel_action(env: &Env, caller: Address, action_id: String) -> Result<(), RentalError> {
     caller.require_auth();
-    require_admin(env, &caller)?;
 
     let mut action: TimelockAction = env
         .storage()
         .persistent()
-        .get(&DataKey::TimelockAction(action_id.clone()))
+        .get(&DataKey::Timelock(TimelockKey::Action(action_id.clone())))
         .ok_or(RentalError::TimelockNotFound)?;
+    access::require_role(env, &caller, role_for(&action.action_type))?;
 
     if action.executed {
         return Err(RentalError::TimelockAlreadyExecuted);
@@ -224,9 +381,10 @@ pub fn cancel_action(env: &Env, caller: Address, action_id: String) -> Result<()
     }
 
     action.cancelled = true;
-    env.storage()
-        .persistent()
-        .set(&DataKey::TimelockAction(action_id.clone()), &action);
+    env.storage().persistent().set(
+        &DataKey::Timelock(TimelockKey::Action(action_id.clone())),
+        &action,
+    );
 
     remove_from_active(env, &action_id);
 
@@ -239,7 +397,7 @@ pub fn cancel_action(env: &Env, caller: Address, action_id: String) -> Result<()
 pub fn get_action(env: &Env, action_id: String) -> Result<TimelockAction, RentalError> {
     env.storage()
         .persistent()
-        .get(&DataKey::TimelockAction(action_id))
+        .get(&DataKey::Timelock(TimelockKey::Action(action_id)))
         .ok_or(RentalError::TimelockNotFound)
 }
 
@@ -247,7 +405,7 @@ pub fn get_action(env: &Env, action_id: String) -> Result<TimelockAction, Rental
 pub fn get_active_actions(env: &Env) -> Vec<String> {
     env.storage()
         .instance()
-        .get(&DataKey::ActiveTimelockActions)
+        .get(&DataKey::Timelock(TimelockKey::Active))
         .unwrap_or(Vec::new(env))
 }
 
@@ -255,6 +413,44 @@ pub fn get_active_actions(env: &Env) -> Vec<String> {
 pub fn get_action_count(env: &Env) -> u32 {
     env.storage()
         .instance()
-        .get(&DataKey::TimelockActionCount)
+        .get(&DataKey::Timelock(TimelockKey::ActionCount))
         .unwrap_or(0)
 }
+
+/// Move the queue from the top-level keys it was stored under before
+/// `DataKey::Timelock` existed. Pending actions move with it; executed and
+/// cancelled ones are left under their old keys to expire.
+pub fn migrate_legacy_keys(env: &Env) {
+    let legacy = |name: &str| -> Vec<Val> { vec![env, Symbol::new(env, name).into_val(env)] };
+
+    let count_key = legacy("TimelockActionCount");
+    if let Some(count) = env.storage().instance().get::<_, u32>(&count_key) {
+        env.storage()
+            .instance()
+            .set(&DataKey::Timelock(TimelockKey::ActionCount), &count);
+        env.storage().instance().remove(&count_key);
+    }
+
+    let active_key = legacy("ActiveTimelockActions");
+    let Some(active) = env.storage().instance().get::<_, Vec<String>>(&active_key) else {
+        return;
+    };
+    for action_id in active.iter() {
+        let mut action_key = legacy("TimelockAction");
+        action_key.push_back(action_id.clone().into_val(env));
+        if let Some(action) = env
+            .storage()
+            .persistent()
+            .get::<_, TimelockAction>(&action_key)
+        {
+            let key = DataKey::Timelock(TimelockKey::Action(action_id));
+            env.storage().persistent().set(&key, &action);
+            env.storage().persistent().extend_ttl(&key, 500000, 500000);
+            env.storage().persistent().remove(&action_key);
+        }
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::Timelock(TimelockKey::Active), &active);
+    env.storage().instance().remove(&active_key);
+}
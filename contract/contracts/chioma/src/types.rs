@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Bytes, String, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, String, Symbol, Vec};
 
 // ─── Timelock Types ───────────────────────────────────────────────────────────
 
@@ -10,6 +10,8 @@ pub enum TimelockActionType {
     UpdateRates,
     PauseContract,
     UnpauseContract,
+    DisableTimelock,
+    UnpauseModule,
 }
 
 #[contracttype]
@@ -78,7 +80,7 @@ pub struct ExtensionHistory {
 pub struct ContractUpgradeProposal {
     pub id: String,
     pub proposer: Address,
-    pub wasm_hash: Bytes,
+    pub wasm_hash: BytesN<32>,
     pub approvals: Vec<Address>,
     pub required_signatures: u32,
     pub eta: u64,
@@ -112,6 +114,40 @@ pub enum ActionType {
     SetRateLimit,
     AddToken,
     RemoveToken,
+    SetActionThreshold,
+    SetAdminWeight,
+    GrantRole,
+    RevokeRole,
+    SetGuardian,
+    SetAuditLogCapacity,
+}
+
+/// Privilege checked by an admin entrypoint in place of the contract admin.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// Pause and unpause.
+    Pauser,
+    /// Fee config, deposit interest and yield vault routing.
+    Treasurer,
+    /// Exchange rates and deposit rate schedules.
+    RateSetter,
+    /// Supported token list.
+    TokenManager,
+    /// Freeze and unfreeze escrows.
+    Arbitrator,
+    /// Version records, contract upgrades and migrations.
+    Upgrader,
+    /// Rate limit configuration, per-function overrides and exemptions.
+    RateLimitAdmin,
+}
+
+/// Approval weight a multi-sig action type needs before it can execute.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActionThreshold {
+    pub action_type: ActionType,
+    pub threshold: u32,
 }
 
 #[contracttype]
@@ -159,6 +195,25 @@ pub struct RentAgreement {
     pub attributes: Vec<Attribute>,
 }
 
+/// Versioned envelope a `RentAgreement` is persisted in.
+///
+/// When `RentAgreement` changes shape, freeze the old layout under a new
+/// name, add a variant for the new one and convert in `into_current`; stored
+/// agreements are then upgraded as they are read.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VersionedAgreement {
+    V1(RentAgreement),
+}
+
+impl VersionedAgreement {
+    pub fn into_current(self) -> RentAgreement {
+        match self {
+            VersionedAgreement::V1(agreement) => agreement,
+        }
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PaymentSplit {
@@ -192,6 +247,26 @@ pub struct PauseState {
     pub paused_at: u64,
     pub paused_by: Address,
     pub pause_reason: String,
+    /// The pause lifts on its own from this timestamp; `None` lasts until unpaused.
+    pub auto_unpause_at: Option<u64>,
+}
+
+/// Group of entrypoints that can be paused on its own.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PauseModule {
+    /// Agreement lifecycle, extensions and metadata.
+    Agreements,
+    /// Rent payments.
+    Payments,
+    /// Escrow releases.
+    Escrow,
+    /// Supported tokens and exchange rates.
+    Tokens,
+    /// Deposit interest and yield vaults.
+    Interest,
+    /// Royalties and the position marketplace.
+    Royalties,
 }
 
 #[contracttype]
@@ -245,15 +320,50 @@ pub enum InterestRecipient {
     Split, // 50/50
 }
 
+/// Basis-point split of distributed interest, with a per-recipient choice to
+/// compound the share into the deposit principal instead of receiving it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterestSplit {
+    pub tenant_bps: u32,
+    pub landlord_bps: u32,
+    /// Paid to the platform `fee_collector`.
+    pub platform_bps: u32,
+    pub tenant_compounds: bool,
+    pub landlord_compounds: bool,
+    pub platform_compounds: bool,
+}
+
+/// Which rate schedule a deposit accrues against.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InterestRateSource {
+    /// The agreement's own schedule (seeded from `annual_rate`).
+    Agreement,
+    /// The admin-published statutory schedule shared by all deposits.
+    Statutory,
+}
+
+/// An annual rate that applies from `effective_from` until the next segment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateSegment {
+    pub effective_from: u64,
+    /// Annual interest rate in basis points (0–10 000 = 0–100 %).
+    pub annual_rate: u32,
+}
+
 /// Configuration for deposit interest on a specific agreement.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DepositInterestConfig {
     pub agreement_id: String,
-    /// Annual interest rate in basis points (0–10 000 = 0–100 %).
+    /// Rate last set for the agreement, in basis points (0–10 000 = 0–100 %).
+    /// Scheduled changes live in the agreement's `RateSegment` schedule.
     pub annual_rate: u32,
     pub compounding_frequency: CompoundingFrequency,
     pub interest_recipient: InterestRecipient,
+    pub rate_source: InterestRateSource,
 }
 
 /// A single interest-accrual snapshot.
@@ -264,6 +374,8 @@ pub struct InterestAccrual {
     pub amount: i128,
     pub rate: u32,
     pub balance: i128,
+    /// Interest folded into the principal at this point (0 for plain accruals).
+    pub compounded: i128,
 }
 
 /// Cumulative interest state for a deposit (keyed by agreement / escrow id).
@@ -275,17 +387,91 @@ pub struct DepositInterest {
     pub accrued_interest: i128,
     pub total_with_interest: i128,
     pub last_accrual_date: u64,
-    pub accrual_history: Vec<InterestAccrual>,
+    /// Number of snapshots recorded; see `get_accrual_history` for paging.
+    pub history_count: u32,
+}
+
+/// A deposit the escrow contract holds in a yield vault.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldPosition {
+    pub escrow_id: String,
+    pub vault: Address,
+    pub token: Address,
+    /// Underlying amount routed into the vault.
+    pub principal: i128,
+    /// Vault shares currently held for this deposit.
+    pub shares: i128,
+    pub deposited_at: u64,
+}
+
+/// Result of one keeper batch over the interest-bearing deposit registry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterestAccrualBatch {
+    /// Escrow ids accrued in this batch; failures are left out.
+    pub processed: Vec<String>,
+    /// Cursor to pass to the next call.
+    pub next_cursor: u32,
+    /// True once the cursor has reached the end of the registry.
+    pub done: bool,
+}
+
+/// Result of one `migrate_records` batch over the agreement index.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordMigrationBatch {
+    /// Agreements rewritten in the current layout in this batch.
+    pub migrated: u32,
+    /// Cursor to pass to the next call.
+    pub next_cursor: u32,
+    /// True once the cursor has reached the end of the index.
+    pub done: bool,
 }
 
+/// One row of the audit log, written by the contract itself.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ErrorContext {
+pub struct AuditEntry {
+    /// Position in the log; increases by one per entry and never repeats.
+    pub seq: u32,
+    pub actor: Address,
+    pub operation: Symbol,
+    pub agreement_id: Option<String>,
+    /// `RentalError` code, or 0 if the operation succeeded.
     pub error_code: u32,
-    pub error_message: String,
-    pub details: String,
     pub timestamp: u64,
-    pub operation: String,
+}
+
+/// Criteria for `get_audit_log`. Unset fields match every entry; the time
+/// range is inclusive.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditFilter {
+    pub agreement_id: Option<String>,
+    pub actor: Option<Address>,
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+/// Page of audit entries returned by `get_audit_log`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditPage {
+    /// Matching entries, oldest first.
+    pub entries: Vec<AuditEntry>,
+    /// Cursor to pass to the next call.
+    pub next_cursor: u32,
+    /// True once the cursor has reached the newest entry.
+    pub done: bool,
+}
+
+/// One royalty recipient and its share of the sale price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoyaltyShare {
+    pub recipient: Address,
+    pub percentage: u32, // basis points
 }
 
 #[contracttype]
@@ -293,8 +479,17 @@ pub struct ErrorContext {
 pub struct RoyaltyConfig {
     pub token_id: String,
     pub creator: Address,
-    pub royalty_percentage: u32, // basis points (0-2500 for 0-25%)
-    pub royalty_recipient: Address,
+    pub royalty_percentage: u32, // total basis points (0-2500 for 0-25%)
+    pub royalty_recipient: Address, // first recipient
+    pub recipients: Vec<RoyaltyShare>,
+}
+
+/// A single royalty transfer made as part of a sale.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoyaltyLeg {
+    pub recipient: Address,
+    pub amount: i128,
 }
 
 #[contracttype]
@@ -305,9 +500,46 @@ pub struct RoyaltyPayment {
     pub to: Address,
     pub amount: i128,
     pub royalty_amount: i128,
+    pub legs: Vec<RoyaltyLeg>,
     pub timestamp: u64,
 }
 
+// ─── Marketplace Types ────────────────────────────────────────────────────────
+
+/// A landlord position offered for sale at an ask price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PositionListing {
+    pub listing_id: u32,
+    pub agreement_id: String,
+    pub seller: Address,
+    pub ask_price: i128,
+    pub token: Address,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BidStatus {
+    Open,
+    Accepted,
+    Withdrawn,
+}
+
+/// An offer on a listing; the bid amount is held by the contract while open.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PositionBid {
+    pub bid_id: u32,
+    pub listing_id: u32,
+    pub agreement_id: String,
+    pub bidder: Address,
+    pub amount: i128,
+    pub placed_at: u64,
+    pub status: BidStatus,
+}
+
 // ─── Rate Limiting Types ──────────────────────────────────────────────────────
 
 #[contracttype]
@@ -316,6 +548,10 @@ pub struct RateLimitConfig {
     pub max_calls_per_block: u32,
     pub max_calls_per_user_per_day: u32,
     pub cooldown_blocks: u32,
+    /// How `max_calls_per_user_per_day` is measured.
+    pub mode: RateLimitMode,
+    /// Window length for the timestamp-based modes.
+    pub window_seconds: u64,
 }
 
 #[contracttype]
@@ -328,6 +564,21 @@ pub struct UserCallCount {
     pub daily_reset_block: u64,
 }
 
+/// Per-user limiting strategy.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RateLimitMode {
+    /// Daily count over a fixed window of ledger sequence numbers, reset all
+    /// at once.
+    LedgerBlocks,
+    /// At most `max_calls_per_user_per_day` calls in any trailing
+    /// `window_seconds`, by ledger timestamp.
+    SlidingWindow,
+    /// A bucket of `max_calls_per_user_per_day` calls that refills
+    /// continuously over `window_seconds`.
+    TokenBucket,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AgreementTerms {
@@ -344,6 +595,7 @@ pub enum RateLimitReason {
     BlockLimitExceeded,
     DailyLimitExceeded,
     CooldownNotMet,
+    WindowLimitExceeded,
 }
 
 #[contracttype]
@@ -0,0 +1,151 @@
+//! Yield adapter for idle security deposits.
+//!
+//! Deposits that accrue interest can be routed into a lending vault so the
+//! interest paid out is backed by real yield instead of the contract's other
+//! balances. Deposits are held by the escrow contract, so the vault position
+//! is too: chioma picks the vault per payment token and has the escrow move
+//! the deposit, pay yield out of it, or bring it back.
+
+use soroban_sdk::{Address, BytesN, Env, String};
+
+use crate::agreement;
+use crate::errors::RentalError;
+use crate::events;
+use crate::peers::{self, EscrowClient};
+use crate::storage::DataKey;
+use crate::types::{RentAgreement, YieldPosition};
+
+// ─── Config ───────────────────────────────────────────────────────────────────
+
+/// Set the vault used for deposits paid in `token`.
+pub fn set_yield_vault(env: &Env, token: Address, vault: Address) -> Result<(), RentalError> {
+    env.storage()
+        .instance()
+        .set(&DataKey::YieldVault(token.clone()), &vault);
+    events::yield_vault_set(env, token, vault);
+    Ok(())
+}
+
+/// Vault configured for `token`, if any.
+pub fn get_yield_vault(env: &Env, token: Address) -> Option<Address> {
+    env.storage().instance().get(&DataKey::YieldVault(token))
+}
+
+// ─── Positions ────────────────────────────────────────────────────────────────
+
+/// The escrow holding a deposit, if the escrow contract is wired and one
+/// was created for the agreement.
+fn linked_escrow<'a>(env: &'a Env, escrow_id: &String) -> Option<(EscrowClient<'a>, BytesN<32>)> {
+    let escrow = EscrowClient::new(env, &peers::escrow(env).ok()?);
+    let id = escrow.get_agreement_escrow(escrow_id)?;
+    Some((escrow, id))
+}
+
+fn linked_escrow_or_err<'a>(
+    env: &'a Env,
+    escrow_id: &String,
+) -> Result<(EscrowClient<'a>, BytesN<32>), RentalError> {
+    linked_escrow(env, escrow_id).ok_or(RentalError::EscrowNotFound)
+}
+
+/// Vault position backing a deposit, if it has been routed.
+pub fn get_yield_position(env: &Env, escrow_id: String) -> Option<YieldPosition> {
+    let (escrow, id) = linked_escrow(env, &escrow_id)?;
+    let position = escrow.get_vault_position(&id)?;
+    let agreement: RentAgreement = agreement::load_agreement(env, &escrow_id)?;
+    Some(YieldPosition {
+        escrow_id,
+        vault: position.vault,
+        token: agreement.payment_token,
+        principal: position.principal,
+        shares: position.shares,
+        deposited_at: position.routed_at,
+    })
+}
+
+/// Yield earned by a deposit's position that has not yet been withdrawn;
+/// 0 when the deposit has no position.
+pub fn vault_yield(env: &Env, escrow_id: String) -> i128 {
+    match linked_escrow(env, &escrow_id) {
+        Some((escrow, id)) => escrow.get_vault_yield(&id),
+        None => 0,
+    }
+}
+
+/// Have the escrow contract move a deposit into its token's vault.
+pub fn route_deposit_to_vault(env: &Env, escrow_id: String) -> Result<YieldPosition, RentalError> {
+    let agreement: RentAgreement =
+        agreement::load_agreement(env, &escrow_id).ok_or(RentalError::AgreementNotFound)?;
+    let vault =
+        get_yield_vault(env, agreement.payment_token.clone()).ok_or(RentalError::InvalidConfig)?;
+    let (escrow, id) = linked_escrow_or_err(env, &escrow_id)?;
+    if escrow.get_vault_position(&id).is_some() {
+        return Err(RentalError::InvalidState);
+    }
+
+    let position = match escrow.try_route_to_vault(&id, &vault) {
+        Ok(Ok(position)) => position,
+        _ => return Err(RentalError::InvalidState),
+    };
+
+    events::deposit_routed_to_vault(
+        env,
+        escrow_id.clone(),
+        vault.clone(),
+        position.principal,
+        position.shares,
+    );
+    Ok(YieldPosition {
+        escrow_id,
+        vault,
+        token: agreement.payment_token,
+        principal: position.principal,
+        shares: position.shares,
+        deposited_at: position.routed_at,
+    })
+}
+
+/// Pay `amount` of a deposit's vault yield to `to`; the deposit itself is
+/// left untouched.
+pub fn withdraw_yield(
+    env: &Env,
+    escrow_id: String,
+    to: Address,
+    amount: i128,
+) -> Result<(), RentalError> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    let (escrow, id) = linked_escrow_or_err(env, &escrow_id)?;
+    if amount > escrow.get_vault_yield(&id) {
+        return Err(RentalError::EscrowInsufficientFunds);
+    }
+    escrow.withdraw_yield(&id, &to, &amount);
+    Ok(())
+}
+
+/// Add `amount` of a deposit's vault yield to the deposit itself.
+pub fn compound_yield(env: &Env, escrow_id: String, amount: i128) -> Result<(), RentalError> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    let (escrow, id) = linked_escrow_or_err(env, &escrow_id)?;
+    if amount > escrow.get_vault_yield(&id) {
+        return Err(RentalError::EscrowInsufficientFunds);
+    }
+    escrow.compound_yield(&id, &amount);
+    Ok(())
+}
+
+/// Close a position, bringing principal and any unpaid yield back into the
+/// deposit's escrow.
+///
+/// Returns the amount withdrawn.
+pub fn close_position(env: &Env, escrow_id: String) -> Result<i128, RentalError> {
+    let position = get_yield_position(env, escrow_id.clone()).ok_or(RentalError::EscrowNotFound)?;
+    let (escrow, id) = linked_escrow_or_err(env, &escrow_id)?;
+    let amount = escrow.close_vault_position(&id);
+
+    events::deposit_withdrawn_from_vault(env, escrow_id, position.vault, amount);
+    Ok(amount)
+}
@@ -1,7 +1,8 @@
-use soroban_sdk::{contracttype, Address, Env, Map, String};
+use soroban_sdk::{contractclient, contracttype, Address, Env, String};
 
 use crate::errors::DisputeError;
 use crate::events;
+use crate::peers;
 use crate::rate_limit;
 use crate::storage::DataKey;
 use crate::types::{
@@ -66,11 +67,13 @@ pub fn set_timeout_config(
     Ok(())
 }
 
+/// Chioma's `AgreementStatus`, mirrored so its agreements can be read.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum AgreementStatus {
     Draft,
     Pending,
+    PendingApproval,
     Active,
     Completed,
     Cancelled,
@@ -78,12 +81,21 @@ pub enum AgreementStatus {
     Disputed,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attribute {
+    pub trait_type: String,
+    pub value: String,
+}
+
+/// Chioma's `RentAgreement`, field for field: `admin` is the landlord and
+/// `user` the tenant.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RentAgreement {
     pub agreement_id: String,
-    pub landlord: Address,
-    pub tenant: Address,
+    pub admin: Address,
+    pub user: Address,
     pub agent: Option<Address>,
     pub monthly_rent: i128,
     pub security_deposit: i128,
@@ -94,19 +106,17 @@ pub struct RentAgreement {
     pub total_rent_paid: i128,
     pub payment_count: u32,
     pub signed_at: Option<u64>,
+    pub witness_id: Option<Address>,
     pub payment_token: Address,
     pub next_payment_due: u64,
-    pub payment_history: Map<u32, PaymentSplit>,
+    pub metadata_uri: String,
+    pub attributes: soroban_sdk::Vec<Attribute>,
 }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PaymentSplit {
-    pub landlord_amount: i128,
-    pub platform_amount: i128,
-    pub token: Address,
-    pub payment_date: u64,
-    pub payer: Address,
+/// The part of the chioma contract disputes are raised against.
+#[contractclient(name = "ChiomaClient")]
+pub trait ChiomaInterface {
+    fn get_agreement(env: Env, agreement_id: String) -> Option<RentAgreement>;
 }
 
 pub fn add_arbiter(env: &Env, admin: Address, arbiter: Address) -> Result<(), DisputeError> {
@@ -186,14 +196,9 @@ pub fn raise_dispute(
         return Err(DisputeError::DisputeAlreadyExists);
     }
 
-    // Cross-contract call to get agreement from chioma contract
-    let agreement: Option<RentAgreement> = env.invoke_contract(
-        &state.chioma_contract,
-        &soroban_sdk::symbol_short!("get_agr"),
-        soroban_sdk::vec![env, agreement_id.clone().into()],
-    );
-
-    let agreement = agreement.ok_or(DisputeError::AgreementNotFound)?;
+    let agreement = ChiomaClient::new(env, &peers::chioma(env, &state)?)
+        .get_agreement(&agreement_id)
+        .ok_or(DisputeError::AgreementNotFound)?;
 
     // Validate agreement is in Active status
     if agreement.status != AgreementStatus::Active {
@@ -201,7 +206,7 @@ pub fn raise_dispute(
     }
 
     // Validate raiser is either tenant or landlord
-    if raiser != agreement.tenant && raiser != agreement.landlord {
+    if raiser != agreement.user && raiser != agreement.admin {
         return Err(DisputeError::Unauthorized);
     }
 
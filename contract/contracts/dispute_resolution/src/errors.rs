@@ -32,4 +32,6 @@ pub enum DisputeError {
     InvalidRating = 26,
     RateLimitExceeded = 27,
     CooldownNotMet = 28,
+    PeerNotRegistered = 29,
+    InvalidRateLimitConfig = 30,
 }
@@ -1,4 +1,4 @@
-use soroban_sdk::{contractevent, Address, Env, String};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String};
 
 use crate::types::DisputeOutcome;
 
@@ -77,6 +77,25 @@ pub struct TimeoutConfigUpdated {
     pub updated_at: u64,
 }
 
+/// Event emitted when the rate limit configuration is updated
+/// Topics: ["rate_limit_config_updated"]
+#[contractevent(topics = ["rate_limit_config_updated"])]
+pub struct RateLimitConfigUpdated {
+    pub max_calls_per_block: u32,
+    pub max_calls_per_user_per_day: u32,
+    pub cooldown_blocks: u32,
+}
+
+/// Event emitted when peers start being resolved through a protocol directory
+/// Topics: ["directory_set", admin: Address]
+#[contractevent(topics = ["directory_set"])]
+pub struct DirectorySet {
+    #[topic]
+    pub admin: Address,
+    pub directory: Address,
+    pub set_at: u64,
+}
+
 /// Event emitted when an arbiter's stats are updated (manual override)
 /// Topics: ["arbiter_stats_set", arbiter: Address, admin: Address]
 #[contractevent(topics = ["arbiter_stats_set"])]
@@ -184,6 +203,17 @@ pub struct UpgradeExecuted {
     pub proposal_id: String,
     pub executor: Address,
     pub executed_at: u64,
+    pub wasm_hash: BytesN<32>,
+    pub version: u32,
+}
+
+/// Event emitted when stored data is migrated after an upgrade
+/// Topics: ["storage_migrated"]
+#[contractevent(topics = ["storage_migrated"])]
+pub struct StorageMigrated {
+    pub contract_version: u32,
+    pub from_version: u32,
+    pub to_version: u32,
 }
 
 /// Helper function to emit contract initialized event
@@ -271,6 +301,31 @@ pub(crate) fn timeout_config_updated(
     .publish(env);
 }
 
+/// Helper function to emit rate limit config updated event
+pub(crate) fn rate_limit_config_updated(
+    env: &Env,
+    max_calls_per_block: u32,
+    max_calls_per_user_per_day: u32,
+    cooldown_blocks: u32,
+) {
+    RateLimitConfigUpdated {
+        max_calls_per_block,
+        max_calls_per_user_per_day,
+        cooldown_blocks,
+    }
+    .publish(env);
+}
+
+/// Helper function to emit directory set event
+pub(crate) fn directory_set(env: &Env, admin: Address, directory: Address) {
+    DirectorySet {
+        admin,
+        directory,
+        set_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
 /// Helper function to emit arbiter stats set event
 pub(crate) fn arbiter_stats_set(
     env: &Env,
@@ -383,11 +438,33 @@ pub(crate) fn upgrade_approved(
 }
 
 /// Helper function to emit upgrade executed event
-pub(crate) fn upgrade_executed(env: &Env, proposal_id: String, executor: Address) {
+pub(crate) fn upgrade_executed(
+    env: &Env,
+    proposal_id: String,
+    executor: Address,
+    wasm_hash: BytesN<32>,
+    version: u32,
+) {
     UpgradeExecuted {
         proposal_id,
         executor,
         executed_at: env.ledger().timestamp(),
+        wasm_hash,
+        version,
+    }
+    .publish(env);
+}
+
+pub(crate) fn storage_migrated(
+    env: &Env,
+    contract_version: u32,
+    from_version: u32,
+    to_version: u32,
+) {
+    StorageMigrated {
+        contract_version,
+        from_version,
+        to_version,
     }
     .publish(env);
 }
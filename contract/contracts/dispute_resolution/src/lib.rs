@@ -5,6 +5,7 @@ use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
 mod dispute;
 mod errors;
 mod events;
+mod peers;
 mod rate_limit;
 mod storage;
 mod types;
@@ -24,13 +25,15 @@ pub use dispute::{
     get_arbiter_count, get_dispute, get_dispute_votes_weighted, get_timeout_config, get_vote,
     get_voting_weight, raise_dispute, resolve_appeal, resolve_dispute, resolve_dispute_on_timeout,
     resolve_dispute_weighted, set_arbiter_stats, set_timeout_config, vote_on_appeal,
-    vote_on_dispute, vote_on_dispute_weighted,
+    vote_on_dispute, vote_on_dispute_weighted, ChiomaClient, ChiomaInterface,
 };
 pub use errors::DisputeError;
+pub use peers::{get_directory, set_directory, Component, DirectoryClient, DirectoryInterface};
 pub use storage::DataKey;
 pub use types::{
     AppealStatus, AppealVote, Arbiter, ArbiterStats, ContractState, Dispute, DisputeAppeal,
-    DisputeOutcome, TimeoutConfig, Vote, VotingWeight, WeightedDisputeVotes, WeightedVote,
+    DisputeOutcome, RateLimitConfig, RateLimitMode, TimeoutConfig, Vote, VotingWeight,
+    WeightedDisputeVotes, WeightedVote,
 };
 
 #[contract]
@@ -43,7 +46,8 @@ impl DisputeResolutionContract {
     /// # Arguments
     /// * `admin` - The address that will have admin privileges to add arbiters
     /// * `min_votes_required` - Minimum number of votes required to resolve a dispute (default: 3)
-    /// * `chioma_contract` - Address of the chioma rental agreement contract
+    /// * `chioma_contract` - Address of the chioma rental agreement contract,
+    ///   used until a protocol directory is set
     ///
     /// # Errors
     /// * `AlreadyInitialized` - If the contract has already been initialized
@@ -113,6 +117,7 @@ impl DisputeResolutionContract {
     /// * `DisputeAlreadyExists` - If a dispute already exists for this agreement
     /// * `InvalidDetailsHash` - If the details hash is empty
     /// * `Unauthorized` - If raiser is not a party to the agreement
+    /// * `PeerNotRegistered` - If the protocol directory has no chioma entry
     pub fn raise_dispute(
         env: Env,
         raiser: Address,
@@ -210,6 +215,25 @@ impl DisputeResolutionContract {
         dispute::get_vote(&env, agreement_id, arbiter)
     }
 
+    /// Resolve the chioma contract through a protocol directory instead of
+    /// the address given at initialization (admin only).
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address performing the action
+    /// * `directory` - The protocol directory contract
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the contract hasn't been initialized
+    /// * `Unauthorized` - If the caller is not the admin
+    pub fn set_directory(env: Env, admin: Address, directory: Address) -> Result<(), DisputeError> {
+        peers::set_directory(&env, admin, directory)
+    }
+
+    /// Get the protocol directory peers are resolved through, if set.
+    pub fn get_directory(env: Env) -> Option<Address> {
+        peers::get_directory(&env)
+    }
+
     pub fn set_timeout_config(
         env: Env,
         admin: Address,
@@ -222,6 +246,25 @@ impl DisputeResolutionContract {
         dispute::get_timeout_config(&env)
     }
 
+    /// Set the rate limit configuration (admin only).
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the contract hasn't been initialized
+    /// * `Unauthorized` - If the caller is not the admin
+    /// * `InvalidRateLimitConfig` - If a timestamp-based mode has no window
+    pub fn set_rate_limit_config(
+        env: Env,
+        admin: Address,
+        config: RateLimitConfig,
+    ) -> Result<(), DisputeError> {
+        rate_limit::set_rate_limit_config(&env, admin, config)
+    }
+
+    /// Get the rate limit configuration.
+    pub fn get_rate_limit_config(env: Env) -> RateLimitConfig {
+        rate_limit::get_rate_limit_config(&env)
+    }
+
     pub fn create_appeal(
         env: Env,
         appellant: Address,
@@ -307,7 +350,7 @@ impl DisputeResolutionContract {
         env: Env,
         proposer: Address,
         proposal_id: String,
-        wasm_hash: soroban_sdk::Bytes,
+        wasm_hash: soroban_sdk::BytesN<32>,
         notes: String,
         delay_seconds: u64,
     ) -> Result<(), DisputeError> {
@@ -339,4 +382,19 @@ impl DisputeResolutionContract {
     ) -> Result<upgrade::UpgradeProposal, DisputeError> {
         upgrade::get_upgrade_proposal(&env, proposal_id)
     }
+
+    /// Run pending storage migrations after an upgrade (admin only).
+    pub fn migrate(env: Env, caller: Address) -> Result<u32, DisputeError> {
+        upgrade::migrate(&env, caller)
+    }
+
+    /// Get the code version, bumped by every executed upgrade.
+    pub fn get_contract_version(env: Env) -> u32 {
+        upgrade::get_contract_version(&env)
+    }
+
+    /// Get the layout version of the stored data.
+    pub fn get_storage_version(env: Env) -> u32 {
+        upgrade::get_storage_version(&env)
+    }
 }
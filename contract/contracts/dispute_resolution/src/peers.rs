@@ -0,0 +1,63 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+use crate::errors::DisputeError;
+use crate::events;
+use crate::storage::DataKey;
+use crate::types::ContractState;
+
+/// The protocol directory's `Component`, mirrored to name peers.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Component {
+    AgentRegistry,
+    Chioma,
+    DisputeResolution,
+    Escrow,
+    Payment,
+    PropertyRegistry,
+    RentObligation,
+    UserProfile,
+}
+
+/// The part of the protocol directory peers are resolved through.
+#[contractclient(name = "DirectoryClient")]
+pub trait DirectoryInterface {
+    fn resolve(env: Env, component: Component) -> Option<Address>;
+}
+
+/// Resolve peers through `directory` from now on (admin only).
+pub fn set_directory(env: &Env, admin: Address, directory: Address) -> Result<(), DisputeError> {
+    let state: ContractState = env
+        .storage()
+        .instance()
+        .get(&DataKey::State)
+        .ok_or(DisputeError::NotInitialized)?;
+
+    admin.require_auth();
+    if admin != state.admin {
+        return Err(DisputeError::Unauthorized);
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::Directory, &directory);
+    env.storage().instance().extend_ttl(500000, 500000);
+
+    events::directory_set(env, admin, directory);
+    Ok(())
+}
+
+pub fn get_directory(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Directory)
+}
+
+/// The chioma contract: the directory's entry once a directory is set,
+/// otherwise the address given at initialization.
+pub(crate) fn chioma(env: &Env, state: &ContractState) -> Result<Address, DisputeError> {
+    match get_directory(env) {
+        Some(directory) => DirectoryClient::new(env, &directory)
+            .resolve(&Component::Chioma)
+            .ok_or(DisputeError::PeerNotRegistered),
+        None => Ok(state.chioma_contract.clone()),
+    }
+}
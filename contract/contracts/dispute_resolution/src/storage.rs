@@ -24,5 +24,10 @@ pub enum DataKey {
     RateLimitConfig,
     UserCallCount(Address, String),
     BlockCallCount(u64, String),
+    RateWindow(Address, String),
     UpgradeProposal(String),
+    ContractVersion,
+    StorageVersion,
+    MigratedVersion,
+    Directory,
 }
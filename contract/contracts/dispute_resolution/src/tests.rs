@@ -27,6 +27,15 @@ fn inject_open_dispute(env: &Env, client: &DisputeResolutionContractClient, disp
     });
 }
 
+/// Minimal WASM module the host accepts as contract code.
+const UPGRADE_WASM: &[u8] = b"\0asm\x01\0\0\0\0\x1e\x11contractenvmetav0\0\0\0\0\0\0\0\x17\0\0\0\0";
+
+/// Upload `UPGRADE_WASM` so an upgrade to its hash can be executed.
+pub(crate) fn upload_upgrade_wasm(env: &Env) -> soroban_sdk::BytesN<32> {
+    env.deployer()
+        .upload_contract_wasm(soroban_sdk::Bytes::from_slice(env, UPGRADE_WASM))
+}
+
 fn create_contract(env: &Env) -> DisputeResolutionContractClient<'_> {
     let contract_id = env.register(DisputeResolutionContract, ());
     DisputeResolutionContractClient::new(env, &contract_id)
@@ -756,3 +765,43 @@ fn test_dispute_timeout_not_reached() {
     let result = client.try_resolve_dispute_on_timeout(&agreement_id);
     assert_eq!(result, Err(Ok(DisputeError::TimeoutNotReached)));
 }
+
+#[test]
+fn test_execute_upgrade_installs_wasm_and_migrates_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &3, &Address::generate(&env));
+
+    let proposal_id = String::from_str(&env, "UPG-001");
+    let wasm_hash = upload_upgrade_wasm(&env);
+    client.propose_upgrade(
+        &admin,
+        &proposal_id,
+        &wasm_hash,
+        &String::from_str(&env, "v2"),
+        &0,
+    );
+
+    // Nothing to migrate before an upgrade.
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(DisputeError::AlreadyInitialized))
+    );
+
+    client.execute_upgrade(&admin, &proposal_id);
+
+    // The installed module exports nothing; reinstall the native contract to
+    // stand in for the new code.
+    assert!(client.try_get_contract_version().is_err());
+    env.register_at(&client.address, DisputeResolutionContract, ());
+
+    assert_eq!(client.get_contract_version(), 2);
+    assert_eq!(client.migrate(&admin), 1);
+    assert_eq!(client.get_storage_version(), 1);
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(DisputeError::AlreadyInitialized))
+    );
+}
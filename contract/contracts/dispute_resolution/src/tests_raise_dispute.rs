@@ -1,8 +1,9 @@
 use crate::dispute::{AgreementStatus, RentAgreement};
 use crate::{
-    DisputeError, DisputeOutcome, DisputeResolutionContract, DisputeResolutionContractClient,
+    Component, DisputeError, DisputeOutcome, DisputeResolutionContract,
+    DisputeResolutionContractClient,
 };
-use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, Map, String};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, String, Vec};
 
 fn create_contract(env: &Env) -> DisputeResolutionContractClient<'_> {
     let contract_id = env.register(DisputeResolutionContract, ());
@@ -10,7 +11,7 @@ fn create_contract(env: &Env) -> DisputeResolutionContractClient<'_> {
 }
 
 /// Minimal Chioma stand-in used to validate `dispute_resolution::raise_dispute`'s
-/// cross-contract agreement fetch (`symbol_short!("get_agr")`).
+/// cross-contract agreement fetch (chioma's `get_agreement`).
 ///
 /// Storage layout:
 /// - instance key: `agreement_id` -> `RentAgreement`
@@ -19,7 +20,7 @@ pub struct MockChiomaContract;
 
 #[contractimpl]
 impl MockChiomaContract {
-    pub fn get_agr(env: Env, agreement_id: String) -> Option<RentAgreement> {
+    pub fn get_agreement(env: Env, agreement_id: String) -> Option<RentAgreement> {
         env.storage().instance().get(&agreement_id)
     }
 }
@@ -28,6 +29,27 @@ fn deploy_mock_chioma(env: &Env) -> Address {
     env.register(MockChiomaContract, ())
 }
 
+/// Minimal protocol directory stand-in: instance key `Component` -> `Address`.
+#[contract]
+pub struct MockDirectoryContract;
+
+#[contractimpl]
+impl MockDirectoryContract {
+    pub fn resolve(env: Env, component: Component) -> Option<Address> {
+        env.storage().instance().get(&component)
+    }
+}
+
+fn deploy_mock_directory(env: &Env, chioma: Option<&Address>) -> Address {
+    let directory = env.register(MockDirectoryContract, ());
+    if let Some(chioma) = chioma {
+        env.as_contract(&directory, || {
+            env.storage().instance().set(&Component::Chioma, chioma);
+        });
+    }
+    directory
+}
+
 fn put_agreement(env: &Env, chioma: &Address, agreement: &RentAgreement) {
     env.as_contract(chioma, || {
         env.storage()
@@ -46,8 +68,8 @@ fn sample_agreement(
     let token = Address::generate(env);
     RentAgreement {
         agreement_id: agreement_id.clone(),
-        landlord: landlord.clone(),
-        tenant: tenant.clone(),
+        admin: landlord.clone(),
+        user: tenant.clone(),
         agent: None,
         monthly_rent: 1_000,
         security_deposit: 2_000,
@@ -58,9 +80,11 @@ fn sample_agreement(
         total_rent_paid: 0,
         payment_count: 0,
         signed_at: None,
+        witness_id: None,
         payment_token: token,
         next_payment_due: 0,
-        payment_history: Map::new(env),
+        metadata_uri: String::from_str(env, "ipfs://agreement"),
+        attributes: Vec::new(env),
     }
 }
 
@@ -390,3 +414,76 @@ fn resolve_dispute_insufficient_votes_after_raise_dispute() {
     let resolved = client.try_resolve_dispute(&agreement_id);
     assert_eq!(resolved, Err(Ok(DisputeError::InsufficientVotes)));
 }
+
+#[test]
+fn raise_dispute_resolves_chioma_through_directory() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    let retired = deploy_mock_chioma(&env);
+    let chioma = deploy_mock_chioma(&env);
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let agreement_id = String::from_str(&env, "agr-redeployed");
+    let agreement = sample_agreement(
+        &env,
+        &agreement_id,
+        &landlord,
+        &tenant,
+        AgreementStatus::Active,
+    );
+    put_agreement(&env, &chioma, &agreement);
+
+    client.initialize(&admin, &3, &retired);
+    let directory = deploy_mock_directory(&env, Some(&chioma));
+    client.set_directory(&admin, &directory);
+    assert_eq!(client.get_directory(), Some(directory));
+
+    let details_hash = String::from_str(&env, "QmDetails");
+    client.raise_dispute(&tenant, &agreement_id, &details_hash);
+    assert!(client.get_dispute(&agreement_id).is_some());
+}
+
+#[test]
+fn raise_dispute_fails_when_directory_lacks_chioma() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    let chioma = deploy_mock_chioma(&env);
+
+    client.initialize(&admin, &3, &chioma);
+    client.set_directory(&admin, &deploy_mock_directory(&env, None));
+
+    let tenant = Address::generate(&env);
+    let agreement_id = String::from_str(&env, "agr-1");
+    let details_hash = String::from_str(&env, "QmDetails");
+    let result = client.try_raise_dispute(&tenant, &agreement_id, &details_hash);
+    assert_eq!(result, Err(Ok(DisputeError::PeerNotRegistered)));
+}
+
+#[test]
+fn set_directory_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    let directory = deploy_mock_directory(&env, None);
+    assert_eq!(
+        client.try_set_directory(&admin, &directory),
+        Err(Ok(DisputeError::NotInitialized))
+    );
+
+    client.initialize(&admin, &3, &deploy_mock_chioma(&env));
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_set_directory(&outsider, &directory),
+        Err(Ok(DisputeError::Unauthorized))
+    );
+    assert_eq!(client.get_directory(), None);
+}
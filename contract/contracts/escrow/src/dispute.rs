@@ -7,6 +7,7 @@ use crate::errors::EscrowError;
 use crate::events;
 use crate::storage::EscrowStorage;
 use crate::types::EscrowStatus;
+use crate::yield_vault;
 
 /// Dispute handling and resolution.
 pub struct DisputeHandler;
@@ -104,6 +105,7 @@ impl DisputeHandler {
         }
 
         // EFFECTS: Update status and clear dispute
+        yield_vault::unwind(&env, &mut escrow);
         escrow.status = EscrowStatus::Released;
         escrow.disputed_at = None;
         escrow.dispute_reason = None;
@@ -154,6 +156,7 @@ impl DisputeHandler {
             return Err(EscrowError::TimeoutNotReached);
         }
 
+        yield_vault::unwind(&env, &mut escrow);
         escrow.status = EscrowStatus::Refunded;
         escrow.disputed_at = None;
         escrow.dispute_reason = None;
@@ -50,4 +50,24 @@ pub enum EscrowError {
     EmptyFreezeReason = 21,
     /// System admin not set
     AdminNotSet = 22,
+    /// The agreement does not exist in chioma
+    AgreementNotFound = 23,
+    /// Parties, token or amount differ from the agreement's
+    AgreementMismatch = 24,
+    /// The agreement is already secured by an escrow
+    AgreementAlreadyEscrowed = 25,
+    /// Chioma is not registered in the protocol directory, or no directory is set
+    PeerNotRegistered = 26,
+    /// The escrow has no installment plan
+    NoInstallmentPlan = 27,
+    /// Installments are empty, too many, out of order, already due or do
+    /// not add up to the escrow amount
+    InvalidInstallmentPlan = 28,
+    /// An installment's due date has passed without it being paid
+    InstallmentDeadlinePassed = 29,
+    /// A timestamp-based rate limit mode was configured without a window
+    InvalidRateLimitConfig = 30,
+    /// The agreement's escrow is being funded in installments that are not
+    /// all paid yet
+    InstallmentsOutstanding = 31,
 }
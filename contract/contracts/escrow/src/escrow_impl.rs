@@ -1,15 +1,20 @@
 //! Core escrow lifecycle logic: creation, funding, approvals, and release.
 //! Implements checks-effects-interactions pattern for reentrancy safety.
-use soroban_sdk::{contract, contractimpl, token, xdr::ToXdr, Address, BytesN, Env, String};
+use soroban_sdk::{contract, contractimpl, token, xdr::ToXdr, Address, BytesN, Env, String, Vec};
 
 use crate::access::AccessControl;
 use crate::dispute::DisputeHandler;
 use crate::errors::EscrowError;
 use crate::events;
+use crate::peers;
 use crate::rate_limit;
 use crate::storage::EscrowStorage;
-use crate::types::{Escrow, EscrowStatus, ReleaseApproval, ReleaseRecord, TimeoutConfig};
+use crate::types::{
+    Escrow, EscrowStatus, Installment, RateLimitConfig, RecordMigrationBatch, ReleaseApproval,
+    ReleaseRecord, TimeoutConfig, VaultPosition, MAX_INSTALLMENTS,
+};
 use crate::upgrade;
+use crate::yield_vault;
 
 /// Core escrow contract implementation.
 #[contract]
@@ -17,15 +22,24 @@ pub struct EscrowContract;
 
 #[contractimpl]
 impl EscrowContract {
-    /// Create a new escrow.
+    /// Create a new escrow holding the security deposit of `agreement_id`.
     ///
     /// CHECKS:
     /// - Amount must be positive
     /// - All addresses must be distinct
+    /// - The agreement must exist in the chioma contract registered in the
+    ///   protocol directory, with `depositor` as tenant, `beneficiary` as
+    ///   landlord, `token` as payment token and `amount` as security deposit
+    /// - The agreement must not already be secured by an escrow, unless its
+    ///   escrow is still Pending with nothing deposited and no installment
+    ///   plan running, or its missed installments were refunded
+    /// - Depositor must authorize, so nobody else can claim the agreement
     ///
     /// EFFECTS:
+    /// - Closes the unfunded escrow being replaced, if any, as Refunded
     /// - Creates new Escrow with Pending status
     /// - Stores escrow in persistent storage
+    /// - Links the agreement to the escrow
     /// - Increments escrow counter
     ///
     /// INTERACTIONS:
@@ -33,6 +47,7 @@ impl EscrowContract {
     ///   would happen after state update
     pub fn create(
         env: Env,
+        agreement_id: String,
         depositor: Address,
         beneficiary: Address,
         arbiter: Address,
@@ -42,53 +57,130 @@ impl EscrowContract {
         token: Address,
     ) -> Result<BytesN<32>, EscrowError> {
         // CHECKS: Validate inputs
-        if amount <= 0 {
-            return Err(EscrowError::InsufficientFunds);
+        let replaced = check_new_escrow(
+            &env,
+            &agreement_id,
+            &depositor,
+            &beneficiary,
+            &arbiter,
+            amount,
+        )?;
+
+        // The escrow must hold exactly the deposit the agreement calls for
+        let agreement = peers::agreement(&env, &agreement_id)?;
+        if agreement.user != depositor
+            || agreement.admin != beneficiary
+            || agreement.payment_token != token
+            || agreement.security_deposit != amount
+        {
+            return Err(EscrowError::AgreementMismatch);
         }
+        depositor.require_auth();
 
-        // Ensure primary parties are distinct
-        if depositor == beneficiary || depositor == arbiter || beneficiary == arbiter {
-            return Err(EscrowError::InvalidSigner);
+        // EFFECTS: Create and save escrow
+        if let Some(replaced) = replaced {
+            close_unfunded(&env, replaced);
         }
+        let escrow = open_escrow(
+            &env,
+            agreement_id,
+            depositor,
+            beneficiary,
+            arbiter,
+            platform_governance,
+            agent_referral,
+            amount,
+            token,
+        );
 
-        // Generate unique escrow ID from hash of parameters
-        let mut data = soroban_sdk::Bytes::new(&env);
-        data.append(&depositor.clone().to_xdr(&env));
-        data.append(&beneficiary.clone().to_xdr(&env));
-        data.append(&arbiter.clone().to_xdr(&env));
-        data.append(&amount.to_xdr(&env));
-        data.append(&token.clone().to_xdr(&env));
-        data.append(&env.ledger().timestamp().to_xdr(&env));
-
-        let escrow_id: BytesN<32> = env.crypto().sha256(&data).into();
+        Ok(escrow.id)
+    }
 
-        // EFFECTS: Create and save escrow
-        let escrow = Escrow {
-            id: escrow_id.clone(),
-            depositor: depositor.clone(),
-            beneficiary: beneficiary.clone(),
-            arbiter: arbiter.clone(),
-            platform_governance: platform_governance.clone(),
-            agent_referral: agent_referral.clone(),
+    /// Create and fund the escrow of `agreement_id` in one call, for the
+    /// chioma contract activating the agreement. `amount` is the security
+    /// deposit only; chioma collects rent as ordinary payments.
+    ///
+    /// An escrow the depositor already funded for the agreement, in full or
+    /// through installments, is adopted instead: it takes chioma's arbiter
+    /// and fee recipients, its id is returned and nothing is transferred.
+    ///
+    /// CHECKS:
+    /// - Caller must be the chioma contract registered in the protocol
+    ///   directory; it vouches for the parties, token and amount, so the
+    ///   agreement is not read back
+    /// - An adopted escrow must have the same depositor, beneficiary, amount
+    ///   and token
+    /// - Amount must be positive
+    /// - All addresses must be distinct
+    /// - The agreement must not already be secured by an escrow, unless its
+    ///   escrow is still Pending with nothing deposited and no installment
+    ///   plan running, or its missed installments were refunded; an escrow
+    ///   with installments still to pay is rejected with
+    ///   `InstallmentsOutstanding`
+    ///
+    /// EFFECTS:
+    /// - Adopting: replaces the arbiter and fee recipients and clears release
+    ///   approvals given before activation
+    /// - Otherwise closes the unfunded escrow being replaced, if any, as
+    ///   Refunded, and creates the escrow, linked to the agreement, with
+    ///   Funded status
+    ///
+    /// INTERACTIONS:
+    /// - Token transfer from depositor to escrow contract after state update,
+    ///   unless adopting
+    pub fn create_funded(
+        env: Env,
+        agreement_id: String,
+        depositor: Address,
+        beneficiary: Address,
+        arbiter: Address,
+        platform_governance: Address,
+        agent_referral: Address,
+        amount: i128,
+        token: Address,
+    ) -> Result<BytesN<32>, EscrowError> {
+        // CHECKS
+        peers::chioma(&env)?.require_auth();
+        if let Some(escrow) = EscrowStorage::get_agreement_escrow(&env, &agreement_id)
+            .and_then(|id| EscrowStorage::get(&env, &id))
+            .filter(|escrow| escrow.status == EscrowStatus::Funded)
+        {
+            if escrow.depositor != depositor
+                || escrow.beneficiary != beneficiary
+                || escrow.amount != amount
+                || escrow.token != token
+            {
+                return Err(EscrowError::AgreementMismatch);
+            }
+            if arbiter == depositor || arbiter == beneficiary {
+                return Err(EscrowError::InvalidSigner);
+            }
+            return Ok(adopt_funded(
+                &env,
+                escrow,
+                arbiter,
+                platform_governance,
+                agent_referral,
+            ));
+        }
+        let replaced = check_new_escrow(
+            &env,
+            &agreement_id,
+            &depositor,
+            &beneficiary,
+            &arbiter,
             amount,
-            token: token.clone(),
-            status: EscrowStatus::Pending,
-            created_at: env.ledger().timestamp(),
-            timeout_days: EscrowStorage::get_timeout_config(&env).escrow_timeout_days,
-            disputed_at: None,
-            dispute_reason: None,
-            is_frozen: false,
-            frozen_at: None,
-            freeze_reason: None,
-        };
-
-        EscrowStorage::save(&env, &escrow);
-        EscrowStorage::increment_count(&env);
+        )?;
+        depositor.require_auth();
 
-        events::escrow_created(
+        // EFFECTS
+        if let Some(replaced) = replaced {
+            close_unfunded(&env, replaced);
+        }
+        let mut escrow = open_escrow(
             &env,
-            escrow_id.clone(),
-            depositor,
+            agreement_id,
+            depositor.clone(),
             beneficiary,
             arbiter,
             platform_governance,
@@ -96,8 +188,60 @@ impl EscrowContract {
             amount,
             token,
         );
+        escrow.status = EscrowStatus::Funded;
+        EscrowStorage::save(&env, &escrow);
+
+        // INTERACTIONS
+        let token_client = token::Client::new(&env, &escrow.token);
+        token_client.transfer(&depositor, env.current_contract_address(), &amount);
+
+        events::escrow_funded(&env, escrow.id.clone(), depositor, amount);
+
+        Ok(escrow.id)
+    }
+
+    /// Hand the escrow of `agreement_id` to `beneficiary`, for the chioma
+    /// contract settling a sale of the agreement's landlord position.
+    ///
+    /// CHECKS:
+    /// - Caller must be the chioma contract registered in the protocol
+    ///   directory
+    /// - The agreement must be linked to an escrow
+    /// - The new beneficiary must be neither the depositor nor the arbiter
+    ///
+    /// EFFECTS:
+    /// - Replaces the beneficiary
+    /// - Clears release approvals given while the previous landlord was
+    ///   beneficiary
+    pub fn transfer_beneficiary(
+        env: Env,
+        agreement_id: String,
+        beneficiary: Address,
+    ) -> Result<(), EscrowError> {
+        // CHECKS
+        peers::chioma(&env)?.require_auth();
+        let escrow_id = EscrowStorage::get_agreement_escrow(&env, &agreement_id)
+            .ok_or(EscrowError::EscrowNotFound)?;
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        if beneficiary == escrow.depositor || beneficiary == escrow.arbiter {
+            return Err(EscrowError::InvalidSigner);
+        }
+
+        // EFFECTS
+        let previous = escrow.beneficiary.clone();
+        EscrowStorage::clear_approvals(&env, &escrow_id);
+        let targets = [previous.clone(), escrow.depositor.clone()];
+        let signers = [
+            escrow.depositor.clone(),
+            previous.clone(),
+            escrow.arbiter.clone(),
+        ];
+        EscrowStorage::clear_approval_counts(&env, &escrow_id, &targets, &signers);
+        escrow.beneficiary = beneficiary.clone();
+        EscrowStorage::save(&env, &escrow);
 
-        Ok(escrow_id)
+        events::beneficiary_transferred(&env, escrow_id, previous, beneficiary);
+        Ok(())
     }
 
     /// Fund an existing escrow by depositing funds.
@@ -132,8 +276,11 @@ impl EscrowContract {
         // Authorize the deposit
         caller.require_auth();
 
-        // EFFECTS: Update status
+        // EFFECTS: Update status; paying in full settles any installment plan
         escrow.status = EscrowStatus::Funded;
+        if !escrow.installments.is_empty() {
+            escrow.installments_funded = escrow.amount;
+        }
         EscrowStorage::save(&env, &escrow);
 
         // INTERACTIONS: Token transfer from depositor to escrow contract
@@ -145,6 +292,152 @@ impl EscrowContract {
         Ok(())
     }
 
+    /// Let the depositor fund a Pending escrow in installments, on a
+    /// schedule both parties agree to.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist, be Pending and not frozen
+    /// - Between 1 and `MAX_INSTALLMENTS` installments, each positive, due
+    ///   in the future and after the previous one, adding up to the escrow
+    ///   amount
+    /// - Depositor and beneficiary must both authorize
+    ///
+    /// EFFECTS:
+    /// - Replaces any earlier plan; each installment must be paid by its
+    ///   due date
+    pub fn set_installment_plan(
+        env: Env,
+        escrow_id: BytesN<32>,
+        schedule: Vec<Installment>,
+    ) -> Result<(), EscrowError> {
+        // CHECKS
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::require_not_frozen(&escrow)?;
+        if escrow.status != EscrowStatus::Pending {
+            return Err(EscrowError::InvalidState);
+        }
+        check_schedule(&env, &schedule, escrow.amount)?;
+        escrow.depositor.require_auth();
+        escrow.beneficiary.require_auth();
+
+        // EFFECTS
+        let installments = schedule.len();
+        let deadline = schedule.last().map(|due| due.due_at).unwrap_or_default();
+        escrow.installments = schedule;
+        escrow.installments_funded = 0;
+        EscrowStorage::save(&env, &escrow);
+
+        events::installments_scheduled(&env, escrow_id, installments, deadline);
+        Ok(())
+    }
+
+    /// Deposit one installment. The escrow is PartiallyFunded until the
+    /// installments reach its amount, then Funded.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist, be funded in installments and not be frozen
+    /// - Escrow must be Pending or PartiallyFunded
+    /// - Caller must be the depositor
+    /// - No installment may have been missed: what is deposited must cover
+    ///   every installment whose due date has passed
+    /// - Amount must be positive and not exceed what is still owed
+    ///
+    /// EFFECTS:
+    /// - Adds the amount to `installments_funded` and updates the status
+    ///
+    /// INTERACTIONS:
+    /// - Token transfer from depositor to escrow contract after state update
+    pub fn fund_installment(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        amount: i128,
+    ) -> Result<(), EscrowError> {
+        // CHECKS
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_depositor(&escrow, &caller)?;
+        AccessControl::require_not_frozen(&escrow)?;
+        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::PartiallyFunded
+        {
+            return Err(EscrowError::InvalidState);
+        }
+        if escrow.installments.is_empty() {
+            return Err(EscrowError::NoInstallmentPlan);
+        }
+        if escrow.installment_missed(env.ledger().timestamp()) {
+            return Err(EscrowError::InstallmentDeadlinePassed);
+        }
+        if amount <= 0 || amount > escrow.amount - escrow.installments_funded {
+            return Err(EscrowError::InvalidAmount);
+        }
+        caller.require_auth();
+
+        // EFFECTS
+        escrow.installments_funded += amount;
+        let funded = escrow.installments_funded;
+        escrow.status = if funded == escrow.amount {
+            EscrowStatus::Funded
+        } else {
+            EscrowStatus::PartiallyFunded
+        };
+        EscrowStorage::save(&env, &escrow);
+
+        // INTERACTIONS
+        let token_client = token::Client::new(&env, &escrow.token);
+        token_client.transfer(&caller, env.current_contract_address(), &amount);
+
+        events::installment_paid(&env, escrow_id.clone(), caller.clone(), amount, funded);
+        if escrow.status == EscrowStatus::Funded {
+            events::escrow_funded(&env, escrow_id, caller, escrow.amount);
+        }
+        Ok(())
+    }
+
+    /// Refund the installments of an escrow whose depositor missed an
+    /// installment. Callable by anyone, so keepers can sweep missed plans.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist, be PartiallyFunded and not be frozen
+    /// - An installment's due date must have passed without it being paid
+    ///
+    /// EFFECTS:
+    /// - Escrow status → Refunded
+    ///
+    /// INTERACTIONS:
+    /// - Token transfer of everything deposited back to the depositor
+    pub fn refund_installments_on_timeout(
+        env: Env,
+        escrow_id: BytesN<32>,
+    ) -> Result<(), EscrowError> {
+        // CHECKS
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::require_not_frozen(&escrow)?;
+        if escrow.status != EscrowStatus::PartiallyFunded {
+            return Err(EscrowError::InvalidState);
+        }
+        if escrow.installments.is_empty() {
+            return Err(EscrowError::NoInstallmentPlan);
+        }
+        if !escrow.installment_missed(env.ledger().timestamp()) {
+            return Err(EscrowError::TimeoutNotReached);
+        }
+
+        // EFFECTS
+        escrow.status = EscrowStatus::Refunded;
+        EscrowStorage::save(&env, &escrow);
+
+        // INTERACTIONS
+        let token_client = token::Client::new(&env, &escrow.token);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &escrow.depositor,
+            &escrow.installments_funded,
+        );
+
+        events::installments_refunded(&env, escrow_id, escrow.installments_funded);
+        Ok(())
+    }
+
     /// Approve release of funds to a target address.
     /// Implements 2-of-3 multi-sig: executes transfer when ≥2 unique signers approve same target.
     ///
@@ -220,6 +513,8 @@ impl EscrowContract {
         if approval_count >= 2 {
             let mut escrow_to_update =
                 EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+            yield_vault::unwind(&env, &mut escrow_to_update);
+            let amount = escrow_to_update.amount;
 
             // Determine final status based on release target
             escrow_to_update.status = EscrowStatus::Released;
@@ -237,15 +532,15 @@ impl EscrowContract {
 
             // INTERACTIONS: Token transfer from escrow contract to release target
             let token_client = token::Client::new(&env, &escrow.token);
-            token_client.transfer(&env.current_contract_address(), &release_to, &escrow.amount);
+            token_client.transfer(&env.current_contract_address(), &release_to, &amount);
 
             let landlord_amount = if release_to == escrow.beneficiary {
-                escrow.amount
+                amount
             } else {
                 0
             };
             let tenant_amount = if release_to == escrow.depositor {
-                escrow.amount
+                amount
             } else {
                 0
             };
@@ -291,6 +586,7 @@ impl EscrowContract {
             return Err(EscrowError::TimeoutNotReached);
         }
 
+        yield_vault::unwind(&env, &mut escrow);
         escrow.status = EscrowStatus::Refunded;
         EscrowStorage::save(&env, &escrow);
 
@@ -352,12 +648,41 @@ impl EscrowContract {
         EscrowStorage::get_timeout_config(&env)
     }
 
+    /// Set the rate limit configuration (system admin only).
+    pub fn set_rate_limit_config(
+        env: Env,
+        caller: Address,
+        config: RateLimitConfig,
+    ) -> Result<(), EscrowError> {
+        AccessControl::is_system_admin(&env, &caller)?;
+        caller.require_auth();
+        rate_limit::set_rate_limit_config(&env, config)
+    }
+
+    /// Get the rate limit configuration.
+    pub fn get_rate_limit_config(env: Env) -> RateLimitConfig {
+        rate_limit::get_rate_limit_config(&env)
+    }
+
     /// Get details of an escrow.
     /// Read-only view function.
     pub fn get_escrow(env: Env, escrow_id: BytesN<32>) -> Result<Escrow, EscrowError> {
         EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)
     }
 
+    /// Get the status of an escrow, for peers that only need to know
+    /// whether it still holds funds.
+    /// Read-only view function.
+    pub fn get_escrow_status(env: Env, escrow_id: BytesN<32>) -> Result<EscrowStatus, EscrowError> {
+        let escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(escrow.status)
+    }
+
+    /// Get the escrow securing an agreement, if one was created.
+    pub fn get_agreement_escrow(env: Env, agreement_id: String) -> Option<BytesN<32>> {
+        EscrowStorage::get_agreement_escrow(&env, &agreement_id)
+    }
+
     /// Get approval count for a specific release target.
     /// Returns number of unique signers approving release to a specific address.
     /// Uses O(1) dedicated counter storage instead of iterating the approvals list.
@@ -486,6 +811,7 @@ impl EscrowContract {
         }
 
         // EFFECTS: Update escrow amount
+        yield_vault::unwind(&env, &mut escrow);
         escrow.amount -= amount;
         EscrowStorage::save(&env, &escrow);
 
@@ -578,6 +904,7 @@ impl EscrowContract {
         }
 
         // Calculate refund amount
+        yield_vault::unwind(&env, &mut escrow);
         let refund_amount = escrow.amount - damage_amount;
 
         // EFFECTS: Update escrow status (all funds will be released)
@@ -710,6 +1037,17 @@ impl EscrowContract {
         EscrowStorage::get_admin(&env)
     }
 
+    /// Resolve chioma through the protocol directory at `directory` from
+    /// now on (system admin only).
+    pub fn set_directory(env: Env, caller: Address, directory: Address) -> Result<(), EscrowError> {
+        peers::set_directory(&env, caller, directory)
+    }
+
+    /// Get the protocol directory peers are resolved through.
+    pub fn get_directory(env: Env) -> Option<Address> {
+        peers::get_directory(&env)
+    }
+
     /// Freeze an escrow to prevent all fund movements.
     /// Used in case of verified exploit, major dispute, or security incident.
     /// Only the system admin or arbiter can freeze an escrow.
@@ -854,6 +1192,7 @@ impl EscrowContract {
 
         caller.require_auth();
 
+        yield_vault::unwind(&env, &mut escrow);
         let total = escrow.amount;
         let beneficiary_share = total * 90 / 100;
         let governance_share = total * 5 / 100;
@@ -934,6 +1273,7 @@ impl EscrowContract {
         }
 
         // EFFECTS
+        yield_vault::unwind(&env, &mut escrow);
         escrow.status = EscrowStatus::Refunded;
         EscrowStorage::save(&env, &escrow);
 
@@ -949,6 +1289,53 @@ impl EscrowContract {
         Ok(())
     }
 
+    // --- Yield Vault Functions ---
+
+    /// Move a Funded escrow's deposit into `vault` to earn yield (chioma
+    /// only). Any later payout of the escrow brings it back first.
+    pub fn route_to_vault(
+        env: Env,
+        escrow_id: BytesN<32>,
+        vault: Address,
+    ) -> Result<VaultPosition, EscrowError> {
+        yield_vault::route_to_vault(&env, escrow_id, vault)
+    }
+
+    /// Pay `amount` of an escrow's vault yield to `to` (chioma only).
+    pub fn withdraw_yield(
+        env: Env,
+        escrow_id: BytesN<32>,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), EscrowError> {
+        yield_vault::withdraw_yield(&env, escrow_id, to, amount)
+    }
+
+    /// Add `amount` of an escrow's vault yield to its deposit (chioma only).
+    pub fn compound_yield(
+        env: Env,
+        escrow_id: BytesN<32>,
+        amount: i128,
+    ) -> Result<(), EscrowError> {
+        yield_vault::compound_yield(&env, escrow_id, amount)
+    }
+
+    /// Bring an escrow's deposit and unpaid yield back from its vault
+    /// (chioma only). Returns the amount withdrawn.
+    pub fn close_vault_position(env: Env, escrow_id: BytesN<32>) -> Result<i128, EscrowError> {
+        yield_vault::close_position(&env, escrow_id)
+    }
+
+    /// Get the vault position holding an escrow's deposit, if any.
+    pub fn get_vault_position(env: Env, escrow_id: BytesN<32>) -> Option<VaultPosition> {
+        yield_vault::get_position(&env, &escrow_id)
+    }
+
+    /// Get the yield an escrow's vault position has earned and not yet paid out.
+    pub fn get_vault_yield(env: Env, escrow_id: BytesN<32>) -> i128 {
+        yield_vault::vault_yield(&env, &escrow_id)
+    }
+
     // --- Upgrade Functions ---
 
     /// Propose a contract upgrade.
@@ -956,7 +1343,7 @@ impl EscrowContract {
         env: Env,
         proposer: Address,
         proposal_id: String,
-        wasm_hash: soroban_sdk::Bytes,
+        wasm_hash: soroban_sdk::BytesN<32>,
         notes: String,
         delay_seconds: u64,
     ) -> Result<(), EscrowError> {
@@ -988,4 +1375,216 @@ impl EscrowContract {
     ) -> Result<upgrade::UpgradeProposal, EscrowError> {
         upgrade::get_upgrade_proposal(&env, proposal_id)
     }
+
+    /// Run pending storage migrations after an upgrade (admin only).
+    pub fn migrate(env: Env, caller: Address) -> Result<u32, EscrowError> {
+        upgrade::migrate(&env, caller)
+    }
+
+    /// Get the code version, bumped by every executed upgrade.
+    pub fn get_contract_version(env: Env) -> u32 {
+        upgrade::get_contract_version(&env)
+    }
+
+    /// Get the layout version of the stored data.
+    pub fn get_storage_version(env: Env) -> u32 {
+        upgrade::get_storage_version(&env)
+    }
+
+    /// Rewrite the next batch of escrows in the current record layout,
+    /// starting at `cursor` (admin only).
+    pub fn migrate_records(
+        env: Env,
+        caller: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<RecordMigrationBatch, EscrowError> {
+        upgrade::migrate_records(&env, caller, cursor, limit)
+    }
+
+    /// Rewrite the listed escrows in the current record layout, for escrows
+    /// created before the creation-order index (admin only).
+    pub fn migrate_records_by_id(
+        env: Env,
+        caller: Address,
+        escrow_ids: Vec<BytesN<32>>,
+    ) -> Result<u32, EscrowError> {
+        upgrade::migrate_records_by_id(&env, caller, escrow_ids)
+    }
+}
+
+/// Checks shared by every way of creating an escrow for `agreement_id`.
+///
+/// Returns the escrow the new one replaces: an agreement's escrow can be
+/// replaced while it is Pending with nothing deposited, so an escrow that was
+/// never funded does not hold the agreement forever. One with an installment
+/// plan the parties agreed to is kept until the plan is paid or missed, and
+/// a missed plan whose installments were refunded can be started over.
+fn check_new_escrow(
+    env: &Env,
+    agreement_id: &String,
+    depositor: &Address,
+    beneficiary: &Address,
+    arbiter: &Address,
+    amount: i128,
+) -> Result<Option<Escrow>, EscrowError> {
+    if amount <= 0 {
+        return Err(EscrowError::InsufficientFunds);
+    }
+
+    // Ensure primary parties are distinct
+    if depositor == beneficiary || depositor == arbiter || beneficiary == arbiter {
+        return Err(EscrowError::InvalidSigner);
+    }
+
+    match EscrowStorage::get_agreement_escrow(env, agreement_id)
+        .and_then(|id| EscrowStorage::get(env, &id))
+    {
+        None => Ok(None),
+        Some(escrow)
+            if escrow.status == EscrowStatus::PartiallyFunded
+                || (escrow.status == EscrowStatus::Pending
+                    && !escrow.installments.is_empty()
+                    && !escrow.installment_missed(env.ledger().timestamp())) =>
+        {
+            Err(EscrowError::InstallmentsOutstanding)
+        }
+        Some(escrow)
+            if escrow.status == EscrowStatus::Pending && escrow.installments_funded == 0 =>
+        {
+            Ok(Some(escrow))
+        }
+        Some(escrow) if escrow.installments_refunded() => Ok(Some(escrow)),
+        Some(_) => Err(EscrowError::AgreementAlreadyEscrowed),
+    }
+}
+
+/// Put a Funded escrow the depositor opened ahead of activation under the
+/// arbiter and fee recipients chioma vouches for, dropping release approvals
+/// given under the depositor's choice of arbiter.
+fn adopt_funded(
+    env: &Env,
+    mut escrow: Escrow,
+    arbiter: Address,
+    platform_governance: Address,
+    agent_referral: Address,
+) -> BytesN<32> {
+    EscrowStorage::clear_approvals(env, &escrow.id);
+    let targets = [escrow.beneficiary.clone(), escrow.depositor.clone()];
+    let signers = [
+        escrow.depositor.clone(),
+        escrow.beneficiary.clone(),
+        escrow.arbiter.clone(),
+    ];
+    EscrowStorage::clear_approval_counts(env, &escrow.id, &targets, &signers);
+    escrow.arbiter = arbiter;
+    escrow.platform_governance = platform_governance;
+    escrow.agent_referral = agent_referral;
+    EscrowStorage::save(env, &escrow);
+    escrow.id
+}
+
+/// Close an escrow that is replaced before anything was deposited into it,
+/// so it can no longer be funded. One already refunded is left as it is.
+fn close_unfunded(env: &Env, mut escrow: Escrow) {
+    if escrow.status != EscrowStatus::Pending {
+        return;
+    }
+    escrow.status = EscrowStatus::Refunded;
+    EscrowStorage::save(env, &escrow);
+    events::escrow_status_updated(
+        env,
+        escrow.id,
+        EscrowStatus::Pending,
+        EscrowStatus::Refunded,
+    );
+}
+
+/// Validate an installment schedule for an escrow of `amount`.
+fn check_schedule(env: &Env, schedule: &Vec<Installment>, amount: i128) -> Result<(), EscrowError> {
+    if schedule.is_empty() || schedule.len() > MAX_INSTALLMENTS {
+        return Err(EscrowError::InvalidInstallmentPlan);
+    }
+    let mut previous_due = env.ledger().timestamp();
+    let mut total: i128 = 0;
+    for installment in schedule.iter() {
+        if installment.amount <= 0 || installment.due_at <= previous_due {
+            return Err(EscrowError::InvalidInstallmentPlan);
+        }
+        previous_due = installment.due_at;
+        total = total
+            .checked_add(installment.amount)
+            .ok_or(EscrowError::InvalidInstallmentPlan)?;
+    }
+    if total != amount {
+        return Err(EscrowError::InvalidInstallmentPlan);
+    }
+    Ok(())
+}
+
+/// Store a new Pending escrow for `agreement_id`, link the agreement to it
+/// and announce it.
+fn open_escrow(
+    env: &Env,
+    agreement_id: String,
+    depositor: Address,
+    beneficiary: Address,
+    arbiter: Address,
+    platform_governance: Address,
+    agent_referral: Address,
+    amount: i128,
+    token: Address,
+) -> Escrow {
+    // Generate unique escrow ID from hash of parameters
+    let mut data = soroban_sdk::Bytes::new(env);
+    data.append(&agreement_id.clone().to_xdr(env));
+    data.append(&depositor.clone().to_xdr(env));
+    data.append(&beneficiary.clone().to_xdr(env));
+    data.append(&arbiter.clone().to_xdr(env));
+    data.append(&amount.to_xdr(env));
+    data.append(&token.clone().to_xdr(env));
+    data.append(&env.ledger().timestamp().to_xdr(env));
+
+    let escrow_id: BytesN<32> = env.crypto().sha256(&data).into();
+
+    let escrow = Escrow {
+        id: escrow_id.clone(),
+        agreement_id: Some(agreement_id.clone()),
+        depositor: depositor.clone(),
+        beneficiary: beneficiary.clone(),
+        arbiter: arbiter.clone(),
+        platform_governance: platform_governance.clone(),
+        agent_referral: agent_referral.clone(),
+        amount,
+        installments: Vec::new(env),
+        installments_funded: 0,
+        token: token.clone(),
+        status: EscrowStatus::Pending,
+        created_at: env.ledger().timestamp(),
+        timeout_days: EscrowStorage::get_timeout_config(env).escrow_timeout_days,
+        disputed_at: None,
+        dispute_reason: None,
+        is_frozen: false,
+        frozen_at: None,
+        freeze_reason: None,
+    };
+
+    EscrowStorage::save(env, &escrow);
+    EscrowStorage::set_agreement_escrow(env, &agreement_id, &escrow_id);
+    EscrowStorage::increment_count(env, &escrow_id);
+
+    events::escrow_created(
+        env,
+        escrow_id,
+        agreement_id,
+        depositor,
+        beneficiary,
+        arbiter,
+        platform_governance,
+        agent_referral,
+        amount,
+        token,
+    );
+
+    escrow
 }
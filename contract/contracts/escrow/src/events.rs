@@ -13,6 +13,7 @@ use crate::types::EscrowStatus;
 pub struct EscrowCreated {
     #[topic]
     pub escrow_id: BytesN<32>,
+    pub agreement_id: String,
     pub depositor: Address,
     pub beneficiary: Address,
     pub arbiter: Address,
@@ -158,6 +159,15 @@ pub struct TimeoutConfigUpdated {
     pub updated_at: u64,
 }
 
+/// Event emitted when the rate limit configuration is updated
+/// Topics: ["rate_limit_config_updated"]
+#[contractevent(topics = ["rate_limit_config_updated"])]
+pub struct RateLimitConfigUpdated {
+    pub max_calls_per_block: u32,
+    pub max_calls_per_user_per_day: u32,
+    pub cooldown_blocks: u32,
+}
+
 /// Event emitted when the admin is initialized
 /// Topics: ["admin_initialized", admin: Address]
 #[contractevent(topics = ["admin_initialized"])]
@@ -217,12 +227,78 @@ pub struct UpgradeExecuted {
     pub proposal_id: String,
     pub executor: Address,
     pub executed_at: u64,
+    pub wasm_hash: BytesN<32>,
+    pub version: u32,
+}
+
+/// Event emitted when stored data is migrated after an upgrade
+/// Topics: ["storage_migrated"]
+#[contractevent(topics = ["storage_migrated"])]
+pub struct StorageMigrated {
+    pub contract_version: u32,
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+/// Event emitted when the protocol directory is set
+/// Topics: ["directory_set", admin: Address]
+#[contractevent(topics = ["directory_set"])]
+pub struct DirectorySet {
+    #[topic]
+    pub admin: Address,
+    pub directory: Address,
+    pub set_at: u64,
+}
+
+/// Event emitted when both parties agree to installment funding
+/// Topics: ["installments_scheduled", escrow_id: BytesN<32>]
+#[contractevent(topics = ["installments_scheduled"])]
+pub struct InstallmentsScheduled {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub installments: u32,
+    pub deadline: u64,
+}
+
+/// Event emitted when the depositor pays an installment
+/// Topics: ["installment_paid", escrow_id: BytesN<32>, depositor: Address]
+#[contractevent(topics = ["installment_paid"])]
+pub struct InstallmentPaid {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    #[topic]
+    pub depositor: Address,
+    pub amount: i128,
+    pub funded: i128,
+    pub paid_at: u64,
+}
+
+/// Event emitted when installments are refunded after a missed deadline
+/// Topics: ["installments_refunded", escrow_id: BytesN<32>]
+#[contractevent(topics = ["installments_refunded"])]
+pub struct InstallmentsRefunded {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub amount: i128,
+    pub refunded_at: u64,
+}
+
+/// Event emitted when a sale hands an escrow to the agreement's new landlord
+/// Topics: ["beneficiary_transferred", escrow_id: BytesN<32>]
+#[contractevent(topics = ["beneficiary_transferred"])]
+pub struct BeneficiaryTransferred {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub previous: Address,
+    pub beneficiary: Address,
+    pub transferred_at: u64,
 }
 
 /// Helper function to emit escrow created event
 pub(crate) fn escrow_created(
     env: &Env,
     escrow_id: BytesN<32>,
+    agreement_id: String,
     depositor: Address,
     beneficiary: Address,
     arbiter: Address,
@@ -233,6 +309,7 @@ pub(crate) fn escrow_created(
 ) {
     EscrowCreated {
         escrow_id,
+        agreement_id,
         depositor,
         beneficiary,
         arbiter,
@@ -410,6 +487,21 @@ pub(crate) fn timeout_config_updated(
     .publish(env);
 }
 
+/// Helper function to emit rate limit config updated event
+pub(crate) fn rate_limit_config_updated(
+    env: &Env,
+    max_calls_per_block: u32,
+    max_calls_per_user_per_day: u32,
+    cooldown_blocks: u32,
+) {
+    RateLimitConfigUpdated {
+        max_calls_per_block,
+        max_calls_per_user_per_day,
+        cooldown_blocks,
+    }
+    .publish(env);
+}
+
 /// Helper function to emit admin initialized event
 pub(crate) fn admin_initialized(env: &Env, admin: Address) {
     AdminInitialized {
@@ -471,11 +563,97 @@ pub(crate) fn upgrade_approved(
 }
 
 /// Helper function to emit upgrade executed event
-pub(crate) fn upgrade_executed(env: &Env, proposal_id: String, executor: Address) {
+pub(crate) fn upgrade_executed(
+    env: &Env,
+    proposal_id: String,
+    executor: Address,
+    wasm_hash: BytesN<32>,
+    version: u32,
+) {
     UpgradeExecuted {
         proposal_id,
         executor,
         executed_at: env.ledger().timestamp(),
+        wasm_hash,
+        version,
+    }
+    .publish(env);
+}
+
+pub(crate) fn storage_migrated(
+    env: &Env,
+    contract_version: u32,
+    from_version: u32,
+    to_version: u32,
+) {
+    StorageMigrated {
+        contract_version,
+        from_version,
+        to_version,
+    }
+    .publish(env);
+}
+
+pub(crate) fn directory_set(env: &Env, admin: Address, directory: Address) {
+    DirectorySet {
+        admin,
+        directory,
+        set_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn installments_scheduled(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    installments: u32,
+    deadline: u64,
+) {
+    InstallmentsScheduled {
+        escrow_id,
+        installments,
+        deadline,
+    }
+    .publish(env);
+}
+
+pub(crate) fn installment_paid(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    depositor: Address,
+    amount: i128,
+    funded: i128,
+) {
+    InstallmentPaid {
+        escrow_id,
+        depositor,
+        amount,
+        funded,
+        paid_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn installments_refunded(env: &Env, escrow_id: BytesN<32>, amount: i128) {
+    InstallmentsRefunded {
+        escrow_id,
+        amount,
+        refunded_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn beneficiary_transferred(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    previous: Address,
+    beneficiary: Address,
+) {
+    BeneficiaryTransferred {
+        escrow_id,
+        previous,
+        beneficiary,
+        transferred_at: env.ledger().timestamp(),
     }
     .publish(env);
 }
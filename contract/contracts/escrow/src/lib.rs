@@ -11,10 +11,12 @@ pub mod dispute;
 pub mod errors;
 pub mod escrow_impl;
 pub mod events;
+pub mod peers;
 pub mod rate_limit;
 pub mod storage;
 pub mod types;
 pub mod upgrade;
+pub mod yield_vault;
 
 #[cfg(test)]
 mod tests;
@@ -28,10 +30,21 @@ mod tests_rbac;
 #[cfg(test)]
 mod tests_property;
 
+#[cfg(test)]
+mod tests_yield_vault;
+
 // Re-export public APIs
 pub use access::AccessControl;
 pub use dispute::DisputeHandler;
 pub use errors::EscrowError;
 pub use escrow_impl::EscrowContract;
+pub use peers::{
+    AgreementStatus, ChiomaClient, ChiomaInterface, Component, DirectoryClient, DirectoryInterface,
+    RentAgreement,
+};
 pub use storage::EscrowStorage;
-pub use types::{DataKey, Escrow, EscrowStatus, ReleaseApproval, TimeoutConfig};
+pub use types::{
+    DataKey, Escrow, EscrowStatus, Installment, RateLimitConfig, RateLimitMode, ReleaseApproval,
+    TimeoutConfig, VaultPosition, MAX_INSTALLMENTS,
+};
+pub use yield_vault::{YieldVault, YieldVaultClient};
@@ -0,0 +1,112 @@
+//! Peer contracts the Escrow contract reads, resolved through the protocol
+//! directory: chioma's agreements, which escrows are validated against.
+use soroban_sdk::{contractclient, contracttype, Address, Env, String, Vec};
+
+use crate::access::AccessControl;
+use crate::errors::EscrowError;
+use crate::events;
+use crate::types::DataKey;
+
+/// The protocol directory's `Component`, mirrored to name peers.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Component {
+    AgentRegistry,
+    Chioma,
+    DisputeResolution,
+    Escrow,
+    Payment,
+    PropertyRegistry,
+    RentObligation,
+    UserProfile,
+}
+
+/// The part of the protocol directory peers are resolved through.
+#[contractclient(name = "DirectoryClient")]
+pub trait DirectoryInterface {
+    fn resolve(env: Env, component: Component) -> Option<Address>;
+}
+
+/// Chioma's `AgreementStatus`, mirrored so its agreements can be read.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AgreementStatus {
+    Draft,
+    Pending,
+    PendingApproval,
+    Active,
+    Completed,
+    Cancelled,
+    Terminated,
+    Disputed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attribute {
+    pub trait_type: String,
+    pub value: String,
+}
+
+/// Chioma's `RentAgreement`, field for field: `admin` is the landlord and
+/// `user` the tenant.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RentAgreement {
+    pub agreement_id: String,
+    pub admin: Address,
+    pub user: Address,
+    pub agent: Option<Address>,
+    pub monthly_rent: i128,
+    pub security_deposit: i128,
+    pub start_date: u64,
+    pub end_date: u64,
+    pub agent_commission_rate: u32,
+    pub status: AgreementStatus,
+    pub total_rent_paid: i128,
+    pub payment_count: u32,
+    pub signed_at: Option<u64>,
+    pub witness_id: Option<Address>,
+    pub payment_token: Address,
+    pub next_payment_due: u64,
+    pub metadata_uri: String,
+    pub attributes: Vec<Attribute>,
+}
+
+/// The part of the chioma contract escrows are checked against.
+#[contractclient(name = "ChiomaClient")]
+pub trait ChiomaInterface {
+    fn get_agreement(env: Env, agreement_id: String) -> Option<RentAgreement>;
+}
+
+/// Resolve peers through `directory` from now on (system admin only).
+pub fn set_directory(env: &Env, caller: Address, directory: Address) -> Result<(), EscrowError> {
+    AccessControl::is_system_admin(env, &caller)?;
+    caller.require_auth();
+
+    env.storage()
+        .instance()
+        .set(&DataKey::Directory, &directory);
+
+    events::directory_set(env, caller, directory);
+    Ok(())
+}
+
+pub fn get_directory(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Directory)
+}
+
+/// The chioma contract registered in the directory.
+pub(crate) fn chioma(env: &Env) -> Result<Address, EscrowError> {
+    let directory = get_directory(env).ok_or(EscrowError::PeerNotRegistered)?;
+    DirectoryClient::new(env, &directory)
+        .resolve(&Component::Chioma)
+        .ok_or(EscrowError::PeerNotRegistered)
+}
+
+/// Read `agreement_id` from chioma.
+pub(crate) fn agreement(env: &Env, agreement_id: &String) -> Result<RentAgreement, EscrowError> {
+    ChiomaClient::new(env, &chioma(env)?)
+        .get_agreement(agreement_id)
+        .ok_or(EscrowError::AgreementNotFound)
+}
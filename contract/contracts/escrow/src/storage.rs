@@ -1,8 +1,10 @@
 //! Storage operations for the Escrow contract.
 //! Implements single-responsibility getter/setter helpers.
-use soroban_sdk::{Address, BytesN, Env, Vec};
+use soroban_sdk::{unwrap::UnwrapOptimized, Address, BytesN, Env, String, TryFromVal, Val, Vec};
 
-use crate::types::{DataKey, Escrow, ReleaseApproval, ReleaseRecord, TimeoutConfig};
+use crate::types::{
+    DataKey, Escrow, EscrowV1, ReleaseApproval, ReleaseRecord, TimeoutConfig, VersionedEscrow,
+};
 
 /// Escrow storage management.
 pub struct EscrowStorage;
@@ -12,18 +14,25 @@ impl EscrowStorage {
     pub const DEFAULT_DISPUTE_TIMEOUT_DAYS: u64 = 30;
     pub const DEFAULT_PAYMENT_TIMEOUT_DAYS: u64 = 7;
 
-    /// Retrieve an escrow by ID.
+    /// Retrieve an escrow by ID, upgraded to the current layout.
     /// Returns None if escrow doesn't exist.
     pub fn get(env: &Env, id: &BytesN<32>) -> Option<Escrow> {
         let key = DataKey::Escrow(id.clone());
-        env.storage().persistent().get::<_, Escrow>(&key)
+        let raw = env.storage().persistent().get::<_, Val>(&key)?;
+        if let Ok(record) = VersionedEscrow::try_from_val(env, &raw) {
+            return Some(record.into_current());
+        }
+        // Written before records were versioned: a bare `EscrowV1`.
+        Some(EscrowV1::try_from_val(env, &raw).unwrap_optimized().into())
     }
 
-    /// Save or update an escrow.
+    /// Save or update an escrow in the current layout.
     /// Updates existing escrow or creates a new one.
     pub fn save(env: &Env, escrow: &Escrow) {
         let key = DataKey::Escrow(escrow.id.clone());
-        env.storage().persistent().set(&key, escrow);
+        env.storage()
+            .persistent()
+            .set(&key, &VersionedEscrow::V3(escrow.clone()));
     }
 
     /// Retrieve all approvals for an escrow release.
@@ -124,14 +133,24 @@ impl EscrowStorage {
             .unwrap_or_default()
     }
 
-    /// Increment escrow counter.
-    pub fn increment_count(env: &Env) {
+    /// Increment escrow counter, recording `escrow_id` at the old count in
+    /// the creation-order index.
+    pub fn increment_count(env: &Env, escrow_id: &BytesN<32>) {
         let count = Self::get_count(env);
+        env.storage()
+            .persistent()
+            .set(&DataKey::EscrowIndex(count), escrow_id);
         env.storage()
             .instance()
             .set(&DataKey::EscrowCount, &(count + 1));
     }
 
+    /// Escrow ID at position `index` in creation order. Escrows created
+    /// before the index existed have no entry.
+    pub fn get_indexed(env: &Env, index: u32) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::EscrowIndex(index))
+    }
+
     /// Fetch timeout config or return defaults.
     pub fn get_timeout_config(env: &Env) -> TimeoutConfig {
         env.storage()
@@ -174,6 +193,20 @@ impl EscrowStorage {
         env.storage().persistent().set(&key, &history);
     }
 
+    /// Escrow securing `agreement_id`, if one was created.
+    pub fn get_agreement_escrow(env: &Env, agreement_id: &String) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AgreementEscrow(agreement_id.clone()))
+    }
+
+    /// Record `escrow_id` as the escrow securing `agreement_id`.
+    pub fn set_agreement_escrow(env: &Env, agreement_id: &String, escrow_id: &BytesN<32>) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::AgreementEscrow(agreement_id.clone()), escrow_id);
+    }
+
     /// Get the system admin address.
     /// Returns None if admin has not been set.
     pub fn get_admin(env: &Env) -> Option<Address> {
@@ -1,12 +1,109 @@
 //! Tests for the Escrow contract.
 
+extern crate alloc;
+
 use soroban_sdk::testutils::{Address as _, Ledger};
 use soroban_sdk::token::Client as TokenClient;
 use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{contract, contractimpl, Address, Env};
 
 use crate::escrow_impl::{EscrowContract, EscrowContractClient};
-use crate::types::{EscrowStatus, TimeoutConfig};
+use crate::peers::{AgreementStatus, Component, DirectoryClient, RentAgreement};
+use crate::types::{DataKey, EscrowStatus, TimeoutConfig};
+use soroban_sdk::String;
+
+/// Minimal WASM module the host accepts as contract code.
+const UPGRADE_WASM: &[u8] = b"\0asm\x01\0\0\0\0\x1e\x11contractenvmetav0\0\0\0\0\0\0\0\x17\0\0\0\0";
+
+/// Upload `UPGRADE_WASM` so an upgrade to its hash can be executed.
+pub(crate) fn upload_upgrade_wasm(env: &Env) -> soroban_sdk::BytesN<32> {
+    env.deployer()
+        .upload_contract_wasm(soroban_sdk::Bytes::from_slice(env, UPGRADE_WASM))
+}
+
+/// Protocol directory resolving the components stored under their names.
+#[contract]
+pub struct MockDirectoryContract;
+
+#[contractimpl]
+impl MockDirectoryContract {
+    pub fn resolve(env: Env, component: Component) -> Option<Address> {
+        env.storage().instance().get(&component)
+    }
+}
+
+/// Chioma serving the agreements stored under their ids.
+#[contract]
+pub struct MockChiomaContract;
+
+#[contractimpl]
+impl MockChiomaContract {
+    pub fn get_agreement(env: Env, agreement_id: String) -> Option<RentAgreement> {
+        env.storage().instance().get(&agreement_id)
+    }
+}
+
+/// Point `escrow` at a directory that lists a mock chioma, as the admin
+/// would with `set_directory`.
+pub(crate) fn wire_chioma(env: &Env, escrow: &Address) {
+    let directory = env.register(MockDirectoryContract, ());
+    let chioma = env.register(MockChiomaContract, ());
+    env.as_contract(&directory, || {
+        env.storage().instance().set(&Component::Chioma, &chioma);
+    });
+    env.as_contract(escrow, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::Directory, &directory);
+    });
+}
+
+/// The chioma `escrow` finds in its directory.
+fn registered_chioma(env: &Env, escrow: &Address) -> Address {
+    let directory = env.as_contract(escrow, || crate::peers::get_directory(env).unwrap());
+    DirectoryClient::new(env, &directory)
+        .resolve(&Component::Chioma)
+        .unwrap()
+}
+
+/// Seed chioma with a fresh agreement whose deposit `depositor` owes
+/// `beneficiary`, and return its id.
+pub(crate) fn agreement(
+    env: &Env,
+    escrow: &Address,
+    depositor: &Address,
+    beneficiary: &Address,
+    amount: &i128,
+    token: &Address,
+) -> String {
+    env.as_contract(&registered_chioma(env, escrow), || {
+        let count: u32 = env.storage().instance().get(&()).unwrap_or(0);
+        env.storage().instance().set(&(), &(count + 1));
+        let agreement_id = String::from_str(env, &alloc::format!("AGR-{count}"));
+        let agreement = RentAgreement {
+            agreement_id: agreement_id.clone(),
+            admin: beneficiary.clone(),
+            user: depositor.clone(),
+            agent: None,
+            monthly_rent: *amount,
+            security_deposit: *amount,
+            start_date: 0,
+            end_date: 0,
+            agent_commission_rate: 0,
+            status: AgreementStatus::Active,
+            total_rent_paid: 0,
+            payment_count: 0,
+            signed_at: None,
+            witness_id: None,
+            payment_token: token.clone(),
+            next_payment_due: 0,
+            metadata_uri: String::from_str(env, ""),
+            attributes: soroban_sdk::Vec::new(env),
+        };
+        env.storage().instance().set(&agreement_id, &agreement);
+        agreement_id
+    })
+}
 
 fn setup_test(
     env: &Env,
@@ -21,6 +118,7 @@ fn setup_test(
 ) {
     let contract_id = env.register(EscrowContract, ());
     let client = EscrowContractClient::new(env, &contract_id);
+    wire_chioma(env, &contract_id);
 
     let depositor = Address::generate(env);
     let beneficiary = Address::generate(env);
@@ -62,6 +160,14 @@ fn test_escrow_lifecycle() {
 
     // 1. Create Escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -127,6 +233,14 @@ fn test_dispute_resolution() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -173,7 +287,17 @@ fn test_unauthorized_funding() {
     ) = setup_test(&env);
     let amount = 1000i128;
 
+    // Only the creation is authorized here.
+    env.mock_all_auths();
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -182,6 +306,7 @@ fn test_unauthorized_funding() {
         &amount,
         &token_address,
     );
+    env.set_auths(&[]);
 
     // Try to fund from beneficiary (should fail since only depositor can fund)
     // We expect an error, but AccessControl check happens before require_auth
@@ -198,7 +323,9 @@ fn test_unique_escrow_ids() {
     struct TestContract;
 
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(TestContract, ());
+    wire_chioma(&env, &contract_id);
 
     let depositor = Address::generate(&env);
     let beneficiary = Address::generate(&env);
@@ -211,6 +338,7 @@ fn test_unique_escrow_ids() {
         .as_contract(&contract_id, || {
             EscrowContract::create(
                 env.clone(),
+                agreement(&env, &contract_id, &depositor, &beneficiary, &1000, &token),
                 depositor.clone(),
                 beneficiary.clone(),
                 arbiter.clone(),
@@ -228,6 +356,7 @@ fn test_unique_escrow_ids() {
         .as_contract(&contract_id, || {
             EscrowContract::create(
                 env.clone(),
+                agreement(&env, &contract_id, &depositor, &beneficiary, &1000, &token),
                 depositor.clone(),
                 beneficiary.clone(),
                 arbiter.clone(),
@@ -276,6 +405,14 @@ fn test_duplicate_approval_rejected() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -318,6 +455,14 @@ fn test_approval_count_tracks_per_target() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -374,6 +519,14 @@ fn test_release_escrow_on_timeout_refunds_depositor() {
     client.set_timeout_config(&depositor, &cfg);
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -391,6 +544,7 @@ fn test_release_escrow_on_timeout_refunds_depositor() {
 
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(client.get_escrow_status(&escrow_id), EscrowStatus::Refunded);
 
     let token_client = TokenClient::new(&env, &token_address);
     assert_eq!(token_client.balance(&depositor), amount);
@@ -420,6 +574,14 @@ fn test_release_escrow_on_timeout_before_deadline_fails() {
     client.set_timeout_config(&depositor, &cfg);
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -453,6 +615,14 @@ fn test_resolve_dispute_on_timeout_refunds_depositor() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -506,6 +676,14 @@ fn test_partial_release_success() {
 
     // Create and fund escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -566,6 +744,14 @@ fn test_partial_release_insufficient_approvals() {
 
     // Create and fund escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -608,6 +794,14 @@ fn test_partial_release_exceeds_balance() {
 
     // Create and fund escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -650,6 +844,14 @@ fn test_multiple_partial_releases() {
 
     // Create and fund escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -715,6 +917,14 @@ fn test_damage_deduction_success() {
 
     // Create and fund escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -770,6 +980,14 @@ fn test_damage_deduction_full_amount() {
 
     // Create and fund escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -821,6 +1039,14 @@ fn test_damage_deduction_no_damage() {
 
     // Create and fund escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -868,6 +1094,14 @@ fn test_damage_deduction_exceeds_balance() {
 
     // Create and fund escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -910,6 +1144,14 @@ fn test_damage_deduction_insufficient_approvals() {
 
     // Create and fund escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -951,6 +1193,14 @@ fn test_partial_release_invalid_recipient() {
 
     // Create and fund escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -988,6 +1238,14 @@ fn test_partial_release_empty_reason() {
 
     // Create and fund escrow
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1031,6 +1289,14 @@ fn test_is_depositor_correct_address() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1062,6 +1328,14 @@ fn test_is_depositor_incorrect_address() {
     let wrong_address = Address::generate(&env);
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1092,6 +1366,14 @@ fn test_is_beneficiary_correct_address() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1123,6 +1405,14 @@ fn test_is_beneficiary_incorrect_address() {
     let wrong_address = Address::generate(&env);
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1153,6 +1443,14 @@ fn test_is_arbiter_correct_address() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1184,6 +1482,14 @@ fn test_is_arbiter_incorrect_address() {
     let wrong_address = Address::generate(&env);
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1214,6 +1520,14 @@ fn test_is_party_depositor() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1249,6 +1563,14 @@ fn test_is_party_beneficiary() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1284,6 +1606,14 @@ fn test_is_party_arbiter() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1318,6 +1648,14 @@ fn test_is_party_non_party() {
     let non_party = Address::generate(&env);
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1350,7 +1688,17 @@ fn test_authorization_fund_escrow_depositor_only() {
     ) = setup_test(&env);
     let amount = 1000i128;
 
+    // Only the creation is authorized here.
+    env.mock_all_auths();
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1359,6 +1707,7 @@ fn test_authorization_fund_escrow_depositor_only() {
         &amount,
         &token_address,
     );
+    env.set_auths(&[]);
 
     // Only depositor can fund
     env.mock_all_auths();
@@ -1383,7 +1732,17 @@ fn test_authorization_fund_escrow_beneficiary_fails() {
     ) = setup_test(&env);
     let amount = 1000i128;
 
+    // Only the creation is authorized here.
+    env.mock_all_auths();
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1392,6 +1751,7 @@ fn test_authorization_fund_escrow_beneficiary_fails() {
         &amount,
         &token_address,
     );
+    env.set_auths(&[]);
 
     // Beneficiary cannot fund
     let result = client.try_fund_escrow(&escrow_id, &beneficiary);
@@ -1412,7 +1772,17 @@ fn test_authorization_fund_escrow_arbiter_fails() {
     ) = setup_test(&env);
     let amount = 1000i128;
 
+    // Only the creation is authorized here.
+    env.mock_all_auths();
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1421,6 +1791,7 @@ fn test_authorization_fund_escrow_arbiter_fails() {
         &amount,
         &token_address,
     );
+    env.set_auths(&[]);
 
     // Arbiter cannot fund
     let result = client.try_fund_escrow(&escrow_id, &arbiter);
@@ -1444,6 +1815,14 @@ fn test_authorization_initiate_dispute_beneficiary() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1479,6 +1858,14 @@ fn test_authorization_initiate_dispute_depositor() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1514,6 +1901,14 @@ fn test_authorization_initiate_dispute_arbiter_fails() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1549,6 +1944,14 @@ fn test_authorization_resolve_dispute_arbiter_only() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1588,6 +1991,14 @@ fn test_authorization_resolve_dispute_depositor_fails() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1627,6 +2038,14 @@ fn test_authorization_resolve_dispute_beneficiary_fails() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1669,6 +2088,7 @@ fn setup_test_with_fees(
 ) {
     let contract_id = env.register(EscrowContract, ());
     let client = EscrowContractClient::new(env, &contract_id);
+    wire_chioma(env, &contract_id);
 
     let depositor = Address::generate(env);
     let beneficiary = Address::generate(env);
@@ -1709,6 +2129,14 @@ fn test_release_rent_splits_90_5_5() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1754,6 +2182,14 @@ fn test_release_rent_rounding_remainder_goes_to_agent() {
     let amount = 101i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1793,6 +2229,14 @@ fn test_release_rent_only_arbiter_can_call() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1830,6 +2274,14 @@ fn test_release_rent_blocked_when_disputed() {
     let amount = 1000i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1870,6 +2322,14 @@ fn test_withdraw_safety_deposit_success_after_timeout() {
     let amount = 500i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1917,6 +2377,14 @@ fn test_withdraw_safety_deposit_blocked_before_timeout() {
     let amount = 500i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1951,6 +2419,14 @@ fn test_withdraw_safety_deposit_blocked_when_disputed() {
     let amount = 500i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -1997,6 +2473,14 @@ fn test_withdraw_safety_deposit_only_depositor_can_call() {
     let amount = 500i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token_address,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -2019,3 +2503,895 @@ fn test_withdraw_safety_deposit_only_depositor_can_call() {
     let result = client.try_withdraw_safety_deposit(&escrow_id, &beneficiary);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_execute_upgrade_installs_wasm_and_migrates_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = EscrowContractClient::new(&env, &env.register(EscrowContract, ()));
+    let admin = Address::generate(&env);
+    client.initialize_admin(&admin);
+
+    let proposal_id = String::from_str(&env, "UPG-001");
+    let wasm_hash = upload_upgrade_wasm(&env);
+    client.propose_upgrade(
+        &admin,
+        &proposal_id,
+        &wasm_hash,
+        &String::from_str(&env, "v2"),
+        &0,
+    );
+
+    // Nothing to migrate before an upgrade.
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(crate::errors::EscrowError::InvalidState))
+    );
+
+    client.execute_upgrade(&admin, &proposal_id);
+
+    // The installed module exports nothing; reinstall the native contract to
+    // stand in for the new code.
+    assert!(client.try_get_contract_version().is_err());
+    env.register_at(&client.address, EscrowContract, ());
+
+    assert_eq!(client.get_contract_version(), 2);
+    assert_eq!(client.migrate(&admin), 3);
+    assert_eq!(client.get_storage_version(), 3);
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(crate::errors::EscrowError::InvalidState))
+    );
+}
+
+#[test]
+fn test_unversioned_escrows_are_upgraded_on_read_and_by_batch_migration() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (
+        client,
+        depositor,
+        beneficiary,
+        arbiter,
+        platform_governance,
+        agent_referral,
+        token_address,
+    ) = setup_test(&env);
+    let admin = Address::generate(&env);
+    client.initialize_admin(&admin);
+
+    let mut ids = soroban_sdk::Vec::new(&env);
+    for amount in [100i128, 200, 300] {
+        ids.push_back(client.create(
+            &agreement(
+                &env,
+                &client.address,
+                &depositor,
+                &beneficiary,
+                &amount,
+                &token_address,
+            ),
+            &depositor,
+            &beneficiary,
+            &arbiter,
+            &platform_governance,
+            &agent_referral,
+            &amount,
+            &token_address,
+        ));
+    }
+
+    // Rewrite the first escrow the way it was stored before records were
+    // versioned, when escrows had no agreement.
+    let legacy_id = ids.get(0).unwrap();
+    let current = client.get_escrow(&legacy_id);
+    let stored = crate::types::EscrowV1 {
+        id: current.id.clone(),
+        depositor: current.depositor.clone(),
+        beneficiary: current.beneficiary.clone(),
+        arbiter: current.arbiter.clone(),
+        platform_governance: current.platform_governance.clone(),
+        agent_referral: current.agent_referral.clone(),
+        amount: current.amount,
+        token: current.token.clone(),
+        status: current.status.clone(),
+        created_at: current.created_at,
+        timeout_days: current.timeout_days,
+        disputed_at: current.disputed_at,
+        dispute_reason: current.dispute_reason.clone(),
+        is_frozen: current.is_frozen,
+        frozen_at: current.frozen_at,
+        freeze_reason: current.freeze_reason.clone(),
+    };
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&crate::types::DataKey::Escrow(legacy_id.clone()), &stored);
+    });
+    let legacy = crate::types::Escrow {
+        agreement_id: None,
+        ..current
+    };
+    assert_eq!(client.get_escrow(&legacy_id), legacy);
+
+    // And the second the way it was stored before installment funding.
+    let v2_id = ids.get(1).unwrap();
+    let current = client.get_escrow(&v2_id);
+    let stored = crate::types::VersionedEscrow::V2(crate::types::EscrowV2 {
+        id: current.id.clone(),
+        agreement_id: current.agreement_id.clone(),
+        depositor: current.depositor.clone(),
+        beneficiary: current.beneficiary.clone(),
+        arbiter: current.arbiter.clone(),
+        platform_governance: current.platform_governance.clone(),
+        agent_referral: current.agent_referral.clone(),
+        amount: current.amount,
+        token: current.token.clone(),
+        status: current.status.clone(),
+        created_at: current.created_at,
+        timeout_days: current.timeout_days,
+        disputed_at: current.disputed_at,
+        dispute_reason: current.dispute_reason.clone(),
+        is_frozen: current.is_frozen,
+        frozen_at: current.frozen_at,
+        freeze_reason: current.freeze_reason.clone(),
+    });
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&crate::types::DataKey::Escrow(v2_id.clone()), &stored);
+    });
+    assert_eq!(client.get_escrow(&v2_id), current);
+
+    let batch = client.migrate_records(&admin, &0, &2);
+    assert_eq!(batch.migrated, 2);
+    assert_eq!(batch.next_cursor, 2);
+    assert!(!batch.done);
+
+    let batch = client.migrate_records(&admin, &batch.next_cursor, &10);
+    assert_eq!(batch.migrated, 1);
+    assert_eq!(batch.next_cursor, 3);
+    assert!(batch.done);
+
+    env.as_contract(&client.address, || {
+        let stored: crate::types::VersionedEscrow = env
+            .storage()
+            .persistent()
+            .get(&crate::types::DataKey::Escrow(legacy_id.clone()))
+            .unwrap();
+        assert_eq!(stored.into_current(), legacy);
+    });
+
+    assert_eq!(
+        client.try_migrate_records(&depositor, &0, &10),
+        Err(Ok(crate::errors::EscrowError::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_escrows_created_before_the_index_are_migrated_by_id() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (
+        client,
+        depositor,
+        beneficiary,
+        arbiter,
+        platform_governance,
+        agent_referral,
+        token_address,
+    ) = setup_test(&env);
+    let admin = Address::generate(&env);
+    client.initialize_admin(&admin);
+
+    let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &100,
+            &token_address,
+        ),
+        &depositor,
+        &beneficiary,
+        &arbiter,
+        &platform_governance,
+        &agent_referral,
+        &100,
+        &token_address,
+    );
+    let current = client.get_escrow(&escrow_id);
+
+    // Stored the way it was before records were versioned and indexed.
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.remove(&crate::types::DataKey::EscrowIndex(0));
+        storage.set(
+            &crate::types::DataKey::Escrow(escrow_id.clone()),
+            &crate::types::EscrowV1 {
+                id: current.id.clone(),
+                depositor: current.depositor.clone(),
+                beneficiary: current.beneficiary.clone(),
+                arbiter: current.arbiter.clone(),
+                platform_governance: current.platform_governance.clone(),
+                agent_referral: current.agent_referral.clone(),
+                amount: current.amount,
+                token: current.token.clone(),
+                status: current.status.clone(),
+                created_at: current.created_at,
+                timeout_days: current.timeout_days,
+                disputed_at: current.disputed_at,
+                dispute_reason: current.dispute_reason.clone(),
+                is_frozen: current.is_frozen,
+                frozen_at: current.frozen_at,
+                freeze_reason: current.freeze_reason.clone(),
+            },
+        );
+    });
+
+    // The index walk cannot see it.
+    assert_eq!(client.migrate_records(&admin, &0, &10).migrated, 0);
+
+    let unknown = soroban_sdk::BytesN::from_array(&env, &[7; 32]);
+    let ids = soroban_sdk::vec![&env, escrow_id.clone(), unknown];
+    assert_eq!(client.migrate_records_by_id(&admin, &ids), 1);
+    env.as_contract(&client.address, || {
+        let stored: crate::types::VersionedEscrow = env
+            .storage()
+            .persistent()
+            .get(&crate::types::DataKey::Escrow(escrow_id.clone()))
+            .unwrap();
+        assert_eq!(stored.into_current().agreement_id, None);
+    });
+
+    assert_eq!(
+        client.try_migrate_records_by_id(&admin, &soroban_sdk::Vec::new(&env)),
+        Err(Ok(crate::errors::EscrowError::InvalidState))
+    );
+    assert_eq!(
+        client.try_migrate_records_by_id(&depositor, &ids),
+        Err(Ok(crate::errors::EscrowError::NotAuthorized))
+    );
+}
+
+// ─── Agreement-linked escrows ───────────────────────────────────────────────
+
+#[test]
+fn test_create_links_escrow_to_agreement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, depositor, beneficiary, arbiter, platform_governance, agent_referral, token) =
+        setup_test(&env);
+    let amount = 2_000i128;
+    let agreement_id = agreement(
+        &env,
+        &client.address,
+        &depositor,
+        &beneficiary,
+        &amount,
+        &token,
+    );
+    assert_eq!(client.get_agreement_escrow(&agreement_id), None);
+
+    let create = |arbiter: &Address| {
+        client.try_create(
+            &agreement_id,
+            &depositor,
+            &beneficiary,
+            arbiter,
+            &platform_governance,
+            &agent_referral,
+            &amount,
+            &token,
+        )
+    };
+    let escrow_id = create(&arbiter).unwrap().unwrap();
+
+    assert_eq!(
+        client.get_agreement_escrow(&agreement_id),
+        Some(escrow_id.clone())
+    );
+    assert_eq!(
+        client.get_escrow(&escrow_id).agreement_id,
+        Some(agreement_id.clone())
+    );
+
+    // Nothing was deposited yet, so the escrow can be replaced; the old one
+    // is closed and can no longer be funded.
+    let replacement = create(&Address::generate(&env)).unwrap().unwrap();
+    assert_eq!(
+        client.get_agreement_escrow(&agreement_id),
+        Some(replacement.clone())
+    );
+    assert_eq!(client.get_escrow(&escrow_id).status, EscrowStatus::Refunded);
+    assert_eq!(
+        client.try_fund_escrow(&escrow_id, &depositor),
+        Err(Ok(crate::errors::EscrowError::InvalidState))
+    );
+
+    // A funded escrow holds the agreement for good.
+    TokenAdminClient::new(&env, &token).mint(&depositor, &amount);
+    client.fund_escrow(&replacement, &depositor);
+    assert_eq!(
+        create(&arbiter),
+        Err(Ok(crate::errors::EscrowError::AgreementAlreadyEscrowed))
+    );
+}
+
+#[test]
+fn test_create_requires_depositor_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, depositor, beneficiary, arbiter, platform_governance, agent_referral, token) =
+        setup_test(&env);
+    let amount = 2_000i128;
+    let agreement_id = agreement(
+        &env,
+        &client.address,
+        &depositor,
+        &beneficiary,
+        &amount,
+        &token,
+    );
+
+    env.set_auths(&[]);
+    let result = client.try_create(
+        &agreement_id,
+        &depositor,
+        &beneficiary,
+        &arbiter,
+        &platform_governance,
+        &agent_referral,
+        &amount,
+        &token,
+    );
+    assert!(result.is_err());
+    assert_eq!(client.get_agreement_escrow(&agreement_id), None);
+}
+
+#[test]
+fn test_create_rejects_escrow_not_matching_agreement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, depositor, beneficiary, arbiter, platform_governance, agent_referral, token) =
+        setup_test(&env);
+    let amount = 2_000i128;
+    let agreement_id = agreement(
+        &env,
+        &client.address,
+        &depositor,
+        &beneficiary,
+        &amount,
+        &token,
+    );
+    let other_token = Address::generate(&env);
+    let create = |agreement_id: &String,
+                  depositor: &Address,
+                  beneficiary: &Address,
+                  amount: i128,
+                  token: &Address| {
+        client.try_create(
+            agreement_id,
+            depositor,
+            beneficiary,
+            &arbiter,
+            &platform_governance,
+            &agent_referral,
+            &amount,
+            token,
+        )
+    };
+
+    let mismatched = [
+        create(&agreement_id, &beneficiary, &depositor, amount, &token),
+        create(&agreement_id, &depositor, &beneficiary, amount - 1, &token),
+        create(
+            &agreement_id,
+            &depositor,
+            &beneficiary,
+            amount,
+            &other_token,
+        ),
+    ];
+    for result in mismatched {
+        assert_eq!(
+            result,
+            Err(Ok(crate::errors::EscrowError::AgreementMismatch))
+        );
+    }
+    assert_eq!(
+        create(
+            &String::from_str(&env, "AGR-UNKNOWN"),
+            &depositor,
+            &beneficiary,
+            amount,
+            &token
+        ),
+        Err(Ok(crate::errors::EscrowError::AgreementNotFound))
+    );
+    assert_eq!(client.get_agreement_escrow(&agreement_id), None);
+}
+
+#[test]
+fn test_create_requires_chioma_in_directory() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = EscrowContractClient::new(&env, &env.register(EscrowContract, ()));
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let token = Address::generate(&env);
+    client.initialize_admin(&admin);
+    let create = || {
+        client.try_create(
+            &String::from_str(&env, "AGR-0"),
+            &depositor,
+            &beneficiary,
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &1_000,
+            &token,
+        )
+    };
+
+    assert_eq!(
+        create(),
+        Err(Ok(crate::errors::EscrowError::PeerNotRegistered))
+    );
+
+    let directory = env.register(MockDirectoryContract, ());
+    assert_eq!(
+        client.try_set_directory(&depositor, &directory),
+        Err(Ok(crate::errors::EscrowError::NotAuthorized))
+    );
+    client.set_directory(&admin, &directory);
+    assert_eq!(client.get_directory(), Some(directory.clone()));
+    assert_eq!(
+        create(),
+        Err(Ok(crate::errors::EscrowError::PeerNotRegistered))
+    );
+
+    let chioma = env.register(MockChiomaContract, ());
+    env.as_contract(&directory, || {
+        env.storage().instance().set(&Component::Chioma, &chioma);
+    });
+    agreement(
+        &env,
+        &client.address,
+        &depositor,
+        &beneficiary,
+        &1_000,
+        &token,
+    );
+    assert!(create().is_ok());
+}
+
+#[test]
+fn test_create_funded_escrows_deposit_for_chioma() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, depositor, beneficiary, arbiter, platform_governance, agent_referral, token) =
+        setup_test(&env);
+    let amount = 3_000i128;
+    TokenAdminClient::new(&env, &token).mint(&depositor, &amount);
+    let agreement_id = String::from_str(&env, "AGR-ACTIVATED");
+    let create = || {
+        client.try_create_funded(
+            &agreement_id,
+            &depositor,
+            &beneficiary,
+            &arbiter,
+            &platform_governance,
+            &agent_referral,
+            &amount,
+            &token,
+        )
+    };
+
+    let chioma = registered_chioma(&env, &client.address);
+    let escrow_id = create().unwrap().unwrap();
+
+    let authorizers: alloc::vec::Vec<Address> = env.auths().into_iter().map(|(a, _)| a).collect();
+    assert!(authorizers.contains(&chioma));
+    assert!(authorizers.contains(&depositor));
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Funded);
+    assert_eq!(escrow.agreement_id, Some(agreement_id.clone()));
+    assert_eq!(
+        client.get_agreement_escrow(&agreement_id),
+        Some(escrow_id.clone())
+    );
+    assert_eq!(
+        TokenClient::new(&env, &token).balance(&client.address),
+        amount
+    );
+
+    // Asking again adopts the funded escrow without a second deposit.
+    assert_eq!(create(), Ok(Ok(escrow_id)));
+    assert_eq!(
+        TokenClient::new(&env, &token).balance(&client.address),
+        amount
+    );
+}
+
+#[test]
+fn test_transfer_beneficiary_hands_the_escrow_to_the_buyer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, depositor, beneficiary, arbiter, platform_governance, agent_referral, token) =
+        setup_test(&env);
+    let amount = 3_000i128;
+    TokenAdminClient::new(&env, &token).mint(&depositor, &amount);
+    let agreement_id = String::from_str(&env, "AGR-SOLD");
+    let buyer = Address::generate(&env);
+
+    assert_eq!(
+        client.try_transfer_beneficiary(&agreement_id, &buyer),
+        Err(Ok(crate::errors::EscrowError::EscrowNotFound))
+    );
+
+    let chioma = registered_chioma(&env, &client.address);
+    let escrow_id = client.create_funded(
+        &agreement_id,
+        &depositor,
+        &beneficiary,
+        &arbiter,
+        &platform_governance,
+        &agent_referral,
+        &amount,
+        &token,
+    );
+    client.approve_release(&escrow_id, &beneficiary, &beneficiary);
+
+    assert_eq!(
+        client.try_transfer_beneficiary(&agreement_id, &depositor),
+        Err(Ok(crate::errors::EscrowError::InvalidSigner))
+    );
+    client.transfer_beneficiary(&agreement_id, &buyer);
+
+    let authorizers: alloc::vec::Vec<Address> = env.auths().into_iter().map(|(a, _)| a).collect();
+    assert!(authorizers.contains(&chioma));
+    assert_eq!(client.get_escrow(&escrow_id).beneficiary, buyer);
+    // The previous landlord's approval no longer counts towards a release.
+    assert_eq!(client.get_approval_count(&escrow_id, &beneficiary), 0);
+    assert_eq!(
+        client.try_approve_release(&escrow_id, &beneficiary, &buyer),
+        Err(Ok(crate::errors::EscrowError::InvalidSigner))
+    );
+    client.approve_release(&escrow_id, &buyer, &buyer);
+    assert_eq!(client.get_approval_count(&escrow_id, &buyer), 1);
+}
+
+// ─── Installment funding ────────────────────────────────────────────────────
+
+/// A Pending escrow of `amount` between the `setup_test` parties, with the
+/// depositor holding `amount`.
+fn pending_escrow(
+    env: &Env,
+    amount: i128,
+) -> (
+    EscrowContractClient<'_>,
+    soroban_sdk::BytesN<32>,
+    Address,
+    Address,
+    Address,
+) {
+    let (client, depositor, beneficiary, arbiter, platform_governance, agent_referral, token) =
+        setup_test(env);
+    let escrow_id = client.create(
+        &agreement(
+            env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token,
+        ),
+        &depositor,
+        &beneficiary,
+        &arbiter,
+        &platform_governance,
+        &agent_referral,
+        &amount,
+        &token,
+    );
+    TokenAdminClient::new(env, &token).mint(&depositor, &amount);
+    (client, escrow_id, depositor, beneficiary, token)
+}
+
+fn schedule(
+    env: &Env,
+    installments: &[(u64, i128)],
+) -> soroban_sdk::Vec<crate::types::Installment> {
+    let mut schedule = soroban_sdk::Vec::new(env);
+    for &(due_at, amount) in installments {
+        schedule.push_back(crate::types::Installment { due_at, amount });
+    }
+    schedule
+}
+
+#[test]
+fn test_set_installment_plan_validates_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let (client, escrow_id, depositor, beneficiary, _) = pending_escrow(&env, 900);
+    let invalid = Err(Ok(crate::errors::EscrowError::InvalidInstallmentPlan));
+
+    for installments in [
+        &[][..],
+        &[(2_000, 300), (3_000, 300)][..],
+        &[(2_000, 300), (3_000, 300), (4_000, 400)][..],
+        &[(2_000, 600), (2_000, 300)][..],
+        &[(1_000, 600), (2_000, 300)][..],
+        &[(2_000, 1_000), (3_000, -100)][..],
+    ] {
+        assert_eq!(
+            client.try_set_installment_plan(&escrow_id, &schedule(&env, installments)),
+            invalid
+        );
+    }
+    let too_many: alloc::vec::Vec<(u64, i128)> = (1..=crate::types::MAX_INSTALLMENTS as u64 + 1)
+        .map(|i| (1_000 + i, 1))
+        .collect();
+    assert_eq!(
+        client.try_set_installment_plan(&escrow_id, &schedule(&env, &too_many)),
+        invalid
+    );
+
+    // Both parties agree to the plan.
+    let plan = schedule(&env, &[(2_000, 300), (3_000, 600)]);
+    client.set_installment_plan(&escrow_id, &plan);
+    let authorizers: alloc::vec::Vec<Address> = env.auths().into_iter().map(|(a, _)| a).collect();
+    assert!(authorizers.contains(&depositor));
+    assert!(authorizers.contains(&beneficiary));
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.installments, plan);
+    assert_eq!(escrow.installments_funded, 0);
+    assert_eq!(escrow.installment_deadline(), Some(3_000));
+}
+
+#[test]
+fn test_fund_installments_until_funded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, escrow_id, depositor, beneficiary, token) = pending_escrow(&env, 900);
+    assert_eq!(
+        client.try_fund_installment(&escrow_id, &depositor, &300),
+        Err(Ok(crate::errors::EscrowError::NoInstallmentPlan))
+    );
+    client.set_installment_plan(&escrow_id, &schedule(&env, &[(1_000, 300), (2_000, 600)]));
+
+    client.fund_installment(&escrow_id, &depositor, &300);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::PartiallyFunded);
+    assert_eq!(escrow.installments_funded, 300);
+    assert_eq!(
+        client.try_fund_installment(&escrow_id, &depositor, &601),
+        Err(Ok(crate::errors::EscrowError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_fund_installment(&escrow_id, &beneficiary, &600),
+        Err(Ok(crate::errors::EscrowError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_fund_escrow(&escrow_id, &depositor),
+        Err(Ok(crate::errors::EscrowError::InvalidState))
+    );
+
+    client.fund_installment(&escrow_id, &depositor, &600);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Funded);
+    assert_eq!(escrow.installments_funded, 900);
+    assert_eq!(TokenClient::new(&env, &token).balance(&client.address), 900);
+    assert_eq!(
+        client.try_refund_installments_on_timeout(&escrow_id),
+        Err(Ok(crate::errors::EscrowError::InvalidState))
+    );
+}
+
+#[test]
+fn test_missed_installment_deadline_refunds_partial_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, escrow_id, depositor, _, token) = pending_escrow(&env, 900);
+    client.set_installment_plan(&escrow_id, &schedule(&env, &[(1_000, 300), (2_000, 600)]));
+    client.fund_installment(&escrow_id, &depositor, &300);
+
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    assert_eq!(
+        client.try_refund_installments_on_timeout(&escrow_id),
+        Err(Ok(crate::errors::EscrowError::TimeoutNotReached))
+    );
+
+    env.ledger().with_mut(|l| l.timestamp = 2_001);
+    assert_eq!(
+        client.try_fund_installment(&escrow_id, &depositor, &600),
+        Err(Ok(crate::errors::EscrowError::InstallmentDeadlinePassed))
+    );
+    client.refund_installments_on_timeout(&escrow_id);
+
+    assert_eq!(client.get_escrow(&escrow_id).status, EscrowStatus::Refunded);
+    let token = TokenClient::new(&env, &token);
+    assert_eq!(token.balance(&depositor), 900);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_missing_an_earlier_installment_ends_the_plan() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, escrow_id, depositor, _, token) = pending_escrow(&env, 900);
+    client.set_installment_plan(
+        &escrow_id,
+        &schedule(&env, &[(1_000, 300), (2_000, 300), (3_000, 300)]),
+    );
+    client.fund_installment(&escrow_id, &depositor, &300);
+
+    // Only the first installment is covered when the second falls due.
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    assert_eq!(
+        client.try_refund_installments_on_timeout(&escrow_id),
+        Err(Ok(crate::errors::EscrowError::TimeoutNotReached))
+    );
+    env.ledger().with_mut(|l| l.timestamp = 2_001);
+    assert_eq!(
+        client.try_fund_installment(&escrow_id, &depositor, &300),
+        Err(Ok(crate::errors::EscrowError::InstallmentDeadlinePassed))
+    );
+
+    // The plan is refunded before its last due date.
+    client.refund_installments_on_timeout(&escrow_id);
+    assert_eq!(client.get_escrow(&escrow_id).status, EscrowStatus::Refunded);
+    assert_eq!(TokenClient::new(&env, &token).balance(&depositor), 900);
+}
+
+#[test]
+fn test_installments_paid_ahead_cover_later_due_dates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, escrow_id, depositor, _, _) = pending_escrow(&env, 900);
+    client.set_installment_plan(
+        &escrow_id,
+        &schedule(&env, &[(1_000, 300), (2_000, 300), (3_000, 300)]),
+    );
+    client.fund_installment(&escrow_id, &depositor, &600);
+
+    env.ledger().with_mut(|l| l.timestamp = 2_500);
+    assert_eq!(
+        client.try_refund_installments_on_timeout(&escrow_id),
+        Err(Ok(crate::errors::EscrowError::TimeoutNotReached))
+    );
+    client.fund_installment(&escrow_id, &depositor, &300);
+    assert_eq!(client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+}
+
+#[test]
+fn test_fund_escrow_in_full_settles_installment_plan() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, escrow_id, depositor, _, _) = pending_escrow(&env, 900);
+    client.set_installment_plan(&escrow_id, &schedule(&env, &[(1_000, 300), (2_000, 600)]));
+
+    client.fund_escrow(&escrow_id, &depositor);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Funded);
+    assert_eq!(escrow.installments_funded, 900);
+    assert_eq!(
+        client.try_fund_installment(&escrow_id, &depositor, &1),
+        Err(Ok(crate::errors::EscrowError::InvalidState))
+    );
+}
+
+#[test]
+fn test_create_funded_adopts_an_escrow_funded_in_installments() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, escrow_id, depositor, _, token) = pending_escrow(&env, 900);
+    let escrow = client.get_escrow(&escrow_id);
+    let (arbiter, platform_governance, agent_referral) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    let activate = |amount: i128| {
+        client.try_create_funded(
+            escrow.agreement_id.as_ref().unwrap(),
+            &escrow.depositor,
+            &escrow.beneficiary,
+            &arbiter,
+            &platform_governance,
+            &agent_referral,
+            &amount,
+            &token,
+        )
+    };
+    let outstanding = Err(Ok(crate::errors::EscrowError::InstallmentsOutstanding));
+
+    // An agreed plan keeps the escrow from being replaced, before and
+    // during funding.
+    client.set_installment_plan(&escrow_id, &schedule(&env, &[(1_000, 300), (2_000, 600)]));
+    assert_eq!(activate(900), outstanding);
+    client.fund_installment(&escrow_id, &depositor, &300);
+    assert_eq!(activate(900), outstanding);
+    assert_eq!(
+        client.get_escrow(&escrow_id).status,
+        EscrowStatus::PartiallyFunded
+    );
+
+    client.fund_installment(&escrow_id, &depositor, &600);
+    client.approve_release(&escrow_id, &depositor, &depositor);
+    assert_eq!(
+        activate(1_000),
+        Err(Ok(crate::errors::EscrowError::AgreementMismatch))
+    );
+    assert_eq!(activate(900), Ok(Ok(escrow_id.clone())));
+
+    // Nothing more is taken from the depositor, and the escrow answers to
+    // the arbiter activation chose.
+    let token = TokenClient::new(&env, &token);
+    assert_eq!(token.balance(&client.address), 900);
+    assert_eq!(token.balance(&depositor), 0);
+    let adopted = client.get_escrow(&escrow_id);
+    assert_eq!(adopted.status, EscrowStatus::Funded);
+    assert_eq!(adopted.arbiter, arbiter);
+    assert_eq!(adopted.platform_governance, platform_governance);
+    assert_eq!(adopted.agent_referral, agent_referral);
+    assert_eq!(client.get_approval_count(&escrow_id, &depositor), 0);
+}
+
+#[test]
+fn test_create_funded_replaces_an_escrow_whose_plan_was_missed_unpaid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, escrow_id, _, _, token) = pending_escrow(&env, 900);
+    let escrow = client.get_escrow(&escrow_id);
+    client.set_installment_plan(&escrow_id, &schedule(&env, &[(1_000, 300), (2_000, 600)]));
+
+    env.ledger().with_mut(|l| l.timestamp = 1_001);
+    let replacement = client.create_funded(
+        escrow.agreement_id.as_ref().unwrap(),
+        &escrow.depositor,
+        &escrow.beneficiary,
+        &escrow.arbiter,
+        &escrow.platform_governance,
+        &escrow.agent_referral,
+        &900,
+        &token,
+    );
+
+    assert_ne!(replacement, escrow_id);
+    assert_eq!(client.get_escrow(&escrow_id).status, EscrowStatus::Refunded);
+    assert_eq!(client.get_escrow(&replacement).status, EscrowStatus::Funded);
+}
+
+#[test]
+fn test_refunded_installment_plan_can_be_started_over() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, escrow_id, depositor, _, token) = pending_escrow(&env, 900);
+    let escrow = client.get_escrow(&escrow_id);
+    let agreement_id = escrow.agreement_id.clone().unwrap();
+    client.set_installment_plan(&escrow_id, &schedule(&env, &[(1_000, 300), (2_000, 600)]));
+    client.fund_installment(&escrow_id, &depositor, &300);
+
+    env.ledger().with_mut(|l| l.timestamp = 2_001);
+    client.refund_installments_on_timeout(&escrow_id);
+
+    let retry = client.create(
+        &agreement_id,
+        &depositor,
+        &escrow.beneficiary,
+        &escrow.arbiter,
+        &escrow.platform_governance,
+        &escrow.agent_referral,
+        &900,
+        &token,
+    );
+    assert_ne!(retry, escrow_id);
+    assert_eq!(client.get_agreement_escrow(&agreement_id), Some(retry));
+    assert_eq!(client.get_escrow(&escrow_id).status, EscrowStatus::Refunded);
+    assert_eq!(TokenClient::new(&env, &token).balance(&depositor), 900);
+}
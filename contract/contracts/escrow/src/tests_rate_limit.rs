@@ -1,11 +1,13 @@
+use crate::errors::EscrowError;
 use crate::rate_limit;
-use crate::types::{DataKey, RateLimitConfig};
+use crate::types::{DataKey, RateLimitConfig, RateLimitMode};
+use rate_limiter::LegacyRateLimitConfig;
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
     Address, Env,
 };
 
-use crate::escrow_impl::EscrowContract;
+use crate::escrow_impl::{EscrowContract, EscrowContractClient};
 
 fn setup() -> (Env, Address, Address) {
     let env = Env::default();
@@ -43,6 +45,8 @@ fn test_check_rate_limit_within_limit() {
             max_calls_per_block: 10,
             max_calls_per_user_per_day: 5,
             cooldown_blocks: 0,
+            mode: RateLimitMode::LedgerBlocks,
+            window_seconds: 0,
         },
     );
 
@@ -65,6 +69,8 @@ fn test_check_rate_limit_exceed_block() {
             max_calls_per_block: 2,
             max_calls_per_user_per_day: 100,
             cooldown_blocks: 0,
+            mode: RateLimitMode::LedgerBlocks,
+            window_seconds: 0,
         },
     );
 
@@ -99,6 +105,8 @@ fn test_check_rate_limit_exceed_daily() {
             max_calls_per_block: 100,
             max_calls_per_user_per_day: 2,
             cooldown_blocks: 0,
+            mode: RateLimitMode::LedgerBlocks,
+            window_seconds: 0,
         },
     );
 
@@ -136,6 +144,8 @@ fn test_check_rate_limit_cooldown() {
             max_calls_per_block: 100,
             max_calls_per_user_per_day: 100,
             cooldown_blocks: 10,
+            mode: RateLimitMode::LedgerBlocks,
+            window_seconds: 0,
         },
     );
 
@@ -156,3 +166,133 @@ fn test_check_rate_limit_cooldown() {
     });
     assert!(r3.is_ok());
 }
+
+#[test]
+fn test_sliding_window_counts_trailing_calls() {
+    let (env, contract_id, user) = setup();
+
+    seed_config(
+        &env,
+        &contract_id,
+        &RateLimitConfig {
+            max_calls_per_block: 100,
+            max_calls_per_user_per_day: 2,
+            cooldown_blocks: 0,
+            mode: RateLimitMode::SlidingWindow,
+            window_seconds: 100,
+        },
+    );
+    let call = || {
+        env.as_contract(&contract_id, || {
+            rate_limit::check_rate_limit(&env, &user, "deposit")
+        })
+    };
+
+    env.ledger().with_mut(|li| li.timestamp = 1_090);
+    assert!(call().is_ok());
+    assert!(call().is_ok());
+    assert!(call().is_err());
+
+    // A new fixed window has started, but both calls still fall in the
+    // trailing window, so there is no burst at the edge.
+    env.ledger().with_mut(|li| li.timestamp = 1_110);
+    assert!(call().is_err());
+
+    // Once the earlier calls have mostly aged out, capacity returns.
+    env.ledger().with_mut(|li| li.timestamp = 1_160);
+    assert!(call().is_ok());
+}
+
+#[test]
+fn test_token_bucket_refills_over_time() {
+    let (env, contract_id, user) = setup();
+
+    seed_config(
+        &env,
+        &contract_id,
+        &RateLimitConfig {
+            max_calls_per_block: 100,
+            max_calls_per_user_per_day: 2,
+            cooldown_blocks: 0,
+            mode: RateLimitMode::TokenBucket,
+            window_seconds: 100,
+        },
+    );
+    let call = || {
+        env.as_contract(&contract_id, || {
+            rate_limit::check_rate_limit(&env, &user, "deposit")
+        })
+    };
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert!(call().is_ok());
+    assert!(call().is_ok());
+    assert!(call().is_err());
+
+    // Two calls per 100 seconds: one call's worth after 50 seconds.
+    env.ledger().with_mut(|li| li.timestamp = 1_049);
+    assert!(call().is_err());
+    env.ledger().with_mut(|li| li.timestamp = 1_050);
+    assert!(call().is_ok());
+    assert!(call().is_err());
+}
+
+#[test]
+fn test_config_stored_before_window_modes_is_still_read() {
+    let (env, contract_id, user) = setup();
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &DataKey::RateLimitConfig,
+            &LegacyRateLimitConfig {
+                max_calls_per_block: 10,
+                max_calls_per_user_per_day: 1,
+                cooldown_blocks: 0,
+            },
+        );
+    });
+
+    let config = env.as_contract(&contract_id, || rate_limit::get_rate_limit_config(&env));
+    assert_eq!(config.max_calls_per_user_per_day, 1);
+    assert_eq!(config.mode, RateLimitMode::LedgerBlocks);
+    assert_eq!(config.window_seconds, 86_400);
+
+    env.as_contract(&contract_id, || {
+        assert!(rate_limit::check_rate_limit(&env, &user, "deposit").is_ok());
+        assert_eq!(
+            rate_limit::check_rate_limit(&env, &user, "deposit"),
+            Err(EscrowError::RateLimitExceeded)
+        );
+    });
+}
+
+#[test]
+fn test_only_the_admin_sets_the_rate_limit_config() {
+    let (env, contract_id, user) = setup();
+    env.mock_all_auths();
+    let client = EscrowContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize_admin(&admin);
+    let config = RateLimitConfig {
+        max_calls_per_block: 4,
+        max_calls_per_user_per_day: 8,
+        cooldown_blocks: 1,
+        mode: RateLimitMode::TokenBucket,
+        window_seconds: 3_600,
+    };
+
+    assert_eq!(
+        client.try_set_rate_limit_config(&user, &config),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+    let no_window = RateLimitConfig {
+        window_seconds: 0,
+        ..config.clone()
+    };
+    assert_eq!(
+        client.try_set_rate_limit_config(&admin, &no_window),
+        Err(Ok(EscrowError::InvalidRateLimitConfig))
+    );
+
+    client.set_rate_limit_config(&admin, &config);
+    assert_eq!(client.get_rate_limit_config(), config);
+}
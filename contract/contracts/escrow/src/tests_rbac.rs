@@ -8,6 +8,7 @@ use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
 use soroban_sdk::{Address, Env};
 
 use crate::escrow_impl::{EscrowContract, EscrowContractClient};
+use crate::tests::{agreement, wire_chioma};
 use crate::types::{EscrowStatus, TimeoutConfig};
 
 fn setup(
@@ -23,6 +24,7 @@ fn setup(
 ) {
     let contract_id = env.register(EscrowContract, ());
     let client = EscrowContractClient::new(env, &contract_id);
+    wire_chioma(env, &contract_id);
 
     let depositor = Address::generate(env);
     let beneficiary = Address::generate(env);
@@ -57,6 +59,7 @@ fn funded_escrow(
     amount: i128,
 ) -> soroban_sdk::BytesN<32> {
     let escrow_id = client.create(
+        &agreement(env, &client.address, depositor, beneficiary, &amount, token),
         depositor,
         beneficiary,
         arbiter,
@@ -330,6 +333,14 @@ fn test_depositor_can_fund_escrow() {
     let amount = 500i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -358,6 +369,14 @@ fn test_beneficiary_cannot_fund_escrow() {
     let amount = 500i128;
 
     let escrow_id = client.create(
+        &agreement(
+            &env,
+            &client.address,
+            &depositor,
+            &beneficiary,
+            &amount,
+            &token,
+        ),
         &depositor,
         &beneficiary,
         &arbiter,
@@ -0,0 +1,255 @@
+//! Tests for deposits moved into a yield vault.
+
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient as TokenAdminClient};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env};
+
+use crate::errors::EscrowError;
+use crate::escrow_impl::{EscrowContract, EscrowContractClient};
+use crate::tests::{agreement, wire_chioma};
+use crate::types::EscrowStatus;
+
+// ─── mock vault ───────────────────────────────────────────────────────────────
+
+#[contracttype]
+enum VaultKey {
+    Token,
+    TotalShares,
+    Shares(Address),
+}
+
+/// Minimal share-based vault: yield is simulated by minting tokens to it.
+#[contract]
+pub struct MockVault;
+
+#[contractimpl]
+impl MockVault {
+    pub fn init(env: Env, token: Address) {
+        env.storage().instance().set(&VaultKey::Token, &token);
+    }
+
+    pub fn deposit(env: Env, from: Address, amount: i128) -> i128 {
+        from.require_auth();
+        let token = Self::token(&env);
+        let assets = token.balance(&env.current_contract_address());
+        let total = Self::total_shares(&env);
+        let shares = if total == 0 || assets == 0 {
+            amount
+        } else {
+            amount * total / assets
+        };
+        token.transfer(&from, env.current_contract_address(), &amount);
+        Self::credit(&env, &from, shares);
+        shares
+    }
+
+    pub fn withdraw(env: Env, owner: Address, to: Address, amount: i128) -> i128 {
+        owner.require_auth();
+        let token = Self::token(&env);
+        let assets = token.balance(&env.current_contract_address());
+        let total = Self::total_shares(&env);
+        // Round shares up so the vault never pays out more than it burns.
+        let shares = (amount * total + assets - 1) / assets;
+        Self::credit(&env, &owner, -shares);
+        token.transfer(&env.current_contract_address(), &to, &amount);
+        shares
+    }
+
+    pub fn convert_to_assets(env: Env, shares: i128) -> i128 {
+        let total = Self::total_shares(&env);
+        if total == 0 {
+            return 0;
+        }
+        shares * Self::token(&env).balance(&env.current_contract_address()) / total
+    }
+}
+
+impl MockVault {
+    fn token(env: &Env) -> TokenClient<'_> {
+        let token: Address = env.storage().instance().get(&VaultKey::Token).unwrap();
+        TokenClient::new(env, &token)
+    }
+
+    fn total_shares(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&VaultKey::TotalShares)
+            .unwrap_or(0)
+    }
+
+    fn credit(env: &Env, owner: &Address, shares: i128) {
+        let key = VaultKey::Shares(owner.clone());
+        let balance: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        assert!(balance + shares >= 0, "insufficient shares");
+        env.storage().instance().set(&key, &(balance + shares));
+        env.storage()
+            .instance()
+            .set(&VaultKey::TotalShares, &(Self::total_shares(env) + shares));
+    }
+}
+
+// ─── helpers ──────────────────────────────────────────────────────────────────
+
+const DEPOSIT: i128 = 10_000;
+
+struct Setup<'a> {
+    client: EscrowContractClient<'a>,
+    token: Address,
+    vault: Address,
+    depositor: Address,
+    beneficiary: Address,
+    arbiter: Address,
+    escrow_id: BytesN<32>,
+}
+
+/// A Funded escrow of `DEPOSIT` next to an empty vault for its token.
+fn setup(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+    let client = EscrowContractClient::new(env, &env.register(EscrowContract, ()));
+    wire_chioma(env, &client.address);
+
+    let depositor = Address::generate(env);
+    let beneficiary = Address::generate(env);
+    let arbiter = Address::generate(env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    TokenAdminClient::new(env, &token).mint(&depositor, &DEPOSIT);
+
+    let agreement_id = agreement(
+        env,
+        &client.address,
+        &depositor,
+        &beneficiary,
+        &DEPOSIT,
+        &token,
+    );
+    let escrow_id = client.create_funded(
+        &agreement_id,
+        &depositor,
+        &beneficiary,
+        &arbiter,
+        &Address::generate(env),
+        &Address::generate(env),
+        &DEPOSIT,
+        &token,
+    );
+
+    let vault = env.register(MockVault, ());
+    MockVaultClient::new(env, &vault).init(&token);
+
+    Setup {
+        client,
+        token,
+        vault,
+        depositor,
+        beneficiary,
+        arbiter,
+        escrow_id,
+    }
+}
+
+fn simulate_yield(env: &Env, s: &Setup, amount: i128) {
+    TokenAdminClient::new(env, &s.token).mint(&s.vault, &amount);
+}
+
+// ─── tests ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_route_to_vault_moves_the_deposit() {
+    let env = Env::default();
+    let s = setup(&env);
+    let token = TokenClient::new(&env, &s.token);
+
+    let position = s.client.route_to_vault(&s.escrow_id, &s.vault);
+    assert_eq!(position.principal, DEPOSIT);
+    assert_eq!(position.shares, DEPOSIT);
+    assert_eq!(token.balance(&s.client.address), 0);
+    assert_eq!(token.balance(&s.vault), DEPOSIT);
+    assert_eq!(s.client.get_vault_position(&s.escrow_id), Some(position));
+    assert_eq!(s.client.get_vault_yield(&s.escrow_id), 0);
+
+    let again = s.client.try_route_to_vault(&s.escrow_id, &s.vault);
+    assert_eq!(again, Err(Ok(EscrowError::InvalidState)));
+}
+
+#[test]
+fn test_only_chioma_moves_deposits_into_a_vault() {
+    let env = Env::default();
+    let s = setup(&env);
+
+    let result = s
+        .client
+        .mock_auths(&[])
+        .try_route_to_vault(&s.escrow_id, &s.vault);
+    assert!(result.is_err());
+    assert_eq!(s.client.get_vault_position(&s.escrow_id), None);
+}
+
+#[test]
+fn test_withdraw_yield_never_touches_the_deposit() {
+    let env = Env::default();
+    let s = setup(&env);
+    let token = TokenClient::new(&env, &s.token);
+    let recipient = Address::generate(&env);
+    s.client.route_to_vault(&s.escrow_id, &s.vault);
+    simulate_yield(&env, &s, 300);
+
+    s.client.withdraw_yield(&s.escrow_id, &recipient, &120);
+    assert_eq!(token.balance(&recipient), 120);
+    assert_eq!(s.client.get_vault_yield(&s.escrow_id), 180);
+
+    let result = s.client.try_withdraw_yield(&s.escrow_id, &recipient, &181);
+    assert_eq!(result, Err(Ok(EscrowError::InsufficientFunds)));
+}
+
+#[test]
+fn test_compound_yield_adds_to_the_deposit() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client.route_to_vault(&s.escrow_id, &s.vault);
+    simulate_yield(&env, &s, 300);
+
+    s.client.compound_yield(&s.escrow_id, &100);
+    assert_eq!(s.client.get_escrow(&s.escrow_id).amount, DEPOSIT + 100);
+    let position = s.client.get_vault_position(&s.escrow_id).unwrap();
+    assert_eq!(position.principal, DEPOSIT + 100);
+    assert_eq!(s.client.get_vault_yield(&s.escrow_id), 200);
+}
+
+#[test]
+fn test_release_brings_the_deposit_and_unpaid_yield_back() {
+    let env = Env::default();
+    let s = setup(&env);
+    let token = TokenClient::new(&env, &s.token);
+    s.client.route_to_vault(&s.escrow_id, &s.vault);
+    simulate_yield(&env, &s, 300);
+
+    s.client
+        .approve_release(&s.escrow_id, &s.depositor, &s.beneficiary);
+    s.client
+        .approve_release(&s.escrow_id, &s.arbiter, &s.beneficiary);
+
+    assert_eq!(token.balance(&s.beneficiary), DEPOSIT + 300);
+    assert_eq!(token.balance(&s.vault), 0);
+    assert_eq!(token.balance(&s.client.address), 0);
+    assert_eq!(s.client.get_vault_position(&s.escrow_id), None);
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.amount, DEPOSIT + 300);
+}
+
+#[test]
+fn test_close_vault_position_returns_funds_to_the_escrow() {
+    let env = Env::default();
+    let s = setup(&env);
+    let token = TokenClient::new(&env, &s.token);
+    s.client.route_to_vault(&s.escrow_id, &s.vault);
+    simulate_yield(&env, &s, 40);
+
+    assert_eq!(s.client.close_vault_position(&s.escrow_id), DEPOSIT + 40);
+    assert_eq!(token.balance(&s.client.address), DEPOSIT + 40);
+    assert_eq!(s.client.get_escrow(&s.escrow_id).amount, DEPOSIT + 40);
+    let result = s.client.try_withdraw_yield(&s.escrow_id, &s.depositor, &1);
+    assert_eq!(result, Err(Ok(EscrowError::EscrowNotFound)));
+}
@@ -1,5 +1,5 @@
 //! Data structures and enums for the Escrow contract.
-use soroban_sdk::{contracttype, Address, BytesN, String};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
 /// Status of an escrow throughout its lifecycle.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -15,6 +15,21 @@ pub enum EscrowStatus {
     Refunded = 3,
     /// Under dispute, awaiting admin resolution
     Disputed = 4,
+    /// Some installments deposited, the rest still due
+    PartiallyFunded = 5,
+}
+
+/// Most installments an escrow's amount can be split into.
+pub const MAX_INSTALLMENTS: u32 = 12;
+
+/// One contribution towards an escrow's amount.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Installment {
+    /// Timestamp by which the contribution is due
+    pub due_at: u64,
+    /// Amount due
+    pub amount: i128,
 }
 
 /// Represents a security deposit escrow managed by 2-of-3 multi-sig.
@@ -23,6 +38,9 @@ pub enum EscrowStatus {
 pub struct Escrow {
     /// Unique identifier for the escrow (hash of agreement_id)
     pub id: BytesN<32>,
+    /// Chioma agreement whose security deposit the escrow holds; `None` for
+    /// escrows created before escrows were linked to agreements
+    pub agreement_id: Option<String>,
     /// The party depositing funds (tenant)
     pub depositor: Address,
     /// The party who benefits from the deposit (landlord/admin)
@@ -35,6 +53,11 @@ pub struct Escrow {
     pub agent_referral: Address,
     /// Amount of funds in the escrow
     pub amount: i128,
+    /// Installments `amount` is funded in by ascending due date; empty
+    /// unless both parties agreed to installment funding
+    pub installments: Vec<Installment>,
+    /// Amount deposited through installments so far
+    pub installments_funded: i128,
     /// Token contract address (USDC, XLM, etc.)
     pub token: Address,
     /// Current status of the escrow
@@ -55,6 +78,210 @@ pub struct Escrow {
     pub freeze_reason: Option<String>,
 }
 
+/// `Escrow` as stored before installment funding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct EscrowV2 {
+    /// Unique identifier for the escrow (hash of agreement_id)
+    pub id: BytesN<32>,
+    /// Chioma agreement whose security deposit the escrow holds; `None` for
+    /// escrows created before escrows were linked to agreements
+    pub agreement_id: Option<String>,
+    /// The party depositing funds (tenant)
+    pub depositor: Address,
+    /// The party who benefits from the deposit (landlord/admin)
+    pub beneficiary: Address,
+    /// The admin/arbiter who can resolve disputes
+    pub arbiter: Address,
+    /// Platform governance address receiving 5% on rent release
+    pub platform_governance: Address,
+    /// Agent/referral address receiving 5% on rent release
+    pub agent_referral: Address,
+    /// Amount of funds in the escrow
+    pub amount: i128,
+    /// Token contract address (USDC, XLM, etc.)
+    pub token: Address,
+    /// Current status of the escrow
+    pub status: EscrowStatus,
+    /// Timestamp when escrow was created
+    pub created_at: u64,
+    /// Timeout threshold in days for automatic escrow release/refund
+    pub timeout_days: u64,
+    /// Timestamp when dispute was raised
+    pub disputed_at: Option<u64>,
+    /// Reason for dispute, if any
+    pub dispute_reason: Option<String>,
+    /// Emergency freeze flag - prevents all fund movements when true
+    pub is_frozen: bool,
+    /// Timestamp when escrow was frozen
+    pub frozen_at: Option<u64>,
+    /// Reason for freezing the escrow
+    pub freeze_reason: Option<String>,
+}
+
+/// `Escrow` as stored before escrows were linked to agreements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct EscrowV1 {
+    /// Unique identifier for the escrow (hash of agreement_id)
+    pub id: BytesN<32>,
+    /// The party depositing funds (tenant)
+    pub depositor: Address,
+    /// The party who benefits from the deposit (landlord/admin)
+    pub beneficiary: Address,
+    /// The admin/arbiter who can resolve disputes
+    pub arbiter: Address,
+    /// Platform governance address receiving 5% on rent release
+    pub platform_governance: Address,
+    /// Agent/referral address receiving 5% on rent release
+    pub agent_referral: Address,
+    /// Amount of funds in the escrow
+    pub amount: i128,
+    /// Token contract address (USDC, XLM, etc.)
+    pub token: Address,
+    /// Current status of the escrow
+    pub status: EscrowStatus,
+    /// Timestamp when escrow was created
+    pub created_at: u64,
+    /// Timeout threshold in days for automatic escrow release/refund
+    pub timeout_days: u64,
+    /// Timestamp when dispute was raised
+    pub disputed_at: Option<u64>,
+    /// Reason for dispute, if any
+    pub dispute_reason: Option<String>,
+    /// Emergency freeze flag - prevents all fund movements when true
+    pub is_frozen: bool,
+    /// Timestamp when escrow was frozen
+    pub frozen_at: Option<u64>,
+    /// Reason for freezing the escrow
+    pub freeze_reason: Option<String>,
+}
+
+/// Versioned envelope an `Escrow` is persisted in.
+///
+/// When `Escrow` changes shape, freeze the old layout under a new name, add a
+/// variant for the new one and convert in `into_current`; stored records are
+/// then upgraded as they are read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum VersionedEscrow {
+    V1(EscrowV1),
+    V2(EscrowV2),
+    V3(Escrow),
+}
+
+impl Escrow {
+    /// Due date of the last installment, if the escrow is funded in
+    /// installments.
+    pub fn installment_deadline(&self) -> Option<u64> {
+        self.installments
+            .last()
+            .map(|installment| installment.due_at)
+    }
+
+    /// Whether the escrow was refunded because its installment plan was
+    /// missed before the installments reached `amount`.
+    pub fn installments_refunded(&self) -> bool {
+        self.status == EscrowStatus::Refunded
+            && !self.installments.is_empty()
+            && self.installments_funded < self.amount
+    }
+
+    /// Whether, at `now`, less has been deposited than the installments
+    /// already due call for. A missed installment ends the plan: nothing
+    /// more can be paid in and what was deposited can be refunded.
+    pub fn installment_missed(&self, now: u64) -> bool {
+        let mut due: i128 = 0;
+        for installment in self.installments.iter() {
+            if installment.due_at >= now {
+                break;
+            }
+            due += installment.amount;
+        }
+        self.installments_funded < due
+    }
+}
+
+impl VersionedEscrow {
+    /// Convert a stored record to the current layout.
+    pub fn into_current(self) -> Escrow {
+        match self {
+            VersionedEscrow::V1(escrow) => escrow.into(),
+            VersionedEscrow::V2(escrow) => escrow.into(),
+            VersionedEscrow::V3(escrow) => escrow,
+        }
+    }
+}
+
+impl From<EscrowV1> for Escrow {
+    fn from(escrow: EscrowV1) -> Self {
+        EscrowV2::from(escrow).into()
+    }
+}
+
+impl From<EscrowV2> for Escrow {
+    fn from(escrow: EscrowV2) -> Self {
+        let env = escrow.id.env().clone();
+        Escrow {
+            id: escrow.id,
+            agreement_id: escrow.agreement_id,
+            depositor: escrow.depositor,
+            beneficiary: escrow.beneficiary,
+            arbiter: escrow.arbiter,
+            platform_governance: escrow.platform_governance,
+            agent_referral: escrow.agent_referral,
+            amount: escrow.amount,
+            installments: Vec::new(&env),
+            installments_funded: 0,
+            token: escrow.token,
+            status: escrow.status,
+            created_at: escrow.created_at,
+            timeout_days: escrow.timeout_days,
+            disputed_at: escrow.disputed_at,
+            dispute_reason: escrow.dispute_reason,
+            is_frozen: escrow.is_frozen,
+            frozen_at: escrow.frozen_at,
+            freeze_reason: escrow.freeze_reason,
+        }
+    }
+}
+
+impl From<EscrowV1> for EscrowV2 {
+    fn from(escrow: EscrowV1) -> Self {
+        EscrowV2 {
+            id: escrow.id,
+            agreement_id: None,
+            depositor: escrow.depositor,
+            beneficiary: escrow.beneficiary,
+            arbiter: escrow.arbiter,
+            platform_governance: escrow.platform_governance,
+            agent_referral: escrow.agent_referral,
+            amount: escrow.amount,
+            token: escrow.token,
+            status: escrow.status,
+            created_at: escrow.created_at,
+            timeout_days: escrow.timeout_days,
+            disputed_at: escrow.disputed_at,
+            dispute_reason: escrow.dispute_reason,
+            is_frozen: escrow.is_frozen,
+            frozen_at: escrow.frozen_at,
+            freeze_reason: escrow.freeze_reason,
+        }
+    }
+}
+
+/// Progress of one `migrate_records` call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct RecordMigrationBatch {
+    /// Records rewritten in the current layout by this call
+    pub migrated: u32,
+    /// Cursor to pass to the next call
+    pub next_cursor: u32,
+    /// Whether every indexed record has been visited
+    pub done: bool,
+}
+
 /// Contract-level timeout configuration.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
@@ -92,6 +319,18 @@ pub struct ReleaseRecord {
     pub reason: String,
 }
 
+/// Vault shares a Funded escrow's deposit was moved into to earn yield.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct VaultPosition {
+    pub vault: Address,
+    /// Part of the escrow amount the shares stand for; anything the
+    /// position is worth above it is yield.
+    pub principal: i128,
+    pub shares: i128,
+    pub routed_at: u64,
+}
+
 /// Rate limiting configuration.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
@@ -99,6 +338,10 @@ pub struct RateLimitConfig {
     pub max_calls_per_block: u32,
     pub max_calls_per_user_per_day: u32,
     pub cooldown_blocks: u32,
+    /// How `max_calls_per_user_per_day` is measured.
+    pub mode: RateLimitMode,
+    /// Window length for the timestamp-based modes.
+    pub window_seconds: u64,
 }
 
 /// User call count for rate limiting.
@@ -112,6 +355,21 @@ pub struct UserCallCount {
     pub daily_reset_block: u64,
 }
 
+/// Per-user limiting strategy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum RateLimitMode {
+    /// Daily count over a fixed window of ledger sequence numbers, reset all
+    /// at once.
+    LedgerBlocks,
+    /// At most `max_calls_per_user_per_day` calls in any trailing
+    /// `window_seconds`, by ledger timestamp.
+    SlidingWindow,
+    /// A bucket of `max_calls_per_user_per_day` calls that refills
+    /// continuously over `window_seconds`.
+    TokenBucket,
+}
+
 /// Storage key variants for persistent storage.
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
@@ -124,6 +382,8 @@ pub enum DataKey {
     DisputeInfo(BytesN<32>),
     /// Counter for total escrows created
     EscrowCount,
+    /// Escrow IDs in creation order: DataKey::EscrowIndex(n) => escrow_id
+    EscrowIndex(u32),
     /// Per-target approval count: DataKey::ApprovalCount(escrow_id, release_to) => u32
     ApprovalCount(BytesN<32>, Address),
     /// Per-signer-per-target flag: DataKey::SignerApproved(escrow_id, signer, release_to) => bool
@@ -138,8 +398,22 @@ pub enum DataKey {
     UserCallCount(Address, String),
     /// Block call count for rate limiting: DataKey::BlockCallCount(block_number, function_name)
     BlockCallCount(u64, String),
+    /// Timestamp-mode limiter state: DataKey::RateWindow(user, function_name)
+    RateWindow(Address, String),
     /// System admin address for emergency operations
     SystemAdmin,
     /// Upgrade proposal
     UpgradeProposal(String),
+    /// Code version, bumped by every executed upgrade
+    ContractVersion,
+    /// Layout version of the stored data
+    StorageVersion,
+    /// Code version at which `migrate` last ran
+    MigratedVersion,
+    /// Escrow securing an agreement: DataKey::AgreementEscrow(agreement_id) => escrow_id
+    AgreementEscrow(String),
+    /// Protocol directory peer contracts are resolved through
+    Directory,
+    /// Vault shares a deposit was moved into: DataKey::VaultPosition(escrow_id)
+    VaultPosition(BytesN<32>),
 }
@@ -1,7 +1,9 @@
+use crate::access::AccessControl;
 use crate::errors::EscrowError;
 use crate::events;
-use crate::types::DataKey;
-use soroban_sdk::{Address, Bytes, Env, String, Vec};
+use crate::storage::EscrowStorage;
+use crate::types::{DataKey, RecordMigrationBatch};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
 /// Contract upgrade proposal
 #[derive(Clone)]
@@ -9,7 +11,7 @@ use soroban_sdk::{Address, Bytes, Env, String, Vec};
 pub struct UpgradeProposal {
     pub id: String,
     pub proposer: Address,
-    pub wasm_hash: Bytes,
+    pub wasm_hash: BytesN<32>,
     pub approvals: Vec<Address>,
     pub required_signatures: u32,
     pub eta: u64,
@@ -18,16 +20,17 @@ pub struct UpgradeProposal {
     pub created_at: u64,
 }
 
-/// Propose a contract upgrade (requires authorization)
+/// Propose a contract upgrade (admin only)
 pub fn propose_upgrade(
     env: &Env,
     proposer: Address,
     proposal_id: String,
-    wasm_hash: Bytes,
+    wasm_hash: BytesN<32>,
     notes: String,
     delay_seconds: u64,
 ) -> Result<(), EscrowError> {
     proposer.require_auth();
+    require_admin(env, &proposer)?;
 
     if env
         .storage()
@@ -67,13 +70,14 @@ pub fn propose_upgrade(
     Ok(())
 }
 
-/// Approve an upgrade proposal
+/// Approve an upgrade proposal (admin only)
 pub fn approve_upgrade(
     env: &Env,
     approver: Address,
     proposal_id: String,
 ) -> Result<(), EscrowError> {
     approver.require_auth();
+    require_admin(env, &approver)?;
 
     let mut proposal: UpgradeProposal = env
         .storage()
@@ -96,13 +100,14 @@ pub fn approve_upgrade(
     Ok(())
 }
 
-/// Execute an approved upgrade
+/// Execute an approved upgrade (admin only)
 pub fn execute_upgrade(
     env: &Env,
     executor: Address,
     proposal_id: String,
 ) -> Result<(), EscrowError> {
     executor.require_auth();
+    require_admin(env, &executor)?;
 
     let mut proposal: UpgradeProposal = env
         .storage()
@@ -123,7 +128,23 @@ pub fn execute_upgrade(
         .persistent()
         .set(&DataKey::UpgradeProposal(proposal_id.clone()), &proposal);
 
-    events::upgrade_executed(env, proposal_id, executor);
+    let version = get_contract_version(env) + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::ContractVersion, &version);
+
+    events::upgrade_executed(
+        env,
+        proposal_id,
+        executor,
+        proposal.wasm_hash.clone(),
+        version,
+    );
+
+    // The new code takes over once this invocation returns; `migrate` then
+    // brings stored data up to date.
+    env.deployer()
+        .update_current_contract_wasm(proposal.wasm_hash);
 
     Ok(())
 }
@@ -138,3 +159,149 @@ pub fn get_upgrade_proposal(
         .get(&DataKey::UpgradeProposal(proposal_id))
         .ok_or(EscrowError::EscrowNotFound)
 }
+
+// ─── Versioning and Migration ─────────────────────────────────────────────────
+
+/// Storage layout this build reads and writes. Bump it, and add a step to
+/// `run_migration`, whenever persisted data changes shape.
+pub const STORAGE_VERSION: u32 = 3;
+
+/// Most records `migrate_records` rewrites in one call.
+pub const MAX_MIGRATION_BATCH: u32 = 50;
+
+fn require_admin(env: &Env, caller: &Address) -> Result<(), EscrowError> {
+    AccessControl::is_system_admin(env, caller)
+}
+
+/// Version of the running code: 1 at deployment, bumped by every executed upgrade.
+pub fn get_contract_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ContractVersion)
+        .unwrap_or(1)
+}
+
+/// Layout of the stored data. Data written before layouts were tracked is
+/// in layout 1.
+pub fn get_storage_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::StorageVersion)
+        .unwrap_or(1)
+}
+
+/// Bring stored data up to `STORAGE_VERSION` after an upgrade (admin only).
+///
+/// Runs once per executed upgrade and returns the resulting layout version.
+pub fn migrate(env: &Env, caller: Address) -> Result<u32, EscrowError> {
+    caller.require_auth();
+    require_admin(env, &caller)?;
+
+    let version = get_contract_version(env);
+    let migrated: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::MigratedVersion)
+        .unwrap_or(1);
+    let from = get_storage_version(env);
+    if migrated >= version || from > STORAGE_VERSION {
+        return Err(EscrowError::InvalidState);
+    }
+
+    for step in (from + 1)..=STORAGE_VERSION {
+        run_migration(env, step)?;
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::StorageVersion, &STORAGE_VERSION);
+    env.storage()
+        .instance()
+        .set(&DataKey::MigratedVersion, &version);
+
+    events::storage_migrated(env, version, from, STORAGE_VERSION);
+    Ok(STORAGE_VERSION)
+}
+
+/// Convert stored data from layout `step - 1` to layout `step`.
+fn run_migration(_env: &Env, step: u32) -> Result<(), EscrowError> {
+    match step {
+        // Layout 1 is the original one; nothing to convert.
+        1 => Ok(()),
+        // Layout 2 links escrows to agreements. V1 records are converted as
+        // they are read, or eagerly with `migrate_records`.
+        2 => Ok(()),
+        // Layout 3 adds installment plans, absent on older records.
+        3 => Ok(()),
+        _ => Err(EscrowError::InvalidState),
+    }
+}
+
+/// Rewrite the next batch of escrows in the current record layout (admin only).
+///
+/// Walks the creation-order index from `cursor`, visiting at most `limit`
+/// entries (capped at `MAX_MIGRATION_BATCH`). Reads already upgrade older
+/// layouts, so this only saves decoding work on later reads; call again with
+/// the returned `next_cursor` until `done` is set. Escrows created before the
+/// index existed have no entry; rewrite those with `migrate_records_by_id`.
+pub fn migrate_records(
+    env: &Env,
+    caller: Address,
+    cursor: u32,
+    limit: u32,
+) -> Result<RecordMigrationBatch, EscrowError> {
+    caller.require_auth();
+    require_admin(env, &caller)?;
+    if limit == 0 {
+        return Err(EscrowError::InvalidState);
+    }
+
+    let count = EscrowStorage::get_count(env);
+    let end = cursor
+        .saturating_add(limit.min(MAX_MIGRATION_BATCH))
+        .min(count);
+
+    let mut migrated = 0;
+    for index in cursor..end {
+        let escrow =
+            EscrowStorage::get_indexed(env, index).and_then(|id| EscrowStorage::get(env, &id));
+        if let Some(escrow) = escrow {
+            EscrowStorage::save(env, &escrow);
+            migrated += 1;
+        }
+    }
+
+    let next_cursor = end.max(cursor);
+    Ok(RecordMigrationBatch {
+        migrated,
+        next_cursor,
+        done: next_cursor >= count,
+    })
+}
+
+/// Rewrite the listed escrows in the current record layout (admin only).
+///
+/// For escrows created before the creation-order index existed, whose ids
+/// are only known from their `escrow_created` events. Takes at most
+/// `MAX_MIGRATION_BATCH` ids; unknown ids are skipped. Returns how many
+/// escrows were rewritten.
+pub fn migrate_records_by_id(
+    env: &Env,
+    caller: Address,
+    escrow_ids: Vec<BytesN<32>>,
+) -> Result<u32, EscrowError> {
+    caller.require_auth();
+    require_admin(env, &caller)?;
+    if escrow_ids.is_empty() || escrow_ids.len() > MAX_MIGRATION_BATCH {
+        return Err(EscrowError::InvalidState);
+    }
+
+    let mut migrated = 0;
+    for escrow_id in escrow_ids.iter() {
+        if let Some(escrow) = EscrowStorage::get(env, &escrow_id) {
+            EscrowStorage::save(env, &escrow);
+            migrated += 1;
+        }
+    }
+    Ok(migrated)
+}
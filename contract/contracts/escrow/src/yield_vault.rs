@@ -0,0 +1,181 @@
+//! Lending vault positions for idle deposits.
+//!
+//! Chioma accrues interest on security deposits and may have a Funded
+//! escrow's deposit moved into a lending vault, so that interest is backed
+//! by real yield. Only chioma, as registered in the protocol directory, can
+//! open a position or draw yield from it. Any payout of the escrow first
+//! brings the whole position back, with yield not yet paid out staying with
+//! the deposit.
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::{contractclient, vec, Address, BytesN, Env, IntoVal, Symbol};
+
+use crate::access::AccessControl;
+use crate::errors::EscrowError;
+use crate::peers;
+use crate::storage::EscrowStorage;
+use crate::types::{DataKey, Escrow, EscrowStatus, VaultPosition};
+
+/// Share-based vault interface a yield strategy must implement.
+///
+/// `deposit` pulls `amount` of the vault's underlying token from `from` and
+/// mints shares; `withdraw` burns the shares needed to send `amount` of the
+/// underlying token to `to`.
+#[contractclient(name = "YieldVaultClient")]
+pub trait YieldVault {
+    fn deposit(env: Env, from: Address, amount: i128) -> i128;
+    fn withdraw(env: Env, owner: Address, to: Address, amount: i128) -> i128;
+    fn convert_to_assets(env: Env, shares: i128) -> i128;
+}
+
+pub fn get_position(env: &Env, escrow_id: &BytesN<32>) -> Option<VaultPosition> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::VaultPosition(escrow_id.clone()))
+}
+
+fn save_position(env: &Env, escrow_id: &BytesN<32>, position: &VaultPosition) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::VaultPosition(escrow_id.clone()), position);
+}
+
+/// Current value of a position in the underlying token.
+fn position_value(env: &Env, position: &VaultPosition) -> i128 {
+    YieldVaultClient::new(env, &position.vault).convert_to_assets(&position.shares)
+}
+
+/// Yield a position has earned and not yet paid out.
+fn earned(env: &Env, position: &VaultPosition) -> i128 {
+    position_value(env, position)
+        .saturating_sub(position.principal)
+        .max(0)
+}
+
+/// Yield an escrow's position has earned and not yet paid out; 0 without
+/// a position.
+pub fn vault_yield(env: &Env, escrow_id: &BytesN<32>) -> i128 {
+    get_position(env, escrow_id)
+        .map(|position| earned(env, &position))
+        .unwrap_or(0)
+}
+
+/// Move the whole amount of a Funded escrow into `vault` (chioma only).
+pub fn route_to_vault(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    vault: Address,
+) -> Result<VaultPosition, EscrowError> {
+    peers::chioma(env)?.require_auth();
+    let escrow = EscrowStorage::get(env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+    AccessControl::require_not_frozen(&escrow)?;
+    if escrow.status != EscrowStatus::Funded || get_position(env, &escrow_id).is_some() {
+        return Err(EscrowError::InvalidState);
+    }
+
+    // The vault pulls the tokens itself, so pre-authorise that transfer.
+    let contract_self = env.current_contract_address();
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: escrow.token.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (contract_self.clone(), vault.clone(), escrow.amount).into_val(env),
+            },
+            sub_invocations: vec![env],
+        }),
+    ]);
+    let shares = YieldVaultClient::new(env, &vault).deposit(&contract_self, &escrow.amount);
+
+    let position = VaultPosition {
+        vault,
+        principal: escrow.amount,
+        shares,
+        routed_at: env.ledger().timestamp(),
+    };
+    save_position(env, &escrow_id, &position);
+    Ok(position)
+}
+
+/// Pay `amount` of a position's yield to `to` (chioma only).
+pub fn withdraw_yield(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    to: Address,
+    amount: i128,
+) -> Result<(), EscrowError> {
+    let (_, mut position) = chioma_position(env, &escrow_id)?;
+    check_yield(env, &position, amount)?;
+
+    let contract_self = env.current_contract_address();
+    let burned = YieldVaultClient::new(env, &position.vault).withdraw(&contract_self, &to, &amount);
+    position.shares = position.shares.saturating_sub(burned);
+    save_position(env, &escrow_id, &position);
+    Ok(())
+}
+
+/// Add `amount` of a position's yield to the deposit itself (chioma only).
+pub fn compound_yield(env: &Env, escrow_id: BytesN<32>, amount: i128) -> Result<(), EscrowError> {
+    let (mut escrow, mut position) = chioma_position(env, &escrow_id)?;
+    check_yield(env, &position, amount)?;
+
+    position.principal += amount;
+    save_position(env, &escrow_id, &position);
+    escrow.amount += amount;
+    EscrowStorage::save(env, &escrow);
+    Ok(())
+}
+
+/// Bring a position back into the escrow on chioma's request, returning
+/// the amount withdrawn.
+pub fn close_position(env: &Env, escrow_id: BytesN<32>) -> Result<i128, EscrowError> {
+    let (mut escrow, _) = chioma_position(env, &escrow_id)?;
+    let amount = unwind(env, &mut escrow);
+    EscrowStorage::save(env, &escrow);
+    Ok(amount)
+}
+
+/// Authorise chioma and load an unfrozen escrow with its position.
+fn chioma_position(
+    env: &Env,
+    escrow_id: &BytesN<32>,
+) -> Result<(Escrow, VaultPosition), EscrowError> {
+    peers::chioma(env)?.require_auth();
+    let escrow = EscrowStorage::get(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+    AccessControl::require_not_frozen(&escrow)?;
+    let position = get_position(env, escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+    Ok((escrow, position))
+}
+
+fn check_yield(env: &Env, position: &VaultPosition, amount: i128) -> Result<(), EscrowError> {
+    if amount <= 0 {
+        return Err(EscrowError::InvalidAmount);
+    }
+    if amount > earned(env, position) {
+        return Err(EscrowError::InsufficientFunds);
+    }
+    Ok(())
+}
+
+/// Withdraw `escrow`'s position, if any, back to this contract and add the
+/// unpaid yield to its amount. Returns the amount withdrawn; the caller
+/// saves `escrow`.
+pub(crate) fn unwind(env: &Env, escrow: &mut Escrow) -> i128 {
+    let Some(position) = get_position(env, &escrow.id) else {
+        return 0;
+    };
+    let amount = position_value(env, &position);
+    if amount > 0 {
+        let contract_self = env.current_contract_address();
+        YieldVaultClient::new(env, &position.vault).withdraw(
+            &contract_self,
+            &contract_self,
+            &amount,
+        );
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::VaultPosition(escrow.id.clone()));
+    escrow.amount += amount - position.principal;
+    amount
+}
@@ -55,4 +55,10 @@ pub enum PaymentError {
     PaymentNotLate = 34,
     /// Caller is not the landlord
     NotLandlord = 35,
+    /// Caller is not the contract admin
+    Unauthorized = 36,
+    /// Admin has not been initialized
+    AdminNotSet = 37,
+    /// A timestamp-based rate limit mode was configured without a window
+    InvalidRateLimitConfig = 38,
 }
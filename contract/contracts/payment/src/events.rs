@@ -1,4 +1,4 @@
-use soroban_sdk::{contractevent, Address, Env, String};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String};
 
 /// Event emitted when a recurring payment schedule is created
 /// Topics: ["recurring_payment_created", agreement_id: String]
@@ -123,6 +123,24 @@ pub struct PlatformFeeCollectorUpdated {
     pub updated_at: u64,
 }
 
+/// Event emitted when the rate limit configuration is updated
+/// Topics: ["rate_limit_config_updated"]
+#[contractevent(topics = ["rate_limit_config_updated"])]
+pub struct RateLimitConfigUpdated {
+    pub max_calls_per_block: u32,
+    pub max_calls_per_user_per_day: u32,
+    pub cooldown_blocks: u32,
+}
+
+/// Event emitted when the admin is initialized
+/// Topics: ["admin_initialized", admin: Address]
+#[contractevent(topics = ["admin_initialized"])]
+pub struct AdminInitialized {
+    #[topic]
+    pub admin: Address,
+    pub initialized_at: u64,
+}
+
 /// Event emitted when a contract upgrade is proposed
 /// Topics: ["upgrade_proposed", proposal_id: String, proposer: Address]
 #[contractevent(topics = ["upgrade_proposed"])]
@@ -156,6 +174,17 @@ pub struct UpgradeExecuted {
     #[topic]
     pub executor: Address,
     pub executed_at: u64,
+    pub wasm_hash: BytesN<32>,
+    pub version: u32,
+}
+
+/// Event emitted when stored data is migrated after an upgrade
+/// Topics: ["storage_migrated"]
+#[contractevent(topics = ["storage_migrated"])]
+pub struct StorageMigrated {
+    pub contract_version: u32,
+    pub from_version: u32,
+    pub to_version: u32,
 }
 
 pub(crate) fn recurring_payment_created(
@@ -322,11 +351,57 @@ pub(crate) fn upgrade_approved(
 }
 
 /// Helper function to emit upgrade executed event
-pub(crate) fn upgrade_executed(env: &Env, proposal_id: String, executor: Address) {
+pub(crate) fn upgrade_executed(
+    env: &Env,
+    proposal_id: String,
+    executor: Address,
+    wasm_hash: BytesN<32>,
+    version: u32,
+) {
     UpgradeExecuted {
         proposal_id,
         executor,
         executed_at: env.ledger().timestamp(),
+        wasm_hash,
+        version,
+    }
+    .publish(env);
+}
+
+pub(crate) fn storage_migrated(
+    env: &Env,
+    contract_version: u32,
+    from_version: u32,
+    to_version: u32,
+) {
+    StorageMigrated {
+        contract_version,
+        from_version,
+        to_version,
+    }
+    .publish(env);
+}
+
+/// Helper function to emit rate limit config updated event
+pub(crate) fn rate_limit_config_updated(
+    env: &Env,
+    max_calls_per_block: u32,
+    max_calls_per_user_per_day: u32,
+    cooldown_blocks: u32,
+) {
+    RateLimitConfigUpdated {
+        max_calls_per_block,
+        max_calls_per_user_per_day,
+        cooldown_blocks,
+    }
+    .publish(env);
+}
+
+/// Helper function to emit admin initialized event
+pub(crate) fn admin_initialized(env: &Env, admin: Address) {
+    AdminInitialized {
+        admin,
+        initialized_at: env.ledger().timestamp(),
     }
     .publish(env);
 }
@@ -35,8 +35,8 @@ pub use payment_impl::{calculate_payment_split, calculate_rent_for_period, creat
 pub use storage::DataKey;
 pub use types::{
     EscalationType, ExecutionStatus, LateFeeConfig, LateFeeRecord, PaymentExecution,
-    PaymentFrequency, PaymentRecord, PaymentSplit, RecurringPayment, RecurringPaymentEvent,
-    RecurringStatus, RentEscalationConfig,
+    PaymentFrequency, PaymentRecord, PaymentSplit, RateLimitConfig, RateLimitMode,
+    RecurringPayment, RecurringPaymentEvent, RecurringStatus, RentEscalationConfig,
 };
 
 use crate::errors::PaymentError as Error;
@@ -181,6 +181,18 @@ impl PaymentContract {
         Ok(())
     }
 
+    /// Initialize the admin that governs upgrades. Can only be set once.
+    pub fn initialize_admin(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&StorageKey::Admin) {
+            return Err(Error::Unauthorized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&StorageKey::Admin, &admin);
+
+        events::admin_initialized(&env, admin);
+        Ok(())
+    }
+
     /// Sets the platform fee collector address
     pub fn set_platform_fee_collector(env: Env, collector: Address) {
         collector.require_auth();
@@ -191,6 +203,22 @@ impl PaymentContract {
         events::platform_fee_collector_updated(&env, collector);
     }
 
+    /// Set the rate limit configuration (admin only).
+    pub fn set_rate_limit_config(
+        env: Env,
+        caller: Address,
+        config: RateLimitConfig,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        upgrade::require_admin(&env, &caller)?;
+        rate_limit::set_rate_limit_config(&env, config)
+    }
+
+    /// Get the rate limit configuration.
+    pub fn get_rate_limit_config(env: Env) -> RateLimitConfig {
+        rate_limit::get_rate_limit_config(&env)
+    }
+
     /// Get a payment record by ID
     pub fn get_payment(env: Env, payment_id: String) -> Result<PaymentRecord, Error> {
         env.storage()
@@ -928,4 +956,51 @@ impl PaymentContract {
             &config,
         ))
     }
+
+    // --- Upgrade Functions ---
+
+    /// Propose a contract upgrade (admin only).
+    pub fn propose_upgrade(
+        env: Env,
+        proposer: Address,
+        proposal_id: String,
+        wasm_hash: soroban_sdk::BytesN<32>,
+        notes: String,
+        delay_seconds: u64,
+    ) -> Result<(), Error> {
+        payment_impl::propose_upgrade(env, proposer, proposal_id, wasm_hash, notes, delay_seconds)
+    }
+
+    /// Approve an upgrade proposal (admin only).
+    pub fn approve_upgrade(env: Env, approver: Address, proposal_id: String) -> Result<(), Error> {
+        payment_impl::approve_upgrade(env, approver, proposal_id)
+    }
+
+    /// Execute an approved upgrade, installing the proposed WASM (admin only).
+    pub fn execute_upgrade(env: Env, executor: Address, proposal_id: String) -> Result<(), Error> {
+        payment_impl::execute_upgrade(env, executor, proposal_id)
+    }
+
+    /// Get an upgrade proposal.
+    pub fn get_upgrade_proposal(
+        env: Env,
+        proposal_id: String,
+    ) -> Result<upgrade::UpgradeProposal, Error> {
+        payment_impl::get_upgrade_proposal(env, proposal_id)
+    }
+
+    /// Run pending storage migrations after an upgrade (admin only).
+    pub fn migrate(env: Env, caller: Address) -> Result<u32, Error> {
+        payment_impl::migrate(env, caller)
+    }
+
+    /// Get the code version, bumped by every executed upgrade.
+    pub fn get_contract_version(env: Env) -> u32 {
+        payment_impl::get_contract_version(env)
+    }
+
+    /// Get the layout version of the stored data.
+    pub fn get_storage_version(env: Env) -> u32 {
+        payment_impl::get_storage_version(env)
+    }
 }
@@ -171,7 +171,7 @@ pub fn propose_upgrade(
     env: Env,
     proposer: Address,
     proposal_id: String,
-    wasm_hash: soroban_sdk::Bytes,
+    wasm_hash: soroban_sdk::BytesN<32>,
     notes: String,
     delay_seconds: u64,
 ) -> Result<(), PaymentError> {
@@ -203,3 +203,18 @@ pub fn get_upgrade_proposal(
 ) -> Result<upgrade::UpgradeProposal, PaymentError> {
     upgrade::get_upgrade_proposal(&env, proposal_id)
 }
+
+/// Run pending storage migrations after an upgrade (admin only).
+pub fn migrate(env: Env, caller: Address) -> Result<u32, PaymentError> {
+    upgrade::migrate(&env, caller)
+}
+
+/// Get the code version, bumped by every executed upgrade.
+pub fn get_contract_version(env: Env) -> u32 {
+    upgrade::get_contract_version(&env)
+}
+
+/// Get the layout version of the stored data.
+pub fn get_storage_version(env: Env) -> u32 {
+    upgrade::get_storage_version(&env)
+}
@@ -1,25 +1,65 @@
 //! Rate limiting module for payment functions.
 
-use soroban_sdk::{Address, Env, String};
+use rate_limiter::{consume_window, load_window, read_config, LegacyRateLimitConfig, Strategy};
+use soroban_sdk::{Address, Env, String, Val};
 
 use crate::errors::PaymentError;
+use crate::events;
 use crate::storage::DataKey;
-use crate::types::{RateLimitConfig, UserCallCount};
+use crate::types::{RateLimitConfig, RateLimitMode, UserCallCount};
 
 const BLOCKS_PER_DAY: u64 = 17280; // Assuming ~5 second blocks
+const SECONDS_PER_DAY: u64 = 86_400;
+
+impl From<LegacyRateLimitConfig> for RateLimitConfig {
+    /// Configs stored before the timestamp-based modes limited by ledger
+    /// blocks over a day.
+    fn from(legacy: LegacyRateLimitConfig) -> Self {
+        RateLimitConfig {
+            max_calls_per_block: legacy.max_calls_per_block,
+            max_calls_per_user_per_day: legacy.max_calls_per_user_per_day,
+            cooldown_blocks: legacy.cooldown_blocks,
+            mode: RateLimitMode::LedgerBlocks,
+            window_seconds: SECONDS_PER_DAY,
+        }
+    }
+}
 
 /// Get current rate limit configuration.
 pub fn get_rate_limit_config(env: &Env) -> RateLimitConfig {
     env.storage()
         .persistent()
-        .get(&DataKey::RateLimitConfig)
+        .get::<_, Val>(&DataKey::RateLimitConfig)
+        .map(|raw| read_config(env, &raw))
         .unwrap_or(RateLimitConfig {
             max_calls_per_block: 10,
             max_calls_per_user_per_day: 100,
             cooldown_blocks: 0,
+            mode: RateLimitMode::LedgerBlocks,
+            window_seconds: SECONDS_PER_DAY,
         })
 }
 
+/// Replace the rate limit configuration.
+pub fn set_rate_limit_config(env: &Env, config: RateLimitConfig) -> Result<(), PaymentError> {
+    if config.mode != RateLimitMode::LedgerBlocks && config.window_seconds == 0 {
+        return Err(PaymentError::InvalidRateLimitConfig);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::RateLimitConfig, &config);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::RateLimitConfig, 500000, 500000);
+    events::rate_limit_config_updated(
+        env,
+        config.max_calls_per_block,
+        config.max_calls_per_user_per_day,
+        config.cooldown_blocks,
+    );
+    Ok(())
+}
+
 /// Check and enforce rate limits for a user calling a specific function.
 pub fn check_rate_limit(
     env: &Env,
@@ -58,10 +98,29 @@ pub fn check_rate_limit(
         user_calls.daily_reset_block = current_block;
     }
 
-    // Check daily limit
-    if user_calls.daily_count >= config.max_calls_per_user_per_day {
-        return Err(PaymentError::RateLimitExceeded);
-    }
+    // Check the per-user limit in the configured mode
+    let window_key = DataKey::RateWindow(user.clone(), String::from_str(env, function_name));
+    let window = match config.mode {
+        RateLimitMode::LedgerBlocks => {
+            if user_calls.daily_count >= config.max_calls_per_user_per_day {
+                return Err(PaymentError::RateLimitExceeded);
+            }
+            None
+        }
+        RateLimitMode::SlidingWindow | RateLimitMode::TokenBucket => {
+            let strategy = if config.mode == RateLimitMode::TokenBucket {
+                Strategy::TokenBucket
+            } else {
+                Strategy::SlidingWindow
+            };
+            let limit = config.max_calls_per_user_per_day;
+            let mut state = load_window(env, &window_key, limit, config.window_seconds);
+            if !consume_window(env, strategy, limit, config.window_seconds, &mut state) {
+                return Err(PaymentError::RateLimitExceeded);
+            }
+            Some(state)
+        }
+    };
 
     // Check per-block limit
     let block_key = DataKey::BlockCallCount(current_block, String::from_str(env, function_name));
@@ -79,6 +138,12 @@ pub fn check_rate_limit(
     // Save user call count
     env.storage().persistent().set(&key, &user_calls);
     env.storage().persistent().extend_ttl(&key, 500000, 500000);
+    if let Some(state) = window {
+        env.storage().persistent().set(&window_key, &state);
+        env.storage()
+            .persistent()
+            .extend_ttl(&window_key, 500000, 500000);
+    }
 
     // Update block call count
     env.storage()
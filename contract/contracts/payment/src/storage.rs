@@ -29,12 +29,22 @@ pub enum DataKey {
     UserCallCount(soroban_sdk::Address, String),
     /// Block call count for rate limiting
     BlockCallCount(u64, String),
+    /// Timestamp-mode limiter state
+    RateWindow(soroban_sdk::Address, String),
     /// Late fee configuration per agreement
     LateFeeConfig(String),
     /// Late fee record per payment
     LateFeeRecord(String),
     /// Rent escalation configuration per agreement
     RentEscalationConfig(String),
+    /// Admin allowed to govern upgrades
+    Admin,
     /// Upgrade proposal
     UpgradeProposal(String),
+    /// Code version, bumped by every executed upgrade
+    ContractVersion,
+    /// Layout version of the stored data
+    StorageVersion,
+    /// Code version at which `migrate` last ran
+    MigratedVersion,
 }
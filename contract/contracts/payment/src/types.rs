@@ -198,6 +198,10 @@ pub struct RateLimitConfig {
     pub max_calls_per_block: u32,
     pub max_calls_per_user_per_day: u32,
     pub cooldown_blocks: u32,
+    /// How `max_calls_per_user_per_day` is measured.
+    pub mode: RateLimitMode,
+    /// Window length for the timestamp-based modes.
+    pub window_seconds: u64,
 }
 
 #[contracttype]
@@ -209,3 +213,18 @@ pub struct UserCallCount {
     pub daily_count: u32,
     pub daily_reset_block: u64,
 }
+
+/// Per-user limiting strategy.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RateLimitMode {
+    /// Daily count over a fixed window of ledger sequence numbers, reset all
+    /// at once.
+    LedgerBlocks,
+    /// At most `max_calls_per_user_per_day` calls in any trailing
+    /// `window_seconds`, by ledger timestamp.
+    SlidingWindow,
+    /// A bucket of `max_calls_per_user_per_day` calls that refills
+    /// continuously over `window_seconds`.
+    TokenBucket,
+}
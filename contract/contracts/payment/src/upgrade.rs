@@ -1,7 +1,7 @@
 use crate::errors::PaymentError;
 use crate::events;
 use crate::storage::DataKey;
-use soroban_sdk::{Address, Bytes, Env, String, Vec};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
 /// Contract upgrade proposal
 #[derive(Clone)]
@@ -9,7 +9,7 @@ use soroban_sdk::{Address, Bytes, Env, String, Vec};
 pub struct UpgradeProposal {
     pub id: String,
     pub proposer: Address,
-    pub wasm_hash: Bytes,
+    pub wasm_hash: BytesN<32>,
     pub approvals: Vec<Address>,
     pub required_signatures: u32,
     pub eta: u64,
@@ -18,16 +18,17 @@ pub struct UpgradeProposal {
     pub created_at: u64,
 }
 
-/// Propose a contract upgrade (requires authorization)
+/// Propose a contract upgrade (admin only)
 pub fn propose_upgrade(
     env: &Env,
     proposer: Address,
     proposal_id: String,
-    wasm_hash: Bytes,
+    wasm_hash: BytesN<32>,
     notes: String,
     delay_seconds: u64,
 ) -> Result<(), PaymentError> {
     proposer.require_auth();
+    require_admin(env, &proposer)?;
 
     if env
         .storage()
@@ -67,13 +68,14 @@ pub fn propose_upgrade(
     Ok(())
 }
 
-/// Approve an upgrade proposal
+/// Approve an upgrade proposal (admin only)
 pub fn approve_upgrade(
     env: &Env,
     approver: Address,
     proposal_id: String,
 ) -> Result<(), PaymentError> {
     approver.require_auth();
+    require_admin(env, &approver)?;
 
     let mut proposal: UpgradeProposal = env
         .storage()
@@ -96,13 +98,14 @@ pub fn approve_upgrade(
     Ok(())
 }
 
-/// Execute an approved upgrade
+/// Execute an approved upgrade (admin only)
 pub fn execute_upgrade(
     env: &Env,
     executor: Address,
     proposal_id: String,
 ) -> Result<(), PaymentError> {
     executor.require_auth();
+    require_admin(env, &executor)?;
 
     let mut proposal: UpgradeProposal = env
         .storage()
@@ -123,7 +126,23 @@ pub fn execute_upgrade(
         .persistent()
         .set(&DataKey::UpgradeProposal(proposal_id.clone()), &proposal);
 
-    events::upgrade_executed(env, proposal_id, executor);
+    let version = get_contract_version(env) + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::ContractVersion, &version);
+
+    events::upgrade_executed(
+        env,
+        proposal_id,
+        executor,
+        proposal.wasm_hash.clone(),
+        version,
+    );
+
+    // The new code takes over once this invocation returns; `migrate` then
+    // brings stored data up to date.
+    env.deployer()
+        .update_current_contract_wasm(proposal.wasm_hash);
 
     Ok(())
 }
@@ -138,3 +157,80 @@ pub fn get_upgrade_proposal(
         .get(&DataKey::UpgradeProposal(proposal_id))
         .ok_or(PaymentError::PaymentNotFound)
 }
+
+// ─── Versioning and Migration ─────────────────────────────────────────────────
+
+/// Storage layout this build reads and writes. Bump it, and add a step to
+/// `run_migration`, whenever persisted data changes shape.
+pub const STORAGE_VERSION: u32 = 1;
+
+pub(crate) fn require_admin(env: &Env, caller: &Address) -> Result<(), PaymentError> {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(PaymentError::AdminNotSet)?;
+    if *caller != admin {
+        return Err(PaymentError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Version of the running code: 1 at deployment, bumped by every executed upgrade.
+pub fn get_contract_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ContractVersion)
+        .unwrap_or(1)
+}
+
+/// Layout of the stored data. Data written before layouts were tracked is
+/// in layout 1.
+pub fn get_storage_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::StorageVersion)
+        .unwrap_or(1)
+}
+
+/// Bring stored data up to `STORAGE_VERSION` after an upgrade (admin only).
+///
+/// Runs once per executed upgrade and returns the resulting layout version.
+pub fn migrate(env: &Env, caller: Address) -> Result<u32, PaymentError> {
+    caller.require_auth();
+    require_admin(env, &caller)?;
+
+    let version = get_contract_version(env);
+    let migrated: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::MigratedVersion)
+        .unwrap_or(1);
+    let from = get_storage_version(env);
+    if migrated >= version || from > STORAGE_VERSION {
+        return Err(PaymentError::PaymentFailed);
+    }
+
+    for step in (from + 1)..=STORAGE_VERSION {
+        run_migration(env, step)?;
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::StorageVersion, &STORAGE_VERSION);
+    env.storage()
+        .instance()
+        .set(&DataKey::MigratedVersion, &version);
+
+    events::storage_migrated(env, version, from, STORAGE_VERSION);
+    Ok(STORAGE_VERSION)
+}
+
+/// Convert stored data from layout `step - 1` to layout `step`.
+fn run_migration(_env: &Env, step: u32) -> Result<(), PaymentError> {
+    match step {
+        // Layout 1 is the original one; nothing to convert.
+        1 => Ok(()),
+        _ => Err(PaymentError::PaymentFailed),
+    }
+}
@@ -12,4 +12,5 @@ pub enum PropertyError {
     AlreadyVerified = 6,
     InvalidPropertyId = 7,
     InvalidMetadata = 8,
+    InvalidBatchSize = 9,
 }
@@ -3,7 +3,7 @@
 // place; remove the allow once the corresponding entrypoints emit them.
 #![allow(dead_code)]
 
-use soroban_sdk::{contractevent, Address, Bytes, Env, String};
+use soroban_sdk::{contractevent, Address, Bytes, BytesN, Env, String};
 
 /// Event emitted when the contract is initialized
 /// Topics: ["initialized", admin: Address]
@@ -109,6 +109,17 @@ pub struct UpgradeExecuted {
     pub proposal_id: String,
     pub executor: Address,
     pub executed_at: u64,
+    pub wasm_hash: BytesN<32>,
+    pub version: u32,
+}
+
+/// Event emitted when stored data is migrated after an upgrade
+/// Topics: ["storage_migrated"]
+#[contractevent(topics = ["storage_migrated"])]
+pub struct StorageMigrated {
+    pub contract_version: u32,
+    pub from_version: u32,
+    pub to_version: u32,
 }
 
 /// Event emitted when a property's ownership is transferred
@@ -229,11 +240,33 @@ pub(crate) fn upgrade_approved(
 }
 
 /// Helper function to emit upgrade executed event
-pub(crate) fn upgrade_executed(env: &Env, proposal_id: String, executor: Address) {
+pub(crate) fn upgrade_executed(
+    env: &Env,
+    proposal_id: String,
+    executor: Address,
+    wasm_hash: BytesN<32>,
+    version: u32,
+) {
     UpgradeExecuted {
         proposal_id,
         executor,
         executed_at: env.ledger().timestamp(),
+        wasm_hash,
+        version,
+    }
+    .publish(env);
+}
+
+pub(crate) fn storage_migrated(
+    env: &Env,
+    contract_version: u32,
+    from_version: u32,
+    to_version: u32,
+) {
+    StorageMigrated {
+        contract_version,
+        from_version,
+        to_version,
     }
     .publish(env);
 }
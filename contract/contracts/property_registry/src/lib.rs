@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, Address, Env, String};
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
 
 mod errors;
 mod events;
@@ -27,7 +27,7 @@ pub use property::{
     update_property_metadata, verify_property,
 };
 pub use storage::DataKey;
-pub use types::{ContractState, PropertyDetails};
+pub use types::{ContractState, PropertyDetails, RecordMigrationBatch, VersionedProperty};
 
 #[contract]
 pub struct PropertyRegistryContract;
@@ -195,7 +195,7 @@ impl PropertyRegistryContract {
         env: Env,
         proposer: Address,
         proposal_id: String,
-        wasm_hash: soroban_sdk::Bytes,
+        wasm_hash: soroban_sdk::BytesN<32>,
         notes: String,
         delay_seconds: u64,
     ) -> Result<(), PropertyError> {
@@ -227,4 +227,40 @@ impl PropertyRegistryContract {
     ) -> Result<upgrade::UpgradeProposal, PropertyError> {
         upgrade::get_upgrade_proposal(&env, proposal_id)
     }
+
+    /// Run pending storage migrations after an upgrade (admin only).
+    pub fn migrate(env: Env, caller: Address) -> Result<u32, PropertyError> {
+        upgrade::migrate(&env, caller)
+    }
+
+    /// Get the code version, bumped by every executed upgrade.
+    pub fn get_contract_version(env: Env) -> u32 {
+        upgrade::get_contract_version(&env)
+    }
+
+    /// Get the layout version of the stored data.
+    pub fn get_storage_version(env: Env) -> u32 {
+        upgrade::get_storage_version(&env)
+    }
+
+    /// Rewrite the next batch of properties in the current record layout,
+    /// starting at `cursor` (admin only).
+    pub fn migrate_records(
+        env: Env,
+        caller: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<RecordMigrationBatch, PropertyError> {
+        upgrade::migrate_records(&env, caller, cursor, limit)
+    }
+
+    /// Rewrite the listed properties in the current record layout, for
+    /// properties registered before the registration-order index (admin only).
+    pub fn migrate_records_by_id(
+        env: Env,
+        caller: Address,
+        property_ids: Vec<String>,
+    ) -> Result<u32, PropertyError> {
+        upgrade::migrate_records_by_id(&env, caller, property_ids)
+    }
 }
@@ -1,9 +1,31 @@
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{unwrap::UnwrapOptimized, Address, Env, String, TryFromVal, Val};
 
 use crate::errors::PropertyError;
 use crate::events;
 use crate::storage::DataKey;
-use crate::types::{ContractState, PropertyDetails};
+use crate::types::{ContractState, PropertyDetails, VersionedProperty};
+
+/// Read a property, upgrading it from whichever layout it was stored in.
+pub(crate) fn load_property(env: &Env, property_id: &String) -> Option<PropertyDetails> {
+    let raw: Val = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Property(property_id.clone()))?;
+    if let Ok(record) = VersionedProperty::try_from_val(env, &raw) {
+        return Some(record.into_current());
+    }
+    // Written before records were versioned: a bare `PropertyDetails`.
+    Some(PropertyDetails::try_from_val(env, &raw).unwrap_optimized())
+}
+
+/// Write a property in the current layout and extend its TTL.
+pub(crate) fn save_property(env: &Env, property: &PropertyDetails) {
+    let key = DataKey::Property(property.property_id.clone());
+    env.storage()
+        .persistent()
+        .set(&key, &VersionedProperty::V1(property.clone()));
+    env.storage().persistent().extend_ttl(&key, 500000, 500000);
+}
 
 pub fn register_property(
     env: &Env,
@@ -39,11 +61,13 @@ pub fn register_property(
         verified_at: None,
     };
 
-    env.storage().persistent().set(&key, &property);
-    env.storage().persistent().extend_ttl(&key, 500000, 500000);
+    save_property(env, &property);
 
     let count_key = DataKey::PropertyCount;
     let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&DataKey::PropertyIndex(count), &property_id);
     env.storage().persistent().set(&count_key, &(count + 1));
     env.storage()
         .persistent()
@@ -71,12 +95,7 @@ pub fn verify_property(
         return Err(PropertyError::Unauthorized);
     }
 
-    let key = DataKey::Property(property_id.clone());
-    let mut property: PropertyDetails = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .ok_or(PropertyError::PropertyNotFound)?;
+    let mut property = load_property(env, &property_id).ok_or(PropertyError::PropertyNotFound)?;
 
     if property.verified {
         return Err(PropertyError::AlreadyVerified);
@@ -85,8 +104,7 @@ pub fn verify_property(
     property.verified = true;
     property.verified_at = Some(env.ledger().timestamp());
 
-    env.storage().persistent().set(&key, &property);
-    env.storage().persistent().extend_ttl(&key, 500000, 500000);
+    save_property(env, &property);
 
     events::property_verified(env, property_id, admin);
 
@@ -94,8 +112,7 @@ pub fn verify_property(
 }
 
 pub fn get_property(env: &Env, property_id: String) -> Option<PropertyDetails> {
-    let key = DataKey::Property(property_id);
-    env.storage().persistent().get(&key)
+    load_property(env, &property_id)
 }
 
 pub fn has_property(env: &Env, property_id: String) -> bool {
@@ -127,12 +144,7 @@ pub fn transfer_property(
 
     current_landlord.require_auth();
 
-    let key = DataKey::Property(property_id.clone());
-    let mut property: PropertyDetails = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .ok_or(PropertyError::PropertyNotFound)?;
+    let mut property = load_property(env, &property_id).ok_or(PropertyError::PropertyNotFound)?;
 
     if property.landlord != current_landlord {
         return Err(PropertyError::Unauthorized);
@@ -140,8 +152,7 @@ pub fn transfer_property(
 
     property.landlord = new_landlord.clone();
 
-    env.storage().persistent().set(&key, &property);
-    env.storage().persistent().extend_ttl(&key, 500000, 500000);
+    save_property(env, &property);
 
     events::property_transferred(env, property_id, current_landlord, new_landlord);
 
@@ -169,12 +180,7 @@ pub fn update_property_metadata(
         return Err(PropertyError::InvalidMetadata);
     }
 
-    let key = DataKey::Property(property_id.clone());
-    let mut property: PropertyDetails = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .ok_or(PropertyError::PropertyNotFound)?;
+    let mut property = load_property(env, &property_id).ok_or(PropertyError::PropertyNotFound)?;
 
     if property.landlord != landlord {
         return Err(PropertyError::Unauthorized);
@@ -184,8 +190,7 @@ pub fn update_property_metadata(
     property.verified = false;
     property.verified_at = None;
 
-    env.storage().persistent().set(&key, &property);
-    env.storage().persistent().extend_ttl(&key, 500000, 500000);
+    save_property(env, &property);
 
     events::property_metadata_updated(env, property_id, landlord, new_metadata_hash);
 
@@ -7,5 +7,9 @@ pub enum DataKey {
     State,
     Initialized,
     PropertyCount,
+    PropertyIndex(u32), // creation order -> property_id
     UpgradeProposal(String),
+    ContractVersion,
+    StorageVersion,
+    MigratedVersion,
 }
@@ -4,6 +4,15 @@ use soroban_sdk::{
     Address, Env, String,
 };
 
+/// Minimal WASM module the host accepts as contract code.
+const UPGRADE_WASM: &[u8] = b"\0asm\x01\0\0\0\0\x1e\x11contractenvmetav0\0\0\0\0\0\0\0\x17\0\0\0\0";
+
+/// Upload `UPGRADE_WASM` so an upgrade to its hash can be executed.
+pub(crate) fn upload_upgrade_wasm(env: &Env) -> soroban_sdk::BytesN<32> {
+    env.deployer()
+        .upload_contract_wasm(soroban_sdk::Bytes::from_slice(env, UPGRADE_WASM))
+}
+
 fn create_contract(env: &Env) -> PropertyRegistryContractClient<'_> {
     let contract_id = env.register(PropertyRegistryContract, ());
     PropertyRegistryContractClient::new(env, &contract_id)
@@ -463,7 +472,7 @@ fn test_upgrade_proposal_full_lifecycle() {
     client.initialize(&admin);
 
     let proposal_id = String::from_str(&env, "UPG-001");
-    let wasm_hash = soroban_sdk::Bytes::from_array(&env, &[7u8; 32]);
+    let wasm_hash = upload_upgrade_wasm(&env);
     let notes = String::from_str(&env, "bump to v2");
 
     client.propose_upgrade(&admin, &proposal_id, &wasm_hash, &notes, &500);
@@ -484,6 +493,7 @@ fn test_upgrade_proposal_full_lifecycle() {
     env.ledger().with_mut(|li| li.timestamp = 1500);
     client.execute_upgrade(&admin, &proposal_id);
 
+    env.register_at(&client.address, PropertyRegistryContract, ());
     let proposal = client.get_upgrade_proposal(&proposal_id);
     assert!(proposal.executed);
 }
@@ -768,3 +778,125 @@ fn test_new_landlord_can_update_metadata_after_transfer() {
     let property = client.get_property(&property_id).unwrap();
     assert_eq!(property.metadata_hash, new_metadata_hash);
 }
+
+#[test]
+fn test_execute_upgrade_installs_wasm_and_migrates_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let proposal_id = String::from_str(&env, "UPG-001");
+    let wasm_hash = upload_upgrade_wasm(&env);
+    client.propose_upgrade(
+        &admin,
+        &proposal_id,
+        &wasm_hash,
+        &String::from_str(&env, "v2"),
+        &0,
+    );
+
+    // Nothing to migrate before an upgrade.
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(PropertyError::AlreadyInitialized))
+    );
+
+    client.execute_upgrade(&admin, &proposal_id);
+
+    // The installed module exports nothing; reinstall the native contract to
+    // stand in for the new code.
+    assert!(client.try_get_contract_version().is_err());
+    env.register_at(&client.address, PropertyRegistryContract, ());
+
+    assert_eq!(client.get_contract_version(), 2);
+    assert_eq!(client.migrate(&admin), 1);
+    assert_eq!(client.get_storage_version(), 1);
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(PropertyError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_unversioned_properties_are_upgraded_on_read_and_by_batch_migration() {
+    let env = Env::default();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let metadata_hash = String::from_str(&env, "QmXoypizjW3WknFiJnKLwHCnL72vedxjQkDDP1mXWo6uco");
+    for id in ["PROP-001", "PROP-002", "PROP-003"] {
+        client.register_property(&landlord, &String::from_str(&env, id), &metadata_hash);
+    }
+
+    // Store the first property the way it was stored before records were
+    // versioned.
+    let legacy_id = String::from_str(&env, "PROP-001");
+    let legacy = client.get_property(&legacy_id).unwrap();
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Property(legacy_id.clone()), &legacy);
+    });
+    assert_eq!(client.get_property(&legacy_id), Some(legacy.clone()));
+
+    assert_eq!(
+        client.try_migrate_records(&admin, &0, &0),
+        Err(Ok(errors::PropertyError::InvalidBatchSize))
+    );
+
+    let batch = client.migrate_records(&admin, &0, &2);
+    assert_eq!(
+        batch,
+        RecordMigrationBatch {
+            migrated: 2,
+            next_cursor: 2,
+            done: false,
+        }
+    );
+    let batch = client.migrate_records(&admin, &batch.next_cursor, &10);
+    assert_eq!(
+        batch,
+        RecordMigrationBatch {
+            migrated: 1,
+            next_cursor: 3,
+            done: true,
+        }
+    );
+
+    env.as_contract(&client.address, || {
+        let stored: VersionedProperty = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Property(legacy_id.clone()))
+            .unwrap();
+        assert_eq!(stored.into_current(), legacy);
+    });
+
+    // A property registered before the index existed is out of the walk's
+    // reach and is rewritten by id instead.
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::Property(legacy_id.clone()), &legacy);
+        storage.remove(&DataKey::PropertyIndex(0));
+    });
+    assert_eq!(client.migrate_records(&admin, &0, &10).migrated, 2);
+    let ids = soroban_sdk::vec![&env, legacy_id.clone(), String::from_str(&env, "PROP-404")];
+    assert_eq!(client.migrate_records_by_id(&admin, &ids), 1);
+    env.as_contract(&client.address, || {
+        let stored: VersionedProperty = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Property(legacy_id.clone()))
+            .unwrap();
+        assert_eq!(stored.into_current(), legacy);
+    });
+    assert_eq!(
+        client.try_migrate_records_by_id(&admin, &soroban_sdk::Vec::new(&env)),
+        Err(Ok(errors::PropertyError::InvalidBatchSize))
+    );
+}
@@ -221,7 +221,7 @@ fn test_propose_upgrade_fails_if_not_initialized() {
     env.mock_all_auths();
 
     let proposal_id = String::from_str(&env, "UPG-001");
-    let wasm_hash = soroban_sdk::Bytes::from_array(&env, &[0u8; 32]);
+    let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
     let notes = String::from_str(&env, "notes");
 
     client.propose_upgrade(&proposer, &proposal_id, &wasm_hash, &notes, &1000);
@@ -239,7 +239,7 @@ fn test_propose_upgrade_fails_if_proposal_id_already_used() {
     client.initialize(&admin);
 
     let proposal_id = String::from_str(&env, "UPG-001");
-    let wasm_hash = soroban_sdk::Bytes::from_array(&env, &[0u8; 32]);
+    let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
     let notes = String::from_str(&env, "notes");
 
     client.propose_upgrade(&admin, &proposal_id, &wasm_hash, &notes, &1000);
@@ -258,7 +258,7 @@ fn test_execute_upgrade_fails_before_eta() {
     client.initialize(&admin);
 
     let proposal_id = String::from_str(&env, "UPG-001");
-    let wasm_hash = soroban_sdk::Bytes::from_array(&env, &[0u8; 32]);
+    let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
     let notes = String::from_str(&env, "notes");
 
     client.propose_upgrade(&admin, &proposal_id, &wasm_hash, &notes, &1_000_000);
@@ -277,11 +277,12 @@ fn test_execute_upgrade_fails_if_already_executed() {
     client.initialize(&admin);
 
     let proposal_id = String::from_str(&env, "UPG-001");
-    let wasm_hash = soroban_sdk::Bytes::from_array(&env, &[0u8; 32]);
+    let wasm_hash = crate::tests::upload_upgrade_wasm(&env);
     let notes = String::from_str(&env, "notes");
 
     client.propose_upgrade(&admin, &proposal_id, &wasm_hash, &notes, &0);
     client.execute_upgrade(&admin, &proposal_id);
+    env.register_at(&client.address, PropertyRegistryContract, ());
     client.execute_upgrade(&admin, &proposal_id);
 }
 
@@ -101,7 +101,7 @@ fn test_propose_upgrade_fails_without_admin_auth() {
     env.mock_auths(&[]);
 
     let proposal_id = String::from_str(&env, "UPG-001");
-    let wasm_hash = soroban_sdk::Bytes::from_array(&env, &[0u8; 32]);
+    let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
     let notes = String::from_str(&env, "notes");
 
     client.propose_upgrade(&admin, &proposal_id, &wasm_hash, &notes, &1000);
@@ -120,7 +120,7 @@ fn test_propose_upgrade_fails_if_not_admin() {
     client.initialize(&admin);
 
     let proposal_id = String::from_str(&env, "UPG-001");
-    let wasm_hash = soroban_sdk::Bytes::from_array(&env, &[0u8; 32]);
+    let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
     let notes = String::from_str(&env, "notes");
 
     client.propose_upgrade(&attacker, &proposal_id, &wasm_hash, &notes, &1000);
@@ -139,7 +139,7 @@ fn test_approve_upgrade_fails_if_not_admin() {
     client.initialize(&admin);
 
     let proposal_id = String::from_str(&env, "UPG-001");
-    let wasm_hash = soroban_sdk::Bytes::from_array(&env, &[0u8; 32]);
+    let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
     let notes = String::from_str(&env, "notes");
 
     client.propose_upgrade(&admin, &proposal_id, &wasm_hash, &notes, &1000);
@@ -159,7 +159,7 @@ fn test_execute_upgrade_fails_if_not_admin() {
     client.initialize(&admin);
 
     let proposal_id = String::from_str(&env, "UPG-001");
-    let wasm_hash = soroban_sdk::Bytes::from_array(&env, &[0u8; 32]);
+    let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
     let notes = String::from_str(&env, "notes");
 
     client.propose_upgrade(&admin, &proposal_id, &wasm_hash, &notes, &0);
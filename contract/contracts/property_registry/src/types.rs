@@ -11,6 +11,34 @@ pub struct PropertyDetails {
     pub verified_at: Option<u64>,
 }
 
+/// Versioned envelope a `PropertyDetails` is persisted in.
+///
+/// When `PropertyDetails` changes shape, freeze the old layout under a new
+/// name, add a variant for the new one and convert in `into_current`; stored
+/// records are then upgraded as they are read.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VersionedProperty {
+    V1(PropertyDetails),
+}
+
+impl VersionedProperty {
+    pub fn into_current(self) -> PropertyDetails {
+        match self {
+            VersionedProperty::V1(property) => property,
+        }
+    }
+}
+
+/// Progress of one `migrate_records` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordMigrationBatch {
+    pub migrated: u32,
+    pub next_cursor: u32,
+    pub done: bool,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ContractState {
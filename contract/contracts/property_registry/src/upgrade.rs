@@ -1,8 +1,9 @@
 use crate::errors::PropertyError;
 use crate::events;
+use crate::property;
 use crate::storage::DataKey;
-use crate::types::ContractState;
-use soroban_sdk::{Address, Bytes, Env, String, Vec};
+use crate::types::{ContractState, RecordMigrationBatch};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
 /// Contract upgrade proposal
 #[derive(Clone)]
@@ -10,7 +11,7 @@ use soroban_sdk::{Address, Bytes, Env, String, Vec};
 pub struct UpgradeProposal {
     pub id: String,
     pub proposer: Address,
-    pub wasm_hash: Bytes,
+    pub wasm_hash: BytesN<32>,
     pub approvals: Vec<Address>,
     pub required_signatures: u32,
     pub eta: u64,
@@ -24,7 +25,7 @@ pub fn propose_upgrade(
     env: &Env,
     proposer: Address,
     proposal_id: String,
-    wasm_hash: Bytes,
+    wasm_hash: BytesN<32>,
     notes: String,
     delay_seconds: u64,
 ) -> Result<(), PropertyError> {
@@ -154,7 +155,23 @@ pub fn execute_upgrade(
         .persistent()
         .set(&DataKey::UpgradeProposal(proposal_id.clone()), &proposal);
 
-    events::upgrade_executed(env, proposal_id, executor);
+    let version = get_contract_version(env) + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::ContractVersion, &version);
+
+    events::upgrade_executed(
+        env,
+        proposal_id,
+        executor,
+        proposal.wasm_hash.clone(),
+        version,
+    );
+
+    // The new code takes over once this invocation returns; `migrate` then
+    // brings stored data up to date.
+    env.deployer()
+        .update_current_contract_wasm(proposal.wasm_hash);
 
     Ok(())
 }
@@ -169,3 +186,157 @@ pub fn get_upgrade_proposal(
         .get(&DataKey::UpgradeProposal(proposal_id))
         .ok_or(PropertyError::NotInitialized)
 }
+
+// ─── Versioning and Migration ─────────────────────────────────────────────────
+
+/// Storage layout this build reads and writes. Bump it, and add a step to
+/// `run_migration`, whenever persisted data changes shape.
+pub const STORAGE_VERSION: u32 = 1;
+
+/// Most records `migrate_records` rewrites in one call.
+pub const MAX_MIGRATION_BATCH: u32 = 50;
+
+fn require_admin(env: &Env, caller: &Address) -> Result<(), PropertyError> {
+    let state = env
+        .storage()
+        .instance()
+        .get::<DataKey, ContractState>(&DataKey::State)
+        .ok_or(PropertyError::NotInitialized)?;
+    if *caller != state.admin {
+        return Err(PropertyError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Version of the running code: 1 at deployment, bumped by every executed upgrade.
+pub fn get_contract_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ContractVersion)
+        .unwrap_or(1)
+}
+
+/// Layout of the stored data. Data written before layouts were tracked is
+/// in layout 1.
+pub fn get_storage_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::StorageVersion)
+        .unwrap_or(1)
+}
+
+/// Bring stored data up to `STORAGE_VERSION` after an upgrade (admin only).
+///
+/// Runs once per executed upgrade and returns the resulting layout version.
+pub fn migrate(env: &Env, caller: Address) -> Result<u32, PropertyError> {
+    caller.require_auth();
+    require_admin(env, &caller)?;
+
+    let version = get_contract_version(env);
+    let migrated: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::MigratedVersion)
+        .unwrap_or(1);
+    let from = get_storage_version(env);
+    if migrated >= version || from > STORAGE_VERSION {
+        return Err(PropertyError::AlreadyInitialized);
+    }
+
+    for step in (from + 1)..=STORAGE_VERSION {
+        run_migration(env, step)?;
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::StorageVersion, &STORAGE_VERSION);
+    env.storage()
+        .instance()
+        .set(&DataKey::MigratedVersion, &version);
+
+    events::storage_migrated(env, version, from, STORAGE_VERSION);
+    Ok(STORAGE_VERSION)
+}
+
+/// Convert stored data from layout `step - 1` to layout `step`.
+fn run_migration(_env: &Env, step: u32) -> Result<(), PropertyError> {
+    match step {
+        // Layout 1 is the original one; nothing to convert.
+        1 => Ok(()),
+        _ => Err(PropertyError::AlreadyInitialized),
+    }
+}
+
+/// Rewrite the next batch of properties in the current record layout (admin only).
+///
+/// Walks the registration-order index from `cursor`, visiting at most `limit`
+/// entries (capped at `MAX_MIGRATION_BATCH`). Reads already upgrade older
+/// layouts, so this only saves decoding work on later reads; call again with
+/// the returned `next_cursor` until `done` is set. Properties registered
+/// before the index existed have no entry; rewrite those with
+/// `migrate_records_by_id`.
+pub fn migrate_records(
+    env: &Env,
+    caller: Address,
+    cursor: u32,
+    limit: u32,
+) -> Result<RecordMigrationBatch, PropertyError> {
+    caller.require_auth();
+    require_admin(env, &caller)?;
+    if limit == 0 {
+        return Err(PropertyError::InvalidBatchSize);
+    }
+
+    let count = property::get_property_count(env);
+    let end = cursor
+        .saturating_add(limit.min(MAX_MIGRATION_BATCH))
+        .min(count);
+
+    let mut migrated = 0;
+    for index in cursor..end {
+        // Properties registered before the index existed have no entry.
+        let details = env
+            .storage()
+            .persistent()
+            .get::<DataKey, String>(&DataKey::PropertyIndex(index))
+            .and_then(|id| property::load_property(env, &id));
+        if let Some(details) = details {
+            property::save_property(env, &details);
+            migrated += 1;
+        }
+    }
+
+    let next_cursor = end.max(cursor);
+    Ok(RecordMigrationBatch {
+        migrated,
+        next_cursor,
+        done: next_cursor >= count,
+    })
+}
+
+/// Rewrite the listed properties in the current record layout (admin only).
+///
+/// For properties registered before the registration-order index existed,
+/// whose ids are only known from their registration events. Takes at most
+/// `MAX_MIGRATION_BATCH` ids; unknown ids are skipped. Returns how many
+/// properties were rewritten.
+pub fn migrate_records_by_id(
+    env: &Env,
+    caller: Address,
+    property_ids: Vec<String>,
+) -> Result<u32, PropertyError> {
+    caller.require_auth();
+    require_admin(env, &caller)?;
+    if property_ids.is_empty() || property_ids.len() > MAX_MIGRATION_BATCH {
+        return Err(PropertyError::InvalidBatchSize);
+    }
+
+    let mut migrated = 0;
+    for property_id in property_ids.iter() {
+        if let Some(details) = property::load_property(env, &property_id) {
+            property::save_property(env, &details);
+            migrated += 1;
+        }
+    }
+    Ok(migrated)
+}
@@ -0,0 +1,228 @@
+use soroban_sdk::{Address, Env, String, Vec};
+
+use crate::errors::DirectoryError;
+use crate::events;
+use crate::storage::DataKey;
+use crate::types::{
+    Component, ComponentEntry, ComponentUpdate, DirectoryConfig, DirectoryProposal,
+};
+
+pub fn get_config(env: &Env) -> Result<DirectoryConfig, DirectoryError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .ok_or(DirectoryError::NotInitialized)
+}
+
+fn require_admin(env: &Env, caller: &Address) -> Result<DirectoryConfig, DirectoryError> {
+    let config = get_config(env)?;
+    if !config.admins.contains(caller) {
+        return Err(DirectoryError::Unauthorized);
+    }
+    Ok(config)
+}
+
+pub fn initialize(
+    env: &Env,
+    admins: Vec<Address>,
+    required_approvals: u32,
+    min_delay_seconds: u64,
+) -> Result<(), DirectoryError> {
+    if env.storage().instance().has(&DataKey::Config) {
+        return Err(DirectoryError::AlreadyInitialized);
+    }
+    if required_approvals == 0 || required_approvals > admins.len() {
+        return Err(DirectoryError::InvalidConfig);
+    }
+    for (i, admin) in admins.iter().enumerate() {
+        if admins.iter().position(|other| other == admin) != Some(i) {
+            return Err(DirectoryError::InvalidConfig);
+        }
+        admin.require_auth();
+    }
+
+    let config = DirectoryConfig {
+        admins,
+        required_approvals,
+        min_delay_seconds,
+    };
+    env.storage().instance().set(&DataKey::Config, &config);
+    env.storage().instance().extend_ttl(500000, 500000);
+
+    events::directory_initialized(env, config.admins.len(), required_approvals);
+    Ok(())
+}
+
+pub fn get_component(env: &Env, component: Component) -> Option<ComponentEntry> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Component(component))
+}
+
+pub fn get_components(env: &Env) -> Vec<Component> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Components)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Reject a change that is empty, names a component twice or does not
+/// raise a component's version.
+fn validate_updates(env: &Env, updates: &Vec<ComponentUpdate>) -> Result<(), DirectoryError> {
+    if updates.is_empty() {
+        return Err(DirectoryError::EmptyUpdate);
+    }
+    for (i, update) in updates.iter().enumerate() {
+        let first = updates
+            .iter()
+            .position(|other| other.component == update.component);
+        if first != Some(i) {
+            return Err(DirectoryError::DuplicateComponent);
+        }
+        if let Some(current) = get_component(env, update.component) {
+            if update.version <= current.version {
+                return Err(DirectoryError::VersionNotIncreased);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn pending_proposal(env: &Env, proposal_id: &String) -> Result<DirectoryProposal, DirectoryError> {
+    let proposal: DirectoryProposal = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id.clone()))
+        .ok_or(DirectoryError::ProposalNotFound)?;
+    if proposal.executed {
+        return Err(DirectoryError::ProposalAlreadyExecuted);
+    }
+    Ok(proposal)
+}
+
+fn save_proposal(env: &Env, proposal: &DirectoryProposal) {
+    let key = DataKey::Proposal(proposal.id.clone());
+    env.storage().persistent().set(&key, proposal);
+    env.storage().persistent().extend_ttl(&key, 500000, 500000);
+}
+
+pub fn propose_update(
+    env: &Env,
+    proposer: Address,
+    proposal_id: String,
+    updates: Vec<ComponentUpdate>,
+    delay_seconds: u64,
+) -> Result<(), DirectoryError> {
+    proposer.require_auth();
+    let config = require_admin(env, &proposer)?;
+    if delay_seconds < config.min_delay_seconds {
+        return Err(DirectoryError::DelayTooShort);
+    }
+
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::Proposal(proposal_id.clone()))
+    {
+        return Err(DirectoryError::ProposalAlreadyExists);
+    }
+    validate_updates(env, &updates)?;
+
+    let now = env.ledger().timestamp();
+    let proposal = DirectoryProposal {
+        id: proposal_id.clone(),
+        proposer: proposer.clone(),
+        updates,
+        approvals: Vec::from_array(env, [proposer.clone()]),
+        eta: now + delay_seconds,
+        executed: false,
+        created_at: now,
+    };
+    save_proposal(env, &proposal);
+
+    events::update_proposed(env, proposal_id, proposer, proposal.eta);
+    Ok(())
+}
+
+pub fn approve_update(
+    env: &Env,
+    approver: Address,
+    proposal_id: String,
+) -> Result<(), DirectoryError> {
+    approver.require_auth();
+    require_admin(env, &approver)?;
+
+    let mut proposal = pending_proposal(env, &proposal_id)?;
+    if proposal.approvals.contains(&approver) {
+        return Err(DirectoryError::AlreadyApproved);
+    }
+    proposal.approvals.push_back(approver.clone());
+    save_proposal(env, &proposal);
+
+    events::update_approved(env, proposal_id, approver, proposal.approvals.len());
+    Ok(())
+}
+
+/// Apply every update of an approved proposal once its ETA has passed.
+/// Versions are checked again, since another change may have executed
+/// since the proposal was made.
+pub fn execute_update(
+    env: &Env,
+    executor: Address,
+    proposal_id: String,
+) -> Result<(), DirectoryError> {
+    executor.require_auth();
+    let config = require_admin(env, &executor)?;
+
+    let mut proposal = pending_proposal(env, &proposal_id)?;
+    if proposal.approvals.len() < config.required_approvals {
+        return Err(DirectoryError::InsufficientApprovals);
+    }
+    if env.ledger().timestamp() < proposal.eta {
+        return Err(DirectoryError::TimelockNotReached);
+    }
+    validate_updates(env, &proposal.updates)?;
+
+    proposal.executed = true;
+    save_proposal(env, &proposal);
+
+    let mut components = get_components(env);
+    for update in proposal.updates.iter() {
+        let key = DataKey::Component(update.component);
+        let previous = get_component(env, update.component).map(|entry| entry.address);
+        if previous.is_none() {
+            components.push_back(update.component);
+        }
+        let entry = ComponentEntry {
+            address: update.address.clone(),
+            version: update.version,
+            updated_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &entry);
+        env.storage().persistent().extend_ttl(&key, 500000, 500000);
+
+        events::component_updated(
+            env,
+            update.component,
+            previous,
+            update.address,
+            update.version,
+        );
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Components, &components);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::Components, 500000, 500000);
+
+    events::update_executed(env, proposal_id, executor);
+    Ok(())
+}
+
+pub fn get_proposal(env: &Env, proposal_id: String) -> Result<DirectoryProposal, DirectoryError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id))
+        .ok_or(DirectoryError::ProposalNotFound)
+}
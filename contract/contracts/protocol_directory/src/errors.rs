@@ -0,0 +1,21 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DirectoryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidConfig = 4,
+    ProposalNotFound = 5,
+    ProposalAlreadyExists = 6,
+    ProposalAlreadyExecuted = 7,
+    AlreadyApproved = 8,
+    InsufficientApprovals = 9,
+    TimelockNotReached = 10,
+    EmptyUpdate = 11,
+    DuplicateComponent = 12,
+    VersionNotIncreased = 13,
+    DelayTooShort = 14,
+}
@@ -0,0 +1,114 @@
+use soroban_sdk::{contractevent, Address, Env, String};
+
+use crate::types::Component;
+
+/// Event emitted when the directory is initialized
+/// Topics: ["initialized"]
+#[contractevent(topics = ["initialized"])]
+pub struct DirectoryInitialized {
+    pub admin_count: u32,
+    pub required_approvals: u32,
+    pub initialized_at: u64,
+}
+
+/// Event emitted when a directory change is proposed
+/// Topics: ["update_proposed", proposal_id: String]
+#[contractevent(topics = ["update_proposed"])]
+pub struct UpdateProposed {
+    #[topic]
+    pub proposal_id: String,
+    pub proposer: Address,
+    pub eta: u64,
+    pub created_at: u64,
+}
+
+/// Event emitted when a directory change is approved
+/// Topics: ["update_approved", proposal_id: String]
+#[contractevent(topics = ["update_approved"])]
+pub struct UpdateApproved {
+    #[topic]
+    pub proposal_id: String,
+    pub approver: Address,
+    pub approval_count: u32,
+}
+
+/// Event emitted when a directory change is executed
+/// Topics: ["update_executed", proposal_id: String]
+#[contractevent(topics = ["update_executed"])]
+pub struct UpdateExecuted {
+    #[topic]
+    pub proposal_id: String,
+    pub executor: Address,
+    pub executed_at: u64,
+}
+
+/// Event emitted for each component an executed change re-points
+/// Topics: ["component_updated", component: Component]
+#[contractevent(topics = ["component_updated"])]
+pub struct ComponentUpdated {
+    #[topic]
+    pub component: Component,
+    pub previous: Option<Address>,
+    pub address: Address,
+    pub version: u32,
+    pub updated_at: u64,
+}
+
+pub(crate) fn directory_initialized(env: &Env, admin_count: u32, required_approvals: u32) {
+    DirectoryInitialized {
+        admin_count,
+        required_approvals,
+        initialized_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn update_proposed(env: &Env, proposal_id: String, proposer: Address, eta: u64) {
+    UpdateProposed {
+        proposal_id,
+        proposer,
+        eta,
+        created_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn update_approved(
+    env: &Env,
+    proposal_id: String,
+    approver: Address,
+    approval_count: u32,
+) {
+    UpdateApproved {
+        proposal_id,
+        approver,
+        approval_count,
+    }
+    .publish(env);
+}
+
+pub(crate) fn update_executed(env: &Env, proposal_id: String, executor: Address) {
+    UpdateExecuted {
+        proposal_id,
+        executor,
+        executed_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+pub(crate) fn component_updated(
+    env: &Env,
+    component: Component,
+    previous: Option<Address>,
+    address: Address,
+    version: u32,
+) {
+    ComponentUpdated {
+        component,
+        previous,
+        address,
+        version,
+        updated_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
@@ -0,0 +1,137 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+
+mod directory;
+mod errors;
+mod events;
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod tests;
+
+pub use errors::DirectoryError;
+pub use storage::DataKey;
+pub use types::{Component, ComponentEntry, ComponentUpdate, DirectoryConfig, DirectoryProposal};
+
+/// Canonical addresses of the protocol contracts.
+///
+/// The directory maps each [`Component`] to the address and version of its
+/// current deployment. Entries change only through governance: an admin
+/// proposes a set of updates, the configured number of admins approve it
+/// and, once its delay has passed, executing it re-points every component
+/// in the set at once. Contracts that call a peer resolve its address here
+/// instead of storing it, so redeploying a component needs one executed
+/// proposal rather than reconfiguring each caller.
+#[contract]
+pub struct ProtocolDirectoryContract;
+
+#[contractimpl]
+impl ProtocolDirectoryContract {
+    /// Initialize the directory with its governing admins.
+    ///
+    /// # Arguments
+    /// * `admins` - The addresses that propose, approve and execute changes;
+    ///   each must authorize
+    /// * `required_approvals` - Approvals a change needs before it executes
+    /// * `min_delay_seconds` - Shortest delay a proposal may set, so a single
+    ///   proposer cannot make a change executable as soon as it is approved
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - If the directory has already been initialized
+    /// * `InvalidConfig` - If `admins` repeats an address, or
+    ///   `required_approvals` is zero or more than the number of admins
+    pub fn initialize(
+        env: Env,
+        admins: Vec<Address>,
+        required_approvals: u32,
+        min_delay_seconds: u64,
+    ) -> Result<(), DirectoryError> {
+        directory::initialize(&env, admins, required_approvals, min_delay_seconds)
+    }
+
+    /// Get the governing admins, required approvals and minimum delay.
+    pub fn get_config(env: Env) -> Result<DirectoryConfig, DirectoryError> {
+        directory::get_config(&env)
+    }
+
+    /// Propose re-pointing one or more components. The proposer's approval
+    /// is counted.
+    ///
+    /// # Arguments
+    /// * `proposer` - An admin
+    /// * `proposal_id` - Unique identifier for the proposal
+    /// * `updates` - The new address and version of each component
+    /// * `delay_seconds` - Time after which the proposal can execute
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the proposer is not an admin
+    /// * `DelayTooShort` - If `delay_seconds` is below the configured minimum
+    /// * `ProposalAlreadyExists` - If the id is taken
+    /// * `EmptyUpdate` - If `updates` is empty
+    /// * `DuplicateComponent` - If a component is updated twice
+    /// * `VersionNotIncreased` - If a version is not above the component's
+    ///   current one
+    pub fn propose_update(
+        env: Env,
+        proposer: Address,
+        proposal_id: String,
+        updates: Vec<ComponentUpdate>,
+        delay_seconds: u64,
+    ) -> Result<(), DirectoryError> {
+        directory::propose_update(&env, proposer, proposal_id, updates, delay_seconds)
+    }
+
+    /// Approve a pending proposal (admin only).
+    ///
+    /// # Errors
+    /// * `ProposalNotFound` / `ProposalAlreadyExecuted` - If it is not pending
+    /// * `AlreadyApproved` - If the approver has already approved it
+    pub fn approve_update(
+        env: Env,
+        approver: Address,
+        proposal_id: String,
+    ) -> Result<(), DirectoryError> {
+        directory::approve_update(&env, approver, proposal_id)
+    }
+
+    /// Apply an approved proposal whose delay has passed (admin only).
+    ///
+    /// # Errors
+    /// * `InsufficientApprovals` - If it lacks approvals
+    /// * `TimelockNotReached` - If its delay has not passed
+    /// * `VersionNotIncreased` - If a change executed since has recorded a
+    ///   version at least as high
+    pub fn execute_update(
+        env: Env,
+        executor: Address,
+        proposal_id: String,
+    ) -> Result<(), DirectoryError> {
+        directory::execute_update(&env, executor, proposal_id)
+    }
+
+    /// Get a proposal.
+    pub fn get_proposal(
+        env: Env,
+        proposal_id: String,
+    ) -> Result<DirectoryProposal, DirectoryError> {
+        directory::get_proposal(&env, proposal_id)
+    }
+
+    /// Get the current deployment of a component, if registered.
+    pub fn get_component(env: Env, component: Component) -> Option<ComponentEntry> {
+        directory::get_component(&env, component)
+    }
+
+    /// Get the address of a component, if registered. This is what peer
+    /// contracts call.
+    pub fn resolve(env: Env, component: Component) -> Option<Address> {
+        directory::get_component(&env, component).map(|entry| entry.address)
+    }
+
+    /// Get the registered components, in registration order.
+    pub fn get_components(env: Env) -> Vec<Component> {
+        directory::get_components(&env)
+    }
+}
@@ -0,0 +1,13 @@
+use soroban_sdk::{contracttype, String};
+
+use crate::types::Component;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Config,
+    /// Registered components, in registration order.
+    Components,
+    Component(Component),
+    Proposal(String),
+}
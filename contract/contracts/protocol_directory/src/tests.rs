@@ -0,0 +1,304 @@
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger},
+    vec, Address, Env, String,
+};
+
+struct Setup<'a> {
+    env: Env,
+    client: ProtocolDirectoryContractClient<'a>,
+    admins: Vec<Address>,
+}
+
+/// A directory governed by three admins, two of whom must approve a change.
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client =
+        ProtocolDirectoryContractClient::new(&env, &env.register(ProtocolDirectoryContract, ()));
+    let admins = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    client.initialize(&admins, &2, &0);
+    Setup {
+        env,
+        client,
+        admins,
+    }
+}
+
+fn id(env: &Env, id: &str) -> String {
+    String::from_str(env, id)
+}
+
+fn update(component: Component, address: &Address, version: u32) -> ComponentUpdate {
+    ComponentUpdate {
+        component,
+        address: address.clone(),
+        version,
+    }
+}
+
+/// Propose, approve and execute `updates` without delay.
+fn apply(s: &Setup, proposal_id: &str, updates: Vec<ComponentUpdate>) {
+    let proposal_id = id(&s.env, proposal_id);
+    s.client
+        .propose_update(&s.admins.get(0).unwrap(), &proposal_id, &updates, &0);
+    s.client
+        .approve_update(&s.admins.get(1).unwrap(), &proposal_id);
+    s.client
+        .execute_update(&s.admins.get(2).unwrap(), &proposal_id);
+}
+
+#[test]
+fn test_initialize_validates_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client =
+        ProtocolDirectoryContractClient::new(&env, &env.register(ProtocolDirectoryContract, ()));
+    let admin = Address::generate(&env);
+
+    assert_eq!(
+        client.try_initialize(&vec![&env, admin.clone()], &2, &0),
+        Err(Ok(DirectoryError::InvalidConfig))
+    );
+    assert_eq!(
+        client.try_initialize(&vec![&env, admin.clone(), admin.clone()], &1, &0),
+        Err(Ok(DirectoryError::InvalidConfig))
+    );
+    assert_eq!(
+        client.try_get_config(),
+        Err(Ok(DirectoryError::NotInitialized))
+    );
+
+    client.initialize(&vec![&env, admin.clone()], &1, &3_600);
+    assert_eq!(
+        client.get_config(),
+        DirectoryConfig {
+            admins: vec![&env, admin.clone()],
+            required_approvals: 1,
+            min_delay_seconds: 3_600,
+        }
+    );
+    assert_eq!(
+        client.try_initialize(&vec![&env, admin], &1, &0),
+        Err(Ok(DirectoryError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_executed_update_registers_components() {
+    let s = setup();
+    let chioma = Address::generate(&s.env);
+    let escrow = Address::generate(&s.env);
+    assert_eq!(s.client.resolve(&Component::Chioma), None);
+
+    apply(
+        &s,
+        "DIR-1",
+        vec![
+            &s.env,
+            update(Component::Chioma, &chioma, 1),
+            update(Component::Escrow, &escrow, 1),
+        ],
+    );
+
+    assert_eq!(s.client.resolve(&Component::Chioma), Some(chioma.clone()));
+    assert_eq!(s.client.resolve(&Component::Escrow), Some(escrow));
+    assert_eq!(
+        s.client.get_components(),
+        vec![&s.env, Component::Chioma, Component::Escrow]
+    );
+    let entry = s.client.get_component(&Component::Chioma).unwrap();
+    assert_eq!((entry.address, entry.version), (chioma, 1));
+    assert!(s.client.get_proposal(&id(&s.env, "DIR-1")).executed);
+}
+
+#[test]
+fn test_redeployment_repoints_component() {
+    let s = setup();
+    let old = Address::generate(&s.env);
+    let new = Address::generate(&s.env);
+    apply(
+        &s,
+        "DIR-1",
+        vec![&s.env, update(Component::Payment, &old, 1)],
+    );
+
+    apply(
+        &s,
+        "DIR-2",
+        vec![&s.env, update(Component::Payment, &new, 2)],
+    );
+
+    // `component_updated` for the payment contract, then `update_executed`.
+    let events = s.env.events().all();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events.get(0).unwrap().1.len(), 2);
+    assert_eq!(s.client.resolve(&Component::Payment), Some(new.clone()));
+    assert_eq!(
+        s.client.get_component(&Component::Payment).unwrap().version,
+        2
+    );
+    assert_eq!(s.client.get_components(), vec![&s.env, Component::Payment]);
+}
+
+#[test]
+fn test_update_needs_approvals_and_delay() {
+    let s = setup();
+    let (proposer, approver) = (s.admins.get(0).unwrap(), s.admins.get(1).unwrap());
+    let proposal_id = id(&s.env, "DIR-1");
+    let updates = vec![
+        &s.env,
+        update(Component::UserProfile, &Address::generate(&s.env), 1),
+    ];
+    s.client
+        .propose_update(&proposer, &proposal_id, &updates, &3_600);
+
+    assert_eq!(
+        s.client.try_execute_update(&proposer, &proposal_id),
+        Err(Ok(DirectoryError::InsufficientApprovals))
+    );
+    assert_eq!(
+        s.client.try_approve_update(&proposer, &proposal_id),
+        Err(Ok(DirectoryError::AlreadyApproved))
+    );
+    s.client.approve_update(&approver, &proposal_id);
+    assert_eq!(
+        s.client.try_execute_update(&proposer, &proposal_id),
+        Err(Ok(DirectoryError::TimelockNotReached))
+    );
+
+    s.env.ledger().with_mut(|ledger| ledger.timestamp += 3_600);
+    s.client.execute_update(&proposer, &proposal_id);
+    assert!(s.client.resolve(&Component::UserProfile).is_some());
+    assert_eq!(
+        s.client.try_execute_update(&proposer, &proposal_id),
+        Err(Ok(DirectoryError::ProposalAlreadyExecuted))
+    );
+}
+
+#[test]
+fn test_proposals_cannot_undercut_the_minimum_delay() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client =
+        ProtocolDirectoryContractClient::new(&env, &env.register(ProtocolDirectoryContract, ()));
+    let admin = Address::generate(&env);
+    client.initialize(&vec![&env, admin.clone()], &1, &86_400);
+
+    let updates = vec![&env, update(Component::Escrow, &Address::generate(&env), 1)];
+    assert_eq!(
+        client.try_propose_update(&admin, &id(&env, "DIR-1"), &updates, &3_600),
+        Err(Ok(DirectoryError::DelayTooShort))
+    );
+    client.propose_update(&admin, &id(&env, "DIR-1"), &updates, &86_400);
+    assert_eq!(
+        client.try_execute_update(&admin, &id(&env, "DIR-1")),
+        Err(Ok(DirectoryError::TimelockNotReached))
+    );
+}
+
+#[test]
+fn test_only_admins_govern() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+    let proposal_id = id(&s.env, "DIR-1");
+    let updates = vec![&s.env, update(Component::Chioma, &outsider, 1)];
+
+    assert_eq!(
+        s.client
+            .try_propose_update(&outsider, &proposal_id, &updates, &0),
+        Err(Ok(DirectoryError::Unauthorized))
+    );
+    s.client
+        .propose_update(&s.admins.get(0).unwrap(), &proposal_id, &updates, &0);
+    assert_eq!(
+        s.client.try_approve_update(&outsider, &proposal_id),
+        Err(Ok(DirectoryError::Unauthorized))
+    );
+    assert_eq!(
+        s.client.try_execute_update(&outsider, &proposal_id),
+        Err(Ok(DirectoryError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_proposal_validation() {
+    let s = setup();
+    let proposer = s.admins.get(0).unwrap();
+    let address = Address::generate(&s.env);
+    let propose = |proposal_id: &str, updates: Vec<ComponentUpdate>| {
+        s.client
+            .try_propose_update(&proposer, &id(&s.env, proposal_id), &updates, &0)
+    };
+
+    assert_eq!(
+        propose("DIR-1", Vec::new(&s.env)),
+        Err(Ok(DirectoryError::EmptyUpdate))
+    );
+    assert_eq!(
+        propose(
+            "DIR-1",
+            vec![
+                &s.env,
+                update(Component::Escrow, &address, 1),
+                update(Component::Escrow, &address, 2),
+            ]
+        ),
+        Err(Ok(DirectoryError::DuplicateComponent))
+    );
+
+    apply(
+        &s,
+        "DIR-1",
+        vec![&s.env, update(Component::Escrow, &address, 3)],
+    );
+    assert_eq!(
+        propose(
+            "DIR-1",
+            vec![&s.env, update(Component::Escrow, &address, 4)]
+        ),
+        Err(Ok(DirectoryError::ProposalAlreadyExists))
+    );
+    assert_eq!(
+        propose(
+            "DIR-2",
+            vec![&s.env, update(Component::Escrow, &address, 3)]
+        ),
+        Err(Ok(DirectoryError::VersionNotIncreased))
+    );
+}
+
+#[test]
+fn test_execution_rechecks_versions() {
+    let s = setup();
+    let address = Address::generate(&s.env);
+    let proposer = s.admins.get(0).unwrap();
+    let stale = id(&s.env, "DIR-STALE");
+    s.client.propose_update(
+        &proposer,
+        &stale,
+        &vec![&s.env, update(Component::Chioma, &address, 2)],
+        &0,
+    );
+    s.client.approve_update(&s.admins.get(1).unwrap(), &stale);
+
+    apply(
+        &s,
+        "DIR-1",
+        vec![&s.env, update(Component::Chioma, &address, 5)],
+    );
+
+    assert_eq!(
+        s.client.try_execute_update(&proposer, &stale),
+        Err(Ok(DirectoryError::VersionNotIncreased))
+    );
+    assert_eq!(
+        s.client.get_component(&Component::Chioma).unwrap().version,
+        5
+    );
+}
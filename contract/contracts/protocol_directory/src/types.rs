@@ -0,0 +1,58 @@
+use soroban_sdk::{contracttype, Address, String, Vec};
+
+/// A contract of the protocol the directory tracks.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Component {
+    AgentRegistry,
+    Chioma,
+    DisputeResolution,
+    Escrow,
+    Payment,
+    PropertyRegistry,
+    RentObligation,
+    UserProfile,
+}
+
+/// The canonical deployment of a component.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComponentEntry {
+    pub address: Address,
+    /// Increases with every redeployment or upgrade recorded for the
+    /// component.
+    pub version: u32,
+    pub updated_at: u64,
+}
+
+/// Point `component` at `address`, recorded as `version`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComponentUpdate {
+    pub component: Component,
+    pub address: Address,
+    pub version: u32,
+}
+
+/// The admins governing the directory, the approvals a change needs and the
+/// shortest delay a proposal may set.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DirectoryConfig {
+    pub admins: Vec<Address>,
+    pub required_approvals: u32,
+    pub min_delay_seconds: u64,
+}
+
+/// A governed change to one or more components, applied together.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DirectoryProposal {
+    pub id: String,
+    pub proposer: Address,
+    pub updates: Vec<ComponentUpdate>,
+    pub approvals: Vec<Address>,
+    pub eta: u64,
+    pub executed: bool,
+    pub created_at: u64,
+}
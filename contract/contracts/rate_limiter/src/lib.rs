@@ -0,0 +1,134 @@
+//! Rate limiting pieces shared by the protocol contracts.
+//!
+//! Each contract keeps its own `RateLimitConfig`, storage keys and errors,
+//! since they are part of its interface. What is the same everywhere lives
+//! here: the per-user state of the timestamp-based modes and the arithmetic
+//! that counts calls against it, and reading configs stored before those
+//! modes existed.
+#![no_std]
+
+use soroban_sdk::{
+    contracttype, unwrap::UnwrapOptimized, Env, IntoVal, Map, Symbol, TryFromVal, Val,
+};
+
+/// Token-bucket levels are kept in thousandths of a call so refills are smooth.
+pub const TOKEN_SCALE: u64 = 1_000;
+
+/// Per-user state for the timestamp-based modes.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateWindow {
+    pub window_start: u64,
+    pub current_count: u32,
+    pub previous_count: u32,
+    /// Bucket level in thousandths of a call.
+    pub tokens: u64,
+    pub last_refill: u64,
+}
+
+/// How a `RateWindow` counts calls.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    /// At most `limit` calls in any trailing window.
+    SlidingWindow,
+    /// A bucket of `limit` calls that refills continuously over the window.
+    TokenBucket,
+}
+
+/// The limiter state stored under `key`; new users start with a full bucket
+/// and an empty window.
+pub fn load_window<K>(env: &Env, key: &K, limit: u32, window_seconds: u64) -> RateWindow
+where
+    K: IntoVal<Env, Val>,
+{
+    let now = env.ledger().timestamp();
+    env.storage().persistent().get(key).unwrap_or(RateWindow {
+        window_start: now - now % window_seconds.max(1),
+        current_count: 0,
+        previous_count: 0,
+        tokens: limit as u64 * TOKEN_SCALE,
+        last_refill: now,
+    })
+}
+
+/// Count one call against `state`, returning `false` if it would exceed
+/// `limit` calls per `window_seconds`. `state` is only meaningful to persist
+/// when this returns `true`.
+pub fn consume_window(
+    env: &Env,
+    strategy: Strategy,
+    limit: u32,
+    window_seconds: u64,
+    state: &mut RateWindow,
+) -> bool {
+    let now = env.ledger().timestamp();
+    let window = window_seconds.max(1);
+    let limit = limit as u64;
+
+    if strategy == Strategy::TokenBucket {
+        let capacity = limit * TOKEN_SCALE;
+        let elapsed = now.saturating_sub(state.last_refill) as u128;
+        let refill = (elapsed * capacity as u128 / window as u128).min(capacity as u128) as u64;
+        state.tokens = state.tokens.saturating_add(refill).min(capacity);
+        state.last_refill = now;
+        if state.tokens < TOKEN_SCALE {
+            return false;
+        }
+        state.tokens -= TOKEN_SCALE;
+        return true;
+    }
+
+    // Sliding-window counter: calls from the previous fixed window count in
+    // proportion to how much of it still overlaps the trailing window,
+    // rounded up so the estimate never under-counts.
+    let start = now - now % window;
+    if start != state.window_start {
+        state.previous_count = if start == state.window_start.saturating_add(window) {
+            state.current_count
+        } else {
+            0
+        };
+        state.current_count = 0;
+        state.window_start = start;
+    }
+    let overlap = window - (now - start);
+    let weighted =
+        (state.previous_count as u64 * overlap).div_ceil(window) + state.current_count as u64;
+    if weighted >= limit {
+        return false;
+    }
+    state.current_count += 1;
+    true
+}
+
+/// A `RateLimitConfig` as stored before `mode` and `window_seconds` were
+/// added; it limited by ledger blocks only.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyRateLimitConfig {
+    pub max_calls_per_block: u32,
+    pub max_calls_per_user_per_day: u32,
+    pub cooldown_blocks: u32,
+}
+
+/// Decode a stored `RateLimitConfig` of either layout.
+///
+/// Decoding a struct of the wrong shape traps rather than failing, so the
+/// layout is told apart by whether the stored map has a `mode` field.
+pub fn read_config<T>(env: &Env, raw: &Val) -> T
+where
+    T: TryFromVal<Env, Val> + From<LegacyRateLimitConfig>,
+{
+    let current = Map::<Symbol, Val>::try_from_val(env, raw)
+        .is_ok_and(|fields| fields.contains_key(Symbol::new(env, "mode")));
+    if current {
+        T::try_from_val(env, raw).unwrap_optimized()
+    } else {
+        LegacyRateLimitConfig::try_from_val(env, raw)
+            .unwrap_optimized()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,115 @@
+use soroban_sdk::{contract, contracttype, testutils::Ledger, Env, IntoVal, Symbol, Val};
+
+use crate::{
+    consume_window, load_window, read_config, LegacyRateLimitConfig, RateWindow, Strategy,
+    TOKEN_SCALE,
+};
+
+#[contract]
+struct Host;
+
+/// A config with the current layout, as a contract would declare it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Config {
+    max_calls_per_block: u32,
+    max_calls_per_user_per_day: u32,
+    cooldown_blocks: u32,
+    mode: u32,
+    window_seconds: u64,
+}
+
+impl From<LegacyRateLimitConfig> for Config {
+    fn from(legacy: LegacyRateLimitConfig) -> Self {
+        Config {
+            max_calls_per_block: legacy.max_calls_per_block,
+            max_calls_per_user_per_day: legacy.max_calls_per_user_per_day,
+            cooldown_blocks: legacy.cooldown_blocks,
+            mode: 0,
+            window_seconds: 86_400,
+        }
+    }
+}
+
+fn at(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+fn calls(env: &Env, strategy: Strategy, state: &mut RateWindow, n: u32) -> u32 {
+    (0..n)
+        .filter(|_| consume_window(env, strategy, 3, 100, state))
+        .count() as u32
+}
+
+#[test]
+fn test_new_users_start_with_a_full_bucket_and_an_empty_window() {
+    let env = Env::default();
+    let host = env.register(Host, ());
+    at(&env, 250);
+    let state = env.as_contract(&host, || {
+        load_window(&env, &Symbol::new(&env, "user"), 3, 100)
+    });
+    assert_eq!(state.window_start, 200);
+    assert_eq!(state.current_count, 0);
+    assert_eq!(state.tokens, 3 * TOKEN_SCALE);
+    assert_eq!(state.last_refill, 250);
+}
+
+#[test]
+fn test_sliding_window_weighs_the_previous_window() {
+    let env = Env::default();
+    let host = env.register(Host, ());
+    at(&env, 0);
+    let mut state = env.as_contract(&host, || {
+        load_window(&env, &Symbol::new(&env, "user"), 3, 100)
+    });
+    assert_eq!(calls(&env, Strategy::SlidingWindow, &mut state, 5), 3);
+
+    // Half of the previous window still overlaps: 1.5 calls, rounded up.
+    at(&env, 150);
+    assert_eq!(calls(&env, Strategy::SlidingWindow, &mut state, 5), 1);
+
+    // Two windows later nothing overlaps.
+    at(&env, 400);
+    assert_eq!(calls(&env, Strategy::SlidingWindow, &mut state, 5), 3);
+}
+
+#[test]
+fn test_token_bucket_refills_over_the_window() {
+    let env = Env::default();
+    let host = env.register(Host, ());
+    at(&env, 0);
+    let mut state = env.as_contract(&host, || {
+        load_window(&env, &Symbol::new(&env, "user"), 3, 100)
+    });
+    assert_eq!(calls(&env, Strategy::TokenBucket, &mut state, 5), 3);
+
+    at(&env, 34);
+    assert_eq!(calls(&env, Strategy::TokenBucket, &mut state, 5), 1);
+
+    // A long pause refills the bucket but never beyond its capacity.
+    at(&env, 10_000);
+    assert_eq!(calls(&env, Strategy::TokenBucket, &mut state, 5), 3);
+}
+
+#[test]
+fn test_read_config_accepts_both_layouts() {
+    let env = Env::default();
+    let current = Config {
+        max_calls_per_block: 5,
+        max_calls_per_user_per_day: 50,
+        cooldown_blocks: 2,
+        mode: 2,
+        window_seconds: 3_600,
+    };
+    let raw: Val = current.clone().into_val(&env);
+    assert_eq!(read_config::<Config>(&env, &raw), current);
+
+    let legacy = LegacyRateLimitConfig {
+        max_calls_per_block: 5,
+        max_calls_per_user_per_day: 50,
+        cooldown_blocks: 2,
+    };
+    let raw: Val = legacy.clone().into_val(&env);
+    assert_eq!(read_config::<Config>(&env, &raw), Config::from(legacy));
+}
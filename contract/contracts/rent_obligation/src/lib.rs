@@ -53,6 +53,24 @@ impl TokenizedRentObligationContract {
         Ok(())
     }
 
+    /// Initialize the admin that governs upgrades. Can only be set once.
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - If an admin has already been set
+    pub fn initialize_admin(env: Env, admin: Address) -> Result<(), ObligationError> {
+        if env.storage().persistent().has(&DataKey::Admin) {
+            return Err(ObligationError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().persistent().set(&DataKey::Admin, &admin);
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::Admin, 500000, 500000);
+
+        events::admin_initialized(&env, admin);
+        Ok(())
+    }
+
     /// Mint a new tokenized rent obligation NFT for a rent agreement.
     ///
     /// # Arguments
@@ -383,7 +401,7 @@ impl TokenizedRentObligationContract {
         env: Env,
         proposer: Address,
         proposal_id: String,
-        wasm_hash: soroban_sdk::Bytes,
+        wasm_hash: soroban_sdk::BytesN<32>,
         notes: String,
         delay_seconds: u64,
     ) -> Result<(), ObligationError> {
@@ -415,4 +433,19 @@ impl TokenizedRentObligationContract {
     ) -> Result<upgrade::UpgradeProposal, ObligationError> {
         upgrade::get_upgrade_proposal(&env, proposal_id)
     }
+
+    /// Run pending storage migrations after an upgrade (admin only).
+    pub fn migrate(env: Env, caller: Address) -> Result<u32, ObligationError> {
+        upgrade::migrate(&env, caller)
+    }
+
+    /// Get the code version, bumped by every executed upgrade.
+    pub fn get_contract_version(env: Env) -> u32 {
+        upgrade::get_contract_version(&env)
+    }
+
+    /// Get the layout version of the stored data.
+    pub fn get_storage_version(env: Env) -> u32 {
+        upgrade::get_storage_version(&env)
+    }
 }
@@ -4,6 +4,7 @@ use soroban_sdk::{contracttype, String};
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     Initialized,
+    Admin,
     Obligation(String),
     Owner(String),
     ObligationCount,
@@ -11,4 +12,7 @@ pub enum DataKey {
     BurnedNfts(String),
     BurnCount,
     UpgradeProposal(String),
+    ContractVersion,
+    StorageVersion,
+    MigratedVersion,
 }
@@ -4,6 +4,15 @@ use soroban_sdk::{
     Address, Env, IntoVal, String,
 };
 
+/// Minimal WASM module the host accepts as contract code.
+const UPGRADE_WASM: &[u8] = b"\0asm\x01\0\0\0\0\x1e\x11contractenvmetav0\0\0\0\0\0\0\0\x17\0\0\0\0";
+
+/// Upload `UPGRADE_WASM` so an upgrade to its hash can be executed.
+pub(crate) fn upload_upgrade_wasm(env: &Env) -> soroban_sdk::BytesN<32> {
+    env.deployer()
+        .upload_contract_wasm(soroban_sdk::Bytes::from_slice(env, UPGRADE_WASM))
+}
+
 fn create_contract(env: &Env) -> TokenizedRentObligationContractClient<'_> {
     let contract_id = env.register(TokenizedRentObligationContract, ());
     TokenizedRentObligationContractClient::new(env, &contract_id)
@@ -819,3 +828,64 @@ fn test_error_paths_return_contract_errors_without_mutating_state() {
     assert!(client.has_obligation(&agreement_id));
     assert_eq!(client.get_obligation_owner(&agreement_id), Some(landlord));
 }
+
+#[test]
+fn test_execute_upgrade_installs_wasm_and_migrates_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    client.initialize();
+    client.initialize_admin(&admin);
+
+    let proposal_id = String::from_str(&env, "UPG-001");
+    let wasm_hash = upload_upgrade_wasm(&env);
+    client.propose_upgrade(
+        &admin,
+        &proposal_id,
+        &wasm_hash,
+        &String::from_str(&env, "v2"),
+        &0,
+    );
+
+    // Nothing to migrate before an upgrade.
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(ObligationError::AlreadyInitialized))
+    );
+
+    client.execute_upgrade(&admin, &proposal_id);
+
+    // The installed module exports nothing; reinstall the native contract to
+    // stand in for the new code.
+    assert!(client.try_get_contract_version().is_err());
+    env.register_at(&client.address, TokenizedRentObligationContract, ());
+
+    assert_eq!(client.get_contract_version(), 2);
+    assert_eq!(client.migrate(&admin), 1);
+    assert_eq!(client.get_storage_version(), 1);
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(ObligationError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_upgrades_require_the_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = create_contract(&env);
+    client.initialize();
+    let admin = Address::generate(&env);
+    let proposal_id = String::from_str(&env, "UPG-001");
+    let wasm_hash = upload_upgrade_wasm(&env);
+    let notes = String::from_str(&env, "v2");
+
+    let result = client.try_propose_upgrade(&admin, &proposal_id, &wasm_hash, &notes, &0);
+    assert_eq!(result, Err(Ok(ObligationError::NotInitialized)));
+
+    client.initialize_admin(&admin);
+    let outsider = Address::generate(&env);
+    let result = client.try_propose_upgrade(&outsider, &proposal_id, &wasm_hash, &notes, &0);
+    assert_eq!(result, Err(Ok(ObligationError::Unauthorized)));
+}
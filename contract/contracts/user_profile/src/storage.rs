@@ -15,4 +15,10 @@ pub enum DataKey {
 
     /// Upgrade proposal
     UpgradeProposal(String),
+    /// Code version, bumped by every executed upgrade
+    ContractVersion,
+    /// Layout version of the stored data
+    StorageVersion,
+    /// Code version at which `migrate` last ran
+    MigratedVersion,
 }
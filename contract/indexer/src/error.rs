@@ -0,0 +1,105 @@
+use std::fmt;
+
+use crate::events::Contract;
+
+/// Why an event could not be decoded into its typed form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The first topic is not a symbol naming the event.
+    MissingName,
+    /// The contract declares no event with this name.
+    UnknownEvent { contract: Contract, name: String },
+    /// A declared topic or data field is absent.
+    MissingField {
+        event: &'static str,
+        field: &'static str,
+    },
+    /// A topic or data field does not hold the declared type.
+    InvalidField {
+        event: &'static str,
+        field: &'static str,
+        expected: &'static str,
+    },
+    /// The event carries more topics than it declares.
+    ExtraTopics { event: &'static str },
+    /// The event data is not a map of named fields.
+    InvalidData { event: &'static str },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingName => f.write_str("first topic is not an event name"),
+            Self::UnknownEvent { contract, name } => {
+                write!(f, "{contract} declares no event `{name}`")
+            }
+            Self::MissingField { event, field } => write!(f, "{event}: missing `{field}`"),
+            Self::InvalidField {
+                event,
+                field,
+                expected,
+            } => write!(f, "{event}: `{field}` is not {expected}"),
+            Self::ExtraTopics { event } => write!(f, "{event}: unexpected extra topics"),
+            Self::InvalidData { event } => write!(f, "{event}: data is not a map"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Errors surfaced by the indexer.
+#[derive(Debug)]
+pub enum Error {
+    /// An event from a known contract did not match its schema.
+    Decode {
+        event_id: String,
+        source: DecodeError,
+    },
+    /// A topic or value is not valid base64 XDR.
+    Xdr(stellar_xdr::curr::Error),
+    /// An input file is not a valid event page.
+    Json(serde_json::Error),
+    Sqlite(rusqlite::Error),
+    Io(std::io::Error),
+    /// A command line or configuration value is invalid.
+    Config(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode { event_id, source } => write!(f, "event {event_id}: {source}"),
+            Self::Xdr(err) => write!(f, "invalid XDR: {err}"),
+            Self::Json(err) => write!(f, "invalid event JSON: {err}"),
+            Self::Sqlite(err) => write!(f, "database error: {err}"),
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Config(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<stellar_xdr::curr::Error> for Error {
+    fn from(err: stellar_xdr::curr::Error) -> Self {
+        Self::Xdr(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Self {
+        Self::Sqlite(err)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
@@ -0,0 +1,64 @@
+//! Events of the agent registry contract (`contracts/agent_registry`).
+
+use stellar_xdr::curr::ScVal;
+
+use crate::scval::Address;
+
+contract_events! {
+    pub enum Event {
+        ContractInitialized("initialized") {
+            #[topic] admin: Address,
+            initialized_at: u64,
+        },
+        AgentRegistered("agent_registered") {
+            #[topic] agent: Address,
+            external_profile_hash: String,
+            registered_at: u64,
+        },
+        AgentVerified("agent_verified") {
+            #[topic] admin: Address,
+            #[topic] agent: Address,
+            verified_at: u64,
+        },
+        AgentRated("agent_rated") {
+            #[topic] agent: Address,
+            #[topic] rater: Address,
+            score: u32,
+            transaction_id: String,
+            rated_at: u64,
+        },
+        TransactionRegistered("transaction_registered") {
+            #[topic] transaction_id: String,
+            #[topic] agent: Address,
+            registered_at: u64,
+        },
+        TransactionCompleted("transaction_completed") {
+            #[topic] transaction_id: String,
+            #[topic] agent: Address,
+            completed_at: u64,
+        },
+        UpgradeProposed("upgrade_proposed") {
+            #[topic] proposal_id: String,
+            proposer: Address,
+            eta: u64,
+            created_at: u64,
+        },
+        UpgradeApproved("upgrade_approved") {
+            #[topic] proposal_id: String,
+            approver: Address,
+            approval_count: u32,
+        },
+        UpgradeExecuted("upgrade_executed") {
+            #[topic] proposal_id: String,
+            executor: Address,
+            executed_at: u64,
+            wasm_hash: [u8; 32],
+            version: u32,
+        },
+        StorageMigrated("storage_migrated") {
+            contract_version: u32,
+            from_version: u32,
+            to_version: u32,
+        },
+    }
+}
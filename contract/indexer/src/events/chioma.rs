@@ -0,0 +1,416 @@
+//! Events of the chioma contract (`contracts/chioma`).
+
+use stellar_xdr::curr::ScVal;
+
+use crate::scval::{map_entries, map_field, Address, FromScVal};
+
+contract_enum! {
+    /// Multi-sig action kinds.
+    pub enum ActionType {
+        Pause,
+        Unpause,
+        UpdateConfig,
+        UpdateRate,
+        AddAdmin,
+        RemoveAdmin,
+        UpdateRequiredSignatures,
+        EmergencyAction,
+        SetRateLimit,
+        AddToken,
+        RemoveToken,
+        SetActionThreshold,
+        SetAdminWeight,
+        GrantRole,
+        RevokeRole,
+        SetGuardian,
+        SetAuditLogCapacity,
+    }
+}
+
+contract_enum! {
+    pub enum Role {
+        Pauser,
+        Treasurer,
+        RateSetter,
+        TokenManager,
+        Arbitrator,
+        Upgrader,
+        RateLimitAdmin,
+    }
+}
+
+contract_enum! {
+    pub enum PauseModule {
+        Agreements,
+        Payments,
+        Escrow,
+        Tokens,
+        Interest,
+        Royalties,
+    }
+}
+
+contract_enum! {
+    pub enum RateLimitReason {
+        BlockLimitExceeded,
+        DailyLimitExceeded,
+        CooldownNotMet,
+        WindowLimitExceeded,
+    }
+}
+
+contract_enum! {
+    pub enum VersionStatus {
+        Active,
+        Deprecated,
+        Archived,
+    }
+}
+
+/// A version record, as carried by `storage_migrated`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub label: String,
+    pub status: VersionStatus,
+    pub hash: Vec<u8>,
+    pub updated_at: u64,
+}
+
+impl FromScVal for ContractVersion {
+    const EXPECTED: &'static str = "ContractVersion";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        let entries = map_entries(val)?;
+        Some(Self {
+            major: map_field(entries, "major")?,
+            minor: map_field(entries, "minor")?,
+            patch: map_field(entries, "patch")?,
+            label: map_field(entries, "label")?,
+            status: map_field(entries, "status")?,
+            hash: map_field(entries, "hash")?,
+            updated_at: map_field(entries, "updated_at")?,
+        })
+    }
+}
+
+contract_events! {
+    pub enum Event {
+        ContractInitialized("initialized") {
+            #[topic] admin: Address,
+            fee_bps: u32,
+            fee_collector: Address,
+            paused: bool,
+        },
+        AgreementCreated("agreement_created") {
+            #[topic] user: Address,
+            #[topic] admin: Address,
+            agreement_id: String,
+            monthly_rent: i128,
+            security_deposit: i128,
+            start_date: u64,
+            end_date: u64,
+            agent: Option<Address>,
+        },
+        AgreementSigned("agreement_signed") {
+            #[topic] user: Address,
+            #[topic] admin: Address,
+            agreement_id: String,
+            signed_at: u64,
+        },
+        AgreementSubmitted("agreement_submitted") {
+            #[topic] admin: Address,
+            #[topic] user: Address,
+            agreement_id: String,
+        },
+        AgreementCancelled("agreement_cancelled") {
+            #[topic] admin: Address,
+            #[topic] user: Address,
+            agreement_id: String,
+        },
+        AgreementApproved("agreement_approved") {
+            #[topic] approver: Address,
+            agreement_id: String,
+        },
+        ConfigUpdated("config_updated") {
+            #[topic] admin: Address,
+            old_fee_bps: u32,
+            new_fee_bps: u32,
+            old_fee_collector: Address,
+            new_fee_collector: Address,
+            old_paused: bool,
+            new_paused: bool,
+        },
+        Paused("paused") {
+            #[topic] paused_by: Address,
+            reason: String,
+        },
+        Unpaused("unpaused") {
+            #[topic] unpaused_by: Address,
+        },
+        TokenAdded("token_added") {
+            #[topic] token: Address,
+            symbol: String,
+        },
+        TokenRemoved("token_removed") {
+            #[topic] token: Address,
+        },
+        ExchangeRateUpdated("exchange_rate_updated") {
+            #[topic] from_token: Address,
+            #[topic] to_token: Address,
+            rate: i128,
+        },
+        PaymentMadeWithToken("payment_made_with_token") {
+            #[topic] agreement_id: String,
+            #[topic] token: Address,
+            amount: i128,
+        },
+        EscrowReleasedWithToken("escrow_released_with_token") {
+            #[topic] escrow_id: String,
+            #[topic] token: Address,
+            amount: i128,
+        },
+        InterestConfigSet("interest_config_set") {
+            #[topic] agreement_id: String,
+            annual_rate: u32,
+        },
+        InterestAccrued("interest_accrued") {
+            #[topic] escrow_id: String,
+            amount: i128,
+            total_accrued: i128,
+        },
+        DepositRateScheduled("deposit_rate_scheduled") {
+            #[topic] agreement_id: String,
+            effective_from: u64,
+            annual_rate: u32,
+        },
+        StatutoryRatePublished("statutory_rate_published") {
+            effective_from: u64,
+            annual_rate: u32,
+        },
+        YieldVaultSet("yield_vault_set") {
+            #[topic] token: Address,
+            vault: Address,
+        },
+        DepositRoutedToVault("deposit_routed_to_vault") {
+            #[topic] escrow_id: String,
+            vault: Address,
+            amount: i128,
+            shares: i128,
+        },
+        DepositWithdrawnFromVault("deposit_withdrawn_from_vault") {
+            #[topic] escrow_id: String,
+            vault: Address,
+            amount: i128,
+        },
+        InterestDistributed("interest_distributed") {
+            #[topic] escrow_id: String,
+            user_share: i128,
+            admin_share: i128,
+            platform_share: i128,
+            compounded: i128,
+        },
+        RoyaltySet("royalty_set") {
+            #[topic] token_id: String,
+            #[topic] recipient: Address,
+            percentage: u32,
+        },
+        RoyaltyPaid("royalty_paid") {
+            #[topic] token_id: String,
+            #[topic] recipient: Address,
+            amount: i128,
+        },
+        PositionListed("position_listed") {
+            #[topic] agreement_id: String,
+            #[topic] seller: Address,
+            listing_id: u32,
+            ask_price: i128,
+            expires_at: u64,
+        },
+        ListingCancelled("listing_cancelled") {
+            #[topic] agreement_id: String,
+            listing_id: u32,
+        },
+        BidPlaced("bid_placed") {
+            #[topic] agreement_id: String,
+            #[topic] bidder: Address,
+            bid_id: u32,
+            amount: i128,
+        },
+        BidWithdrawn("bid_withdrawn") {
+            #[topic] agreement_id: String,
+            #[topic] bidder: Address,
+            bid_id: u32,
+            amount: i128,
+        },
+        PositionSold("position_sold") {
+            #[topic] agreement_id: String,
+            #[topic] tenant: Address,
+            seller: Address,
+            buyer: Address,
+            price: i128,
+        },
+        RateLimitExceeded("rate_limit_exceeded") {
+            #[topic] user: Address,
+            function_name: String,
+            reason: RateLimitReason,
+            timestamp: u64,
+        },
+        RateLimitConfigUpdated("rate_limit_config_updated") {
+            max_calls_per_block: u32,
+            max_calls_per_user_per_day: u32,
+            cooldown_blocks: u32,
+        },
+        FunctionRateLimitSet("fn_rate_limit_set") {
+            #[topic] function_name: String,
+            max_calls_per_block: u32,
+            max_calls_per_user_per_day: u32,
+            cooldown_blocks: u32,
+        },
+        FunctionRateLimitRemoved("fn_rate_limit_removed") {
+            #[topic] function_name: String,
+        },
+        RateLimitExemptionUpdated("rate_limit_exempt") {
+            #[topic] account: Address,
+            exempt: bool,
+        },
+        MultiSigInitialized("multisig_initialized") {
+            admins: u32,
+            required_signatures: u32,
+        },
+        ActionProposed("action_proposed") {
+            #[topic] proposal_id: String,
+            #[topic] proposer: Address,
+            action_type: ActionType,
+        },
+        ActionApproved("action_approved") {
+            #[topic] proposal_id: String,
+            #[topic] approver: Address,
+            approval_count: u32,
+            approval_weight: u32,
+        },
+        ActionExecuted("action_executed") {
+            #[topic] proposal_id: String,
+            action_type: ActionType,
+        },
+        ActionApplied("action_applied") {
+            #[topic] proposal_id: String,
+            action_type: ActionType,
+            before: Vec<u8>,
+            after: Vec<u8>,
+        },
+        ActionRejected("action_rejected") {
+            #[topic] proposal_id: String,
+        },
+        AdminAdded("admin_added") {
+            #[topic] admin: Address,
+            total_admins: u32,
+        },
+        AdminRemoved("admin_removed") {
+            #[topic] admin: Address,
+            total_admins: u32,
+        },
+        RequiredSignaturesUpdated("signatures_updated") {
+            old_required: u32,
+            new_required: u32,
+        },
+        ActionThresholdUpdated("threshold_updated") {
+            action_type: ActionType,
+            old_threshold: u32,
+            new_threshold: u32,
+        },
+        AdminWeightUpdated("weight_updated") {
+            #[topic] admin: Address,
+            old_weight: u32,
+            new_weight: u32,
+        },
+        RoleGranted("role_granted") {
+            #[topic] account: Address,
+            role: Role,
+        },
+        RoleRevoked("role_revoked") {
+            #[topic] account: Address,
+            role: Role,
+        },
+        ModulePaused("module_paused") {
+            #[topic] paused_by: Address,
+            module: PauseModule,
+            reason: String,
+            auto_unpause_at: Option<u64>,
+        },
+        ModuleUnpaused("module_unpaused") {
+            #[topic] unpaused_by: Address,
+            module: PauseModule,
+        },
+        GuardianUpdated("guardian_updated") {
+            old_guardian: Option<Address>,
+            new_guardian: Option<Address>,
+        },
+        TimelockActionQueued("timelock_queued") {
+            #[topic] action_id: String,
+            eta: u64,
+        },
+        TimelockActionExecuted("timelock_executed") {
+            #[topic] action_id: String,
+        },
+        TimelockActionCancelled("timelock_cancelled") {
+            #[topic] action_id: String,
+        },
+        TimelockStatusChanged("timelock_status") {
+            enabled: bool,
+        },
+        VersionUpdated("version_updated") {
+            major: u32,
+            minor: u32,
+            patch: u32,
+        },
+        ExtensionProposed("extension_proposed") {
+            #[topic] extension_id: String,
+            agreement_id: String,
+            new_end_date: u64,
+        },
+        ExtensionAccepted("extension_accepted") {
+            #[topic] extension_id: String,
+        },
+        ExtensionRejected("extension_rejected") {
+            #[topic] extension_id: String,
+            reason: String,
+        },
+        ExtensionActivated("extension_activated") {
+            #[topic] extension_id: String,
+        },
+        ExtensionCancelled("extension_cancelled") {
+            #[topic] extension_id: String,
+            reason: String,
+        },
+        UpgradeProposed("upgrade_proposed") {
+            #[topic] proposal_id: String,
+            eta: u64,
+        },
+        UpgradeApproved("upgrade_approved") {
+            #[topic] proposal_id: String,
+            approvals: u32,
+        },
+        UpgradeExecuted("upgrade_executed") {
+            #[topic] proposal_id: String,
+            wasm_hash: [u8; 32],
+        },
+        StorageMigrated("storage_migrated") {
+            version: ContractVersion,
+            from_version: u32,
+            to_version: u32,
+        },
+        DirectorySet("directory_set") {
+            #[topic] admin: Address,
+            directory: Address,
+        },
+        DepositEscrowed("deposit_escrowed") {
+            #[topic] user: Address,
+            agreement_id: String,
+            escrow_id: [u8; 32],
+            security_deposit: i128,
+        },
+    }
+}
@@ -0,0 +1,127 @@
+//! Events of the dispute resolution contract (`contracts/dispute_resolution`).
+
+use stellar_xdr::curr::ScVal;
+
+use crate::scval::Address;
+
+contract_enum! {
+    pub enum DisputeOutcome {
+        FavorLandlord,
+        FavorTenant,
+    }
+}
+
+contract_events! {
+    pub enum Event {
+        ContractInitialized("initialized") {
+            #[topic] admin: Address,
+            initialized_at: u64,
+        },
+        ArbiterAdded("arbiter_added") {
+            #[topic] arbiter: Address,
+            #[topic] admin: Address,
+            added_at: u64,
+        },
+        DisputeRaised("dispute_raised") {
+            #[topic] agreement_id: String,
+            details_hash: String,
+            raised_at: u64,
+        },
+        VoteCast("vote_cast") {
+            #[topic] agreement_id: String,
+            #[topic] arbiter: Address,
+            favor_landlord: bool,
+            voted_at: u64,
+        },
+        DisputeResolved("dispute_resolved") {
+            #[topic] agreement_id: String,
+            result: DisputeOutcome,
+            voted_for_landlord_count: u32,
+            voted_for_tenant_count: u32,
+            resolved_at: u64,
+        },
+        DisputeTimeout("dispute_timeout") {
+            #[topic] agreement_id: String,
+            timed_out_at: u64,
+        },
+        TimeoutConfigUpdated("timeout_config_updated") {
+            #[topic] admin: Address,
+            escrow_timeout_days: u64,
+            dispute_timeout_days: u64,
+            payment_timeout_days: u64,
+            updated_at: u64,
+        },
+        RateLimitConfigUpdated("rate_limit_config_updated") {
+            max_calls_per_block: u32,
+            max_calls_per_user_per_day: u32,
+            cooldown_blocks: u32,
+        },
+        DirectorySet("directory_set") {
+            #[topic] admin: Address,
+            directory: Address,
+            set_at: u64,
+        },
+        ArbiterStatsSet("arbiter_stats_set") {
+            #[topic] arbiter: Address,
+            #[topic] admin: Address,
+            resolved_count: u32,
+            average_score: u32,
+            updated_at: u64,
+        },
+        AppealCreated("appeal_created") {
+            #[topic] appeal_id: String,
+            #[topic] dispute_id: String,
+            appellant: Address,
+            created_at: u64,
+        },
+        AppealVoted("appeal_voted") {
+            #[topic] appeal_id: String,
+            #[topic] arbiter: Address,
+            voted_at: u64,
+        },
+        AppealResolved("appeal_resolved") {
+            #[topic] appeal_id: String,
+            outcome: DisputeOutcome,
+            resolved_at: u64,
+        },
+        AppealCancelled("appeal_cancelled") {
+            #[topic] appeal_id: String,
+            cancelled_at: u64,
+        },
+        WeightedVoteCast("weighted_vote_cast") {
+            #[topic] dispute_id: String,
+            #[topic] arbiter: Address,
+            weight: u32,
+            voted_at: u64,
+        },
+        DisputeResolvedByWeight("dispute_resolved_by_weight") {
+            #[topic] dispute_id: String,
+            outcome: DisputeOutcome,
+            total_weight: u32,
+            resolved_at: u64,
+        },
+        UpgradeProposed("upgrade_proposed") {
+            #[topic] proposal_id: String,
+            proposer: Address,
+            eta: u64,
+            created_at: u64,
+        },
+        UpgradeApproved("upgrade_approved") {
+            #[topic] proposal_id: String,
+            approver: Address,
+            approval_count: u32,
+        },
+        UpgradeExecuted("upgrade_executed") {
+            #[topic] proposal_id: String,
+            executor: Address,
+            executed_at: u64,
+            wasm_hash: [u8; 32],
+            version: u32,
+        },
+        StorageMigrated("storage_migrated") {
+            contract_version: u32,
+            from_version: u32,
+            to_version: u32,
+        },
+    }
+}
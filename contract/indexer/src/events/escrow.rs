@@ -0,0 +1,173 @@
+//! Events of the escrow contract (`contracts/escrow`).
+
+use stellar_xdr::curr::ScVal;
+
+use crate::scval::Address;
+
+contract_enum! {
+    pub enum EscrowStatus {
+        Pending = 0,
+        Funded = 1,
+        Released = 2,
+        Refunded = 3,
+        Disputed = 4,
+        PartiallyFunded = 5,
+    }
+}
+
+contract_events! {
+    pub enum Event {
+        EscrowCreated("escrow_created") {
+            #[topic] escrow_id: [u8; 32],
+            agreement_id: String,
+            depositor: Address,
+            beneficiary: Address,
+            arbiter: Address,
+            platform_governance: Address,
+            agent_referral: Address,
+            amount: i128,
+            token: Address,
+            created_at: u64,
+        },
+        EscrowFunded("escrow_funded") {
+            #[topic] escrow_id: [u8; 32],
+            #[topic] funder: Address,
+            amount: i128,
+            funded_at: u64,
+        },
+        ReleaseApproved("release_approved") {
+            #[topic] escrow_id: [u8; 32],
+            #[topic] approver: Address,
+            approval_count: u32,
+            approved_at: u64,
+        },
+        EscrowReleased("escrow_released") {
+            #[topic] escrow_id: [u8; 32],
+            landlord_amount: i128,
+            tenant_amount: i128,
+            platform_fee: i128,
+            agent_fee: i128,
+            released_at: u64,
+        },
+        EscrowTimeout("escrow_timeout") {
+            #[topic] escrow_id: [u8; 32],
+            timed_out_at: u64,
+        },
+        PartialRelease("partial_release") {
+            #[topic] escrow_id: [u8; 32],
+            #[topic] recipient: Address,
+            amount: i128,
+            released_at: u64,
+        },
+        DamageDeduction("damage_deduction") {
+            #[topic] escrow_id: [u8; 32],
+            damage_amount: i128,
+            refund_amount: i128,
+            deducted_at: u64,
+        },
+        EscrowFrozen("escrow_frozen") {
+            #[topic] escrow_id: [u8; 32],
+            #[topic] caller: Address,
+            reason: String,
+            frozen_at: u64,
+        },
+        EscrowUnfrozen("escrow_unfrozen") {
+            #[topic] escrow_id: [u8; 32],
+            #[topic] caller: Address,
+            unfrozen_at: u64,
+        },
+        RentReleased("rent_released") {
+            #[topic] escrow_id: [u8; 32],
+            landlord_amount: i128,
+            platform_fee: i128,
+            agent_fee: i128,
+            released_at: u64,
+        },
+        SafetyDepositWithdrawn("safety_deposit_withdrawn") {
+            #[topic] escrow_id: [u8; 32],
+            amount: i128,
+            withdrawn_at: u64,
+        },
+        DisputeTimeout("dispute_timeout") {
+            #[topic] escrow_id: [u8; 32],
+            timed_out_at: u64,
+        },
+        TimeoutConfigUpdated("timeout_config_updated") {
+            #[topic] admin: Address,
+            escrow_timeout_days: u64,
+            dispute_timeout_days: u64,
+            payment_timeout_days: u64,
+            updated_at: u64,
+        },
+        AdminInitialized("admin_initialized") {
+            #[topic] admin: Address,
+            initialized_at: u64,
+        },
+        AdminUpdated("admin_updated") {
+            #[topic] old_admin: Address,
+            #[topic] new_admin: Address,
+            updated_at: u64,
+        },
+        EscrowStatusUpdated("escrow_status_updated") {
+            #[topic] escrow_id: [u8; 32],
+            old_status: EscrowStatus,
+            new_status: EscrowStatus,
+        },
+        UpgradeProposed("upgrade_proposed") {
+            #[topic] proposal_id: String,
+            proposer: Address,
+            eta: u64,
+            created_at: u64,
+        },
+        UpgradeApproved("upgrade_approved") {
+            #[topic] proposal_id: String,
+            approver: Address,
+            approval_count: u32,
+        },
+        UpgradeExecuted("upgrade_executed") {
+            #[topic] proposal_id: String,
+            executor: Address,
+            executed_at: u64,
+            wasm_hash: [u8; 32],
+            version: u32,
+        },
+        StorageMigrated("storage_migrated") {
+            contract_version: u32,
+            from_version: u32,
+            to_version: u32,
+        },
+        RateLimitConfigUpdated("rate_limit_config_updated") {
+            max_calls_per_block: u32,
+            max_calls_per_user_per_day: u32,
+            cooldown_blocks: u32,
+        },
+        DirectorySet("directory_set") {
+            #[topic] admin: Address,
+            directory: Address,
+            set_at: u64,
+        },
+        InstallmentsScheduled("installments_scheduled") {
+            #[topic] escrow_id: [u8; 32],
+            installments: u32,
+            deadline: u64,
+        },
+        InstallmentPaid("installment_paid") {
+            #[topic] escrow_id: [u8; 32],
+            #[topic] depositor: Address,
+            amount: i128,
+            funded: i128,
+            paid_at: u64,
+        },
+        InstallmentsRefunded("installments_refunded") {
+            #[topic] escrow_id: [u8; 32],
+            amount: i128,
+            refunded_at: u64,
+        },
+        BeneficiaryTransferred("beneficiary_transferred") {
+            #[topic] escrow_id: [u8; 32],
+            previous: Address,
+            beneficiary: Address,
+            transferred_at: u64,
+        },
+    }
+}
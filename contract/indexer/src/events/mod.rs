@@ -0,0 +1,277 @@
+//! Typed forms of the events every protocol contract emits.
+//!
+//! Each submodule mirrors one contract's `src/events.rs`: a variant per
+//! `#[contractevent]` struct, named after it, with the topic fields first in
+//! declaration order followed by the data fields. A contract event is
+//! published as `[name, topics...]` with its data fields in a map keyed by
+//! field name, which is what [`Event::decode`] reads back.
+
+use std::{fmt, str::FromStr};
+
+use stellar_xdr::curr::ScVal;
+
+use crate::error::DecodeError;
+
+/// Declare a contract's event enum and its decoder.
+macro_rules! contract_events {
+    (
+        $(#[$enum_meta:meta])*
+        pub enum $Enum:ident {
+            $(
+                $(#[$meta:meta])*
+                $Variant:ident($name:literal) {
+                    $( #[topic] $topic:ident: $TopicTy:ty, )*
+                    $( $field:ident: $FieldTy:ty, )*
+                },
+            )*
+        }
+    ) => {
+        $(#[$enum_meta])*
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum $Enum {
+            $(
+                $(#[$meta])*
+                $Variant {
+                    $( $topic: $TopicTy, )*
+                    $( $field: $FieldTy, )*
+                },
+            )*
+        }
+
+        impl $Enum {
+            /// The event name, i.e. its first topic.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $( Self::$Variant { .. } => $name, )*
+                }
+            }
+
+            /// Decode the event called `name` from the topics that follow
+            /// the name and its data, or `None` if no such event exists.
+            pub(crate) fn decode(
+                name: &str,
+                topics: &[ScVal],
+                data: &ScVal,
+            ) -> Result<Option<Self>, $crate::error::DecodeError> {
+                match name {
+                    $(
+                        $name => {
+                            #[allow(unused_mut)]
+                            let mut topics = $crate::scval::Topics::new($name, topics);
+                            let data = $crate::scval::Data::new($name, data)?;
+                            let event = Self::$Variant {
+                                $( $topic: topics.next(stringify!($topic))?, )*
+                                $( $field: data.field(stringify!($field))?, )*
+                            };
+                            let _ = data;
+                            topics.finish()?;
+                            Ok(Some(event))
+                        }
+                    )*
+                    _ => Ok(None),
+                }
+            }
+        }
+    };
+}
+
+/// Declare a contract enum used in event fields. Unit enums are encoded as
+/// their variant name; enums with explicit discriminants as a `u32`.
+macro_rules! contract_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $Enum:ident { $( $Variant:ident = $value:literal, )* }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum $Enum {
+            $( $Variant = $value, )*
+        }
+
+        impl $Enum {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $( Self::$Variant => stringify!($Variant), )*
+                }
+            }
+        }
+
+        impl $crate::scval::FromScVal for $Enum {
+            const EXPECTED: &'static str = stringify!($Enum);
+
+            fn from_sc_val(val: &ScVal) -> Option<Self> {
+                match val {
+                    $( ScVal::U32($value) => Some(Self::$Variant), )*
+                    _ => None,
+                }
+            }
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        pub enum $Enum:ident { $( $Variant:ident, )* }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum $Enum {
+            $( $Variant, )*
+        }
+
+        impl $Enum {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $( Self::$Variant => stringify!($Variant), )*
+                }
+            }
+        }
+
+        impl $crate::scval::FromScVal for $Enum {
+            const EXPECTED: &'static str = stringify!($Enum);
+
+            fn from_sc_val(val: &ScVal) -> Option<Self> {
+                let name = $crate::scval::variant_name(val)?;
+                $(
+                    if name == stringify!($Variant) {
+                        return Some(Self::$Variant);
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+
+pub mod agent_registry;
+pub mod chioma;
+pub mod dispute_resolution;
+pub mod escrow;
+pub mod payment;
+pub mod property_registry;
+pub mod rent_obligation;
+pub mod user_profile;
+
+/// A protocol contract whose events the indexer understands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Contract {
+    AgentRegistry,
+    Chioma,
+    DisputeResolution,
+    Escrow,
+    Payment,
+    PropertyRegistry,
+    RentObligation,
+    UserProfile,
+}
+
+impl Contract {
+    pub const ALL: [Contract; 8] = [
+        Contract::AgentRegistry,
+        Contract::Chioma,
+        Contract::DisputeResolution,
+        Contract::Escrow,
+        Contract::Payment,
+        Contract::PropertyRegistry,
+        Contract::RentObligation,
+        Contract::UserProfile,
+    ];
+
+    /// The contract's directory name under `contracts/`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Contract::AgentRegistry => "agent_registry",
+            Contract::Chioma => "chioma",
+            Contract::DisputeResolution => "dispute_resolution",
+            Contract::Escrow => "escrow",
+            Contract::Payment => "payment",
+            Contract::PropertyRegistry => "property_registry",
+            Contract::RentObligation => "rent_obligation",
+            Contract::UserProfile => "user_profile",
+        }
+    }
+}
+
+impl fmt::Display for Contract {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Contract {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Contract::ALL
+            .into_iter()
+            .find(|contract| contract.name() == name)
+            .ok_or_else(|| format!("unknown contract `{name}`"))
+    }
+}
+
+/// An event decoded from any protocol contract.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    AgentRegistry(agent_registry::Event),
+    Chioma(chioma::Event),
+    DisputeResolution(dispute_resolution::Event),
+    Escrow(escrow::Event),
+    Payment(payment::Event),
+    PropertyRegistry(property_registry::Event),
+    RentObligation(rent_obligation::Event),
+    UserProfile(user_profile::Event),
+}
+
+impl Event {
+    /// Decode an event `contract` published with `topics` and `data`.
+    pub fn decode(contract: Contract, topics: &[ScVal], data: &ScVal) -> Result<Self, DecodeError> {
+        let (name, topics) = match topics.split_first() {
+            Some((ScVal::Symbol(name), rest)) => (name.to_utf8_string_lossy(), rest),
+            _ => return Err(DecodeError::MissingName),
+        };
+        let event = match contract {
+            Contract::AgentRegistry => {
+                agent_registry::Event::decode(&name, topics, data)?.map(Event::AgentRegistry)
+            }
+            Contract::Chioma => chioma::Event::decode(&name, topics, data)?.map(Event::Chioma),
+            Contract::DisputeResolution => dispute_resolution::Event::decode(&name, topics, data)?
+                .map(Event::DisputeResolution),
+            Contract::Escrow => escrow::Event::decode(&name, topics, data)?.map(Event::Escrow),
+            Contract::Payment => payment::Event::decode(&name, topics, data)?.map(Event::Payment),
+            Contract::PropertyRegistry => {
+                property_registry::Event::decode(&name, topics, data)?.map(Event::PropertyRegistry)
+            }
+            Contract::RentObligation => {
+                rent_obligation::Event::decode(&name, topics, data)?.map(Event::RentObligation)
+            }
+            Contract::UserProfile => {
+                user_profile::Event::decode(&name, topics, data)?.map(Event::UserProfile)
+            }
+        };
+        event.ok_or(DecodeError::UnknownEvent { contract, name })
+    }
+
+    pub fn contract(&self) -> Contract {
+        match self {
+            Event::AgentRegistry(_) => Contract::AgentRegistry,
+            Event::Chioma(_) => Contract::Chioma,
+            Event::DisputeResolution(_) => Contract::DisputeResolution,
+            Event::Escrow(_) => Contract::Escrow,
+            Event::Payment(_) => Contract::Payment,
+            Event::PropertyRegistry(_) => Contract::PropertyRegistry,
+            Event::RentObligation(_) => Contract::RentObligation,
+            Event::UserProfile(_) => Contract::UserProfile,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::AgentRegistry(event) => event.name(),
+            Event::Chioma(event) => event.name(),
+            Event::DisputeResolution(event) => event.name(),
+            Event::Escrow(event) => event.name(),
+            Event::Payment(event) => event.name(),
+            Event::PropertyRegistry(event) => event.name(),
+            Event::RentObligation(event) => event.name(),
+            Event::UserProfile(event) => event.name(),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Events of the payment contract (`contracts/payment`).
+
+use stellar_xdr::curr::ScVal;
+
+use crate::scval::Address;
+
+contract_events! {
+    pub enum Event {
+        RecurringPaymentCreated("recurring_payment_created") {
+            #[topic] agreement_id: String,
+            recurring_id: String,
+            amount: i128,
+            created_at: u64,
+        },
+        RecurringPaymentExecuted("recurring_payment_executed") {
+            #[topic] recurring_id: String,
+            executed_at: u64,
+        },
+        RecurringPaymentPaused("recurring_payment_paused") {
+            #[topic] recurring_id: String,
+            paused_at: u64,
+        },
+        RecurringPaymentResumed("recurring_payment_resumed") {
+            #[topic] recurring_id: String,
+            resumed_at: u64,
+        },
+        RecurringPaymentCancelled("recurring_payment_cancelled") {
+            #[topic] recurring_id: String,
+            cancelled_at: u64,
+        },
+        RecurringPaymentFailed("recurring_payment_failed") {
+            #[topic] recurring_id: String,
+            failed_at: u64,
+        },
+        LateFeeApplied("late_fee_applied") {
+            #[topic] payment_id: String,
+            late_fee: i128,
+            days_over_grace: u32,
+            applied_at: u64,
+        },
+        LateFeeWaived("late_fee_waived") {
+            #[topic] payment_id: String,
+            reason: String,
+            waived_at: u64,
+        },
+        LateFeeConfigSet("late_fee_config_set") {
+            #[topic] agreement_id: String,
+            late_fee_percentage: u32,
+            grace_period_days: u32,
+            set_at: u64,
+        },
+        RentEscalationConfigSet("rent_escalation_config_set") {
+            #[topic] agreement_id: String,
+            annual_rate_bps: u32,
+            set_at: u64,
+        },
+        RentPaid("rent_paid") {
+            #[topic] agreement_id: String,
+            #[topic] from: Address,
+            landlord: Address,
+            token: Address,
+            payment_amount: i128,
+            landlord_amount: i128,
+            platform_amount: i128,
+            paid_at: u64,
+        },
+        PlatformFeeCollectorUpdated("platform_fee_collector_updated") {
+            #[topic] collector: Address,
+            updated_at: u64,
+        },
+        RateLimitConfigUpdated("rate_limit_config_updated") {
+            max_calls_per_block: u32,
+            max_calls_per_user_per_day: u32,
+            cooldown_blocks: u32,
+        },
+        AdminInitialized("admin_initialized") {
+            #[topic] admin: Address,
+            initialized_at: u64,
+        },
+        UpgradeProposed("upgrade_proposed") {
+            #[topic] proposal_id: String,
+            #[topic] proposer: Address,
+            eta: u64,
+            proposed_at: u64,
+        },
+        UpgradeApproved("upgrade_approved") {
+            #[topic] proposal_id: String,
+            #[topic] approver: Address,
+            approval_count: u32,
+            approved_at: u64,
+        },
+        UpgradeExecuted("upgrade_executed") {
+            #[topic] proposal_id: String,
+            #[topic] executor: Address,
+            executed_at: u64,
+            wasm_hash: [u8; 32],
+            version: u32,
+        },
+        StorageMigrated("storage_migrated") {
+            contract_version: u32,
+            from_version: u32,
+            to_version: u32,
+        },
+    }
+}
@@ -0,0 +1,77 @@
+//! Events of the property registry contract (`contracts/property_registry`).
+
+use stellar_xdr::curr::ScVal;
+
+use crate::scval::Address;
+
+contract_events! {
+    pub enum Event {
+        ContractInitialized("initialized") {
+            #[topic] admin: Address,
+            initialized_at: u64,
+        },
+        PropertyRegistered("property_registered") {
+            #[topic] property_id: String,
+            #[topic] owner: Address,
+            registered_at: u64,
+        },
+        PropertyVerified("property_verified") {
+            #[topic] property_id: String,
+            #[topic] admin: Address,
+            verified_at: u64,
+        },
+        PropertyUpdated("property_updated") {
+            #[topic] property_id: String,
+            #[topic] updater: Address,
+            updated_at: u64,
+        },
+        AdminUpdated("admin_updated") {
+            #[topic] old_admin: Address,
+            #[topic] new_admin: Address,
+            updated_at: u64,
+        },
+        MetadataUpdated("metadata_updated") {
+            #[topic] property_id: String,
+            #[topic] owner: Address,
+            data_hash: Vec<u8>,
+        },
+        OwnershipTransferred("ownership_transferred") {
+            #[topic] property_id: String,
+            #[topic] new_owner: Address,
+            transferred_at: u64,
+        },
+        UpgradeProposed("upgrade_proposed") {
+            #[topic] proposal_id: String,
+            proposer: Address,
+            eta: u64,
+            created_at: u64,
+        },
+        UpgradeApproved("upgrade_approved") {
+            #[topic] proposal_id: String,
+            approver: Address,
+            approval_count: u32,
+        },
+        UpgradeExecuted("upgrade_executed") {
+            #[topic] proposal_id: String,
+            executor: Address,
+            executed_at: u64,
+            wasm_hash: [u8; 32],
+            version: u32,
+        },
+        StorageMigrated("storage_migrated") {
+            contract_version: u32,
+            from_version: u32,
+            to_version: u32,
+        },
+        PropertyTransferred("property_transferred") {
+            #[topic] previous_landlord: Address,
+            #[topic] property_id: String,
+            new_landlord: Address,
+        },
+        PropertyMetadataUpdated("property_metadata_updated") {
+            #[topic] landlord: Address,
+            #[topic] property_id: String,
+            new_metadata_hash: String,
+        },
+    }
+}
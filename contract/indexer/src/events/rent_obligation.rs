@@ -0,0 +1,55 @@
+//! Events of the rent obligation contract (`contracts/rent_obligation`).
+
+use stellar_xdr::curr::ScVal;
+
+use crate::scval::Address;
+
+contract_events! {
+    pub enum Event {
+        ContractInitialized("initialized") {
+            initialized_at: u64,
+        },
+        ObligationMinted("minted") {
+            #[topic] landlord: Address,
+            agreement_id: String,
+            minted_at: u64,
+        },
+        ObligationTransferred("transferred") {
+            #[topic] from: Address,
+            #[topic] to: Address,
+            agreement_id: String,
+        },
+        ObligationBurned("burned") {
+            #[topic] owner: Address,
+            token_id: String,
+            reason: String,
+        },
+        AdminInitialized("admin_initialized") {
+            #[topic] admin: Address,
+            initialized_at: u64,
+        },
+        UpgradeProposed("upgrade_proposed") {
+            #[topic] proposal_id: String,
+            proposer: Address,
+            eta: u64,
+            created_at: u64,
+        },
+        UpgradeApproved("upgrade_approved") {
+            #[topic] proposal_id: String,
+            approver: Address,
+            approval_count: u32,
+        },
+        UpgradeExecuted("upgrade_executed") {
+            #[topic] proposal_id: String,
+            executor: Address,
+            executed_at: u64,
+            wasm_hash: [u8; 32],
+            version: u32,
+        },
+        StorageMigrated("storage_migrated") {
+            contract_version: u32,
+            from_version: u32,
+            to_version: u32,
+        },
+    }
+}
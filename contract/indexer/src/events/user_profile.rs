@@ -0,0 +1,75 @@
+//! Events of the user profile contract (`contracts/user_profile`).
+
+use stellar_xdr::curr::ScVal;
+
+use crate::scval::Address;
+
+contract_enum! {
+    pub enum AccountType {
+        Tenant = 0,
+        Landlord = 1,
+        Agent = 2,
+    }
+}
+
+contract_events! {
+    pub enum Event {
+        Initialized("initialized") {
+            #[topic] admin: Address,
+            initialized_at: u64,
+        },
+        ProfileCreated("profile_created") {
+            #[topic] account_id: Address,
+            account_type: AccountType,
+            data_hash: Vec<u8>,
+        },
+        ProfileUpdated("profile_updated") {
+            #[topic] account_id: Address,
+            account_type: AccountType,
+            data_hash: Vec<u8>,
+        },
+        ProfileVerified("profile_verified") {
+            #[topic] account_id: Address,
+            verified_by: Address,
+            verified_at: u64,
+        },
+        ProfileUnverified("profile_unverified") {
+            #[topic] account_id: Address,
+            unverified_by: Address,
+            reason: String,
+        },
+        ProfileDeleted("profile_deleted") {
+            #[topic] account_id: Address,
+            deleted_by: Address,
+            deleted_at: u64,
+        },
+        PlatformAdminUpdated("platform_admin_updated") {
+            #[topic] old_admin: Address,
+            #[topic] new_admin: Address,
+            updated_at: u64,
+        },
+        UpgradeProposed("upgrade_proposed") {
+            #[topic] proposal_id: String,
+            proposer: Address,
+            eta: u64,
+            created_at: u64,
+        },
+        UpgradeApproved("upgrade_approved") {
+            #[topic] proposal_id: String,
+            approver: Address,
+            approval_count: u32,
+        },
+        UpgradeExecuted("upgrade_executed") {
+            #[topic] proposal_id: String,
+            executor: Address,
+            executed_at: u64,
+            wasm_hash: [u8; 32],
+            version: u32,
+        },
+        StorageMigrated("storage_migrated") {
+            contract_version: u32,
+            from_version: u32,
+            to_version: u32,
+        },
+    }
+}
@@ -0,0 +1,39 @@
+//! Off-chain indexer for the protocol contracts.
+//!
+//! Contract events, as returned by the Stellar RPC `getEvents` method, are
+//! decoded into the typed enums of [`events`] and applied to a SQLite
+//! database holding the current agreements, payments, escrows, disputes,
+//! properties, agents and profiles (see `src/schema.sql`).
+//!
+//! ```text
+//! let mut deployment = Deployment::new();
+//! deployment.insert(Contract::Chioma, "CABC...");
+//! let events = parse_events(&page_json)?
+//!     .iter()
+//!     .filter_map(|raw| deployment.decode(raw).transpose())
+//!     .collect::<Result<Vec<_>, _>>()?;
+//! Store::open("chioma.db")?.apply(&events)?;
+//! ```
+//!
+//! Events are applied in order and at most once, keyed by their RPC event
+//! id, so pages can be re-fetched safely. The fixtures under `fixtures/` are
+//! recorded from the contracts themselves; regenerate them after changing an
+//! event with `INDEXER_RECORD=1 cargo test -p indexer`.
+
+pub mod error;
+pub mod events;
+pub mod scval;
+pub mod source;
+pub mod store;
+
+#[cfg(test)]
+mod tests_decode;
+#[cfg(test)]
+mod tests_fixtures;
+#[cfg(test)]
+mod tests_store;
+
+pub use error::{DecodeError, Error};
+pub use events::{Contract, Event};
+pub use source::{parse_events, Deployment, IndexedEvent, RpcEvent};
+pub use store::Store;
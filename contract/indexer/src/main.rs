@@ -0,0 +1,79 @@
+//! `chioma-indexer --db <PATH> --contract <NAME>=<ID>... <EVENTS.json>...`
+//!
+//! Applies saved `getEvents` pages to the database at `PATH`. Each
+//! `--contract` maps a deployed contract id to the protocol contract it
+//! runs, e.g. `--contract escrow=CABC...`; events of other contracts are
+//! skipped.
+
+use std::{env, fs, process::ExitCode};
+
+use indexer::{parse_events, Contract, Deployment, Error, Store};
+
+const USAGE: &str = "usage: chioma-indexer --db <PATH> --contract <NAME>=<ID>... <EVENTS.json>...";
+
+struct Args {
+    db: String,
+    deployment: Deployment,
+    files: Vec<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, Error> {
+    let mut db = None;
+    let mut deployment = Deployment::new();
+    let mut files = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--db" => db = args.next(),
+            "--contract" => {
+                let mapping = args.next().unwrap_or_default();
+                let (name, id) = mapping.split_once('=').ok_or_else(|| {
+                    Error::Config(format!("expected <NAME>=<ID>, got `{mapping}`"))
+                })?;
+                let contract: Contract = name.parse().map_err(Error::Config)?;
+                deployment.insert(contract, id);
+            }
+            _ if arg.starts_with("--") => {
+                return Err(Error::Config(format!("unknown option `{arg}`\n{USAGE}")))
+            }
+            _ => files.push(arg),
+        }
+    }
+    let db = db.ok_or_else(|| Error::Config(USAGE.to_string()))?;
+    Ok(Args {
+        db,
+        deployment,
+        files,
+    })
+}
+
+fn run() -> Result<(), Error> {
+    let args = parse_args(env::args().skip(1))?;
+    let mut store = Store::open(&args.db)?;
+    for file in &args.files {
+        let raw = parse_events(&fs::read_to_string(file)?)?;
+        let events = raw
+            .iter()
+            .filter_map(|event| args.deployment.decode(event).transpose())
+            .collect::<Result<Vec<_>, _>>()?;
+        let applied = store.apply(&events)?;
+        println!(
+            "{file}: {applied} applied, {} already indexed, {} skipped",
+            events.len() - applied,
+            raw.len() - events.len()
+        );
+    }
+    if let Some(cursor) = store.last_event_id()? {
+        println!("latest event: {cursor}");
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("chioma-indexer: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,112 @@
+-- Protocol state materialized from contract events. Amounts are i128 values
+-- stored as decimal text; hashes and escrow ids are lower-case hex.
+
+-- Every event applied, so a rerun over the same pages is a no-op.
+CREATE TABLE IF NOT EXISTS events (
+    id          TEXT PRIMARY KEY,
+    ledger      INTEGER NOT NULL,
+    contract    TEXT NOT NULL,
+    contract_id TEXT NOT NULL,
+    name        TEXT NOT NULL
+);
+
+-- chioma agreements; `landlord` is the agreement's admin, `tenant` its user.
+CREATE TABLE IF NOT EXISTS agreements (
+    agreement_id     TEXT PRIMARY KEY,
+    landlord         TEXT NOT NULL,
+    tenant           TEXT NOT NULL,
+    agent            TEXT,
+    monthly_rent     TEXT NOT NULL,
+    security_deposit TEXT NOT NULL,
+    start_date       INTEGER NOT NULL,
+    end_date         INTEGER NOT NULL,
+    status           TEXT NOT NULL,
+    signed_at        INTEGER,
+    total_paid       TEXT NOT NULL DEFAULT '0',
+    updated_ledger   INTEGER NOT NULL
+);
+
+-- chioma agreement extensions, needed to apply `extension_activated`.
+CREATE TABLE IF NOT EXISTS extensions (
+    extension_id TEXT PRIMARY KEY,
+    agreement_id TEXT NOT NULL,
+    new_end_date INTEGER NOT NULL,
+    status       TEXT NOT NULL
+);
+
+-- Rent paid through chioma or the payment contract.
+CREATE TABLE IF NOT EXISTS payments (
+    event_id        TEXT PRIMARY KEY REFERENCES events (id),
+    contract        TEXT NOT NULL,
+    agreement_id    TEXT NOT NULL,
+    payer           TEXT,
+    token           TEXT NOT NULL,
+    amount          TEXT NOT NULL,
+    landlord_amount TEXT,
+    platform_amount TEXT,
+    paid_at         INTEGER,
+    ledger          INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS payments_by_agreement ON payments (agreement_id);
+
+-- escrow contract escrows; `balance` is what the escrow still holds.
+CREATE TABLE IF NOT EXISTS escrows (
+    escrow_id      TEXT PRIMARY KEY,
+    agreement_id   TEXT NOT NULL,
+    depositor      TEXT NOT NULL,
+    beneficiary    TEXT NOT NULL,
+    arbiter        TEXT NOT NULL,
+    token          TEXT NOT NULL,
+    amount         TEXT NOT NULL,
+    balance        TEXT NOT NULL DEFAULT '0',
+    status         TEXT NOT NULL,
+    approvals      INTEGER NOT NULL DEFAULT 0,
+    frozen         INTEGER NOT NULL DEFAULT 0,
+    created_at     INTEGER NOT NULL,
+    updated_ledger INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS escrows_by_agreement ON escrows (agreement_id);
+
+-- dispute_resolution disputes, keyed by the disputed agreement.
+CREATE TABLE IF NOT EXISTS disputes (
+    agreement_id   TEXT PRIMARY KEY,
+    details_hash   TEXT NOT NULL,
+    raised_at      INTEGER NOT NULL,
+    votes_landlord INTEGER NOT NULL DEFAULT 0,
+    votes_tenant   INTEGER NOT NULL DEFAULT 0,
+    outcome        TEXT,
+    resolved_at    INTEGER,
+    timed_out      INTEGER NOT NULL DEFAULT 0
+);
+
+-- property_registry properties; the metadata hash is only known once it
+-- has been updated, as registration does not emit it.
+CREATE TABLE IF NOT EXISTS properties (
+    property_id    TEXT PRIMARY KEY,
+    owner          TEXT NOT NULL,
+    metadata_hash  TEXT,
+    verified       INTEGER NOT NULL DEFAULT 0,
+    registered_at  INTEGER NOT NULL,
+    updated_ledger INTEGER NOT NULL
+);
+
+-- agent_registry agents.
+CREATE TABLE IF NOT EXISTS agents (
+    agent                  TEXT PRIMARY KEY,
+    profile_hash           TEXT NOT NULL,
+    verified               INTEGER NOT NULL DEFAULT 0,
+    rating_total           INTEGER NOT NULL DEFAULT 0,
+    rating_count           INTEGER NOT NULL DEFAULT 0,
+    completed_transactions INTEGER NOT NULL DEFAULT 0,
+    registered_at          INTEGER NOT NULL
+);
+
+-- user_profile profiles; deleted profiles are removed.
+CREATE TABLE IF NOT EXISTS profiles (
+    account_id   TEXT PRIMARY KEY,
+    account_type TEXT NOT NULL,
+    data_hash    TEXT NOT NULL,
+    verified     INTEGER NOT NULL DEFAULT 0
+);
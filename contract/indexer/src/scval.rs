@@ -0,0 +1,227 @@
+//! Conversion of XDR values into the plain Rust types events decode into.
+
+use std::fmt;
+
+use stellar_xdr::curr::{ScMapEntry, ScVal};
+
+use crate::error::DecodeError;
+
+/// An account (`G...`) or contract (`C...`) address in strkey form.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Address(String);
+
+impl Address {
+    pub fn new(strkey: impl Into<String>) -> Self {
+        Self(strkey.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A Rust type an event topic or data field can be decoded into.
+pub trait FromScVal: Sized {
+    /// Name of the expected contract type, for error messages.
+    const EXPECTED: &'static str;
+
+    fn from_sc_val(val: &ScVal) -> Option<Self>;
+}
+
+impl FromScVal for bool {
+    const EXPECTED: &'static str = "bool";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl FromScVal for u32 {
+    const EXPECTED: &'static str = "u32";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::U32(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl FromScVal for u64 {
+    const EXPECTED: &'static str = "u64";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::U64(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl FromScVal for i128 {
+    const EXPECTED: &'static str = "i128";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::I128(parts) => Some(((parts.hi as i128) << 64) | parts.lo as i128),
+            _ => None,
+        }
+    }
+}
+
+impl FromScVal for String {
+    const EXPECTED: &'static str = "String";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::String(value) => value.to_utf8_string().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl FromScVal for Vec<u8> {
+    const EXPECTED: &'static str = "Bytes";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::Bytes(bytes) => Some(bytes.to_vec()),
+            _ => None,
+        }
+    }
+}
+
+impl FromScVal for [u8; 32] {
+    const EXPECTED: &'static str = "BytesN<32>";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::Bytes(bytes) => bytes.as_slice().try_into().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl FromScVal for Address {
+    const EXPECTED: &'static str = "Address";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::Address(address) => Some(Self(address.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl<T: FromScVal> FromScVal for Option<T> {
+    const EXPECTED: &'static str = T::EXPECTED;
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::Void => Some(None),
+            _ => T::from_sc_val(val).map(Some),
+        }
+    }
+}
+
+/// The symbol naming a unit enum variant, which the contracts encode as a
+/// one-element vector.
+pub(crate) fn variant_name(val: &ScVal) -> Option<String> {
+    let ScVal::Vec(Some(items)) = val else {
+        return None;
+    };
+    match items.as_slice() {
+        [ScVal::Symbol(symbol)] => symbol.to_utf8_string().ok(),
+        _ => None,
+    }
+}
+
+/// Entries of a contract struct or event data map.
+pub(crate) fn map_entries(val: &ScVal) -> Option<&[ScMapEntry]> {
+    match val {
+        ScVal::Map(Some(map)) => Some(map.as_slice()),
+        ScVal::Map(None) | ScVal::Void => Some(&[]),
+        _ => None,
+    }
+}
+
+fn lookup<'a>(entries: &'a [ScMapEntry], key: &str) -> Option<&'a ScVal> {
+    entries
+        .iter()
+        .find(|entry| matches!(&entry.key, ScVal::Symbol(symbol) if symbol.as_slice() == key.as_bytes()))
+        .map(|entry| &entry.val)
+}
+
+/// Decode the value stored under `key` in map `entries`.
+pub(crate) fn map_field<T: FromScVal>(entries: &[ScMapEntry], key: &str) -> Option<T> {
+    lookup(entries, key).and_then(T::from_sc_val)
+}
+
+/// Reads the topics following an event's name in declaration order.
+pub(crate) struct Topics<'a> {
+    event: &'static str,
+    topics: std::slice::Iter<'a, ScVal>,
+}
+
+impl<'a> Topics<'a> {
+    pub(crate) fn new(event: &'static str, topics: &'a [ScVal]) -> Self {
+        Self {
+            event,
+            topics: topics.iter(),
+        }
+    }
+
+    pub(crate) fn next<T: FromScVal>(&mut self, field: &'static str) -> Result<T, DecodeError> {
+        let val = self.topics.next().ok_or(DecodeError::MissingField {
+            event: self.event,
+            field,
+        })?;
+        T::from_sc_val(val).ok_or(DecodeError::InvalidField {
+            event: self.event,
+            field,
+            expected: T::EXPECTED,
+        })
+    }
+
+    /// Fail if the event carried more topics than it declares.
+    pub(crate) fn finish(mut self) -> Result<(), DecodeError> {
+        match self.topics.next() {
+            Some(_) => Err(DecodeError::ExtraTopics { event: self.event }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Reads the named fields of an event's data map.
+pub(crate) struct Data<'a> {
+    event: &'static str,
+    entries: &'a [ScMapEntry],
+}
+
+impl<'a> Data<'a> {
+    pub(crate) fn new(event: &'static str, data: &'a ScVal) -> Result<Self, DecodeError> {
+        let entries = map_entries(data).ok_or(DecodeError::InvalidData { event })?;
+        Ok(Self { event, entries })
+    }
+
+    pub(crate) fn field<T: FromScVal>(&self, field: &'static str) -> Result<T, DecodeError> {
+        let val = lookup(self.entries, field).ok_or(DecodeError::MissingField {
+            event: self.event,
+            field,
+        })?;
+        T::from_sc_val(val).ok_or(DecodeError::InvalidField {
+            event: self.event,
+            field,
+            expected: T::EXPECTED,
+        })
+    }
+}
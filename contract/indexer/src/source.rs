@@ -0,0 +1,114 @@
+//! Events as returned by the Stellar RPC `getEvents` method, and their
+//! attribution to protocol contracts.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use stellar_xdr::curr::{Limits, ReadXdr, ScVal};
+
+use crate::{
+    error::Error,
+    events::{Contract, Event},
+};
+
+/// One event in a `getEvents` response. Topics and value are base64 XDR
+/// `ScVal`s.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEvent {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub ledger: u32,
+    pub contract_id: String,
+    /// Unique and ordered: events are applied in `id` order.
+    pub id: String,
+    #[serde(default = "successful")]
+    pub in_successful_contract_call: bool,
+    pub topic: Vec<String>,
+    pub value: String,
+}
+
+fn successful() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EventFile {
+    Response { result: EventPage },
+    Page(EventPage),
+    Events(Vec<RpcEvent>),
+}
+
+#[derive(Deserialize)]
+struct EventPage {
+    events: Vec<RpcEvent>,
+}
+
+/// Parse a `getEvents` JSON-RPC response, its `result` object or a bare
+/// array of events.
+pub fn parse_events(json: &str) -> Result<Vec<RpcEvent>, Error> {
+    Ok(match serde_json::from_str(json)? {
+        EventFile::Response { result } => result.events,
+        EventFile::Page(page) => page.events,
+        EventFile::Events(events) => events,
+    })
+}
+
+/// A decoded event with the position it was published at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexedEvent {
+    pub id: String,
+    pub ledger: u32,
+    pub contract_id: String,
+    pub event: Event,
+}
+
+/// Which contract each deployed contract id runs.
+#[derive(Clone, Debug, Default)]
+pub struct Deployment {
+    contracts: BTreeMap<String, Contract>,
+}
+
+impl Deployment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, contract: Contract, contract_id: impl Into<String>) {
+        self.contracts.insert(contract_id.into(), contract);
+    }
+
+    pub fn contract(&self, contract_id: &str) -> Option<Contract> {
+        self.contracts.get(contract_id).copied()
+    }
+
+    /// Decode `raw`, or return `None` when it is not a contract event of a
+    /// successful call to one of the deployed protocol contracts.
+    pub fn decode(&self, raw: &RpcEvent) -> Result<Option<IndexedEvent>, Error> {
+        let Some(contract) = self.contract(&raw.contract_id) else {
+            return Ok(None);
+        };
+        if raw.kind != "contract" || !raw.in_successful_contract_call {
+            return Ok(None);
+        }
+
+        let topics = raw
+            .topic
+            .iter()
+            .map(|topic| ScVal::from_xdr_base64(topic, Limits::none()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let data = ScVal::from_xdr_base64(&raw.value, Limits::none())?;
+        let event = Event::decode(contract, &topics, &data).map_err(|source| Error::Decode {
+            event_id: raw.id.clone(),
+            source,
+        })?;
+
+        Ok(Some(IndexedEvent {
+            id: raw.id.clone(),
+            ledger: raw.ledger,
+            contract_id: raw.contract_id.clone(),
+            event,
+        }))
+    }
+}
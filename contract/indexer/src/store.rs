@@ -0,0 +1,607 @@
+//! SQLite database the decoded events are applied to.
+
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use crate::{
+    error::Error,
+    events::{
+        agent_registry, chioma, dispute_resolution, escrow, payment, property_registry,
+        user_profile, Event,
+    },
+    source::IndexedEvent,
+};
+
+const SCHEMA: &str = include_str!("schema.sql");
+
+/// The materialized protocol state.
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    /// Open the database at `path`, creating it and its tables if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Error> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self, Error> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// The connection, for queries against the materialized tables.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Id of the latest applied event, to resume `getEvents` from.
+    pub fn last_event_id(&self) -> Result<Option<String>, Error> {
+        Ok(self
+            .conn
+            .query_row("SELECT MAX(id) FROM events", [], |row| row.get(0))
+            .optional()?
+            .flatten())
+    }
+
+    /// Apply `events` in order in one transaction and return how many were
+    /// new. Events already applied are skipped.
+    pub fn apply(&mut self, events: &[IndexedEvent]) -> Result<usize, Error> {
+        let tx = self.conn.transaction()?;
+        let mut applied = 0;
+        for indexed in events {
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO events (id, ledger, contract, contract_id, name)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    indexed.id,
+                    indexed.ledger,
+                    indexed.event.contract().name(),
+                    indexed.contract_id,
+                    indexed.event.name(),
+                ],
+            )?;
+            if inserted == 0 {
+                continue;
+            }
+            apply_event(&tx, indexed)?;
+            applied += 1;
+        }
+        tx.commit()?;
+        Ok(applied)
+    }
+}
+
+fn apply_event(tx: &Transaction, indexed: &IndexedEvent) -> rusqlite::Result<()> {
+    let ledger = indexed.ledger;
+    match &indexed.event {
+        Event::Chioma(event) => apply_chioma(tx, indexed, event),
+        Event::Payment(payment::Event::RentPaid {
+            agreement_id,
+            from,
+            token,
+            payment_amount,
+            landlord_amount,
+            platform_amount,
+            paid_at,
+            ..
+        }) => {
+            tx.execute(
+                "INSERT INTO payments (event_id, contract, agreement_id, payer, token, amount,
+                     landlord_amount, platform_amount, paid_at, ledger)
+                 VALUES (?1, 'payment', ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    indexed.id,
+                    agreement_id,
+                    from.as_str(),
+                    token.as_str(),
+                    payment_amount.to_string(),
+                    landlord_amount.to_string(),
+                    platform_amount.to_string(),
+                    paid_at,
+                    ledger,
+                ],
+            )?;
+            add_to_total_paid(tx, agreement_id, *payment_amount, ledger)
+        }
+        Event::Escrow(event) => apply_escrow(tx, event, ledger),
+        Event::DisputeResolution(event) => apply_dispute(tx, event),
+        Event::PropertyRegistry(event) => apply_property(tx, event, ledger),
+        Event::AgentRegistry(event) => apply_agent(tx, event),
+        Event::UserProfile(event) => apply_profile(tx, event),
+        Event::Payment(_) | Event::RentObligation(_) => Ok(()),
+    }
+}
+
+fn apply_chioma(
+    tx: &Transaction,
+    indexed: &IndexedEvent,
+    event: &chioma::Event,
+) -> rusqlite::Result<()> {
+    use chioma::Event::*;
+
+    let ledger = indexed.ledger;
+    match event {
+        AgreementCreated {
+            user,
+            admin,
+            agreement_id,
+            monthly_rent,
+            security_deposit,
+            start_date,
+            end_date,
+            agent,
+        } => tx
+            .execute(
+                "INSERT INTO agreements (agreement_id, landlord, tenant, agent, monthly_rent,
+                     security_deposit, start_date, end_date, status, updated_ledger)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'Draft', ?9)",
+                params![
+                    agreement_id,
+                    admin.as_str(),
+                    user.as_str(),
+                    agent.as_ref().map(|agent| agent.as_str()),
+                    monthly_rent.to_string(),
+                    security_deposit.to_string(),
+                    start_date,
+                    end_date,
+                    ledger,
+                ],
+            )
+            .map(drop),
+        AgreementSubmitted { agreement_id, .. } => {
+            set_agreement_status(tx, agreement_id, "Pending", ledger)
+        }
+        AgreementSigned {
+            agreement_id,
+            signed_at,
+            ..
+        } => {
+            tx.execute(
+                "UPDATE agreements SET signed_at = ?2 WHERE agreement_id = ?1",
+                params![agreement_id, signed_at],
+            )?;
+            set_agreement_status(tx, agreement_id, "PendingApproval", ledger)
+        }
+        AgreementApproved { agreement_id, .. } => {
+            set_agreement_status(tx, agreement_id, "Active", ledger)
+        }
+        AgreementCancelled { agreement_id, .. } => {
+            set_agreement_status(tx, agreement_id, "Cancelled", ledger)
+        }
+        PositionSold {
+            agreement_id,
+            buyer,
+            ..
+        } => tx
+            .execute(
+                "UPDATE agreements SET landlord = ?2, updated_ledger = ?3 WHERE agreement_id = ?1",
+                params![agreement_id, buyer.as_str(), ledger],
+            )
+            .map(drop),
+        PaymentMadeWithToken {
+            agreement_id,
+            token,
+            amount,
+        } => {
+            tx.execute(
+                "INSERT INTO payments (event_id, contract, agreement_id, token, amount, ledger)
+                 VALUES (?1, 'chioma', ?2, ?3, ?4, ?5)",
+                params![
+                    indexed.id,
+                    agreement_id,
+                    token.as_str(),
+                    amount.to_string(),
+                    ledger
+                ],
+            )?;
+            add_to_total_paid(tx, agreement_id, *amount, ledger)
+        }
+        ExtensionProposed {
+            extension_id,
+            agreement_id,
+            new_end_date,
+        } => tx
+            .execute(
+                "INSERT OR REPLACE INTO extensions (extension_id, agreement_id, new_end_date, status)
+                 VALUES (?1, ?2, ?3, 'Pending')",
+                params![extension_id, agreement_id, new_end_date],
+            )
+            .map(drop),
+        ExtensionAccepted { extension_id } => {
+            set_extension_status(tx, extension_id, "Accepted")
+        }
+        ExtensionRejected { extension_id, .. } => {
+            set_extension_status(tx, extension_id, "Rejected")
+        }
+        ExtensionCancelled { extension_id, .. } => {
+            set_extension_status(tx, extension_id, "Cancelled")
+        }
+        ExtensionActivated { extension_id } => {
+            set_extension_status(tx, extension_id, "Active")?;
+            tx.execute(
+                "UPDATE agreements
+                 SET end_date = (SELECT new_end_date FROM extensions WHERE extension_id = ?1),
+                     updated_ledger = ?2
+                 WHERE agreement_id = (SELECT agreement_id FROM extensions WHERE extension_id = ?1)",
+                params![extension_id, ledger],
+            )
+            .map(drop)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn set_agreement_status(
+    tx: &Transaction,
+    agreement_id: &str,
+    status: &str,
+    ledger: u32,
+) -> rusqlite::Result<()> {
+    tx.execute(
+        "UPDATE agreements SET status = ?2, updated_ledger = ?3 WHERE agreement_id = ?1",
+        params![agreement_id, status, ledger],
+    )
+    .map(drop)
+}
+
+fn set_extension_status(
+    tx: &Transaction,
+    extension_id: &str,
+    status: &str,
+) -> rusqlite::Result<()> {
+    tx.execute(
+        "UPDATE extensions SET status = ?2 WHERE extension_id = ?1",
+        params![extension_id, status],
+    )
+    .map(drop)
+}
+
+fn add_to_total_paid(
+    tx: &Transaction,
+    agreement_id: &str,
+    amount: i128,
+    ledger: u32,
+) -> rusqlite::Result<()> {
+    let paid: Option<String> = tx
+        .query_row(
+            "SELECT total_paid FROM agreements WHERE agreement_id = ?1",
+            [agreement_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let Some(paid) = paid else {
+        return Ok(());
+    };
+    let total = parse_amount(&paid) + amount;
+    tx.execute(
+        "UPDATE agreements SET total_paid = ?2, updated_ledger = ?3 WHERE agreement_id = ?1",
+        params![agreement_id, total.to_string(), ledger],
+    )
+    .map(drop)
+}
+
+fn apply_escrow(tx: &Transaction, event: &escrow::Event, ledger: u32) -> rusqlite::Result<()> {
+    use escrow::Event::*;
+
+    match event {
+        EscrowCreated {
+            escrow_id,
+            agreement_id,
+            depositor,
+            beneficiary,
+            arbiter,
+            amount,
+            token,
+            created_at,
+            ..
+        } => tx
+            .execute(
+                "INSERT INTO escrows (escrow_id, agreement_id, depositor, beneficiary, arbiter,
+                     token, amount, status, created_at, updated_ledger)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'Pending', ?8, ?9)",
+                params![
+                    hex(escrow_id),
+                    agreement_id,
+                    depositor.as_str(),
+                    beneficiary.as_str(),
+                    arbiter.as_str(),
+                    token.as_str(),
+                    amount.to_string(),
+                    created_at,
+                    ledger,
+                ],
+            )
+            .map(drop),
+        EscrowFunded {
+            escrow_id, amount, ..
+        } => update_escrow(
+            tx,
+            escrow_id,
+            "status = 'Funded', balance = ?2",
+            amount.to_string(),
+            ledger,
+        ),
+        InstallmentPaid {
+            escrow_id, funded, ..
+        } => update_escrow(
+            tx,
+            escrow_id,
+            "status = 'PartiallyFunded', balance = ?2",
+            funded.to_string(),
+            ledger,
+        ),
+        ReleaseApproved {
+            escrow_id,
+            approval_count,
+            ..
+        } => update_escrow(tx, escrow_id, "approvals = ?2", approval_count, ledger),
+        PartialRelease {
+            escrow_id, amount, ..
+        } => {
+            let balance: Option<String> = tx
+                .query_row(
+                    "SELECT balance FROM escrows WHERE escrow_id = ?1",
+                    [hex(escrow_id)],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let Some(balance) = balance else {
+                return Ok(());
+            };
+            let balance = parse_amount(&balance) - amount;
+            update_escrow(tx, escrow_id, "balance = ?2", balance.to_string(), ledger)
+        }
+        EscrowReleased { escrow_id, .. }
+        | DamageDeduction { escrow_id, .. }
+        | RentReleased { escrow_id, .. } => update_escrow(
+            tx,
+            escrow_id,
+            "status = ?2, balance = '0'",
+            "Released",
+            ledger,
+        ),
+        EscrowTimeout { escrow_id, .. }
+        | DisputeTimeout { escrow_id, .. }
+        | SafetyDepositWithdrawn { escrow_id, .. }
+        | InstallmentsRefunded { escrow_id, .. } => update_escrow(
+            tx,
+            escrow_id,
+            "status = ?2, balance = '0'",
+            "Refunded",
+            ledger,
+        ),
+        BeneficiaryTransferred {
+            escrow_id,
+            beneficiary,
+            ..
+        } => update_escrow(
+            tx,
+            escrow_id,
+            "beneficiary = ?2, approvals = 0",
+            beneficiary.as_str(),
+            ledger,
+        ),
+        EscrowFrozen { escrow_id, .. } => update_escrow(tx, escrow_id, "frozen = ?2", true, ledger),
+        EscrowUnfrozen { escrow_id, .. } => {
+            update_escrow(tx, escrow_id, "frozen = ?2", false, ledger)
+        }
+        EscrowStatusUpdated {
+            escrow_id,
+            new_status,
+            ..
+        } => update_escrow(tx, escrow_id, "status = ?2", new_status.as_str(), ledger),
+        _ => Ok(()),
+    }
+}
+
+/// Run `UPDATE escrows SET <set>` on one escrow, with `value` bound to `?2`.
+fn update_escrow(
+    tx: &Transaction,
+    escrow_id: &[u8; 32],
+    set: &str,
+    value: impl rusqlite::ToSql,
+    ledger: u32,
+) -> rusqlite::Result<()> {
+    tx.execute(
+        &format!("UPDATE escrows SET {set}, updated_ledger = ?3 WHERE escrow_id = ?1"),
+        params![hex(escrow_id), value, ledger],
+    )
+    .map(drop)
+}
+
+fn apply_dispute(tx: &Transaction, event: &dispute_resolution::Event) -> rusqlite::Result<()> {
+    use dispute_resolution::Event::*;
+
+    let updated = match event {
+        DisputeRaised {
+            agreement_id,
+            details_hash,
+            raised_at,
+        } => tx.execute(
+            "INSERT OR REPLACE INTO disputes (agreement_id, details_hash, raised_at)
+             VALUES (?1, ?2, ?3)",
+            params![agreement_id, details_hash, raised_at],
+        ),
+        VoteCast {
+            agreement_id,
+            favor_landlord,
+            ..
+        } => {
+            let column = if *favor_landlord {
+                "votes_landlord"
+            } else {
+                "votes_tenant"
+            };
+            tx.execute(
+                &format!("UPDATE disputes SET {column} = {column} + 1 WHERE agreement_id = ?1"),
+                [agreement_id],
+            )
+        }
+        DisputeResolved {
+            agreement_id,
+            result,
+            voted_for_landlord_count,
+            voted_for_tenant_count,
+            resolved_at,
+        } => tx.execute(
+            "UPDATE disputes SET outcome = ?2, votes_landlord = ?3, votes_tenant = ?4,
+                 resolved_at = ?5
+             WHERE agreement_id = ?1",
+            params![
+                agreement_id,
+                result.as_str(),
+                voted_for_landlord_count,
+                voted_for_tenant_count,
+                resolved_at,
+            ],
+        ),
+        DisputeTimeout { agreement_id, .. } => tx.execute(
+            "UPDATE disputes SET timed_out = 1 WHERE agreement_id = ?1",
+            [agreement_id],
+        ),
+        DisputeResolvedByWeight {
+            dispute_id,
+            outcome,
+            resolved_at,
+            ..
+        } => tx.execute(
+            "UPDATE disputes SET outcome = ?2, resolved_at = ?3 WHERE agreement_id = ?1",
+            params![dispute_id, outcome.as_str(), resolved_at],
+        ),
+        _ => Ok(0),
+    };
+    updated.map(drop)
+}
+
+fn apply_property(
+    tx: &Transaction,
+    event: &property_registry::Event,
+    ledger: u32,
+) -> rusqlite::Result<()> {
+    use property_registry::Event::*;
+
+    let (property_id, set, value): (&str, &str, String) = match event {
+        PropertyRegistered {
+            property_id,
+            owner,
+            registered_at,
+        } => {
+            return tx
+                .execute(
+                    "INSERT INTO properties (property_id, owner, registered_at, updated_ledger)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![property_id, owner.as_str(), registered_at, ledger],
+                )
+                .map(drop);
+        }
+        PropertyVerified { property_id, .. } => (property_id, "verified", "1".into()),
+        PropertyTransferred {
+            property_id,
+            new_landlord,
+            ..
+        } => (property_id, "owner", new_landlord.to_string()),
+        OwnershipTransferred {
+            property_id,
+            new_owner,
+            ..
+        } => (property_id, "owner", new_owner.to_string()),
+        PropertyMetadataUpdated {
+            property_id,
+            new_metadata_hash,
+            ..
+        } => (property_id, "metadata_hash", new_metadata_hash.clone()),
+        MetadataUpdated {
+            property_id,
+            data_hash,
+            ..
+        } => (property_id, "metadata_hash", hex(data_hash)),
+        _ => return Ok(()),
+    };
+    tx.execute(
+        &format!("UPDATE properties SET {set} = ?2, updated_ledger = ?3 WHERE property_id = ?1"),
+        params![property_id, value, ledger],
+    )
+    .map(drop)
+}
+
+fn apply_agent(tx: &Transaction, event: &agent_registry::Event) -> rusqlite::Result<()> {
+    use agent_registry::Event::*;
+
+    let updated = match event {
+        AgentRegistered {
+            agent,
+            external_profile_hash,
+            registered_at,
+        } => tx.execute(
+            "INSERT INTO agents (agent, profile_hash, registered_at) VALUES (?1, ?2, ?3)",
+            params![agent.as_str(), external_profile_hash, registered_at],
+        ),
+        AgentVerified { agent, .. } => tx.execute(
+            "UPDATE agents SET verified = 1 WHERE agent = ?1",
+            [agent.as_str()],
+        ),
+        AgentRated { agent, score, .. } => tx.execute(
+            "UPDATE agents SET rating_total = rating_total + ?2, rating_count = rating_count + 1
+             WHERE agent = ?1",
+            params![agent.as_str(), score],
+        ),
+        TransactionCompleted { agent, .. } => tx.execute(
+            "UPDATE agents SET completed_transactions = completed_transactions + 1
+             WHERE agent = ?1",
+            [agent.as_str()],
+        ),
+        _ => Ok(0),
+    };
+    updated.map(drop)
+}
+
+fn apply_profile(tx: &Transaction, event: &user_profile::Event) -> rusqlite::Result<()> {
+    use user_profile::Event::*;
+
+    let updated = match event {
+        ProfileCreated {
+            account_id,
+            account_type,
+            data_hash,
+        }
+        | ProfileUpdated {
+            account_id,
+            account_type,
+            data_hash,
+        } => tx.execute(
+            "INSERT INTO profiles (account_id, account_type, data_hash) VALUES (?1, ?2, ?3)
+             ON CONFLICT (account_id) DO UPDATE
+             SET account_type = excluded.account_type, data_hash = excluded.data_hash",
+            params![account_id.as_str(), account_type.as_str(), hex(data_hash)],
+        ),
+        ProfileVerified { account_id, .. } => tx.execute(
+            "UPDATE profiles SET verified = 1 WHERE account_id = ?1",
+            [account_id.as_str()],
+        ),
+        ProfileUnverified { account_id, .. } => tx.execute(
+            "UPDATE profiles SET verified = 0 WHERE account_id = ?1",
+            [account_id.as_str()],
+        ),
+        ProfileDeleted { account_id, .. } => tx.execute(
+            "DELETE FROM profiles WHERE account_id = ?1",
+            [account_id.as_str()],
+        ),
+        _ => Ok(0),
+    };
+    updated.map(drop)
+}
+
+/// Amounts are only ever written by this module, so they always parse.
+fn parse_amount(amount: &str) -> i128 {
+    amount.parse().expect("stored amount is an i128")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
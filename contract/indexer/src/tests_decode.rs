@@ -0,0 +1,182 @@
+use stellar_xdr::curr::{Limits, ReadXdr, ScMap, ScMapEntry, ScSymbol, ScVal, WriteXdr};
+
+use crate::{
+    events::{payment, user_profile, Contract, Event},
+    tests_fixtures::load,
+    DecodeError, Error,
+};
+
+fn symbol(name: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(name.try_into().unwrap()))
+}
+
+/// Topics and data of the escrow's recorded `timeout_config_updated`.
+fn timeout_config_updated() -> (Vec<ScVal>, Vec<ScMapEntry>) {
+    let (_, events) = load();
+    let raw = events
+        .iter()
+        .find(|raw| {
+            raw.topic[0]
+                == symbol("timeout_config_updated")
+                    .to_xdr_base64(Limits::none())
+                    .unwrap()
+        })
+        .unwrap();
+    let topics = raw
+        .topic
+        .iter()
+        .map(|topic| ScVal::from_xdr_base64(topic, Limits::none()).unwrap())
+        .collect();
+    let ScVal::Map(Some(data)) = ScVal::from_xdr_base64(&raw.value, Limits::none()).unwrap() else {
+        unreachable!("event data is a map");
+    };
+    (topics, data.0.into())
+}
+
+fn map(entries: Vec<ScMapEntry>) -> ScVal {
+    ScVal::Map(Some(ScMap(entries.try_into().unwrap())))
+}
+
+#[test]
+fn test_every_fixture_event_decodes() {
+    let (deployment, events) = load();
+    assert!(!events.is_empty());
+    for raw in &events {
+        let indexed = deployment
+            .decode(raw)
+            .unwrap_or_else(|err| panic!("{err}"))
+            .expect("fixture events come from protocol contracts");
+        assert_eq!(
+            Some(indexed.event.contract()),
+            deployment.contract(&raw.contract_id)
+        );
+    }
+}
+
+#[test]
+fn test_decodes_topics_and_data_fields() {
+    let (deployment, events) = load();
+    let decoded = events
+        .iter()
+        .filter_map(|raw| deployment.decode(raw).unwrap())
+        .map(|indexed| indexed.event)
+        .collect::<Vec<_>>();
+
+    let rent_paid = decoded
+        .iter()
+        .find_map(|event| match event {
+            Event::Payment(event @ payment::Event::RentPaid { .. }) => Some(event.clone()),
+            _ => None,
+        })
+        .unwrap();
+    let payment::Event::RentPaid {
+        agreement_id,
+        payment_amount,
+        landlord_amount,
+        platform_amount,
+        ..
+    } = rent_paid
+    else {
+        unreachable!();
+    };
+    assert_eq!(agreement_id, "AGR-PAY");
+    assert_eq!(payment_amount, 1_000);
+    assert_eq!(landlord_amount + platform_amount, payment_amount);
+
+    let created = decoded
+        .iter()
+        .find(|event| event.name() == "profile_created")
+        .unwrap();
+    let Event::UserProfile(user_profile::Event::ProfileCreated {
+        account_type,
+        data_hash,
+        ..
+    }) = created
+    else {
+        unreachable!();
+    };
+    assert_eq!(*account_type, user_profile::AccountType::Tenant);
+    assert_eq!(*data_hash, vec![1; 32]);
+}
+
+#[test]
+fn test_rejects_undeclared_event() {
+    let err = Event::decode(Contract::Escrow, &[symbol("escrow_vanished")], &ScVal::Void);
+    assert_eq!(
+        err,
+        Err(DecodeError::UnknownEvent {
+            contract: Contract::Escrow,
+            name: "escrow_vanished".into(),
+        })
+    );
+}
+
+#[test]
+fn test_rejects_missing_name() {
+    let err = Event::decode(Contract::Escrow, &[ScVal::U32(1)], &ScVal::Void);
+    assert_eq!(err, Err(DecodeError::MissingName));
+}
+
+#[test]
+fn test_rejects_field_of_wrong_type() {
+    let (topics, mut data) = timeout_config_updated();
+    let days = data
+        .iter_mut()
+        .find(|entry| entry.key == symbol("escrow_timeout_days"))
+        .unwrap();
+    days.val = ScVal::U32(30);
+    let err = Event::decode(Contract::Escrow, &topics, &map(data));
+    assert_eq!(
+        err,
+        Err(DecodeError::InvalidField {
+            event: "timeout_config_updated",
+            field: "escrow_timeout_days",
+            expected: "u64",
+        })
+    );
+}
+
+#[test]
+fn test_rejects_extra_topics_and_missing_fields() {
+    let (mut topics, data) = timeout_config_updated();
+    assert!(Event::decode(Contract::Escrow, &topics, &map(data.clone())).is_ok());
+
+    let missing = data
+        .iter()
+        .filter(|entry| entry.key != symbol("dispute_timeout_days"))
+        .cloned()
+        .collect();
+    let err = Event::decode(Contract::Escrow, &topics, &map(missing));
+    assert_eq!(
+        err,
+        Err(DecodeError::MissingField {
+            event: "timeout_config_updated",
+            field: "dispute_timeout_days",
+        })
+    );
+
+    topics.push(ScVal::U32(1));
+    let err = Event::decode(Contract::Escrow, &topics, &map(data));
+    assert_eq!(
+        err,
+        Err(DecodeError::ExtraTopics {
+            event: "timeout_config_updated"
+        })
+    );
+}
+
+#[test]
+fn test_skips_foreign_and_failed_events() {
+    let (deployment, events) = load();
+    let mut foreign = events[0].clone();
+    foreign.contract_id = "CUNKNOWN".into();
+    assert!(deployment.decode(&foreign).unwrap().is_none());
+
+    let mut failed = events[0].clone();
+    failed.in_successful_contract_call = false;
+    assert!(deployment.decode(&failed).unwrap().is_none());
+
+    let mut corrupt = events[0].clone();
+    corrupt.value = "not base64".into();
+    assert!(matches!(deployment.decode(&corrupt), Err(Error::Xdr(_))));
+}
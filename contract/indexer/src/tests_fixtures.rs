@@ -0,0 +1,563 @@
+//! Records the fixtures under `fixtures/` by driving every protocol contract
+//! through its main flows in one environment, capturing the events each
+//! call publishes in the form `getEvents` returns them.
+//!
+//! The fixtures are compared against a fresh recording so an event change
+//! in a contract fails here until they are regenerated with
+//! `INDEXER_RECORD=1 cargo test -p indexer`.
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token::StellarAssetClient,
+    vec,
+    xdr::{Limits, ScVal, WriteXdr},
+    Address, Bytes, Env, Map, String, TryFromVal, Val,
+};
+
+use crate::{
+    events::Contract,
+    source::{Deployment, RpcEvent},
+};
+
+/// The events one contract published, with the id it was deployed at.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+struct Fixture {
+    contract_id: std::string::String,
+    events: Vec<RpcEvent>,
+}
+
+/// Deployment and events of every fixture, in publication order.
+pub(crate) fn load() -> (Deployment, Vec<RpcEvent>) {
+    let mut deployment = Deployment::new();
+    let mut events = Vec::new();
+    for contract in Contract::ALL {
+        let json = fs::read_to_string(fixture_path(contract)).expect("fixture is recorded");
+        let fixture: Fixture = serde_json::from_str(&json).expect("fixture is valid JSON");
+        deployment.insert(contract, fixture.contract_id);
+        events.extend(fixture.events);
+    }
+    events.sort_by(|a, b| a.id.cmp(&b.id));
+    (deployment, events)
+}
+
+fn fixture_path(contract: Contract) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(format!("{contract}.json"))
+}
+
+#[test]
+fn fixtures_match_contracts() {
+    let recorded = record();
+    let update = std::env::var_os("INDEXER_RECORD").is_some();
+    for (contract, fixture) in recorded {
+        let path = fixture_path(contract);
+        let json = serde_json::to_string_pretty(&fixture).unwrap() + "\n";
+        if update {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, json).unwrap();
+        } else {
+            let stored = fs::read_to_string(&path).unwrap_or_default();
+            assert!(
+                stored == json,
+                "{} is out of date; rerun with INDEXER_RECORD=1",
+                path.display()
+            );
+        }
+    }
+}
+
+/// Captures the events of each protocol call, one ledger per call.
+struct Recorder {
+    env: Env,
+    contracts: BTreeMap<std::string::String, Contract>,
+    fixtures: BTreeMap<Contract, Fixture>,
+}
+
+impl Recorder {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths_allowing_non_root_auth();
+        Self {
+            env,
+            contracts: BTreeMap::new(),
+            fixtures: BTreeMap::new(),
+        }
+    }
+
+    fn deploy(&mut self, contract: Contract, address: &Address) {
+        let contract_id = self.strkey(address);
+        self.contracts.insert(contract_id.clone(), contract);
+        self.fixtures.insert(
+            contract,
+            Fixture {
+                contract_id,
+                events: Vec::new(),
+            },
+        );
+    }
+
+    /// Move to the next ledger, a minute later, run `call` and keep the
+    /// events it published from protocol contracts.
+    fn call<T>(&mut self, call: impl FnOnce(&Env) -> T) -> T {
+        self.env.ledger().with_mut(|ledger| {
+            ledger.sequence_number += 1;
+            ledger.timestamp += 60;
+        });
+        let result = call(&self.env);
+
+        let ledger = self.env.ledger().sequence();
+        for (index, (address, topics, data)) in self.env.events().all().iter().enumerate() {
+            let Some(&contract) = self.contracts.get(&self.strkey(&address)) else {
+                continue;
+            };
+            let event = RpcEvent {
+                kind: "contract".into(),
+                ledger,
+                contract_id: self.strkey(&address),
+                id: format!("{:019}-{:010}", u64::from(ledger) << 32, index),
+                in_successful_contract_call: true,
+                topic: topics.iter().map(|topic| self.base64(topic)).collect(),
+                value: self.base64(data),
+            };
+            self.fixtures.get_mut(&contract).unwrap().events.push(event);
+        }
+        result
+    }
+
+    fn strkey(&self, address: &Address) -> std::string::String {
+        match ScVal::try_from_val(&self.env, address.as_val()).unwrap() {
+            ScVal::Address(address) => address.to_string(),
+            _ => unreachable!("an address converts to ScVal::Address"),
+        }
+    }
+
+    fn base64(&self, val: Val) -> std::string::String {
+        ScVal::try_from_val(&self.env, &val)
+            .unwrap()
+            .to_xdr_base64(Limits::none())
+            .unwrap()
+    }
+
+    fn text(&self, value: &str) -> String {
+        String::from_str(&self.env, value)
+    }
+
+    fn token(&self) -> StellarAssetClient<'static> {
+        let admin = Address::generate(&self.env);
+        let address = self.env.register_stellar_asset_contract_v2(admin).address();
+        StellarAssetClient::new(&self.env, &address)
+    }
+}
+
+const RENT: i128 = 1_000;
+const DEPOSIT: i128 = 2_000;
+const DAY: u64 = 86_400;
+
+fn record() -> BTreeMap<Contract, Fixture> {
+    let mut rec = Recorder::new();
+    let peers = peers(&mut rec);
+    let tenant = chioma(&mut rec, &peers);
+    escrow(&mut rec, &peers);
+    payment(&mut rec);
+    dispute_resolution(&mut rec, &peers.chioma, &tenant);
+    property_registry(&mut rec);
+    agent_registry(&mut rec);
+    user_profile(&mut rec);
+    rent_obligation(&mut rec);
+    rec.fixtures
+}
+
+/// Chioma and the escrow contract, which find each other through a
+/// directory: chioma moves deposits into escrows on activation, and escrow
+/// checks the agreements it secures against chioma.
+struct Peers {
+    chioma: Address,
+    escrow: Address,
+    escrow_admin: Address,
+    directory: Address,
+}
+
+/// Register chioma and escrow, list both in a directory and set escrow up.
+fn peers(rec: &mut Recorder) -> Peers {
+    use escrow::{escrow_impl::EscrowContractClient, EscrowContract, TimeoutConfig};
+    use protocol_directory::{
+        Component, ComponentUpdate, ProtocolDirectoryContract, ProtocolDirectoryContractClient,
+    };
+
+    let chioma = rec.env.register(chioma::Contract, ());
+    rec.deploy(Contract::Chioma, &chioma);
+    let client = EscrowContractClient::new(&rec.env, &rec.env.register(EscrowContract, ()));
+    rec.deploy(Contract::Escrow, &client.address);
+    let admin = Address::generate(&rec.env);
+
+    let directory = ProtocolDirectoryContractClient::new(
+        &rec.env,
+        &rec.env.register(ProtocolDirectoryContract, ()),
+    );
+    directory.initialize(&vec![&rec.env, admin.clone()], &1, &0);
+    let entries = vec![
+        &rec.env,
+        ComponentUpdate {
+            component: Component::Chioma,
+            address: chioma.clone(),
+            version: 1,
+        },
+        ComponentUpdate {
+            component: Component::Escrow,
+            address: client.address.clone(),
+            version: 1,
+        },
+    ];
+    let proposal_id = rec.text("DIR-1");
+    directory.propose_update(&admin, &proposal_id, &entries, &0);
+    directory.execute_update(&admin, &proposal_id);
+
+    rec.call(|_| client.initialize_admin(&admin));
+    rec.call(|_| client.set_directory(&admin, &directory.address));
+    let timeouts = TimeoutConfig {
+        escrow_timeout_days: 30,
+        dispute_timeout_days: 14,
+        payment_timeout_days: 7,
+    };
+    rec.call(|_| client.set_timeout_config(&admin, &timeouts));
+
+    Peers {
+        chioma,
+        escrow: client.address,
+        escrow_admin: admin,
+        directory: directory.address,
+    }
+}
+
+/// Two agreements: one activated with its deposit escrowed, paid twice,
+/// extended and sold, one cancelled as a draft. Returns the tenant of the active one.
+fn chioma(rec: &mut Recorder, peers: &Peers) -> Address {
+    use chioma::{AgreementInput, AgreementTerms, Config, ContractClient};
+
+    let client = ContractClient::new(&rec.env, &peers.chioma);
+    let admin = Address::generate(&rec.env);
+    let landlord = Address::generate(&rec.env);
+    let tenant = Address::generate(&rec.env);
+    let token = rec.token();
+    token.mint(&tenant, &(DEPOSIT + RENT * 2));
+
+    let config = Config {
+        fee_bps: 100,
+        fee_collector: Address::generate(&rec.env),
+        paused: false,
+    };
+    rec.call(|_| client.initialize(&admin, &config));
+    rec.call(|_| client.set_directory(&admin, &peers.directory));
+
+    let input = |rec: &Recorder, id: &str| {
+        let now = rec.env.ledger().timestamp();
+        AgreementInput {
+            agreement_id: rec.text(id),
+            admin: landlord.clone(),
+            user: tenant.clone(),
+            agent: None,
+            terms: AgreementTerms {
+                monthly_rent: RENT,
+                security_deposit: DEPOSIT,
+                start_date: now + DAY,
+                end_date: now + 365 * DAY,
+                agent_commission_rate: 0,
+            },
+            payment_token: token.address.clone(),
+            metadata_uri: rec.text("ipfs://agreement"),
+            attributes: soroban_sdk::Vec::new(&rec.env),
+        }
+    };
+
+    let live = rec.text("AGR-LIVE");
+    let agreement = input(rec, "AGR-LIVE");
+    rec.call(|_| client.create_agreement(&agreement));
+    rec.call(|_| client.submit_agreement(&landlord, &live));
+    rec.call(|_| client.sign_agreement(&tenant, &live));
+    rec.call(|_| client.approve_agreement(&landlord, &live));
+    for _ in 0..2 {
+        rec.call(|_| client.make_payment_with_token(&live, &RENT, &token.address));
+    }
+    rec.call(|_| client.propose_extension(&tenant, &live, &12, &Some(RENT + 50), &None));
+    rec.call(|_| client.accept_extension(&landlord, &live));
+    rec.call(|_| client.activate_extension(&landlord, &live));
+    // Selling the landlord position hands the escrowed deposit to the buyer.
+    let buyer = Address::generate(&rec.env);
+    token.mint(&buyer, &RENT);
+    rec.call(|_| client.list_position(&live, &RENT, &DAY));
+    rec.call(|_| client.buy_position(&live, &buyer));
+
+    let cancelled = rec.text("AGR-CANCEL");
+    let agreement = input(rec, "AGR-CANCEL");
+    rec.call(|_| client.create_agreement(&agreement));
+    rec.call(|_| client.cancel_agreement(&landlord, &cancelled));
+    tenant
+}
+
+/// Three more escrows securing agreements drafted for them: released by two
+/// approvals after a freeze, partially released, and refunded on timeout.
+fn escrow(rec: &mut Recorder, peers: &Peers) {
+    use chioma::{AgreementInput, AgreementTerms, ContractClient};
+    use escrow::escrow_impl::EscrowContractClient;
+
+    let client = EscrowContractClient::new(&rec.env, &peers.escrow);
+    let admin = peers.escrow_admin.clone();
+    let depositor = Address::generate(&rec.env);
+    let beneficiary = Address::generate(&rec.env);
+    let arbiter = Address::generate(&rec.env);
+    let governance = Address::generate(&rec.env);
+    let agent = Address::generate(&rec.env);
+    let token = rec.token();
+    token.mint(&depositor, &(DEPOSIT * 4));
+
+    let chioma = ContractClient::new(&rec.env, &peers.chioma);
+    let mut drafts = 0;
+    let mut draft = |rec: &mut Recorder| {
+        drafts += 1;
+        let now = rec.env.ledger().timestamp();
+        let agreement = AgreementInput {
+            agreement_id: rec.text(&format!("AGR-ESCROW-{drafts}")),
+            admin: beneficiary.clone(),
+            user: depositor.clone(),
+            agent: None,
+            terms: AgreementTerms {
+                monthly_rent: RENT,
+                security_deposit: DEPOSIT,
+                start_date: now + DAY,
+                end_date: now + 365 * DAY,
+                agent_commission_rate: 0,
+            },
+            payment_token: token.address.clone(),
+            metadata_uri: rec.text("ipfs://agreement"),
+            attributes: soroban_sdk::Vec::new(&rec.env),
+        };
+        rec.call(|_| chioma.create_agreement(&agreement));
+        rec.call(|_| {
+            client.create(
+                &agreement.agreement_id,
+                &depositor,
+                &beneficiary,
+                &arbiter,
+                &governance,
+                &agent,
+                &DEPOSIT,
+                &token.address,
+            )
+        })
+    };
+    let mut open = |rec: &mut Recorder| {
+        let escrow_id = draft(rec);
+        rec.call(|_| client.fund_escrow(&escrow_id, &depositor));
+        escrow_id
+    };
+    let released = open(rec);
+    let partial = open(rec);
+    let expired = open(rec);
+
+    let unfunded = draft(rec);
+    let now = rec.env.ledger().timestamp();
+    let schedule = vec![
+        &rec.env,
+        escrow::Installment {
+            due_at: now + DAY,
+            amount: DEPOSIT / 2,
+        },
+        escrow::Installment {
+            due_at: now + 2 * DAY,
+            amount: DEPOSIT / 2,
+        },
+    ];
+    rec.call(|_| client.set_installment_plan(&unfunded, &schedule));
+    rec.call(|_| client.fund_installment(&unfunded, &depositor, &(DEPOSIT / 2)));
+
+    rec.call(|env| client.freeze_escrow(&released, &admin, &String::from_str(env, "incident")));
+    rec.call(|_| client.unfreeze_escrow(&released, &admin));
+    rec.call(|_| client.approve_release(&released, &depositor, &beneficiary));
+    rec.call(|_| client.approve_release(&released, &beneficiary, &beneficiary));
+
+    rec.call(|_| client.approve_partial_release(&partial, &depositor, &beneficiary));
+    rec.call(|_| client.approve_partial_release(&partial, &arbiter, &beneficiary));
+    rec.call(|env| {
+        client.release_escrow_partial(
+            &partial,
+            &(DEPOSIT / 4),
+            &beneficiary,
+            &String::from_str(env, "repairs"),
+        )
+    });
+
+    rec.env
+        .ledger()
+        .with_mut(|ledger| ledger.timestamp += 31 * DAY);
+    rec.call(|_| client.release_escrow_on_timeout(&expired));
+    rec.call(|_| client.refund_installments_on_timeout(&unfunded));
+}
+
+/// One rent payment against an agreement seeded into storage, as the
+/// payment contract has no entrypoint creating agreements.
+fn payment(rec: &mut Recorder) {
+    use payment::{
+        types::{AgreementStatus, RentAgreement},
+        DataKey, PaymentContract, PaymentContractClient,
+    };
+
+    let client = PaymentContractClient::new(&rec.env, &rec.env.register(PaymentContract, ()));
+    rec.deploy(Contract::Payment, &client.address);
+    let admin = Address::generate(&rec.env);
+    let tenant = Address::generate(&rec.env);
+    let token = rec.token();
+    token.mint(&tenant, &RENT);
+
+    rec.call(|_| client.initialize_admin(&admin));
+    rec.call(|env| client.set_platform_fee_collector(&Address::generate(env)));
+
+    let agreement_id = rec.text("AGR-PAY");
+    let agreement = RentAgreement {
+        agreement_id: agreement_id.clone(),
+        landlord: Address::generate(&rec.env),
+        tenant: tenant.clone(),
+        agent: None,
+        monthly_rent: RENT,
+        security_deposit: DEPOSIT,
+        start_date: 0,
+        end_date: 365 * DAY,
+        agent_commission_rate: 0,
+        status: AgreementStatus::Active,
+        total_rent_paid: 0,
+        payment_count: 0,
+        signed_at: Some(0),
+        payment_token: token.address.clone(),
+        next_payment_due: 0,
+        payment_history: Map::new(&rec.env),
+    };
+    rec.env.as_contract(&client.address, || {
+        rec.env
+            .storage()
+            .persistent()
+            .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    });
+    rec.call(|_| client.pay_rent(&tenant, &agreement_id, &RENT));
+}
+
+/// A dispute the tenant raises on chioma's active agreement, resolved for
+/// the landlord.
+fn dispute_resolution(rec: &mut Recorder, chioma: &Address, tenant: &Address) {
+    use dispute_resolution::{DisputeResolutionContract, DisputeResolutionContractClient};
+
+    let client = DisputeResolutionContractClient::new(
+        &rec.env,
+        &rec.env.register(DisputeResolutionContract, ()),
+    );
+    rec.deploy(Contract::DisputeResolution, &client.address);
+    let admin = Address::generate(&rec.env);
+    let arbiter = Address::generate(&rec.env);
+    let agreement_id = rec.text("AGR-LIVE");
+
+    rec.call(|_| client.initialize(&admin, &1, chioma));
+    rec.call(|_| client.add_arbiter(&admin, &arbiter));
+    rec.call(|env| {
+        client.raise_dispute(tenant, &agreement_id, &String::from_str(env, "QmDetails"))
+    });
+    rec.call(|_| client.vote_on_dispute(&arbiter, &agreement_id, &true));
+    rec.call(|_| client.resolve_dispute(&agreement_id));
+}
+
+/// A property registered, verified, re-described and sold.
+fn property_registry(rec: &mut Recorder) {
+    use property_registry::{PropertyRegistryContract, PropertyRegistryContractClient};
+
+    let client = PropertyRegistryContractClient::new(
+        &rec.env,
+        &rec.env.register(PropertyRegistryContract, ()),
+    );
+    rec.deploy(Contract::PropertyRegistry, &client.address);
+    let admin = Address::generate(&rec.env);
+    let landlord = Address::generate(&rec.env);
+    let buyer = Address::generate(&rec.env);
+    let property_id = rec.text("PROP-1");
+
+    rec.call(|_| client.initialize(&admin));
+    rec.call(|env| {
+        client.register_property(
+            &landlord,
+            &property_id,
+            &String::from_str(env, "QmOriginal"),
+        )
+    });
+    rec.call(|_| client.verify_property(&admin, &property_id));
+    rec.call(|env| {
+        client.update_property_metadata(
+            &landlord,
+            &property_id,
+            &String::from_str(env, "QmRenovated"),
+        )
+    });
+    rec.call(|_| client.transfer_property(&landlord, &buyer, &property_id));
+}
+
+/// A verified agent rated after completing a transaction.
+fn agent_registry(rec: &mut Recorder) {
+    use agent_registry::{AgentRegistryContract, AgentRegistryContractClient};
+
+    let client =
+        AgentRegistryContractClient::new(&rec.env, &rec.env.register(AgentRegistryContract, ()));
+    rec.deploy(Contract::AgentRegistry, &client.address);
+    let admin = Address::generate(&rec.env);
+    let agent = Address::generate(&rec.env);
+    let tenant = Address::generate(&rec.env);
+    let transaction_id = rec.text("TX-1");
+
+    rec.call(|_| client.initialize(&admin));
+    rec.call(|env| client.register_agent(&agent, &String::from_str(env, "QmAgent")));
+    rec.call(|_| client.verify_agent(&admin, &agent));
+    rec.call(|env| {
+        client.register_transaction(&transaction_id, &agent, &vec![env, tenant.clone()])
+    });
+    rec.call(|_| client.complete_transaction(&transaction_id, &agent));
+    rec.call(|_| client.rate_agent(&tenant, &agent, &4, &transaction_id));
+}
+
+/// Two profiles: one updated and verified, one deleted.
+fn user_profile(rec: &mut Recorder) {
+    use user_profile::{AccountType, UserProfileContract, UserProfileContractClient};
+
+    let client =
+        UserProfileContractClient::new(&rec.env, &rec.env.register(UserProfileContract, ()));
+    rec.deploy(Contract::UserProfile, &client.address);
+    let admin = Address::generate(&rec.env);
+    let kept = Address::generate(&rec.env);
+    let deleted = Address::generate(&rec.env);
+    let hash = |rec: &Recorder, byte: u8| Bytes::from_array(&rec.env, &[byte; 32]);
+
+    rec.call(|_| client.initialize(&admin));
+    let created = hash(rec, 1);
+    rec.call(|_| client.create_profile(&kept, &AccountType::Tenant, &created));
+    let updated = hash(rec, 2);
+    rec.call(|_| client.update_profile(&kept, &Some(AccountType::Landlord), &Some(updated)));
+    rec.call(|_| client.verify_profile(&admin, &kept));
+    let created = hash(rec, 3);
+    rec.call(|_| client.create_profile(&deleted, &AccountType::Agent, &created));
+    rec.call(|_| client.delete_profile(&deleted));
+}
+
+/// An obligation minted to the landlord and sold on.
+fn rent_obligation(rec: &mut Recorder) {
+    use rent_obligation::{TokenizedRentObligationContract, TokenizedRentObligationContractClient};
+
+    let client = TokenizedRentObligationContractClient::new(
+        &rec.env,
+        &rec.env.register(TokenizedRentObligationContract, ()),
+    );
+    rec.deploy(Contract::RentObligation, &client.address);
+    let landlord = Address::generate(&rec.env);
+    let investor = Address::generate(&rec.env);
+    let agreement_id = rec.text("AGR-LIVE");
+
+    rec.call(|_| client.initialize());
+    rec.call(|_| client.mint_obligation(&agreement_id, &landlord));
+    rec.call(|_| client.transfer_obligation(&landlord, &investor, &agreement_id));
+}
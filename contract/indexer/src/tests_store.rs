@@ -0,0 +1,243 @@
+use rusqlite::{params, Connection};
+
+use crate::{source::IndexedEvent, tests_fixtures::load, Store};
+
+fn indexed_fixtures() -> Vec<IndexedEvent> {
+    let (deployment, events) = load();
+    events
+        .iter()
+        .filter_map(|raw| deployment.decode(raw).unwrap())
+        .collect()
+}
+
+fn indexed_store() -> Store {
+    let mut store = Store::open_in_memory().unwrap();
+    let events = indexed_fixtures();
+    assert_eq!(store.apply(&events).unwrap(), events.len());
+    store
+}
+
+fn text(conn: &Connection, sql: &str, key: &str) -> String {
+    conn.query_row(sql, params![key], |row| row.get(0)).unwrap()
+}
+
+fn count(conn: &Connection, sql: &str) -> i64 {
+    conn.query_row(sql, [], |row| row.get(0)).unwrap()
+}
+
+#[test]
+fn test_agreements_follow_lifecycle_payments_and_extensions() {
+    let store = indexed_store();
+    let conn = store.connection();
+
+    let (status, total_paid, start, end): (String, String, u64, u64) = conn
+        .query_row(
+            "SELECT status, total_paid, start_date, end_date FROM agreements
+             WHERE agreement_id = 'AGR-LIVE'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(status, "Active");
+    assert_eq!(total_paid, "2000");
+    // The 12 month extension moved the end date past the original year.
+    assert!(end > start + 365 * 86_400);
+
+    let status = text(
+        conn,
+        "SELECT status FROM agreements WHERE agreement_id = ?1",
+        "AGR-CANCEL",
+    );
+    assert_eq!(status, "Cancelled");
+    let extension = text(
+        conn,
+        "SELECT status FROM extensions WHERE agreement_id = ?1",
+        "AGR-LIVE",
+    );
+    assert_eq!(extension, "Active");
+    assert_eq!(
+        count(
+            conn,
+            "SELECT COUNT(*) FROM payments WHERE contract = 'chioma'"
+        ),
+        2
+    );
+    assert_eq!(
+        count(
+            conn,
+            "SELECT COUNT(*) FROM payments WHERE contract = 'payment'"
+        ),
+        1
+    );
+}
+
+#[test]
+fn test_escrows_track_status_and_balance() {
+    let store = indexed_store();
+    let mut rows = store
+        .connection()
+        .prepare("SELECT agreement_id, status, balance, approvals, frozen FROM escrows ORDER BY created_at")
+        .unwrap()
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, u32>(3)?,
+                row.get::<_, bool>(4)?,
+            ))
+        })
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .into_iter();
+
+    // The deposit chioma escrowed when AGR-LIVE was activated.
+    assert_eq!(
+        rows.next().unwrap(),
+        ("AGR-LIVE".into(), "Funded".into(), "2000".into(), 0, false)
+    );
+    assert_eq!(
+        rows.next().unwrap(),
+        (
+            "AGR-ESCROW-1".into(),
+            "Released".into(),
+            "0".into(),
+            2,
+            false
+        )
+    );
+    assert_eq!(
+        rows.next().unwrap(),
+        (
+            "AGR-ESCROW-2".into(),
+            "Funded".into(),
+            "1500".into(),
+            0,
+            false
+        )
+    );
+    assert_eq!(
+        rows.next().unwrap(),
+        (
+            "AGR-ESCROW-3".into(),
+            "Refunded".into(),
+            "0".into(),
+            0,
+            false
+        )
+    );
+    // Refunded after its installment deadline passed half funded.
+    assert_eq!(
+        rows.next().unwrap(),
+        (
+            "AGR-ESCROW-4".into(),
+            "Refunded".into(),
+            "0".into(),
+            0,
+            false
+        )
+    );
+    assert!(rows.next().is_none());
+
+    // AGR-LIVE was sold, and its deposit went to the buyer with it.
+    let conn = store.connection();
+    let landlord = text(
+        conn,
+        "SELECT landlord FROM agreements WHERE agreement_id = ?1",
+        "AGR-LIVE",
+    );
+    let beneficiary = text(
+        conn,
+        "SELECT beneficiary FROM escrows WHERE agreement_id = ?1",
+        "AGR-LIVE",
+    );
+    assert_eq!(beneficiary, landlord);
+    assert_ne!(
+        landlord,
+        text(
+            conn,
+            "SELECT landlord FROM agreements WHERE agreement_id = ?1",
+            "AGR-CANCEL",
+        )
+    );
+}
+
+#[test]
+fn test_registries_reflect_latest_state() {
+    let store = indexed_store();
+    let conn = store.connection();
+
+    let outcome = text(
+        conn,
+        "SELECT outcome FROM disputes WHERE agreement_id = ?1",
+        "AGR-LIVE",
+    );
+    assert_eq!(outcome, "FavorLandlord");
+
+    let (metadata, verified): (String, bool) = conn
+        .query_row(
+            "SELECT metadata_hash, verified FROM properties WHERE property_id = 'PROP-1'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(metadata, "QmRenovated");
+    assert!(verified);
+
+    let (rating_total, rating_count, completed): (u32, u32, u32) = conn
+        .query_row(
+            "SELECT rating_total, rating_count, completed_transactions FROM agents",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!((rating_total, rating_count, completed), (4, 1, 1));
+
+    // The deleted profile is gone; the other was updated and verified.
+    let (account_type, data_hash, verified): (String, String, bool) = conn
+        .query_row(
+            "SELECT account_type, data_hash, verified FROM profiles",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(account_type, "Landlord");
+    assert_eq!(data_hash, "02".repeat(32));
+    assert!(verified);
+}
+
+#[test]
+fn test_reapplying_events_is_a_no_op() {
+    let mut store = indexed_store();
+    let events = indexed_fixtures();
+    let last = events.iter().map(|event| event.id.clone()).max();
+    assert_eq!(store.last_event_id().unwrap(), last);
+
+    assert_eq!(store.apply(&events).unwrap(), 0);
+    let total_paid = text(
+        store.connection(),
+        "SELECT total_paid FROM agreements WHERE agreement_id = ?1",
+        "AGR-LIVE",
+    );
+    assert_eq!(total_paid, "2000");
+}
+
+#[test]
+fn test_opens_existing_database() {
+    let dir = std::env::temp_dir().join(format!("indexer-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("chioma.db");
+    let _ = std::fs::remove_file(&path);
+
+    let events = indexed_fixtures();
+    let (first, rest) = events.split_at(events.len() / 2);
+    assert_eq!(
+        Store::open(&path).unwrap().apply(first).unwrap(),
+        first.len()
+    );
+    let mut store = Store::open(&path).unwrap();
+    assert_eq!(store.apply(&events).unwrap(), rest.len());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,103 @@
+//! Multi-step agreement flows on the chioma contract.
+//!
+//! A flow makes the calls a lease goes through in order and stops at the
+//! first one that fails, reporting which. Each call needs the same
+//! authorization as when made on its own: the landlord (`admin`) submits
+//! and approves, the tenant (`user`) signs and pays, and also authorizes the
+//! escrow deposit nested in the approval.
+
+use soroban_sdk::{InvokeError, String};
+
+use crate::chioma::{AgreementInput, Client, RentalError};
+
+/// The chioma call a flow stopped at and why.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FlowError {
+    /// The contract rejected the call.
+    Contract {
+        step: &'static str,
+        error: RentalError,
+    },
+    /// The host aborted the call, e.g. on a missing authorization.
+    Host {
+        step: &'static str,
+        error: InvokeError,
+    },
+}
+
+impl FlowError {
+    /// Name of the entrypoint that failed.
+    pub fn step(&self) -> &'static str {
+        match self {
+            Self::Contract { step, .. } | Self::Host { step, .. } => step,
+        }
+    }
+}
+
+fn step<T, C: core::fmt::Debug>(
+    step: &'static str,
+    result: Result<Result<T, C>, Result<RentalError, InvokeError>>,
+) -> Result<T, FlowError> {
+    match result {
+        Ok(value) => Ok(value.expect("chioma returns its declared types")),
+        Err(Ok(error)) => Err(FlowError::Contract { step, error }),
+        Err(Err(error)) => Err(FlowError::Host { step, error }),
+    }
+}
+
+/// Create the agreement in `input` and take it to `Active`: the landlord
+/// submits it, the tenant signs it and the landlord approves it, which moves
+/// the tenant's deposit into escrow. Returns the agreement id.
+pub fn activate_agreement(chioma: &Client, input: &AgreementInput) -> Result<String, FlowError> {
+    let id = submit_and_sign(chioma, input)?;
+    step(
+        "approve_agreement",
+        chioma.try_approve_agreement(&input.admin, &id),
+    )?;
+    Ok(id)
+}
+
+/// Create, submit and sign the agreement in `input`, leaving it for the
+/// landlord's approval.
+fn submit_and_sign(chioma: &Client, input: &AgreementInput) -> Result<String, FlowError> {
+    let id = &input.agreement_id;
+    step("create_agreement", chioma.try_create_agreement(input))?;
+    step(
+        "submit_agreement",
+        chioma.try_submit_agreement(&input.admin, id),
+    )?;
+    step("sign_agreement", chioma.try_sign_agreement(&input.user, id))?;
+    Ok(id.clone())
+}
+
+/// Pay `months` months of rent on an agreement, in its payment token.
+pub fn pay_rent(chioma: &Client, agreement_id: &String, months: u32) -> Result<(), FlowError> {
+    let agreement = chioma
+        .get_agreement(agreement_id)
+        .ok_or(FlowError::Contract {
+            step: "get_agreement",
+            error: RentalError::AgreementNotFound,
+        })?;
+    for _ in 0..months {
+        step(
+            "make_payment_with_token",
+            chioma.try_make_payment_with_token(
+                agreement_id,
+                &agreement.monthly_rent,
+                &agreement.payment_token,
+            ),
+        )?;
+    }
+    Ok(())
+}
+
+/// Activate the agreement in `input` and pay its first month's rent in the
+/// same call; the deposit is escrowed and the rent paid as the first payment.
+pub fn open_lease(chioma: &Client, input: &AgreementInput) -> Result<String, FlowError> {
+    let id = submit_and_sign(chioma, input)?;
+    step(
+        "approve_with_first_rent",
+        chioma.try_approve_with_first_rent(&input.admin, &id),
+    )?;
+    Ok(id)
+}
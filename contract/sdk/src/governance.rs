@@ -0,0 +1,665 @@
+//! Governance calls on the chioma contract, built offline from JSON.
+//!
+//! A [`Request`] names a governance entrypoint and gives its arguments as
+//! structured fields instead of XDR. Building its [`Invocation`] encodes each
+//! action payload as the contract type the action decodes (see the payload
+//! tables in chioma's `multi_sig` and `timelock` modules), and rejects input
+//! the contract would reject whatever its state, such as a fee above 100% or
+//! a timelock delay below the action's minimum. Checks against contract
+//! state, like whether the proposer is an admin, are left to the contract.
+//!
+//! ```json
+//! { "propose_action": {
+//!     "proposer": "GB...",
+//!     "action": {
+//!       "type": "UpdateConfig",
+//!       "config": { "fee_bps": 250, "fee_collector": "GC...", "paused": false }
+//!     }
+//! } }
+//! ```
+//!
+//! Enum values such as roles, pause modules and version statuses are given
+//! by their variant name, e.g. `"role": "Treasurer"`. Addresses are strkeys
+//! and the upgrade WASM hash is hex.
+
+use core::{fmt, str::FromStr};
+
+use serde::Deserialize;
+use soroban_sdk::{
+    xdr::{
+        AccountId, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo,
+        MuxedAccount, Operation, OperationBody, Preconditions, PublicKey, ReadXdr, ScAddress,
+        ScSpecEntry, ScSpecUdtUnionCaseV0, ScVal, SequenceNumber, SorobanAuthorizationEntry,
+        SorobanAuthorizedFunction, SorobanAuthorizedInvocation, SorobanCredentials,
+        SorobanTransactionData, ToXdr, Transaction, TransactionEnvelope, TransactionExt,
+        TransactionV1Envelope, VecM,
+    },
+    Address, Bytes, BytesN, Env, Symbol, TryFromVal, Val, Vec,
+};
+
+use crate::{
+    chioma::{
+        get_min_delay, ActionThreshold, ActionType, Config, ContractVersion, PauseModule,
+        RateLimitConfig, RateLimitMode, RentalError, Role, SupportedToken, TimelockActionType,
+        TokenExchangeRate, VersionStatus, MAX_AUDIT_CAPACITY,
+    },
+    Invocation,
+};
+
+/// Why a [`Request`] could not be built.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RequestError {
+    /// The input does not parse, e.g. a malformed address or an unknown
+    /// enum variant.
+    Invalid(std::string::String),
+    /// The contract would reject the call with this error.
+    Rejected(RentalError),
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(message) => f.write_str(message),
+            Self::Rejected(error) => write!(f, "the contract would reject this call: {error:?}"),
+        }
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+fn invalid(message: impl Into<std::string::String>) -> RequestError {
+    RequestError::Invalid(message.into())
+}
+
+fn reject_if(condition: bool, error: RentalError) -> Result<(), RequestError> {
+    if condition {
+        return Err(RequestError::Rejected(error));
+    }
+    Ok(())
+}
+
+/// An account (`G...`) or contract (`C...`) address from its strkey.
+pub fn parse_address(env: &Env, strkey: &str) -> Result<Address, RequestError> {
+    let address = match ScAddress::from_str(strkey) {
+        Ok(address @ (ScAddress::Account(_) | ScAddress::Contract(_))) => address,
+        _ => {
+            return Err(invalid(format!(
+                "`{strkey}` is not an account or contract address"
+            )))
+        }
+    };
+    let val = Val::try_from_val(env, &ScVal::Address(address)).expect("addresses convert");
+    Ok(Address::try_from_val(env, &val).expect("an address value is an address"))
+}
+
+fn text(env: &Env, value: &str) -> soroban_sdk::String {
+    soroban_sdk::String::from_str(env, value)
+}
+
+/// A unit variant of a contract enum by name, e.g. a `Role` from
+/// `"Treasurer"`. The name is checked against the enum's contract spec.
+fn variant<T: TryFromVal<Env, Val>>(
+    env: &Env,
+    spec: &[u8],
+    kind: &str,
+    name: &str,
+) -> Result<T, RequestError> {
+    let Ok(ScSpecEntry::UdtUnionV0(union)) = ScSpecEntry::from_xdr(spec, Limits::none()) else {
+        unreachable!("a unit enum's spec is a union");
+    };
+    let known = union.cases.iter().any(|case| {
+        matches!(case, ScSpecUdtUnionCaseV0::VoidV0(case) if case.name.to_utf8_string_lossy() == name)
+    });
+    if !known {
+        return Err(invalid(format!("`{name}` is not a {kind}")));
+    }
+    let encoded = Vec::<Val>::from_array(env, [Symbol::new(env, name).to_val()]);
+    Ok(T::try_from_val(env, &encoded.to_val()).expect("a spec case converts"))
+}
+
+fn wasm_hash(env: &Env, hex: &str) -> Result<BytesN<32>, RequestError> {
+    let not_a_hash = || invalid(format!("`{hex}` is not a 32-byte hex hash"));
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(not_a_hash());
+    }
+    let mut hash = [0u8; 32];
+    for (byte, pair) in hash.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let pair = core::str::from_utf8(pair).expect("ASCII");
+        *byte = u8::from_str_radix(pair, 16).map_err(|_| not_a_hash())?;
+    }
+    Ok(BytesN::from_array(env, &hash))
+}
+
+fn reason(env: &Env, reason: &Option<std::string::String>) -> Bytes {
+    match reason {
+        Some(reason) => text(env, reason).to_xdr(env),
+        None => Bytes::new(env),
+    }
+}
+
+/// Fields of chioma's `Config`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigInput {
+    pub fee_bps: u32,
+    pub fee_collector: std::string::String,
+    pub paused: bool,
+}
+
+impl ConfigInput {
+    fn build(&self, env: &Env) -> Result<Config, RequestError> {
+        reject_if(self.fee_bps > 10_000, RentalError::InvalidConfig)?;
+        Ok(Config {
+            fee_bps: self.fee_bps,
+            fee_collector: parse_address(env, &self.fee_collector)?,
+            paused: self.paused,
+        })
+    }
+}
+
+/// An exchange rate, scaled by 10^18. The contract records when it is set.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateInput {
+    pub from_token: std::string::String,
+    pub to_token: std::string::String,
+    pub rate: i128,
+}
+
+impl RateInput {
+    fn build(&self, env: &Env) -> Result<TokenExchangeRate, RequestError> {
+        reject_if(self.rate <= 0, RentalError::InvalidAmount)?;
+        Ok(TokenExchangeRate {
+            from_token: parse_address(env, &self.from_token)?,
+            to_token: parse_address(env, &self.to_token)?,
+            rate: self.rate,
+            updated_at: 0,
+        })
+    }
+}
+
+/// Fields of chioma's `RateLimitConfig`; `mode` is a `RateLimitMode`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitInput {
+    pub max_calls_per_block: u32,
+    pub max_calls_per_user_per_day: u32,
+    pub cooldown_blocks: u32,
+    pub mode: std::string::String,
+    pub window_seconds: u64,
+}
+
+impl RateLimitInput {
+    fn build(&self, env: &Env) -> Result<RateLimitConfig, RequestError> {
+        let mode: RateLimitMode = variant(
+            env,
+            &RateLimitMode::spec_xdr(),
+            "rate limit mode",
+            &self.mode,
+        )?;
+        reject_if(
+            mode != RateLimitMode::LedgerBlocks && self.window_seconds == 0,
+            RentalError::InvalidConfig,
+        )?;
+        Ok(RateLimitConfig {
+            max_calls_per_block: self.max_calls_per_block,
+            max_calls_per_user_per_day: self.max_calls_per_user_per_day,
+            cooldown_blocks: self.cooldown_blocks,
+            mode,
+            window_seconds: self.window_seconds,
+        })
+    }
+}
+
+/// A token to accept for payments.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenInput {
+    pub address: std::string::String,
+    pub symbol: std::string::String,
+    pub decimals: u32,
+    pub min_amount: i128,
+    pub max_amount: i128,
+}
+
+impl TokenInput {
+    fn build(&self, env: &Env) -> Result<SupportedToken, RequestError> {
+        Ok(SupportedToken {
+            token_address: parse_address(env, &self.address)?,
+            symbol: text(env, &self.symbol),
+            decimals: self.decimals,
+            enabled: true,
+            min_amount: self.min_amount,
+            max_amount: self.max_amount,
+        })
+    }
+}
+
+/// The version an upgrade installs; `status` is a `VersionStatus`. The
+/// contract fills in the WASM hash and time.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VersionInput {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub label: std::string::String,
+    #[serde(default = "VersionInput::active")]
+    pub status: std::string::String,
+}
+
+impl VersionInput {
+    fn active() -> std::string::String {
+        "Active".into()
+    }
+
+    fn build(&self, env: &Env) -> Result<ContractVersion, RequestError> {
+        Ok(ContractVersion {
+            major: self.major,
+            minor: self.minor,
+            patch: self.patch,
+            label: text(env, &self.label),
+            status: variant::<VersionStatus>(
+                env,
+                &VersionStatus::spec_xdr(),
+                "version status",
+                &self.status,
+            )?,
+            hash: Bytes::new(env),
+            updated_at: 0,
+        })
+    }
+}
+
+/// A multi-sig action for `propose_action`, tagged by its `ActionType`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", deny_unknown_fields)]
+pub enum ProposedAction {
+    Pause {
+        reason: Option<std::string::String>,
+    },
+    Unpause,
+    UpdateConfig {
+        config: ConfigInput,
+    },
+    UpdateRate {
+        rate: RateInput,
+    },
+    AddAdmin {
+        admin: std::string::String,
+    },
+    RemoveAdmin {
+        admin: std::string::String,
+    },
+    UpdateRequiredSignatures {
+        required_signatures: u32,
+    },
+    EmergencyAction {
+        new_admin: std::string::String,
+    },
+    SetRateLimit {
+        config: RateLimitInput,
+    },
+    AddToken {
+        token: TokenInput,
+    },
+    RemoveToken {
+        token: std::string::String,
+    },
+    SetActionThreshold {
+        action_type: std::string::String,
+        threshold: u32,
+    },
+    SetAdminWeight {
+        admin: std::string::String,
+        weight: u32,
+    },
+    GrantRole {
+        account: std::string::String,
+        role: std::string::String,
+    },
+    RevokeRole {
+        account: std::string::String,
+        role: std::string::String,
+    },
+    /// Without a guardian, removes the current one.
+    SetGuardian {
+        guardian: Option<std::string::String>,
+    },
+    SetAuditLogCapacity {
+        capacity: u32,
+    },
+}
+
+impl ProposedAction {
+    /// The `action_type`, `target` and `data` arguments of `propose_action`.
+    pub fn encode(&self, env: &Env) -> Result<(ActionType, Option<Address>, Bytes), RequestError> {
+        let empty = Bytes::new(env);
+        let target = |strkey: &str| parse_address(env, strkey).map(Some);
+        Ok(match self {
+            Self::Pause { reason: why } => (ActionType::Pause, None, reason(env, why)),
+            Self::Unpause => (ActionType::Unpause, None, empty),
+            Self::UpdateConfig { config } => (
+                ActionType::UpdateConfig,
+                None,
+                config.build(env)?.to_xdr(env),
+            ),
+            Self::UpdateRate { rate } => {
+                (ActionType::UpdateRate, None, rate.build(env)?.to_xdr(env))
+            }
+            Self::AddAdmin { admin } => (ActionType::AddAdmin, target(admin)?, empty),
+            Self::RemoveAdmin { admin } => (ActionType::RemoveAdmin, target(admin)?, empty),
+            Self::UpdateRequiredSignatures {
+                required_signatures,
+            } => (
+                ActionType::UpdateRequiredSignatures,
+                None,
+                required_signatures.to_xdr(env),
+            ),
+            Self::EmergencyAction { new_admin } => {
+                (ActionType::EmergencyAction, target(new_admin)?, empty)
+            }
+            Self::SetRateLimit { config } => (
+                ActionType::SetRateLimit,
+                None,
+                config.build(env)?.to_xdr(env),
+            ),
+            Self::AddToken { token } => (ActionType::AddToken, None, token.build(env)?.to_xdr(env)),
+            Self::RemoveToken { token } => (ActionType::RemoveToken, target(token)?, empty),
+            Self::SetActionThreshold {
+                action_type,
+                threshold,
+            } => {
+                reject_if(*threshold == 0, RentalError::InvalidConfig)?;
+                let update = ActionThreshold {
+                    action_type: variant(env, &ActionType::spec_xdr(), "action type", action_type)?,
+                    threshold: *threshold,
+                };
+                (ActionType::SetActionThreshold, None, update.to_xdr(env))
+            }
+            Self::SetAdminWeight { admin, weight } => {
+                reject_if(*weight == 0, RentalError::InvalidConfig)?;
+                (
+                    ActionType::SetAdminWeight,
+                    target(admin)?,
+                    weight.to_xdr(env),
+                )
+            }
+            Self::GrantRole { account, role } => (
+                ActionType::GrantRole,
+                target(account)?,
+                variant::<Role>(env, &Role::spec_xdr(), "role", role)?.to_xdr(env),
+            ),
+            Self::RevokeRole { account, role } => (
+                ActionType::RevokeRole,
+                target(account)?,
+                variant::<Role>(env, &Role::spec_xdr(), "role", role)?.to_xdr(env),
+            ),
+            Self::SetGuardian { guardian } => {
+                let guardian = match guardian {
+                    Some(guardian) => target(guardian)?,
+                    None => None,
+                };
+                (ActionType::SetGuardian, guardian, empty)
+            }
+            Self::SetAuditLogCapacity { capacity } => {
+                reject_if(
+                    *capacity == 0 || *capacity > MAX_AUDIT_CAPACITY,
+                    RentalError::InvalidConfig,
+                )?;
+                (ActionType::SetAuditLogCapacity, None, capacity.to_xdr(env))
+            }
+        })
+    }
+}
+
+/// A timelocked action for `queue_timelock_action`, tagged by its
+/// `TimelockActionType`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", deny_unknown_fields)]
+pub enum QueuedAction {
+    UpdateAdmin { new_admin: std::string::String },
+    UpdateConfig { config: ConfigInput },
+    UpdateRates { rates: std::vec::Vec<RateInput> },
+    PauseContract { reason: Option<std::string::String> },
+    UnpauseContract,
+    DisableTimelock,
+    UnpauseModule { module: std::string::String },
+}
+
+impl QueuedAction {
+    /// The `action_type`, `target` and `data` arguments of
+    /// `queue_timelock_action`. Actions other than `UpdateAdmin` have no
+    /// target of their own and name the contract itself.
+    pub fn encode(
+        &self,
+        env: &Env,
+        contract: &Address,
+    ) -> Result<(TimelockActionType, Address, Bytes), RequestError> {
+        let empty = Bytes::new(env);
+        let contract = contract.clone();
+        Ok(match self {
+            Self::UpdateAdmin { new_admin } => (
+                TimelockActionType::UpdateAdmin,
+                parse_address(env, new_admin)?,
+                empty,
+            ),
+            Self::UpdateConfig { config } => (
+                TimelockActionType::UpdateConfig,
+                contract,
+                config.build(env)?.to_xdr(env),
+            ),
+            Self::UpdateRates { rates } => {
+                reject_if(rates.is_empty(), RentalError::InvalidInput)?;
+                let mut encoded = Vec::new(env);
+                for rate in rates {
+                    encoded.push_back(rate.build(env)?);
+                }
+                (
+                    TimelockActionType::UpdateRates,
+                    contract,
+                    encoded.to_xdr(env),
+                )
+            }
+            Self::PauseContract { reason: why } => (
+                TimelockActionType::PauseContract,
+                contract,
+                reason(env, why),
+            ),
+            Self::UnpauseContract => (TimelockActionType::UnpauseContract, contract, empty),
+            Self::DisableTimelock => (TimelockActionType::DisableTimelock, contract, empty),
+            Self::UnpauseModule { module } => (
+                TimelockActionType::UnpauseModule,
+                contract,
+                variant::<PauseModule>(env, &PauseModule::spec_xdr(), "pause module", module)?
+                    .to_xdr(env),
+            ),
+        })
+    }
+}
+
+/// A governance call on chioma, keyed by its entrypoint name.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Request {
+    ProposeAction {
+        proposer: std::string::String,
+        action: ProposedAction,
+    },
+    QueueTimelockAction {
+        caller: std::string::String,
+        action: QueuedAction,
+        delay: u64,
+    },
+    AddSupportedToken {
+        caller: std::string::String,
+        token: TokenInput,
+    },
+    ProposeContractUpgrade {
+        proposer: std::string::String,
+        proposal_id: std::string::String,
+        wasm_hash: std::string::String,
+        notes: std::string::String,
+        delay_seconds: u64,
+    },
+    ExecuteContractUpgrade {
+        executor: std::string::String,
+        proposal_id: std::string::String,
+        version: VersionInput,
+    },
+}
+
+/// Parameters of the transaction a [`Request`] is sent in.
+#[derive(Clone, Debug)]
+pub struct TransactionOptions {
+    /// The source account's next sequence number.
+    pub sequence: i64,
+    /// Inclusion fee in stroops; the resource fee in `resources` is added.
+    pub fee: u32,
+    /// Footprint and resources from simulating the transaction. Without
+    /// them the envelope still has to be simulated, which changes it, so
+    /// it must be signed afterwards.
+    pub resources: Option<SorobanTransactionData>,
+}
+
+impl Request {
+    pub fn from_json(json: &str) -> Result<Self, RequestError> {
+        serde_json::from_str(json).map_err(|err| invalid(format!("invalid request: {err}")))
+    }
+
+    /// The address whose authorization the call needs.
+    pub fn signer(&self) -> &str {
+        match self {
+            Self::ProposeAction { proposer, .. }
+            | Self::ProposeContractUpgrade { proposer, .. } => proposer,
+            Self::QueueTimelockAction { caller, .. } | Self::AddSupportedToken { caller, .. } => {
+                caller
+            }
+            Self::ExecuteContractUpgrade { executor, .. } => executor,
+        }
+    }
+
+    /// The call on the chioma deployment at `chioma`.
+    pub fn invocation(&self, env: &Env, chioma: &Address) -> Result<Invocation, RequestError> {
+        let signer = parse_address(env, self.signer())?;
+        Ok(match self {
+            Self::ProposeAction { action, .. } => {
+                let (action_type, target, data) = action.encode(env)?;
+                Invocation::new(env, chioma, "propose_action")
+                    .arg(signer)
+                    .arg(action_type)
+                    .arg(target)
+                    .arg(data)
+            }
+            Self::QueueTimelockAction { action, delay, .. } => {
+                let (action_type, target, data) = action.encode(env, chioma)?;
+                reject_if(
+                    *delay < get_min_delay(&action_type),
+                    RentalError::TimelockDelayTooShort,
+                )?;
+                Invocation::new(env, chioma, "queue_timelock_action")
+                    .arg(signer)
+                    .arg(action_type)
+                    .arg(target)
+                    .arg(data)
+                    .arg(*delay)
+            }
+            Self::AddSupportedToken { token, .. } => {
+                let token = token.build(env)?;
+                Invocation::new(env, chioma, "add_supported_token")
+                    .arg(signer)
+                    .arg(token.token_address)
+                    .arg(token.symbol)
+                    .arg(token.decimals)
+                    .arg(token.min_amount)
+                    .arg(token.max_amount)
+            }
+            Self::ProposeContractUpgrade {
+                proposal_id,
+                wasm_hash: hash,
+                notes,
+                delay_seconds,
+                ..
+            } => Invocation::new(env, chioma, "propose_contract_upgrade")
+                .arg(signer)
+                .arg(text(env, proposal_id))
+                .arg(wasm_hash(env, hash)?)
+                .arg(text(env, notes))
+                .arg(*delay_seconds),
+            Self::ExecuteContractUpgrade {
+                proposal_id,
+                version,
+                ..
+            } => Invocation::new(env, chioma, "execute_contract_upgrade")
+                .arg(signer)
+                .arg(text(env, proposal_id))
+                .arg(version.build(env)?),
+        })
+    }
+
+    /// An unsigned transaction making the call from the signer's account.
+    /// The signer authorizes the call as the transaction source, so the
+    /// envelope signature is the only one needed; it must therefore be an
+    /// account, not a contract.
+    pub fn transaction(
+        &self,
+        env: &Env,
+        chioma: &Address,
+        options: &TransactionOptions,
+    ) -> Result<TransactionEnvelope, RequestError> {
+        let call = self.invocation(env, chioma)?.to_xdr();
+        let source = match ScAddress::from_str(self.signer()) {
+            Ok(ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(key)))) => {
+                MuxedAccount::Ed25519(key)
+            }
+            _ => {
+                return Err(invalid(format!(
+                    "`{}` cannot sign a transaction; the signer must be an account",
+                    self.signer()
+                )))
+            }
+        };
+        let (resource_fee, ext) = match &options.resources {
+            Some(resources) => (
+                resources.resource_fee,
+                TransactionExt::V1(resources.clone()),
+            ),
+            None => (0, TransactionExt::V0),
+        };
+        let fee = u32::try_from(i64::from(options.fee) + resource_fee)
+            .map_err(|_| invalid("the total fee does not fit a transaction"))?;
+        let operation = Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function: HostFunction::InvokeContract(call.clone()),
+                auth: vec_m(std::vec![source_auth(call)]),
+            }),
+        };
+        Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: source,
+                fee,
+                seq_num: SequenceNumber(options.sequence),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: vec_m(std::vec![operation]),
+                ext,
+            },
+            signatures: VecM::default(),
+        }))
+    }
+}
+
+fn source_auth(call: InvokeContractArgs) -> SorobanAuthorizationEntry {
+    SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::SourceAccount,
+        root_invocation: SorobanAuthorizedInvocation {
+            function: SorobanAuthorizedFunction::ContractFn(call),
+            sub_invocations: VecM::default(),
+        },
+    }
+}
+
+fn vec_m<T, const MAX: u32>(items: std::vec::Vec<T>) -> VecM<T, MAX> {
+    items.try_into().expect("one entry fits an XDR vector")
+}
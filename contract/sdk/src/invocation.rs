@@ -0,0 +1,76 @@
+use soroban_sdk::{
+    xdr::{InvokeContractArgs, ScVal},
+    Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+};
+
+/// A contract call assembled at runtime: the contract, the function and its
+/// arguments in order.
+///
+/// ```text
+/// let call = Invocation::new(&env, &chioma, "get_agreement").arg(agreement_id);
+/// let agreement: Option<RentAgreement> = call.invoke();
+/// ```
+#[derive(Clone)]
+pub struct Invocation {
+    env: Env,
+    contract: Address,
+    function: Symbol,
+    args: Vec<Val>,
+}
+
+impl Invocation {
+    pub fn new(env: &Env, contract: &Address, function: &str) -> Self {
+        Self {
+            env: env.clone(),
+            contract: contract.clone(),
+            function: Symbol::new(env, function),
+            args: Vec::new(env),
+        }
+    }
+
+    /// Append the next argument.
+    pub fn arg(mut self, value: impl IntoVal<Env, Val>) -> Self {
+        self.args.push_back(value.into_val(&self.env));
+        self
+    }
+
+    pub fn contract(&self) -> &Address {
+        &self.contract
+    }
+
+    pub fn function(&self) -> &Symbol {
+        &self.function
+    }
+
+    pub fn args(&self) -> &Vec<Val> {
+        &self.args
+    }
+
+    /// Call the contract and convert its return value. Like a generated
+    /// client, this panics if the call fails.
+    pub fn invoke<T: TryFromVal<Env, Val>>(&self) -> T {
+        self.env
+            .invoke_contract(&self.contract, &self.function, self.args.clone())
+    }
+
+    /// The call as the arguments of an `InvokeHostFunction` operation.
+    pub fn to_xdr(&self) -> InvokeContractArgs {
+        let ScVal::Symbol(function_name) = self.sc_val(self.function.to_val()) else {
+            unreachable!("a symbol converts to ScVal::Symbol");
+        };
+        let args = self
+            .args
+            .iter()
+            .map(|arg| self.sc_val(arg))
+            .collect::<std::vec::Vec<_>>();
+        InvokeContractArgs {
+            contract_address: (&self.contract).into(),
+            function_name,
+            args: args.try_into().expect("argument count fits an XDR vector"),
+        }
+    }
+
+    fn sc_val(&self, val: Val) -> ScVal {
+        ScVal::try_from_val(&self.env, &val).expect("host values convert to XDR")
+    }
+}
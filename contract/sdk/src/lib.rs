@@ -0,0 +1,92 @@
+//! Typed Rust clients for the protocol contracts.
+//!
+//! Each contract has a module re-exporting its public types and the client
+//! its `#[contract]` generates, aliased as `Client`, so integrators call
+//! entrypoints with checked argument and return types instead of assembling
+//! `invoke_contract` calls by hand:
+//!
+//! ```text
+//! let chioma = chioma_sdk::chioma::Client::new(&env, &chioma_address);
+//! let agreement = chioma.get_agreement(&agreement_id);
+//! ```
+//!
+//! [`Invocation`] builds the argument vector of a call that has to be made
+//! or serialized dynamically, [`flows`] runs multi-step agreement flows,
+//! [`governance`] builds chioma governance calls from JSON for offline
+//! signing (the `chioma-admin` binary), and
+//! with the `testutils` feature [`Protocol`] deploys every contract into one
+//! `Env` for integration tests.
+//!
+//! The contracts are linked natively, so this crate is for off-chain code
+//! and tests; a contract calling another one uses a `#[contractclient]`
+//! interface instead.
+
+pub mod flows;
+pub mod governance;
+mod invocation;
+#[cfg(any(test, feature = "testutils"))]
+mod protocol;
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+mod tests_governance;
+
+pub use flows::FlowError;
+pub use invocation::Invocation;
+#[cfg(any(test, feature = "testutils"))]
+pub use protocol::Protocol;
+
+/// The agent registry contract (`contracts/agent_registry`).
+pub mod agent_registry {
+    pub use ::agent_registry::AgentRegistryContractClient as Client;
+    pub use ::agent_registry::*;
+}
+
+/// The chioma agreement contract (`contracts/chioma`).
+pub mod chioma {
+    pub use ::chioma::ContractClient as Client;
+    pub use ::chioma::*;
+}
+
+/// The dispute resolution contract (`contracts/dispute_resolution`).
+pub mod dispute_resolution {
+    pub use ::dispute_resolution::DisputeResolutionContractClient as Client;
+    pub use ::dispute_resolution::*;
+}
+
+/// The escrow contract (`contracts/escrow`).
+pub mod escrow {
+    pub use ::escrow::escrow_impl::EscrowContractClient as Client;
+    pub use ::escrow::*;
+}
+
+/// The payment contract (`contracts/payment`).
+pub mod payment {
+    pub use ::payment::PaymentContractClient as Client;
+    pub use ::payment::*;
+}
+
+/// The property registry contract (`contracts/property_registry`).
+pub mod property_registry {
+    pub use ::property_registry::PropertyRegistryContractClient as Client;
+    pub use ::property_registry::*;
+}
+
+/// The protocol directory contract (`contracts/protocol_directory`).
+pub mod protocol_directory {
+    pub use ::protocol_directory::ProtocolDirectoryContractClient as Client;
+    pub use ::protocol_directory::*;
+}
+
+/// The tokenized rent obligation contract (`contracts/rent_obligation`).
+pub mod rent_obligation {
+    pub use ::rent_obligation::TokenizedRentObligationContractClient as Client;
+    pub use ::rent_obligation::*;
+}
+
+/// The user profile contract (`contracts/user_profile`).
+pub mod user_profile {
+    pub use ::user_profile::UserProfileContractClient as Client;
+    pub use ::user_profile::*;
+}
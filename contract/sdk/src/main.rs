@@ -0,0 +1,111 @@
+//! `chioma-admin --contract <ID> [--sequence <N> [--fee <STROOPS>] [--resources <BASE64>]] <REQUEST.json>`
+//!
+//! Prints the chioma governance call described in `REQUEST.json` (see
+//! `chioma_sdk::governance`) as base64 `InvokeContractArgs` XDR. With
+//! `--sequence`, also prints an unsigned transaction envelope from the
+//! call's signer with that sequence number, ready for offline signing;
+//! `--resources` attaches the `SorobanTransactionData` of a simulation.
+//! Nothing is sent to the network.
+
+use std::{env, fs, process::ExitCode};
+
+use chioma_sdk::governance::{parse_address, Request, RequestError, TransactionOptions};
+use soroban_sdk::Env;
+use stellar_xdr::curr::{Limits, ReadXdr, SorobanTransactionData, WriteXdr};
+
+const USAGE: &str = "usage: chioma-admin --contract <ID> [--sequence <N> [--fee <STROOPS>] [--resources <BASE64>]] <REQUEST.json>";
+
+/// Inclusion fee when `--fee` is not given.
+const BASE_FEE: u32 = 100;
+
+struct Args {
+    contract: String,
+    sequence: Option<i64>,
+    fee: u32,
+    resources: Option<SorobanTransactionData>,
+    request: String,
+}
+
+fn parse_number<T: std::str::FromStr>(
+    option: &str,
+    value: Option<String>,
+) -> Result<T, RequestError> {
+    let value = value.unwrap_or_default();
+    value
+        .parse()
+        .map_err(|_| RequestError::Invalid(format!("{option} expects a number, got `{value}`")))
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, RequestError> {
+    let usage = || RequestError::Invalid(USAGE.to_string());
+    let mut contract = None;
+    let mut sequence = None;
+    let mut fee = BASE_FEE;
+    let mut resources = None;
+    let mut request = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--contract" => contract = args.next(),
+            "--sequence" => sequence = Some(parse_number(&arg, args.next())?),
+            "--fee" => fee = parse_number(&arg, args.next())?,
+            "--resources" => {
+                let encoded = args.next().unwrap_or_default();
+                let data = SorobanTransactionData::from_xdr_base64(&encoded, Limits::none())
+                    .map_err(|err| RequestError::Invalid(format!("--resources: {err}")))?;
+                resources = Some(data);
+            }
+            _ if arg.starts_with("--") => {
+                return Err(RequestError::Invalid(format!(
+                    "unknown option `{arg}`\n{USAGE}"
+                )))
+            }
+            _ if request.is_none() => request = Some(arg),
+            _ => return Err(usage()),
+        }
+    }
+    Ok(Args {
+        contract: contract.ok_or_else(usage)?,
+        sequence,
+        fee,
+        resources,
+        request: request.ok_or_else(usage)?,
+    })
+}
+
+fn base64(value: &impl WriteXdr) -> String {
+    value
+        .to_xdr_base64(Limits::none())
+        .expect("built XDR encodes")
+}
+
+fn run() -> Result<(), RequestError> {
+    let args = parse_args(env::args().skip(1))?;
+    let json = fs::read_to_string(&args.request)
+        .map_err(|err| RequestError::Invalid(format!("{}: {err}", args.request)))?;
+    let request = Request::from_json(&json)?;
+
+    let env = Env::default();
+    let contract = parse_address(&env, &args.contract)?;
+    let invocation = request.invocation(&env, &contract)?;
+    println!("invocation: {}", base64(&invocation.to_xdr()));
+    if let Some(sequence) = args.sequence {
+        let options = TransactionOptions {
+            sequence,
+            fee: args.fee,
+            resources: args.resources,
+        };
+        let envelope = request.transaction(&env, &contract, &options)?;
+        println!("transaction: {}", base64(&envelope));
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("chioma-admin: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use soroban_sdk::{vec, Address, Env, String};
+
+use crate::{
+    agent_registry, chioma, dispute_resolution, escrow, payment, property_registry,
+    protocol_directory::{self, Component, ComponentUpdate},
+    rent_obligation, user_profile,
+};
+
+/// Every protocol contract registered in one `Env` and initialized with the
+/// same administrator, for integration tests.
+pub struct Protocol {
+    pub admin: Address,
+    pub agent_registry: agent_registry::Client<'static>,
+    pub chioma: chioma::Client<'static>,
+    pub dispute_resolution: dispute_resolution::Client<'static>,
+    pub escrow: escrow::Client<'static>,
+    pub payment: payment::Client<'static>,
+    pub property_registry: property_registry::Client<'static>,
+    pub protocol_directory: protocol_directory::Client<'static>,
+    pub rent_obligation: rent_obligation::Client<'static>,
+    pub user_profile: user_profile::Client<'static>,
+}
+
+impl Protocol {
+    /// Register and initialize every contract with `admin` as administrator
+    /// and `config` as chioma's configuration. The protocol directory,
+    /// governed by `admin` alone, lists every other contract at version 1,
+    /// and escrow and dispute resolution read their agreements from the
+    /// chioma entry, while chioma moves deposits into the escrow entry; a
+    /// dispute is resolved on one arbiter vote. The initializers require
+    /// `admin`'s authorization.
+    pub fn deploy(env: &Env, admin: &Address, config: &chioma::Config) -> Self {
+        let protocol = Self {
+            admin: admin.clone(),
+            agent_registry: agent_registry::Client::new(
+                env,
+                &env.register(agent_registry::AgentRegistryContract, ()),
+            ),
+            chioma: chioma::Client::new(env, &env.register(chioma::Contract, ())),
+            dispute_resolution: dispute_resolution::Client::new(
+                env,
+                &env.register(dispute_resolution::DisputeResolutionContract, ()),
+            ),
+            escrow: escrow::Client::new(env, &env.register(escrow::EscrowContract, ())),
+            payment: payment::Client::new(env, &env.register(payment::PaymentContract, ())),
+            property_registry: property_registry::Client::new(
+                env,
+                &env.register(property_registry::PropertyRegistryContract, ()),
+            ),
+            protocol_directory: protocol_directory::Client::new(
+                env,
+                &env.register(protocol_directory::ProtocolDirectoryContract, ()),
+            ),
+            rent_obligation: rent_obligation::Client::new(
+                env,
+                &env.register(rent_obligation::TokenizedRentObligationContract, ()),
+            ),
+            user_profile: user_profile::Client::new(
+                env,
+                &env.register(user_profile::UserProfileContract, ()),
+            ),
+        };
+
+        protocol.agent_registry.initialize(admin);
+        protocol.chioma.initialize(admin, config);
+        protocol
+            .dispute_resolution
+            .initialize(admin, &1, &protocol.chioma.address);
+        protocol.escrow.initialize_admin(admin);
+        protocol.payment.initialize_admin(admin);
+        protocol.property_registry.initialize(admin);
+        protocol.rent_obligation.initialize();
+        protocol.rent_obligation.initialize_admin(admin);
+        protocol.user_profile.initialize(admin);
+
+        let directory = &protocol.protocol_directory;
+        directory.initialize(&vec![env, admin.clone()], &1, &0);
+        let proposal_id = String::from_str(env, "PROTOCOL-V1");
+        directory.propose_update(admin, &proposal_id, &protocol.components(env), &0);
+        directory.execute_update(admin, &proposal_id);
+        protocol
+            .dispute_resolution
+            .set_directory(admin, &directory.address);
+        protocol.escrow.set_directory(admin, &directory.address);
+        protocol.chioma.set_directory(admin, &directory.address);
+        protocol
+    }
+
+    /// Every contract but the directory, at version 1.
+    fn components(&self, env: &Env) -> soroban_sdk::Vec<ComponentUpdate> {
+        let update = |component, address: &Address| ComponentUpdate {
+            component,
+            address: address.clone(),
+            version: 1,
+        };
+        vec![
+            env,
+            update(Component::AgentRegistry, &self.agent_registry.address),
+            update(Component::Chioma, &self.chioma.address),
+            update(
+                Component::DisputeResolution,
+                &self.dispute_resolution.address,
+            ),
+            update(Component::Escrow, &self.escrow.address),
+            update(Component::Payment, &self.payment.address),
+            update(Component::PropertyRegistry, &self.property_registry.address),
+            update(Component::RentObligation, &self.rent_obligation.address),
+            update(Component::UserProfile, &self.user_profile.address),
+        ]
+    }
+}
@@ -0,0 +1,403 @@
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, String, Vec,
+};
+
+use crate::{
+    chioma::{AgreementInput, AgreementStatus, AgreementTerms, Config, RentAgreement, RentalError},
+    dispute_resolution::DisputeError,
+    escrow,
+    flows::{activate_agreement, open_lease, pay_rent},
+    protocol_directory::Component,
+    FlowError, Invocation, Protocol,
+};
+
+const RENT: i128 = 1_000;
+const DEPOSIT: i128 = 2_000;
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    protocol: Protocol,
+    token: StellarAssetClient<'static>,
+    landlord: Address,
+    tenant: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    // The tenant authorizes the escrow deposit nested in `approve_agreement`.
+    env.mock_all_auths_allowing_non_root_auth();
+    let admin = Address::generate(&env);
+    let config = Config {
+        fee_bps: 0,
+        fee_collector: Address::generate(&env),
+        paused: false,
+    };
+    let protocol = Protocol::deploy(&env, &admin, &config);
+    let token = StellarAssetClient::new(
+        &env,
+        &env.register_stellar_asset_contract_v2(admin).address(),
+    );
+    let tenant = Address::generate(&env);
+    token.mint(&tenant, &(RENT * 12));
+    Setup {
+        landlord: Address::generate(&env),
+        tenant,
+        env,
+        protocol,
+        token,
+    }
+}
+
+/// An agreement between the setup's parties. Agreement creation is rate
+/// limited per ledger, so each one is created on the next.
+fn input(s: &Setup, id: &str) -> AgreementInput {
+    s.env.ledger().with_mut(|ledger| {
+        ledger.sequence_number += 1;
+        ledger.timestamp += 60;
+    });
+    let now = s.env.ledger().timestamp();
+    AgreementInput {
+        agreement_id: String::from_str(&s.env, id),
+        admin: s.landlord.clone(),
+        user: s.tenant.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: RENT,
+            security_deposit: DEPOSIT,
+            start_date: now + DAY,
+            end_date: now + 365 * DAY,
+            agent_commission_rate: 0,
+        },
+        payment_token: s.token.address.clone(),
+        metadata_uri: String::from_str(&s.env, "ipfs://agreement"),
+        attributes: Vec::new(&s.env),
+    }
+}
+
+#[test]
+fn test_protocol_wires_dispute_resolution_to_chioma() {
+    let s = setup();
+    let state = s.protocol.dispute_resolution.get_state().unwrap();
+    assert_eq!(state.chioma_contract, s.protocol.chioma.address);
+    assert_eq!(state.admin, s.protocol.admin);
+    assert_eq!(
+        s.protocol.escrow.get_admin(),
+        Some(s.protocol.admin.clone())
+    );
+    assert_eq!(s.protocol.user_profile.get_admin(), s.protocol.admin);
+
+    let directory = &s.protocol.protocol_directory;
+    assert_eq!(directory.get_components().len(), 8);
+    assert_eq!(
+        directory.resolve(&Component::Chioma),
+        Some(s.protocol.chioma.address.clone())
+    );
+    assert_eq!(
+        s.protocol.dispute_resolution.get_directory(),
+        Some(directory.address.clone())
+    );
+    assert_eq!(
+        s.protocol.escrow.get_directory(),
+        Some(directory.address.clone())
+    );
+    assert_eq!(
+        s.protocol.chioma.get_directory(),
+        Some(directory.address.clone())
+    );
+}
+
+#[test]
+fn test_open_lease_escrows_deposit_and_pays_first_month() {
+    let s = setup();
+    let id = open_lease(&s.protocol.chioma, &input(&s, "AGR-1")).unwrap();
+
+    let agreement = s.protocol.chioma.get_agreement(&id).unwrap();
+    assert_eq!(agreement.status, AgreementStatus::Active);
+    assert_eq!(agreement.payment_count, 1);
+    assert_eq!(agreement.total_rent_paid, RENT);
+    let token = TokenClient::new(&s.env, &s.token.address);
+    assert_eq!(token.balance(&s.tenant), RENT * 11 - DEPOSIT);
+
+    let escrow_id = s.protocol.escrow.get_agreement_escrow(&id).unwrap();
+    let escrow = s.protocol.escrow.get_escrow(&escrow_id);
+    assert_eq!(escrow.depositor, s.tenant);
+    assert_eq!(escrow.beneficiary, s.landlord);
+    assert_eq!(escrow.amount, DEPOSIT);
+    assert_eq!(token.balance(&s.protocol.escrow.address), DEPOSIT);
+    assert_eq!(token.balance(&s.protocol.chioma.address), RENT);
+
+    pay_rent(&s.protocol.chioma, &id, 2).unwrap();
+    let agreement = s.protocol.chioma.get_agreement(&id).unwrap();
+    assert_eq!(agreement.payment_count, 3);
+}
+
+#[test]
+fn test_flow_reports_failing_step() {
+    let s = setup();
+    let agreement = input(&s, "AGR-1");
+    activate_agreement(&s.protocol.chioma, &agreement).unwrap();
+
+    let err = activate_agreement(&s.protocol.chioma, &input(&s, "AGR-1")).unwrap_err();
+    assert_eq!(
+        err,
+        FlowError::Contract {
+            step: "create_agreement",
+            error: RentalError::AgreementAlreadyExists,
+        }
+    );
+
+    let missing = String::from_str(&s.env, "AGR-MISSING");
+    let err = pay_rent(&s.protocol.chioma, &missing, 1).unwrap_err();
+    assert_eq!(err.step(), "get_agreement");
+}
+
+#[test]
+fn test_flow_stops_before_later_steps() {
+    let s = setup();
+    let mut agreement = input(&s, "AGR-1");
+    // A tenant without funds can sign, but activation cannot take the
+    // deposit.
+    agreement.user = Address::generate(&s.env);
+
+    let err = open_lease(&s.protocol.chioma, &agreement).unwrap_err();
+    assert_eq!(
+        err,
+        FlowError::Contract {
+            step: "approve_with_first_rent",
+            error: RentalError::PaymentFailed,
+        }
+    );
+    let stored = s
+        .protocol
+        .chioma
+        .get_agreement(&agreement.agreement_id)
+        .unwrap();
+    assert_eq!(stored.status, AgreementStatus::PendingApproval);
+    assert_eq!(stored.payment_count, 0);
+    assert_eq!(
+        s.protocol
+            .escrow
+            .get_agreement_escrow(&agreement.agreement_id),
+        None
+    );
+}
+
+#[test]
+fn test_activation_takes_over_an_unfunded_escrow() {
+    let s = setup();
+    let agreement = input(&s, "AGR-1");
+    let id = &agreement.agreement_id;
+    let chioma = &s.protocol.chioma;
+    chioma.create_agreement(&agreement);
+    chioma.submit_agreement(&s.landlord, id);
+    chioma.sign_agreement(&s.tenant, id);
+
+    // The tenant opened an escrow of their own but never funded it.
+    let unfunded = s.protocol.escrow.create(
+        id,
+        &s.tenant,
+        &s.landlord,
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &DEPOSIT,
+        &s.token.address,
+    );
+
+    chioma.approve_agreement(&s.landlord, id);
+    let escrow_id = s.protocol.escrow.get_agreement_escrow(id).unwrap();
+    assert_ne!(escrow_id, unfunded);
+    let escrow = s.protocol.escrow.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, escrow::EscrowStatus::Funded);
+    assert_eq!(escrow.arbiter, s.protocol.admin);
+    assert_eq!(
+        s.protocol.escrow.get_escrow(&unfunded).status,
+        escrow::EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_activation_adopts_an_escrow_funded_in_installments() {
+    let s = setup();
+    let agreement = input(&s, "AGR-1");
+    let id = &agreement.agreement_id;
+    let chioma = &s.protocol.chioma;
+    let escrow = &s.protocol.escrow;
+    chioma.create_agreement(&agreement);
+    chioma.submit_agreement(&s.landlord, id);
+    chioma.sign_agreement(&s.tenant, id);
+
+    // The tenant pays the deposit in two halves ahead of activation.
+    let escrow_id = escrow.create(
+        id,
+        &s.tenant,
+        &s.landlord,
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &DEPOSIT,
+        &s.token.address,
+    );
+    let now = s.env.ledger().timestamp();
+    let mut plan = Vec::new(&s.env);
+    for due_at in [now + DAY, now + 2 * DAY] {
+        plan.push_back(escrow::Installment {
+            due_at,
+            amount: DEPOSIT / 2,
+        });
+    }
+    escrow.set_installment_plan(&escrow_id, &plan);
+    escrow.fund_installment(&escrow_id, &s.tenant, &(DEPOSIT / 2));
+    escrow.fund_installment(&escrow_id, &s.tenant, &(DEPOSIT / 2));
+
+    chioma.approve_agreement(&s.landlord, id);
+    assert_eq!(
+        chioma.get_agreement(id).unwrap().status,
+        AgreementStatus::Active
+    );
+    assert_eq!(escrow.get_agreement_escrow(id), Some(escrow_id.clone()));
+    let adopted = escrow.get_escrow(&escrow_id);
+    assert_eq!(adopted.status, escrow::EscrowStatus::Funded);
+    assert_eq!(adopted.arbiter, s.protocol.admin);
+    let token = TokenClient::new(&s.env, &s.token.address);
+    assert_eq!(token.balance(&s.tenant), RENT * 12 - DEPOSIT);
+    assert_eq!(token.balance(&escrow.address), DEPOSIT);
+}
+
+/// A signed agreement whose tenant opened its escrow with a plan paying the
+/// deposit in two halves, one and two days out. Returns the escrow id.
+fn deposit_in_two_installments(s: &Setup, id: &String) -> soroban_sdk::BytesN<32> {
+    let agreement = input(s, &id.to_string());
+    s.protocol.chioma.create_agreement(&agreement);
+    s.protocol.chioma.submit_agreement(&s.landlord, id);
+    s.protocol.chioma.sign_agreement(&s.tenant, id);
+
+    let escrow_id = s.protocol.escrow.create(
+        id,
+        &s.tenant,
+        &s.landlord,
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &DEPOSIT,
+        &s.token.address,
+    );
+    let now = s.env.ledger().timestamp();
+    let mut plan = Vec::new(&s.env);
+    for due_at in [now + DAY, now + 2 * DAY] {
+        plan.push_back(escrow::Installment {
+            due_at,
+            amount: DEPOSIT / 2,
+        });
+    }
+    s.protocol.escrow.set_installment_plan(&escrow_id, &plan);
+    escrow_id
+}
+
+#[test]
+fn test_lease_with_deposit_paid_in_installments() {
+    let s = setup();
+    let chioma = &s.protocol.chioma;
+    let escrow = &s.protocol.escrow;
+    let token = TokenClient::new(&s.env, &s.token.address);
+    let id = String::from_str(&s.env, "AGR-1");
+    let escrow_id = deposit_in_two_installments(&s, &id);
+
+    // Activation waits until the whole deposit is in.
+    escrow.fund_installment(&escrow_id, &s.tenant, &(DEPOSIT / 2));
+    assert_eq!(
+        chioma.try_approve_with_first_rent(&s.landlord, &id),
+        Err(Ok(RentalError::EscrowInsufficientFunds))
+    );
+    assert_eq!(
+        chioma.get_agreement(&id).unwrap().status,
+        AgreementStatus::PendingApproval
+    );
+
+    escrow.fund_installment(&escrow_id, &s.tenant, &(DEPOSIT / 2));
+    chioma.approve_with_first_rent(&s.landlord, &id);
+    let agreement = chioma.get_agreement(&id).unwrap();
+    assert_eq!(agreement.status, AgreementStatus::Active);
+    assert_eq!(agreement.payment_count, 1);
+    assert_eq!(escrow.get_agreement_escrow(&id), Some(escrow_id.clone()));
+    assert_eq!(token.balance(&s.tenant), RENT * 11 - DEPOSIT);
+    assert_eq!(token.balance(&escrow.address), DEPOSIT);
+
+    // At the end of the lease the deposit goes back to the tenant on the
+    // word of the landlord and chioma's admin, the arbiter activation set.
+    escrow.approve_release(&escrow_id, &s.landlord, &s.tenant);
+    escrow.approve_release(&escrow_id, &s.protocol.admin, &s.tenant);
+    assert_eq!(
+        escrow.get_escrow_status(&escrow_id),
+        escrow::EscrowStatus::Released
+    );
+    assert_eq!(token.balance(&s.tenant), RENT * 11);
+}
+
+#[test]
+fn test_activation_after_a_missed_installment_takes_the_deposit() {
+    let s = setup();
+    let id = String::from_str(&s.env, "AGR-1");
+    let escrow_id = deposit_in_two_installments(&s, &id);
+    s.protocol
+        .escrow
+        .fund_installment(&escrow_id, &s.tenant, &(DEPOSIT / 2));
+
+    // The second half is never paid, and the first is refunded.
+    s.env
+        .ledger()
+        .with_mut(|ledger| ledger.timestamp += 3 * DAY);
+    s.protocol.escrow.refund_installments_on_timeout(&escrow_id);
+
+    s.protocol.chioma.approve_agreement(&s.landlord, &id);
+    let deposit = s.protocol.escrow.get_agreement_escrow(&id).unwrap();
+    assert_ne!(deposit, escrow_id);
+    assert_eq!(
+        s.protocol.escrow.get_escrow_status(&deposit),
+        escrow::EscrowStatus::Funded
+    );
+    let token = TokenClient::new(&s.env, &s.token.address);
+    assert_eq!(token.balance(&s.tenant), RENT * 12 - DEPOSIT);
+}
+
+#[test]
+fn test_dispute_resolution_reads_chioma_agreements() {
+    let s = setup();
+    let disputes = &s.protocol.dispute_resolution;
+    let details = String::from_str(&s.env, "QmDetails");
+
+    let draft = input(&s, "AGR-DRAFT");
+    s.protocol.chioma.create_agreement(&draft);
+    assert_eq!(
+        disputes.try_raise_dispute(&s.tenant, &draft.agreement_id, &details),
+        Err(Ok(DisputeError::InvalidAgreementState))
+    );
+
+    let id = open_lease(&s.protocol.chioma, &input(&s, "AGR-1")).unwrap();
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        disputes.try_raise_dispute(&stranger, &id, &details),
+        Err(Ok(DisputeError::Unauthorized))
+    );
+    disputes.raise_dispute(&s.tenant, &id, &details);
+    assert_eq!(disputes.get_dispute(&id).unwrap().agreement_id, id);
+}
+
+#[test]
+fn test_invocation_matches_typed_client() {
+    let s = setup();
+    let id = open_lease(&s.protocol.chioma, &input(&s, "AGR-1")).unwrap();
+
+    let call = Invocation::new(&s.env, &s.protocol.chioma.address, "get_agreement").arg(id.clone());
+    let agreement: Option<RentAgreement> = call.invoke();
+    assert_eq!(agreement, s.protocol.chioma.get_agreement(&id));
+    assert_eq!(call.args().len(), 1);
+
+    let xdr = call.to_xdr();
+    assert_eq!(xdr.function_name.to_utf8_string_lossy(), "get_agreement");
+    assert_eq!(xdr.contract_address, (&s.protocol.chioma.address).into());
+    assert_eq!(xdr.args.len(), 1);
+}
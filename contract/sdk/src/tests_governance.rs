@@ -0,0 +1,287 @@
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec,
+    xdr::{
+        FromXdr, LedgerFootprint, Limits, OperationBody, ReadXdr, ScAddress,
+        SorobanAuthorizedFunction, SorobanCredentials, SorobanResources, SorobanTransactionData,
+        SorobanTransactionDataExt, TransactionEnvelope, TransactionExt, WriteXdr,
+    },
+    Address, Env, String, TryFromVal,
+};
+
+use crate::{
+    chioma::{
+        get_min_delay, Config, ContractVersion, PauseModule, RentalError, Role, TimelockActionType,
+        VersionStatus,
+    },
+    governance::{Request, RequestError, TransactionOptions},
+    Protocol,
+};
+
+/// An account strkey (the all-zero ed25519 key).
+const ACCOUNT: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+const HASH: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+
+fn strkey(address: &Address) -> std::string::String {
+    ScAddress::from(address).to_string()
+}
+
+fn setup() -> (Env, Protocol) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let config = Config {
+        fee_bps: 100,
+        fee_collector: Address::generate(&env),
+        paused: false,
+    };
+    let protocol = Protocol::deploy(&env, &admin, &config);
+    protocol.chioma.initialize_multisig(&vec![&env, admin], &1);
+    (env, protocol)
+}
+
+fn request(json: &str) -> Request {
+    Request::from_json(json).unwrap()
+}
+
+fn propose(admin: &Address, action: &str) -> Request {
+    request(&format!(
+        r#"{{ "propose_action": {{ "proposer": "{}", "action": {action} }} }}"#,
+        strkey(admin)
+    ))
+}
+
+fn rejection(env: &Env, json: &str) -> RequestError {
+    let chioma = Address::generate(env);
+    request(json).invocation(env, &chioma).err().unwrap()
+}
+
+#[test]
+fn test_proposed_actions_execute_on_chioma() {
+    let (env, protocol) = setup();
+    let (chioma, admin) = (&protocol.chioma, &protocol.admin);
+    let collector = Address::generate(&env);
+    let treasurer = Address::generate(&env);
+    let actions = [
+        format!(
+            r#"{{ "type": "UpdateConfig",
+                  "config": {{ "fee_bps": 250, "fee_collector": "{}", "paused": false }} }}"#,
+            strkey(&collector)
+        ),
+        format!(
+            r#"{{ "type": "GrantRole", "account": "{}", "role": "Treasurer" }}"#,
+            strkey(&treasurer)
+        ),
+        r#"{ "type": "SetActionThreshold", "action_type": "UpdateConfig", "threshold": 1 }"#.into(),
+        r#"{ "type": "SetAuditLogCapacity", "capacity": 500 }"#.into(),
+    ];
+    for action in &actions {
+        let call = propose(admin, action)
+            .invocation(&env, &chioma.address)
+            .unwrap();
+        let proposal_id: String = call.invoke();
+        chioma.execute_action(admin, &proposal_id);
+    }
+
+    let config = chioma.get_state().unwrap().config;
+    assert_eq!((config.fee_bps, config.fee_collector), (250, collector));
+    assert!(chioma.has_role(&treasurer, &Role::Treasurer));
+}
+
+#[test]
+fn test_timelock_request_is_queued() {
+    let (env, protocol) = setup();
+    let (chioma, admin) = (&protocol.chioma, &protocol.admin);
+    let min_delay = get_min_delay(&TimelockActionType::UnpauseModule);
+    let queue = |delay: u64| {
+        request(&format!(
+            r#"{{ "queue_timelock_action": {{
+                    "caller": "{}",
+                    "action": {{ "type": "UnpauseModule", "module": "Payments" }},
+                    "delay": {delay} }} }}"#,
+            strkey(admin)
+        ))
+    };
+
+    let action_id: String = queue(min_delay)
+        .invocation(&env, &chioma.address)
+        .unwrap()
+        .invoke();
+    let action = chioma.get_timelock_action(&action_id);
+    assert_eq!(action.action_type, TimelockActionType::UnpauseModule);
+    assert_eq!(action.target, chioma.address);
+    assert_eq!(
+        PauseModule::from_xdr(&env, &action.data),
+        Ok(PauseModule::Payments)
+    );
+
+    assert_eq!(
+        queue(min_delay - 1).invocation(&env, &chioma.address).err(),
+        Some(RequestError::Rejected(RentalError::TimelockDelayTooShort))
+    );
+}
+
+#[test]
+fn test_token_and_upgrade_requests() {
+    let (env, protocol) = setup();
+    let (chioma, admin) = (&protocol.chioma, &protocol.admin);
+    let token = Address::generate(&env);
+    request(&format!(
+        r#"{{ "add_supported_token": {{
+                "caller": "{}",
+                "token": {{ "address": "{}", "symbol": "USDC", "decimals": 7,
+                            "min_amount": 1, "max_amount": 1000000000000 }} }} }}"#,
+        strkey(admin),
+        strkey(&token)
+    ))
+    .invocation(&env, &chioma.address)
+    .unwrap()
+    .invoke::<()>();
+    assert!(chioma.is_token_supported(&token));
+
+    request(&format!(
+        r#"{{ "propose_contract_upgrade": {{
+                "proposer": "{}", "proposal_id": "UPG-1", "wasm_hash": "{HASH}",
+                "notes": "v2", "delay_seconds": 86400 }} }}"#,
+        strkey(admin)
+    ))
+    .invocation(&env, &chioma.address)
+    .unwrap()
+    .invoke::<()>();
+    let proposal = chioma.get_upgrade_proposal(&String::from_str(&env, "UPG-1"));
+    assert_eq!(proposal.wasm_hash.to_array(), [1; 32]);
+
+    let execute = request(&format!(
+        r#"{{ "execute_contract_upgrade": {{
+                "executor": "{}", "proposal_id": "UPG-1",
+                "version": {{ "major": 2, "minor": 0, "patch": 0, "label": "v2" }} }} }}"#,
+        strkey(admin)
+    ));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    let call = execute.invocation(&env, &chioma.address).unwrap();
+    let version = ContractVersion::try_from_val(&env, &call.args().get(2).unwrap()).unwrap();
+    assert_eq!((version.major, version.status), (2, VersionStatus::Active));
+}
+
+#[test]
+fn test_rejects_what_the_contract_would() {
+    let env = Env::default();
+    let account = strkey(&Address::generate(&env));
+    let config = |fee_bps: u32| {
+        format!(
+            r#"{{ "propose_action": {{ "proposer": "{account}", "action": {{
+                    "type": "UpdateConfig",
+                    "config": {{ "fee_bps": {fee_bps}, "fee_collector": "{account}", "paused": false }}
+                }} }} }}"#
+        )
+    };
+    assert_eq!(
+        rejection(&env, &config(10_001)),
+        RequestError::Rejected(RentalError::InvalidConfig)
+    );
+    assert_eq!(
+        rejection(
+            &env,
+            &format!(
+                r#"{{ "propose_action": {{ "proposer": "{account}",
+                        "action": {{ "type": "SetAuditLogCapacity", "capacity": 0 }} }} }}"#
+            )
+        ),
+        RequestError::Rejected(RentalError::InvalidConfig)
+    );
+    assert_eq!(
+        rejection(
+            &env,
+            &format!(
+                r#"{{ "queue_timelock_action": {{ "caller": "{account}",
+                        "action": {{ "type": "UpdateRates", "rates": [] }}, "delay": 604800 }} }}"#
+            )
+        ),
+        RequestError::Rejected(RentalError::InvalidInput)
+    );
+
+    let invalid = [
+        config(100).replace(
+            &format!(r#""fee_collector": "{account}""#),
+            r#""fee_collector": "GABC""#,
+        ),
+        format!(
+            r#"{{ "propose_action": {{ "proposer": "{account}",
+                    "action": {{ "type": "GrantRole", "account": "{account}", "role": "Owner" }} }} }}"#
+        ),
+        format!(
+            r#"{{ "propose_contract_upgrade": {{ "proposer": "{account}", "proposal_id": "UPG-1",
+                    "wasm_hash": "zz", "notes": "", "delay_seconds": 0 }} }}"#
+        ),
+    ];
+    for json in &invalid {
+        assert!(
+            matches!(rejection(&env, json), RequestError::Invalid(_)),
+            "{json}"
+        );
+    }
+    assert!(matches!(
+        Request::from_json(&config(100).replace("paused", "frozen")),
+        Err(RequestError::Invalid(_))
+    ));
+}
+
+#[test]
+fn test_transaction_is_authorized_by_its_source() {
+    let env = Env::default();
+    let chioma = Address::generate(&env);
+    let unpause = |proposer: &str| {
+        request(&format!(
+            r#"{{ "propose_action": {{ "proposer": "{proposer}", "action": {{ "type": "Unpause" }} }} }}"#
+        ))
+    };
+    let resources = SorobanTransactionData {
+        ext: SorobanTransactionDataExt::V0,
+        resources: SorobanResources {
+            footprint: LedgerFootprint {
+                read_only: Default::default(),
+                read_write: Default::default(),
+            },
+            instructions: 1_000_000,
+            disk_read_bytes: 0,
+            write_bytes: 1_000,
+        },
+        resource_fee: 50_000,
+    };
+    let options = TransactionOptions {
+        sequence: 42,
+        fee: 100,
+        resources: Some(resources.clone()),
+    };
+
+    let request = unpause(ACCOUNT);
+    let envelope = request.transaction(&env, &chioma, &options).unwrap();
+    let encoded = envelope.to_xdr_base64(Limits::none()).unwrap();
+    let TransactionEnvelope::Tx(envelope) =
+        TransactionEnvelope::from_xdr_base64(encoded, Limits::none()).unwrap()
+    else {
+        panic!("expected a v1 envelope");
+    };
+    assert!(envelope.signatures.is_empty());
+    let tx = envelope.tx;
+    assert_eq!(tx.source_account.to_string(), ACCOUNT);
+    assert_eq!((tx.seq_num.0, tx.fee), (42, 50_100));
+    assert_eq!(tx.ext, TransactionExt::V1(resources));
+
+    let OperationBody::InvokeHostFunction(op) = &tx.operations[0].body else {
+        panic!("expected an invocation");
+    };
+    let call = request.invocation(&env, &chioma).unwrap().to_xdr();
+    assert_eq!(op.auth.len(), 1);
+    assert_eq!(op.auth[0].credentials, SorobanCredentials::SourceAccount);
+    assert_eq!(
+        op.auth[0].root_invocation.function,
+        SorobanAuthorizedFunction::ContractFn(call)
+    );
+
+    let contract = strkey(&Address::generate(&env));
+    assert!(matches!(
+        unpause(&contract).transaction(&env, &chioma, &options),
+        Err(RequestError::Invalid(_))
+    ));
+}